@@ -6,6 +6,11 @@ import "./ExternalTranscript.sol";
 import "./VerifierKey_20.sol";
 
 abstract contract ShuffleVerifier is PlonkVerifier {
+    // The layout tag of the shuffle proofs, the first byte of `PlonkProof::to_bytes_be`.
+    uint256 private constant SHUFFLE_PROOF_TAG = 1;
+    // The number of words of the proof after the layout tag.
+    uint256 private constant SHUFFLE_PROOF_WORDS = 51;
+
     address _extraVk1;
     address _extraVk2;
     function(uint256, uint256) pure _verifyKey;
@@ -27,9 +32,11 @@ abstract contract ShuffleVerifier is PlonkVerifier {
         // The scalar field of BN254.
         uint256 r = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
 
-        // Load the proof.
+        require(_proof.length == SHUFFLE_PROOF_WORDS * 32 + 1 && uint8(_proof[0]) == SHUFFLE_PROOF_TAG, "SV00");
+
+        // Load the proof after the layout tag.
         assembly {
-            let data_ptr := add(calldataload(0x04), 0x24)
+            let data_ptr := add(_proof.offset, 1)
             mstore(CM_W0_X_LOC, mod(calldataload(add(data_ptr, 0x00)), r))
             mstore(CM_W0_Y_LOC, mod(calldataload(add(data_ptr, 0x20)), r))
             mstore(CM_W1_X_LOC, mod(calldataload(add(data_ptr, 0x40)), r))
@@ -0,0 +1,533 @@
+// SPDX-License-Identifier: UNLICENSED
+// Generated file from uzkge/gen-params, DONOT edit!
+pragma solidity ^0.8.20;
+
+contract PlonkSquareVerifier {
+    error InvalidInput();
+    error PrecompileFailed();
+
+    /// The opening of a batch of polynomials at one point.
+    struct Opening {
+        uint256[2] cm;
+        uint256 eval;
+    }
+
+    struct Transcript {
+        bytes data;
+        uint256 len;
+    }
+
+    struct Challenges {
+        uint256 beta;
+        uint256 gamma;
+        uint256 alpha;
+        uint256 zeta;
+        uint256 u;
+        uint256 zH;
+        uint256 l1;
+        uint256[17] alphaPows;
+    }
+
+    /// The scalar field of BN254.
+    uint256 private constant R = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+    /// The base field of BN254.
+    uint256 private constant Q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+
+    uint256 private constant CS_SIZE = 4;
+    uint256 private constant MAX_DEGREE = 6;
+    uint256 private constant ROOT = 0x30644e72e131a029048b6e193fd841045cea24f6fd736bec231204708f703636;
+    uint256 private constant ANEMOI_GENERATOR = 0x0000000000000000000000000000000000000000000000000000000000000000;
+    uint256 private constant ANEMOI_GENERATOR_INV = 0x0000000000000000000000000000000000000000000000000000000000000000;
+    uint256 private constant K_0 = 0x0000000000000000000000000000000000000000000000000000000000000001;
+    uint256 private constant K_1 = 0x2f8dd1f1a7583c42c4e12a44e110404c73ca6c94813f85835da4fb7bb1301d4a;
+    uint256 private constant K_2 = 0x2042a587a90c187b0a087c03e29c968b950b1db26d5c82d666905a6895790c0a;
+    uint256 private constant K_3 = 0x2db4944e13e6e33cf0ef0734796ff332d73b5fa160dca733bf529e9b758e4960;
+    uint256 private constant K_4 = 0x1d9e3a4aaf01052d9925138dc6d7d05aa614e311040142458b045d0053d22f46;
+
+    /// The SRS: the generator of G1, and [1]_2, [x]_2 with the imaginary parts first.
+    uint256 private constant G1_X = 0x0000000000000000000000000000000000000000000000000000000000000001;
+    uint256 private constant G1_Y = 0x0000000000000000000000000000000000000000000000000000000000000002;
+    uint256 private constant G2_X1 = 0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2;
+    uint256 private constant G2_X0 = 0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed;
+    uint256 private constant G2_Y1 = 0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b;
+    uint256 private constant G2_Y0 = 0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa;
+    uint256 private constant G2_TAU_X1 = 0x2b96e298c76123fcb52238070d701ca78008f6f99c51d7b380fc9525f8f69e14;
+    uint256 private constant G2_TAU_X0 = 0x0d975ac583ea8ae387b6a659a950325d2f1d82bb34cad21b30a8bd9f3a6fa037;
+    uint256 private constant G2_TAU_Y1 = 0x1b8dae74560fb75c484af9e831fe26607b4f8d4608b33ac01d4f0e0733bea30a;
+    uint256 private constant G2_TAU_Y0 = 0x2ef88213530827ae7eb05ca0265859afb7811ce235e4d2d13e5b6bb6b1f2be7f;
+
+    /// The layout of the proof, in words of `PlonkProof::to_bytes_be` after the layout tag.
+    uint256 private constant PROOF_TAG = 0;
+    uint256 private constant PROOF_WORDS = 41;
+    uint256 private constant P_CM_W = 0;
+    uint256 private constant P_CM_T = 10;
+    uint256 private constant P_CM_Z = 20;
+    uint256 private constant P_PRK_3 = 22;
+    uint256 private constant P_PRK_4 = 23;
+    uint256 private constant P_W = 24;
+    uint256 private constant P_W_OMEGA = 29;
+    uint256 private constant P_Z_OMEGA = 32;
+    uint256 private constant P_S = 33;
+    uint256 private constant P_OPENING_ZETA = 37;
+    uint256 private constant P_OPENING_ZETA_OMEGA = 39;
+
+    /// The layout of the verifier key, in points of `VK_POINTS`.
+    uint256 private constant N_Q = 10;
+    uint256 private constant VK_Q = 0;
+    uint256 private constant VK_S = 10;
+    uint256 private constant VK_QB = 15;
+    uint256 private constant VK_PRK = 16;
+    bytes private constant VK_POINTS = hex"000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000056cbd47dfe5e9ac39c8152e20d0d9b01396599ffe0967302774967bffce5cd724b871fdeb9c6e79a1344bad177cbc6bc98ca3552feb4ffa559b1b72ddf875ea0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000029816dae5841e34e06ef2adac70b5728c5e4f4fe2f37749728665203064f2ce704d73ab3cf9a400889f669f6d8a365ca2c18f6f6fa18ca6b6093a4271ee58ba41c7ab430cb6a8caf0ed33f26d170f15604a14f4670d13d96a79adf1c97618df8211e2288358d9f6bf7d652f6ad63bcf4fabff28565e123513c2b450c0028a22c0700a2eaeecaca37c6cdae09e77a5339f896ef558b928970669eb25a491005c90b4f902da8de7802702a7e42006231d76a5d4ab5a24dcace7f519fcf96d5e97121b732a204720a68a4ca013504852254187f4e98d8300dc0a5c1aa56d6da30e20b593cdca1d2b2b02ed0b68f02bcceb3e51d333f1e852644353a0926209501db1e790ec416d804379a2fea83ddd606bd71eef4cd71a6bb047f441bba538236c330399e79020ab1069af7a39971468e50a4cf0fc219875fe655d8f188f4deccca1013b745c625cda6d3468785db461804b70e4283762124220b9522b782fec38218108d99b4abd22b646b5013d718fd88f4a7512798cc30597780d81f387100c71d98c546b18f8eba36bbd9e7e13b98b6b48a3b382d642dfc4a20d44a01d08c8625ef732c89acb3062124e1d74a2b20ed9143b0a163f9360a3aea3216da8ea0020000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    /// The public inputs: \omega^j and the lagrange constant c_j of their constraints.
+    uint256 private constant NUM_PUBLIC_INPUTS = 1;
+    bytes private constant PI_ROOTS = hex"0000000000000000b3c4d79d41a91758cb49c3517c4604a520cff123608fc9cb";
+    bytes private constant PI_LAGRANGE_CONSTANTS = hex"0c19139cb84c680a9b054754f0ca9bed7cdf6ae67d7fdd4d992c79add423f273";
+
+    /// The transcript after the domain separator and the verifier key.
+    bytes private constant TRANSCRIPT_PREFIX = hex"0000000000000000000000000000000000000000000000000000000054657374000000000000000000000000000000000000000000000000000000504c4f4e4b000000000000000000000000000000000000000000000000000000000000000430644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000056cbd47dfe5e9ac39c8152e20d0d9b01396599ffe0967302774967bffce5cd724b871fdeb9c6e79a1344bad177cbc6bc98ca3552feb4ffa559b1b72ddf875ea0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000029816dae5841e34e06ef2adac70b5728c5e4f4fe2f37749728665203064f2ce704d73ab3cf9a400889f669f6d8a365ca2c18f6f6fa18ca6b6093a4271ee58ba41c7ab430cb6a8caf0ed33f26d170f15604a14f4670d13d96a79adf1c97618df8211e2288358d9f6bf7d652f6ad63bcf4fabff28565e123513c2b450c0028a22c0700a2eaeecaca37c6cdae09e77a5339f896ef558b928970669eb25a491005c90b4f902da8de7802702a7e42006231d76a5d4ab5a24dcace7f519fcf96d5e97121b732a204720a68a4ca013504852254187f4e98d8300dc0a5c1aa56d6da30e20b593cdca1d2b2b02ed0b68f02bcceb3e51d333f1e852644353a0926209501db1e790ec416d804379a2fea83ddd606bd71eef4cd71a6bb047f441bba538236c330399e79020ab1069af7a39971468e50a4cf0fc219875fe655d8f188f4deccca1013b745c625cda6d3468785db461804b70e4283762124220b9522b782fec38218108d99b4abd22b646b5013d718fd88f4a7512798cc30597780d81f387100c71d98c546b18f8eba36bbd9e7e13b98b6b48a3b382d642dfc4a20d44a01d08c8625ef732c89acb3062124e1d74a2b20ed9143b0a163f9360a3aea3216da8ea00230644e72e131a029048b6e193fd841045cea24f6fd736bec231204708f70363600000000000000000000000000000000000000000000000000000000000000012f8dd1f1a7583c42c4e12a44e110404c73ca6c94813f85835da4fb7bb1301d4a2042a587a90c187b0a087c03e29c968b950b1db26d5c82d666905a6895790c0a2db4944e13e6e33cf0ef0734796ff332d73b5fa160dca733bf529e9b758e49601d9e3a4aaf01052d9925138dc6d7d05aa614e311040142458b045d0053d22f46";
+    uint256 private constant TRANSCRIPT_PREFIX_LENGTH = 1280;
+    uint256 private constant TRANSCRIPT_CAPACITY = 1056;
+    /// "New PCS-Batch-Eval Protocol" in one slot.
+    uint256 private constant PCS_BATCH_DOMAIN = 0x00000000004e6577205043532d42617463682d4576616c2050726f746f636f6c;
+
+    /// Verify a Plonk proof, encoded by `PlonkProof::to_bytes_be`.
+    function verify(bytes calldata proof, uint256[] calldata publicInputs) external view returns (bool) {
+        if (
+            proof.length != PROOF_WORDS * 32 + 1 || uint8(proof[0]) != PROOF_TAG
+                || publicInputs.length != NUM_PUBLIC_INPUTS
+        ) {
+            revert InvalidInput();
+        }
+        uint256[] memory p = new uint256[](PROOF_WORDS);
+        assembly {
+            calldatacopy(add(p, 0x20), add(proof.offset, 1), mul(PROOF_WORDS, 32))
+        }
+        for (uint256 i = P_PRK_3; i < P_OPENING_ZETA; i++) {
+            if (p[i] >= R) {
+                revert InvalidInput();
+            }
+        }
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            if (publicInputs[i] >= R) {
+                revert InvalidInput();
+            }
+        }
+
+        // 1. compute all challenges such as gamma, beta, alpha, zeta and u.
+        Transcript memory t = _initTranscript(publicInputs);
+        Challenges memory c = _computeChallenges(t, p);
+
+        // 2. compute Z_h(\zeta) and L_1(\zeta).
+        c.zH = _sub(_pow(c.zeta, CS_SIZE), 1);
+        c.l1 = _mul(c.zH, _inverse(_sub(c.zeta, 1)));
+
+        // 3. compute PI(\zeta).
+        uint256 piEval = _evalPublicInputs(publicInputs, c.zeta, c.zH);
+
+        // 4. derive the linearization polynomial commitment.
+        bytes memory vk = VK_POINTS;
+        Opening memory r;
+        r.eval = _rEvalZeta(p, c, piEval);
+        _rCommitment(r.cm, p, c, vk);
+
+        // 5. verify opening proofs.
+        Opening memory atZeta = _batchZeta(p, vk, r, _batchChallenge(t, c.zeta));
+        Opening memory atZetaOmega = _batchZetaOmega(t, p, c);
+        return _verifyOpenings(p, c, atZeta, atZetaOmega);
+    }
+
+    function _initTranscript(uint256[] calldata publicInputs) private pure returns (Transcript memory t) {
+        t.data = bytes.concat(TRANSCRIPT_PREFIX, new bytes(TRANSCRIPT_CAPACITY));
+        t.len = TRANSCRIPT_PREFIX_LENGTH;
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            _append(t, publicInputs[i]);
+        }
+    }
+
+    function _computeChallenges(Transcript memory t, uint256[] memory p) private pure returns (Challenges memory c) {
+        // the witness commitments, followed by the witness selector commitments.
+        _appendWords(t, p, P_CM_W, P_CM_T - P_CM_W);
+        c.beta = _challenge(t);
+        _appendByte(t, 0x01);
+        c.gamma = _challenge(t);
+
+        _appendWords(t, p, P_CM_Z, 2);
+        c.alpha = _challenge(t);
+        _appendWords(t, p, P_CM_T, P_CM_Z - P_CM_T);
+
+        c.zeta = _challenge(t);
+        _appendWords(t, p, P_W, 5);
+        _appendWords(t, p, P_S, 4);
+        _append(t, p[P_PRK_3]);
+        _append(t, p[P_PRK_4]);
+        _append(t, p[P_Z_OMEGA]);
+        _appendWords(t, p, P_W_OMEGA, 3);
+        c.u = _challenge(t);
+
+        c.alphaPows[0] = 1;
+        for (uint256 i = 1; i < 17; i++) {
+            c.alphaPows[i] = _mul(c.alphaPows[i - 1], c.alpha);
+        }
+    }
+
+    /// Compute sum_i pi_i c_j (\zeta^n - 1) / (\zeta - \omega^j), where j is the constraint of pi_i.
+    function _evalPublicInputs(uint256[] calldata publicInputs, uint256 zeta, uint256 zH)
+        private
+        view
+        returns (uint256 r)
+    {
+        bytes memory roots = PI_ROOTS;
+        bytes memory constants = PI_LAGRANGE_CONSTANTS;
+
+        // batch inversion of the denominators.
+        uint256[] memory prods = new uint256[](NUM_PUBLIC_INPUTS);
+        uint256 acc = 1;
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            prods[i] = acc;
+            acc = _mul(acc, _sub(zeta, _word(roots, i)));
+        }
+        acc = _inverse(acc);
+        for (uint256 i = NUM_PUBLIC_INPUTS; i > 0; i--) {
+            // acc * prods[i - 1] is the inverse of the i-th denominator.
+            r = _add(r, _mul(_mul(_word(constants, i - 1), _mul(acc, prods[i - 1])), publicInputs[i - 1]));
+            acc = _mul(acc, _sub(zeta, _word(roots, i - 1)));
+        }
+        r = _mul(r, zH);
+    }
+
+    /// Evaluate the r polynomial at point \zeta.
+    function _rEvalZeta(uint256[] memory p, Challenges memory c, uint256 piEval) private pure returns (uint256 r) {
+        uint256 term1 = _mul(c.alpha, p[P_Z_OMEGA]);
+        for (uint256 i = 0; i < 4; i++) {
+            term1 = _mul(term1, _add(_add(p[P_W + i], _mul(c.beta, p[P_S + i])), c.gamma));
+        }
+        term1 = _mul(term1, _add(p[P_W + 4], c.gamma));
+
+        r = _add(term1, _mul(c.l1, c.alphaPows[2]));
+        r = _sub(r, piEval);
+        r = _add(r, _anemoiTerms(p, c.alphaPows));
+        r = _add(r, _anemoiNextTerms(p, c.alphaPows));
+    }
+
+    /// The Anemoi terms of the r polynomial that use the third round key.
+    function _anemoiTerms(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 r) {
+        uint256 w3w0 = _add(p[P_W + 3], p[P_W]);
+        uint256 w2w1 = _add(p[P_W + 2], p[P_W + 1]);
+        uint256 tmp = _add(_add(w3w0, _mul(ANEMOI_GENERATOR, w2w1)), p[P_PRK_3]);
+        uint256 pow5 = _pow5(_sub(tmp, p[P_W_OMEGA + 2]));
+        // w3 + 2 * w0 + g * (w2 + 2 * w1)
+        uint256 rhs = _add(_add(w3w0, p[P_W]), _mul(ANEMOI_GENERATOR, _add(w2w1, p[P_W + 1])));
+
+        r = _mul(_mul(a[6], p[P_PRK_3]), _sub(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), rhs));
+        tmp = p[P_W_OMEGA + 2];
+        rhs = _add(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), ANEMOI_GENERATOR_INV);
+        r = _add(r, _mul(_mul(a[8], p[P_PRK_3]), _sub(rhs, p[P_W_OMEGA])));
+    }
+
+    /// The Anemoi terms of the r polynomial that use the fourth round key.
+    function _anemoiNextTerms(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 r) {
+        uint256 gSquarePlusOne = _add(_mul(ANEMOI_GENERATOR, ANEMOI_GENERATOR), 1);
+        uint256 w3w0 = _add(p[P_W + 3], p[P_W]);
+        uint256 w2w1 = _add(p[P_W + 2], p[P_W + 1]);
+        uint256 tmp = _add(_add(_mul(ANEMOI_GENERATOR, w3w0), _mul(gSquarePlusOne, w2w1)), p[P_PRK_4]);
+        uint256 pow5 = _pow5(_sub(tmp, p[P_W + 4]));
+        // g * (w3 + 2 * w0) + (g^2 + 1) * (w2 + 2 * w1)
+        uint256 rhs = _add(
+            _mul(ANEMOI_GENERATOR, _add(w3w0, p[P_W])),
+            _mul(gSquarePlusOne, _add(w2w1, p[P_W + 1]))
+        );
+
+        r = _mul(_mul(a[7], p[P_PRK_3]), _sub(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), rhs));
+        tmp = p[P_W + 4];
+        rhs = _add(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), ANEMOI_GENERATOR_INV);
+        r = _add(r, _mul(_mul(a[9], p[P_PRK_3]), _sub(rhs, p[P_W_OMEGA + 1])));
+    }
+
+    /// Compute the commitment of the r polynomial.
+    function _rCommitment(uint256[2] memory acc, uint256[] memory p, Challenges memory c, bytes memory vk)
+        private
+        view
+    {
+        uint256[N_Q] memory m = _selectorMultipliers(p);
+        for (uint256 i = 0; i < N_Q; i++) {
+            _ecMulAddVk(acc, vk, VK_Q + i, m[i]);
+        }
+
+        _ecMulAdd(acc, p[P_CM_Z], p[P_CM_Z + 1], _zScalar(p, c));
+        _ecMulAddVk(acc, vk, VK_S + 4, _neg(_sLastScalar(p, c)));
+        _ecMulAddVk(acc, vk, VK_QB, _qbScalar(p, c.alphaPows));
+        _ecMulAddVk(acc, vk, VK_PRK, _mul(p[P_PRK_3], c.alphaPows[6]));
+        _ecMulAddVk(acc, vk, VK_PRK + 1, _mul(p[P_PRK_3], c.alphaPows[7]));
+        _tCommitment(acc, p, c);
+    }
+
+    /// - Z_h(\zeta) * (t_1(X) + \zeta^{n+2} * t_2(X) + \zeta^{2(n+2)} * t_3(X) + ...)
+    function _tCommitment(uint256[2] memory acc, uint256[] memory p, Challenges memory c) private view {
+        uint256 factor = _pow(c.zeta, MAX_DEGREE);
+        uint256 exponent = _neg(c.zH);
+        for (uint256 i = P_CM_T; i < P_CM_Z; i += 2) {
+            _ecMulAdd(acc, p[i], p[i + 1], exponent);
+            exponent = _mul(exponent, factor);
+        }
+    }
+
+    /// The coefficients of the selectors in the gate identity.
+    function _selectorMultipliers(uint256[] memory p) private pure returns (uint256[N_Q] memory m) {
+        uint256 w0 = p[P_W];
+        uint256 w1 = p[P_W + 1];
+        uint256 w2 = p[P_W + 2];
+        uint256 w3 = p[P_W + 3];
+        uint256 w4 = p[P_W + 4];
+        m[0] = w0;
+        m[1] = w1;
+        m[2] = w2;
+        m[3] = w3;
+        m[4] = mulmod(w0, w1, R);
+        m[5] = mulmod(w2, w3, R);
+        m[6] = 1;
+        m[7] = mulmod(mulmod(m[4], m[5], R), w4, R);
+        m[8] = _neg(w4);
+        m[9] = addmod(mulmod(w0, w0, R), R - w4, R);
+    }
+
+    /// alpha * prod_i (w_i(\zeta) + beta * k_i * \zeta + gamma) + alpha^2 * L_1(\zeta)
+    function _zScalar(uint256[] memory p, Challenges memory c) private pure returns (uint256 z) {
+        uint256 betaZeta = _mul(c.beta, c.zeta);
+        z = c.alpha;
+        z = _mul(z, _add(_add(p[P_W + 0], _mul(K_0, betaZeta)), c.gamma));
+        z = _mul(z, _add(_add(p[P_W + 1], _mul(K_1, betaZeta)), c.gamma));
+        z = _mul(z, _add(_add(p[P_W + 2], _mul(K_2, betaZeta)), c.gamma));
+        z = _mul(z, _add(_add(p[P_W + 3], _mul(K_3, betaZeta)), c.gamma));
+        z = _mul(z, _add(_add(p[P_W + 4], _mul(K_4, betaZeta)), c.gamma));
+        z = _add(z, _mul(c.l1, c.alphaPows[2]));
+    }
+
+    /// alpha * z(\zeta\omega) * beta * prod_{i < 4} (w_i(\zeta) + beta * s_i(\zeta) + gamma)
+    function _sLastScalar(uint256[] memory p, Challenges memory c) private pure returns (uint256 s) {
+        s = _mul(_mul(c.alpha, p[P_Z_OMEGA]), c.beta);
+        for (uint256 i = 0; i < 4; i++) {
+            s = _mul(s, _add(_add(p[P_W + i], _mul(c.beta, p[P_S + i])), c.gamma));
+        }
+    }
+    /// w1 * (w1 - 1) * alpha^3 + w2 * (w2 - 1) * alpha^4 + w3 * (w3 - 1) * alpha^5
+    function _qbScalar(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 s) {
+        uint256 w1 = p[P_W + 1];
+        uint256 w2 = p[P_W + 2];
+        uint256 w3 = p[P_W + 3];
+        s = _mul(_mul(w1, _sub(w1, 1)), a[3]);
+        s = _add(s, _mul(_mul(w2, _sub(w2, 1)), a[4]));
+        s = _add(s, _mul(_mul(w3, _sub(w3, 1)), a[5]));
+    }
+
+    /// Batch the openings at \zeta.
+    function _batchZeta(uint256[] memory p, bytes memory vk, Opening memory r, uint256 alpha)
+        private
+        view
+        returns (Opening memory o)
+    {
+        uint256 m = 1;
+        for (uint256 i = 0; i < 5; i++) {
+            _accumulate(o, p[P_CM_W + 2 * i], p[P_CM_W + 2 * i + 1], p[P_W + i], m);
+            m = _mul(m, alpha);
+        }
+        for (uint256 i = 0; i < 4; i++) {
+            _accumulateVk(o, vk, VK_S + i, p[P_S + i], m);
+            m = _mul(m, alpha);
+        }
+        _accumulateVk(o, vk, VK_PRK + 2, p[P_PRK_3], m);
+        m = _mul(m, alpha);
+        _accumulateVk(o, vk, VK_PRK + 3, p[P_PRK_4], m);
+        m = _mul(m, alpha);
+        _accumulate(o, r.cm[0], r.cm[1], r.eval, m);
+    }
+
+    /// Batch the openings at \zeta\omega.
+    function _batchZetaOmega(Transcript memory t, uint256[] memory p, Challenges memory c)
+        private
+        view
+        returns (Opening memory o)
+    {
+        uint256 alpha = _batchChallenge(t, _mul(c.zeta, ROOT));
+        _accumulate(o, p[P_CM_Z], p[P_CM_Z + 1], p[P_Z_OMEGA], 1);
+        uint256 m = alpha;
+        for (uint256 i = 0; i < 3; i++) {
+            _accumulate(o, p[P_CM_W + 2 * i], p[P_CM_W + 2 * i + 1], p[P_W_OMEGA + i], m);
+            m = _mul(m, alpha);
+        }
+    }
+
+    function _batchChallenge(Transcript memory t, uint256 point) private pure returns (uint256) {
+        _append(t, PCS_BATCH_DOMAIN);
+        _append(t, R);
+        _append(t, MAX_DEGREE);
+        _append(t, point);
+        return _challenge(t);
+    }
+
+    /// Check both openings with the random combination of u, in a single pairing.
+    function _verifyOpenings(uint256[] memory p, Challenges memory c, Opening memory atZeta, Opening memory atZetaOmega)
+        private
+        view
+        returns (bool)
+    {
+        uint256[2] memory left = [p[P_OPENING_ZETA], p[P_OPENING_ZETA + 1]];
+        _ecMulAdd(left, p[P_OPENING_ZETA_OMEGA], p[P_OPENING_ZETA_OMEGA + 1], c.u);
+        uint256[2] memory right = _openingsRight(p, c, atZeta, atZetaOmega);
+
+        // e(left, [x]_2) * e(-right, [1]_2) == 1
+        uint256[12] memory input = [
+            left[0],
+            left[1],
+            G2_TAU_X1,
+            G2_TAU_X0,
+            G2_TAU_Y1,
+            G2_TAU_Y0,
+            right[0],
+            (Q - right[1]) % Q,
+            G2_X1,
+            G2_X0,
+            G2_Y1,
+            G2_Y0
+        ];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x08, input, 0x180, out, 0x20)
+        }
+        return ok && out[0] == 1;
+    }
+
+    /// cm + u * cm' + \zeta * W + u * \zeta\omega * W' - (eval + u * eval') * [1]_1
+    function _openingsRight(uint256[] memory p, Challenges memory c, Opening memory atZeta, Opening memory atZetaOmega)
+        private
+        view
+        returns (uint256[2] memory right)
+    {
+        uint256 uZetaOmega = _mul(c.u, _mul(c.zeta, ROOT));
+        right[0] = atZeta.cm[0];
+        right[1] = atZeta.cm[1];
+        _ecMulAdd(right, atZetaOmega.cm[0], atZetaOmega.cm[1], c.u);
+        _ecMulAdd(right, p[P_OPENING_ZETA], p[P_OPENING_ZETA + 1], c.zeta);
+        _ecMulAdd(right, p[P_OPENING_ZETA_OMEGA], p[P_OPENING_ZETA_OMEGA + 1], uZetaOmega);
+        _ecMulAdd(right, G1_X, G1_Y, _neg(_add(atZeta.eval, _mul(c.u, atZetaOmega.eval))));
+    }
+
+    function _accumulate(Opening memory o, uint256 x, uint256 y, uint256 eval, uint256 m) private view {
+        _ecMulAdd(o.cm, x, y, m);
+        o.eval = _add(o.eval, _mul(eval, m));
+    }
+
+    function _accumulateVk(Opening memory o, bytes memory vk, uint256 i, uint256 eval, uint256 m) private view {
+        _ecMulAddVk(o.cm, vk, i, m);
+        o.eval = _add(o.eval, _mul(eval, m));
+    }
+
+    function _ecMulAddVk(uint256[2] memory acc, bytes memory vk, uint256 i, uint256 s) private view {
+        _ecMulAdd(acc, _word(vk, 2 * i), _word(vk, 2 * i + 1), s);
+    }
+
+    /// acc += s * (x, y)
+    function _ecMulAdd(uint256[2] memory acc, uint256 x, uint256 y, uint256 s) private view {
+        uint256[3] memory mulInput = [x, y, s];
+        uint256[4] memory addInput;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x07, mulInput, 0x60, add(addInput, 0x40), 0x40)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+        addInput[0] = acc[0];
+        addInput[1] = acc[1];
+        assembly {
+            ok := staticcall(gas(), 0x06, addInput, 0x80, acc, 0x40)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+    }
+
+    function _append(Transcript memory t, uint256 v) private pure {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        assembly {
+            mstore(add(add(data, 0x20), len), v)
+        }
+        t.len = len + 32;
+    }
+
+    function _appendWords(Transcript memory t, uint256[] memory p, uint256 start, uint256 n) private pure {
+        for (uint256 i = start; i < start + n; i++) {
+            _append(t, p[i]);
+        }
+    }
+
+    function _appendByte(Transcript memory t, uint8 b) private pure {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        assembly {
+            mstore8(add(add(data, 0x20), len), b)
+        }
+        t.len = len + 1;
+    }
+
+    /// Hash the transcript into a challenge, and restart the transcript from the challenge.
+    function _challenge(Transcript memory t) private pure returns (uint256 c) {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        bytes32 h;
+        assembly {
+            h := keccak256(add(data, 0x20), len)
+        }
+        c = uint256(h) % R;
+        assembly {
+            mstore(add(data, 0x20), c)
+        }
+        t.len = 32;
+    }
+
+    function _word(bytes memory b, uint256 i) private pure returns (uint256 w) {
+        assembly {
+            w := mload(add(b, mul(add(i, 1), 0x20)))
+        }
+    }
+
+    function _add(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a, b, R);
+    }
+
+    function _sub(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a, R - b, R);
+    }
+
+    function _mul(uint256 a, uint256 b) private pure returns (uint256) {
+        return mulmod(a, b, R);
+    }
+
+    function _neg(uint256 a) private pure returns (uint256) {
+        return (R - a) % R;
+    }
+
+    function _pow5(uint256 a) private pure returns (uint256) {
+        uint256 a2 = mulmod(a, a, R);
+        return mulmod(mulmod(a2, a2, R), a, R);
+    }
+
+    function _pow(uint256 base, uint256 e) private view returns (uint256 r) {
+        uint256[6] memory input = [uint256(0x20), 0x20, 0x20, base, e, R];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x05, input, 0xc0, out, 0x20)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+        r = out[0];
+    }
+
+    function _inverse(uint256 a) private view returns (uint256) {
+        return _pow(a, R - 2);
+    }
+}
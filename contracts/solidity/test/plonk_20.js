@@ -10,7 +10,7 @@ describe("Plonk 20 Verify Contract", function () {
     const vk2_address = await vk2.getAddress();
     const service = await ethers.deployContract("ShuffleService", [vk1_address, vk2_address, 20]);
 
-    const proof = "0x15891aac880c74f937aa70a36723a980b72bbede6d8331a0bc26f641a0de04531115e6f9d65695c7393698f1714abac584c57de57bffad368d505c822a281ba4227596fcbb4ae27b7af9e487dce3e3454128f8f6e7bb8ac13696af67d81964341537795f17f7e70e674c7a89fed99da977ad88f810a4f609318d14105ea7a615131adc0a9553a66291dac1549199169a36831475daa209f9477272d8ea5d71740b28ff18c2e0f3349491f9f0582033bd44e1791bca02aeb1e08aebb1d911df5b09c97db95a4871489c9ca4b30e083d4161b21b7ad24afa19aaaa81c692fdc50c03c12b7fda8245fcc44e7929715358db0ef4dea768de5bab0272f6d0317098b20672843381213993ad65bc83ed2638f03fb03bb6cee3596b5353368ed58ea23113b51d459cf815d465f4623780fb4125ca47fe6153be0a0dde13b190b937e1b2297fcb279598565a4c729b937d59b38083bf1b08fef0a57f6383f38a274ee8991a235a2de319f0fd8cb6dc2b4ac4d4ce00421381a90bb5406769e6f3ab6f801628775afa1db0d9fb732cd3b52522b635b3b663cdf2a822b555ba8dea66273d9a0b6c33af46a95f85326b48caad29922e7ccdd9984f02cf41e041e5ef37a392e20f67fd26f9119e29df76ad1a3d73301c00b42e3ea48bd928ab833f643e29f1051187cacfdcc35d1f461e7ef2ee5ed44ca35d704e349adbaef833af56a1cb92f7301bd9d884772e6922502ae1224b9c8225fa3c348793e68d03e2e054ab2bd6d3000a7c18b708cb752d49efa061d28ee158418742d5855fbec6a515d88a585b1a170c419d9e9ac9f22b9f4c71bc5532eb9c6f77f6ee9f7714a0a0e6f815fc12361455e132ba26f2dc245b8ec23219d0b547a7d38355e3acd8a1b51d2ad5c141c521a0ae723836ff1c132e4f070e3e418179d1b38bf42e6d2cadac6196ad8d3acd207117e9bf4d3da68c2151c968912306a326f0c437ad045ff704f2d34a3e2f120eb0139867eaae4b9c14628567f0d3925e249599f471b2220387ca275ddf09c712b0b1ac510b273127405273d2212b079bdf0c001d182205b4d27561dc9ef37311c26d31217fb77e43fa3d684735bf68162ec92d3c52dd80dba03f74cf0e66970ad415b1a968a57e71a61f6b5a7f8dded389656d2523cf10e2e5fd00692950182e189ba71ca4bbd128154dd86dc65e5cc812245a46b56063ac8e2423c041389d2b5b3adecbe8274b6724ba54c74d105138ea209fd1131b0125861b2c678cec76000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ee0fabae5212b28a86ed2cc5204d9a718cd99c1d8467fca87a4aa0f65f65c7404655dfb0e277630b9e769576543237b88b54a8a9a6e720fad05878b6034e7291c2f0091182f40cb4bd35ba023a5569b2bf52d2adc79c4ba431511c94117e2a2279cea6584cd77a4f5a39b41b048867a9888c3592dcad2102b19e7ea441c8ae11de4a78aae7277d8d105df89fd3e894bf68a15ea8e6c09ad92f39c9b6222e07b2c1ee1cdf9bb04346a293dfc12e00f02f5d52ca2f81cb7fcada60bcdc2d223b215c4dba953570872f7fd82e93443c23947de1e534ee8a2cc67c4baaec660186917067977b3185b04ca94670b59a9585f2b6e2a74f28231729d9c3d10c9ab4f9c125ff4eb965599a01494f4fd93c708e5e4e745f5370cc770fa7f1f32c18efc52062369cdc4da342eb2d29f982ea496ad9f78c0889e86ff3a8dd5cae512cf028e08b49369c73ac4bf0705737b37ed49dbba8efbd9bce8d37e6bacc0dd6062c7c605490faefa9866bbf770f1535fae0910126e8dcd383b701b0da3fc129a452bec0823389ae2a32b7df276ef5d8e90c8d60b2cccca48fed6b9d7bdc61b08a29b780cb0e9faa64c764a9e3a11de6e5f3c9aa221c06fe723f20ed2ca760a69fdc1f32f7328d59cfbdc84cfb9061bb8c0452307127c0a4f3b7e19eff060aa6df48e8e01ae1acf6f9bee75a0b492cca11eecde351aa6eb78247b6c19c997001d5e0fc02a1c2c0988835b25e325f11405b1d5099312c2df8edcad269d95f8e0117d2cf9182da3daeaab41cf23be26252a289a54892b7a3ecadc50ab3d8175af840637e6113cde42693544975e9f87da6a6f7cb30d9ff11b73be060332609568bb8a61662a52cbd8d8920ef0ba74b47d376c4a6d055e2ebf9907fae297f1404072b6d4c0072ee4e97cdd713a104a0fae53b188077d235f185d4a8c5eacc92ee48884679a";
+    const proof = "0x0115891aac880c74f937aa70a36723a980b72bbede6d8331a0bc26f641a0de04531115e6f9d65695c7393698f1714abac584c57de57bffad368d505c822a281ba4227596fcbb4ae27b7af9e487dce3e3454128f8f6e7bb8ac13696af67d81964341537795f17f7e70e674c7a89fed99da977ad88f810a4f609318d14105ea7a615131adc0a9553a66291dac1549199169a36831475daa209f9477272d8ea5d71740b28ff18c2e0f3349491f9f0582033bd44e1791bca02aeb1e08aebb1d911df5b09c97db95a4871489c9ca4b30e083d4161b21b7ad24afa19aaaa81c692fdc50c03c12b7fda8245fcc44e7929715358db0ef4dea768de5bab0272f6d0317098b20672843381213993ad65bc83ed2638f03fb03bb6cee3596b5353368ed58ea23113b51d459cf815d465f4623780fb4125ca47fe6153be0a0dde13b190b937e1b2297fcb279598565a4c729b937d59b38083bf1b08fef0a57f6383f38a274ee8991a235a2de319f0fd8cb6dc2b4ac4d4ce00421381a90bb5406769e6f3ab6f801628775afa1db0d9fb732cd3b52522b635b3b663cdf2a822b555ba8dea66273d9a0b6c33af46a95f85326b48caad29922e7ccdd9984f02cf41e041e5ef37a392e20f67fd26f9119e29df76ad1a3d73301c00b42e3ea48bd928ab833f643e29f1051187cacfdcc35d1f461e7ef2ee5ed44ca35d704e349adbaef833af56a1cb92f7301bd9d884772e6922502ae1224b9c8225fa3c348793e68d03e2e054ab2bd6d3000a7c18b708cb752d49efa061d28ee158418742d5855fbec6a515d88a585b1a170c419d9e9ac9f22b9f4c71bc5532eb9c6f77f6ee9f7714a0a0e6f815fc12361455e132ba26f2dc245b8ec23219d0b547a7d38355e3acd8a1b51d2ad5c141c521a0ae723836ff1c132e4f070e3e418179d1b38bf42e6d2cadac6196ad8d3acd207117e9bf4d3da68c2151c968912306a326f0c437ad045ff704f2d34a3e2f120eb0139867eaae4b9c14628567f0d3925e249599f471b2220387ca275ddf09c712b0b1ac510b273127405273d2212b079bdf0c001d182205b4d27561dc9ef37311c26d31217fb77e43fa3d684735bf68162ec92d3c52dd80dba03f74cf0e66970ad415b1a968a57e71a61f6b5a7f8dded389656d2523cf10e2e5fd00692950182e189ba71ca4bbd128154dd86dc65e5cc812245a46b56063ac8e2423c041389d2b5b3adecbe8274b6724ba54c74d105138ea209fd1131b0125861b2c678cec76000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ee0fabae5212b28a86ed2cc5204d9a718cd99c1d8467fca87a4aa0f65f65c7404655dfb0e277630b9e769576543237b88b54a8a9a6e720fad05878b6034e7291c2f0091182f40cb4bd35ba023a5569b2bf52d2adc79c4ba431511c94117e2a2279cea6584cd77a4f5a39b41b048867a9888c3592dcad2102b19e7ea441c8ae11de4a78aae7277d8d105df89fd3e894bf68a15ea8e6c09ad92f39c9b6222e07b2c1ee1cdf9bb04346a293dfc12e00f02f5d52ca2f81cb7fcada60bcdc2d223b215c4dba953570872f7fd82e93443c23947de1e534ee8a2cc67c4baaec660186917067977b3185b04ca94670b59a9585f2b6e2a74f28231729d9c3d10c9ab4f9c125ff4eb965599a01494f4fd93c708e5e4e745f5370cc770fa7f1f32c18efc52062369cdc4da342eb2d29f982ea496ad9f78c0889e86ff3a8dd5cae512cf028e08b49369c73ac4bf0705737b37ed49dbba8efbd9bce8d37e6bacc0dd6062c7c605490faefa9866bbf770f1535fae0910126e8dcd383b701b0da3fc129a452bec0823389ae2a32b7df276ef5d8e90c8d60b2cccca48fed6b9d7bdc61b08a29b780cb0e9faa64c764a9e3a11de6e5f3c9aa221c06fe723f20ed2ca760a69fdc1f32f7328d59cfbdc84cfb9061bb8c0452307127c0a4f3b7e19eff060aa6df48e8e01ae1acf6f9bee75a0b492cca11eecde351aa6eb78247b6c19c997001d5e0fc02a1c2c0988835b25e325f11405b1d5099312c2df8edcad269d95f8e0117d2cf9182da3daeaab41cf23be26252a289a54892b7a3ecadc50ab3d8175af840637e6113cde42693544975e9f87da6a6f7cb30d9ff11b73be060332609568bb8a61662a52cbd8d8920ef0ba74b47d376c4a6d055e2ebf9907fae297f1404072b6d4c0072ee4e97cdd713a104a0fae53b188077d235f185d4a8c5eacc92ee48884679a";
 
     const deck1 = [
       "0x218ce9bf8e71ec0e86503b7c5bb6dc41a8555e2f831aa06baa50fa2fecce1cfa",
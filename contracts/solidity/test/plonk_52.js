@@ -10,7 +10,7 @@ describe("Plonk 52 Verify Contract", function () {
     const vk2_address = await vk2.getAddress();
     const service = await ethers.deployContract("ShuffleService", [vk1_address, vk2_address, 52]);
 
-    const proof = "0x1b02a8c5a24debeec3ec84000ee7453bf4bb2d4387ba168dffecee311be9f16104eae0cc5ba83328daf1e238575c032cd62b9691378c78791f4ac42557bbf0982aeb694f31c0da57f34554406f2693d8a05db6d9f8cf1d8aaae739b375caf24f15aa818f0ccd4e73516f8cdb1b1a2c5b49927c76790d097df3011ef94c72f96f2766ba90f2e966bfdda13c220a54f5b358433645b726a98c63b78e0633f5eebf2641d2503127a63aa60aaf943ca06b22d6c8bb3956bd8324689fa3c32d6d346a098f5625e21d53b506a9f6dc52fc7b6e18d3ea89c90d456dfe885250723f06613058fba81b6067f4c19d3c8b2eec7b9fcd52a0b4aa441c8a2c1cdd00111612af252d1d8078504430e741d1521a0d6563470ba6c74850e5bd8990f4df47a1a72c1ad5f5376ca5a64a3e8591ef20df912a5c7551e13f817b99936650f487b866361b83104079e4114b691994cdd49f14d06c9c4cff00cf3f79fea7f01bdc4005da0ec3b6e589de1616a8b2546cd5808a4931df26a84cc98374a7d6fc980730b53127ea69708e5aa149d80ec2bd124b30f33c19e7dd7c12d887b8b470d1e084f7b500cf361c6afca26a1d32369a364834d9e69c2dc1aa0a98db9daf5dfe1a27a2561c6eed47f91d3466cebd2a42bcb078629ec27b1e2d2305530155393cfe87281524e10add03a95746ab5f045e9d349a91f3a6c76e48e0c1fd1a9410d24e9475722b83107788aee0dcb149d7997254efa7836515c1105a0910f5a029e6ce0e6e030080a47c35ef2799825d4149fb18d850e9bbbd08f25af9dcb2617b77da0cca390fa3d866b7e42b9a5d739b8a116418baa7e19247cc70ddab14f224f4093f507b16a338f87a957985d4351d675fadcc58df379f8ae4740ed267d2abb6d3ab73391162883cfcd084634132e77ab2639bde527131b6ab40a13f736de18ef5a9c94b27c4a6301b143868590ae95706bd0edf3f2da4eb3654988715ef7f338d85899515cff2e224a890b44222068cbcd4e4cd436e593d498a9c4fa3b7a6b8a3f15b3f0e2d47374363a2215fc6ab73ec209158b895b937bcab362efaafdbd880a4b14212b0f3af770ce2e6eb5543faca91cc37f2143893b71d11cac9aea60cba4a6bd21d5d21905357f717bb068e6c92ffd1b1829d95726ec27ea0fbd00b14ffc7657907f90204fe8e1f57440e629f3885cc08abd9e30f6a83f57db775ff7d9ccc8f0e17b97b51520f1271272d6e37b2efe38d4b89b029490133f363528b9362038c51000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a8f3fa4e7d559287f26c997a6a77ed9b058efb7087695d6728dc301b3c784e82eae8cd3bbc21ec789de55b262be05645f8c2f7a4618f592f7629c42c005f6641f328a74babc3895c89403be9dad73e9c5c70dc21bbc8f6a7a6a52a913e0116e1a7e05076a09b1fc352ab19e095fc7dd9eb88e148b787a18090ec14f032739a30a3b3dbaf39248c749025bc77795e54c5626e30a03fc4900dc2fb96d183872ef24704017e840fd58a32685e43b5aa8a77f00ab78b342b3b2914e5b07505aabff0e1d2e1dbfe1570c5344ac8576128d26d7da55a52f78dadfc5a0db34a54ea8ad0eaf78212f71767b41c1dd9f99279734d146f5b2fe9bd7bfec890d9903d9771602156b52f9854e5cb96d8703d15c0187bc6937dde60eae40d617353a0d5f1b540503a1a7261d41468e6124625bbefa6caac5b73fc00a61ae4f0518689cb2102c2d1323ad16e2d76b5e583a77115d032478f2deac186d6ce82c1b1e874a3e719714e08fe152a04d37f79aefb837d7d1abce29b8426c99ed617b3fea0ae054a85b0698788ffb8f6d730ee3bf6f78fb5274dde3b4f528bd9f19b83f441ec10da716086c4f8aba2d871253c819b0d8e89ebd1b3251fcd3c47b7091cf36ee668493301b03aa9befb5e96d6fb134a8133ac1e5f29535add92fef882bb45b9dbf0c2d11288e1f2b01db1dec5d2a86dd66d9e0c94dffe973c5ed4fa06e7ed96f1ad6a2d11570a5d16b429eee296324d06f078f4797532f4835ae97cf84a6005a1bcad94b04f5b4f919267ddba37f930659ed5e8bc0fbeb601071402ed82a9a140e47505e2e8eab739b0d751d7c001336da896ca2be03ddd332567c3e5eb4a12f935926820e596ddc96963a628eef40aa6f94f0ec161707fb0b1c0d824c5174b89b88af6027c83b5ee5074987e7ca5f65afc03354e39bdcded581e0a8b64c0d3454e6346b";
+    const proof = "0x011b02a8c5a24debeec3ec84000ee7453bf4bb2d4387ba168dffecee311be9f16104eae0cc5ba83328daf1e238575c032cd62b9691378c78791f4ac42557bbf0982aeb694f31c0da57f34554406f2693d8a05db6d9f8cf1d8aaae739b375caf24f15aa818f0ccd4e73516f8cdb1b1a2c5b49927c76790d097df3011ef94c72f96f2766ba90f2e966bfdda13c220a54f5b358433645b726a98c63b78e0633f5eebf2641d2503127a63aa60aaf943ca06b22d6c8bb3956bd8324689fa3c32d6d346a098f5625e21d53b506a9f6dc52fc7b6e18d3ea89c90d456dfe885250723f06613058fba81b6067f4c19d3c8b2eec7b9fcd52a0b4aa441c8a2c1cdd00111612af252d1d8078504430e741d1521a0d6563470ba6c74850e5bd8990f4df47a1a72c1ad5f5376ca5a64a3e8591ef20df912a5c7551e13f817b99936650f487b866361b83104079e4114b691994cdd49f14d06c9c4cff00cf3f79fea7f01bdc4005da0ec3b6e589de1616a8b2546cd5808a4931df26a84cc98374a7d6fc980730b53127ea69708e5aa149d80ec2bd124b30f33c19e7dd7c12d887b8b470d1e084f7b500cf361c6afca26a1d32369a364834d9e69c2dc1aa0a98db9daf5dfe1a27a2561c6eed47f91d3466cebd2a42bcb078629ec27b1e2d2305530155393cfe87281524e10add03a95746ab5f045e9d349a91f3a6c76e48e0c1fd1a9410d24e9475722b83107788aee0dcb149d7997254efa7836515c1105a0910f5a029e6ce0e6e030080a47c35ef2799825d4149fb18d850e9bbbd08f25af9dcb2617b77da0cca390fa3d866b7e42b9a5d739b8a116418baa7e19247cc70ddab14f224f4093f507b16a338f87a957985d4351d675fadcc58df379f8ae4740ed267d2abb6d3ab73391162883cfcd084634132e77ab2639bde527131b6ab40a13f736de18ef5a9c94b27c4a6301b143868590ae95706bd0edf3f2da4eb3654988715ef7f338d85899515cff2e224a890b44222068cbcd4e4cd436e593d498a9c4fa3b7a6b8a3f15b3f0e2d47374363a2215fc6ab73ec209158b895b937bcab362efaafdbd880a4b14212b0f3af770ce2e6eb5543faca91cc37f2143893b71d11cac9aea60cba4a6bd21d5d21905357f717bb068e6c92ffd1b1829d95726ec27ea0fbd00b14ffc7657907f90204fe8e1f57440e629f3885cc08abd9e30f6a83f57db775ff7d9ccc8f0e17b97b51520f1271272d6e37b2efe38d4b89b029490133f363528b9362038c51000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001a8f3fa4e7d559287f26c997a6a77ed9b058efb7087695d6728dc301b3c784e82eae8cd3bbc21ec789de55b262be05645f8c2f7a4618f592f7629c42c005f6641f328a74babc3895c89403be9dad73e9c5c70dc21bbc8f6a7a6a52a913e0116e1a7e05076a09b1fc352ab19e095fc7dd9eb88e148b787a18090ec14f032739a30a3b3dbaf39248c749025bc77795e54c5626e30a03fc4900dc2fb96d183872ef24704017e840fd58a32685e43b5aa8a77f00ab78b342b3b2914e5b07505aabff0e1d2e1dbfe1570c5344ac8576128d26d7da55a52f78dadfc5a0db34a54ea8ad0eaf78212f71767b41c1dd9f99279734d146f5b2fe9bd7bfec890d9903d9771602156b52f9854e5cb96d8703d15c0187bc6937dde60eae40d617353a0d5f1b540503a1a7261d41468e6124625bbefa6caac5b73fc00a61ae4f0518689cb2102c2d1323ad16e2d76b5e583a77115d032478f2deac186d6ce82c1b1e874a3e719714e08fe152a04d37f79aefb837d7d1abce29b8426c99ed617b3fea0ae054a85b0698788ffb8f6d730ee3bf6f78fb5274dde3b4f528bd9f19b83f441ec10da716086c4f8aba2d871253c819b0d8e89ebd1b3251fcd3c47b7091cf36ee668493301b03aa9befb5e96d6fb134a8133ac1e5f29535add92fef882bb45b9dbf0c2d11288e1f2b01db1dec5d2a86dd66d9e0c94dffe973c5ed4fa06e7ed96f1ad6a2d11570a5d16b429eee296324d06f078f4797532f4835ae97cf84a6005a1bcad94b04f5b4f919267ddba37f930659ed5e8bc0fbeb601071402ed82a9a140e47505e2e8eab739b0d751d7c001336da896ca2be03ddd332567c3e5eb4a12f935926820e596ddc96963a628eef40aa6f94f0ec161707fb0b1c0d824c5174b89b88af6027c83b5ee5074987e7ca5f65afc03354e39bdcded581e0a8b64c0d3454e6346b";
 
     const deck1 =
       ["0x157f4104cd467bbc626581ab1a599dd8067da7910deddfc1fc6d76566602d8bb", "0x1188da9248b991bc88e2359016ed19a5f47070ae109e5f09f3eb3363d4a91434", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2fd9a493c1e52c123b60fc9777b2f0a1fae8a1ea0b4a490ea39f51e9ab1d6d32", "0x186d7b06a2464f6c44331d6a05f498eb69da41db00006bc67d3eeb3ab02c594b", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x013e236c93af5586f374a18d1c5162475eac4a2fcfe9a8537cac2272def55817", "0x110b09bd8520ea7689fe56c362bcc4cbe986f6009c728c616d07b36c3d541328", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0c96363bf15bfa5aad9a93c5bb143fe8e79f9ca1ed4dbf11425269ba7b4c11a6", "0x165baf6882c972282cfbb27a42014b82135ed2761d0a08e31187b875cdd5e501", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x28169880dc77cfd0953ab1ac91fca42db3b7a646e4009b3daf3bc266989b9459", "0x20b1baa7310d5ca67f5ac41d47de40f8e793bd09a2c2d6f4f1d11b3867d98483", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0008b26497ed7a7c9c722bb8c5d686081edd98288d581418e166e20a1d5e261e", "0x0bd8ed105e111ff2bf921f0b068aa0d6776c24b36b5d6f87fea4d5631bac1f01", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x269b9bc18d5739ba0a15591a7202bd1c684dd3f404966b8f2d73d71134a5db34", "0x169f77a937cf12c80d11be2df2efccd14533f4f32bd247e1a48b3d1c4db5a636", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0d04532a9682e9f786364797f95a97cdc29caa23a8a9f05a5cbd2d6a526ce3c1", "0x093048408aabff962b492ee68529f0134aa0b5694223cf228558f6f8f11decef", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x070739d0b9054737eeda09974ce075a7e9a911e763b06548d9c25e785025ddbf", "0x0d5268cc7d06e856022556931fb7a28f7a5c7cfcd6265bcd4a17c15b142934c7", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2eea6fcee170fe20fda86ad787a4b58c7de5145e76db84418635d1effcf79d18", "0x0180a75c997a6729e967dd7466413b924bf8a74c00eb8543b89e903748301b1c", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x007a7d105cd2f35b5f95dd5365f15708a71eda13da9bfd004f8136d30268895d", "0x09037c8a25c2c27fefbfda258e9d50020e3a89bb9f5e0400686ddd8098347ee0", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x23ccb8230036731ab92fb4e8358389947a4d37999ab13c89bc15149bd1dd45ad", "0x2356eb584d403989520fb6353d73f92c882edae99cca9fea5326eba61cd8d8aa", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x152bcd24b02c929136c6e75b2a9ab88834f596501e6bc0109c254fb1b091fb84", "0x065ca73b07206e94fb6ae3eeee8e04810e950b5871fa8614feebec1ac7644f09", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x05bcfe2c02f8c44256d94363fd22fbcdf33c584bede762097e771a898a8cfe4a", "0x08b36100d1eb204e528f399edd00e20859044c6fd2f2fdf3737c5288bc83a8bc", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2ef864009d3435fba0ed9c9a5bdb437be81fe2db2462a4c82e7e22e77ebf18e7", "0x2efa7910e8b57be1d47139962f7095bc24d646a2594ee9af195c3667ca4c20a4", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x09ebf85a57275f97d6de0eb6a46fd4abf91cbdca21bc3fe5fd1e51942fcdcc63", "0x02fc8d2e68182a4dc4b999fe14c87e8a5b8780a521dc3422ec58c6d150b5cef8", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0a3319ffd44b02066e47ff44253b551e871f1c55d3a2c28fb874bf1b957856ae", "0x2faa8eedd861231ccaf057219f2b960e2299f536e63e3c7a29be38e97eacc9b0", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x04edcbedf1796a13591487383124867ace1ce6f19dba69fd6966035c01f1c655", "0x0bd3a17552ac56b7148802074087b930cc996a43ee8e21ae6dbac17b4f6deae9", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x080b476c8c9bf2967f105aaf6cde74f7b3041cb053781b7f7e60372350b07f91", "0x2ac190015b579cb832a25ed4ee38465b1e30e509375badc0a82bf3fadddd74bf", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2e3a4f77bb70648f0be66093925db6abc4540ac583ea2e70149e3df6084e91b8", "0x0e3f74be4f795207493403e461e9cc006d1d393aab00a0a0443b30988148dade", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1c77191ea929506bacef6e5871ce780a6eafcd4959f758d550bf5747c5cd5dca", "0x169eee6fad519cc468cc30ecb7f4c244cb92ca77a5baa8672073df5c588ae50a", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x062b9d15faceaf24103c8ea061d805197e06a0a9f0643cb269eb506106013b51", "0x16a72fb41aa7f555c1f1e0752812ad97707bc80a236aea79b41eb59ebc52dd47", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x076228162e139ae616f5cce2d2e883a0a4a3f9023a9f45665fb15201db3ae59a", "0x17e1aa2c4ad8c5d8ed9dfb509eaf0924334d4985fe3ce88c8e9fad961eac296d", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x15eef25e3be4d34f5a8ef8722dffc78259b9f557fd3cbe4ec72be458896a7adc", "0x28dd89b2c4cc6817d2fd553305bcd52b61c99c0597b258657bcc36f0ae877354", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x184f5fe352fa9873fc9707ce14341f12777af7127d6392032d4205b25ff251bc", "0x0ae27a2227d4a302c4db76005ae67b02663faa28f61fb961430f7a5d80e2b024", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x25dce7fb380648b6873685912abb490d79c56480d68096b96960e8d00a4a84fa", "0x2f987e19438e81cd285cedfd18b25da2912b6b39ce1d9fc93c7d841cc53f8043", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2e94166886fc0c5650f541ae98f02c3f1c3770d357d437f8ddf5f1902bdad107", "0x18f6991ac10477a635ef9a7c81aa95573111a504fe01aa08ea24e43fd4a3d038", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1ee14d24a033b1b38cbb100f572f258055055650819fe2de31b440ffc7547fe5", "0x1d16f9b6af8712bfa31f3638d0d00bcaa114e964e4758442718beb6fc39659eb", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2ba720468ed5062f234669cfd5e23f724508836af93f8e85e0c27c8fd502f798", "0x14e5f89110fa3e40e5ba00dbd46fe1bcd58c485298125eb1f2c4b74d127ccc27", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2aa0f9ae9563ae1e3654fe24c6447b69ef93c9adc05e601ffdf11c217a0f266a", "0x0bb737aa3e3b0bf214436361249509c766591533571dbd3ed3b0951946a394f6", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x02541c3e3585f964968ecaf9c2dcdcba47958d54460471c7d28b81799646abd5", "0x00c9c1084bc79e8dbb1b5fa724579ef36eea2b6cbaf110d0e21cbe807c74a978", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x21a49f9300a8f15a97559953457962403c18cf96e49d8547649c58d337f1ef2a", "0x27686ce8ea2837f14981b83188fdfae3eb22711b0fbdae255ae5cbe093cb0339", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x27baec4632dcc3b57d1421e64b348bf887e1de565fd697e4618e85e4d7b30407", "0x112f04a13860e85bb91c76010428cfc8a2b54c8931c2d1e4b1aae80dfee66d9b", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0cdb4fafc94e4843d28a96f57654a77b8756fbc9d0fc67b1872da9b3a146efda", "0x16245f3bcb4a65764810e0d73ec0db16f436b445a7b0764f07cfd2d8e694edeb", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x004b7d3c019df15a026346370593bbe2a1965b4e6765a7173e8e72c9f3eecd4f", "0x204a2d9d02959c7768b631e8f05185ef8ede08d333fd18c0a22ab950724ce237", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x155ae0b0d8fcb16ca4bb9a6aace5c4ba2e5b35bc73ed0e90e6db5459ec7dca17", "0x0ffa3c64420d869c21a71e7e0005c6218da3cde048e237db62ca6736a63147a4", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x2cd4e0611443e824e28c17155c675c08b4c8c4e8923086689b957bbefda2d076", "0x18624e7b1125bc7bad6dda3fa5ca0d94351c3a4d1b505860d80b89031be17ac6", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x224557b0b4a381bdc55e5bbb963dff102dd579652d95a8c718fc23eacc8a44cc", "0x1b4581d2ab75aad6906f8682e6e6199802b2ae2623db9420123b70dc205b6624", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0712e2e4b8e0a9686bbe73af2502ac4c729a7ad05b948f120fd0b4974f4d44b9", "0x10c489644f806c5b07ab748bd6d4789bce7fedf659ed21ccbb279ec496a95d90", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x29dd63e328684087dc97616ea5f9948344fb392d1741145935ae8a9a410de30f", "0x1577908fc4057b429c049553d0b351799bd06685454e79a47c64e65e8ab6eaee", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x15a1d3f454baa9b9f120e0bacfe007a552f0c268102971ffb351a8c6978d10c2", "0x01d6ef3feb6fe2cf4662824082a743c40d5d28dcd5a796ef3b1a083f35900cd2", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1e5b26afe7d05e7b057148da2d280d245204a5bf1748d3ed2b9cb20557ce31a1", "0x1135f38abcac48f884a9da9e656c0ac670661821ad0900f289c28faf1df3eba4", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x123325f3e62425de6518b38fc5490ab0ac02f829b1c4f1b969cd5b1fd82d9ea9", "0x14c3e5722656950198a15098ccdf71716a246077bda7fdfde8649a770fd7317c", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x27b2f34f040af318d7361ea04086da5029b9bf0528261734475fb80823b98118", "0x0ae0d07be0ee695c8bcb2d364eea14a23fefd88b9a08323199d2cebdaff6faff", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1b61a6711676840e164db7bf0ead44321cb5e46d894be8c5ac18e25efbce9232", "0x0375dca49152b6927de9f4be33c993de22e50deafa7ada7fffbe405ae023c42b", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1b7f465cec369ad92108bc939a324c5de173d9c4e4aafd74a8228fd646c78abd", "0x1503180deed2db94a06b9bd720856272b42cdc32f94725baecc8c0b90bbe9677", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x14435e0f2053a2ac30e7b0918fc270ca3c30334a6ba563cb2640be51a2790060", "0x079dd8bd23d23d505097b02192d68e5335f2e85b5093b9927dc479d3b1c0dfb3", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0dcb12232b5138a97bef847ba8e10e2e3412937a2b06fa84eb80606c860985c9", "0x0f593e1fc0be3f3d0d142348b4932679eb92af590cd77df45f6cb7aeb25f6f5b", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x0a159aa0e19ebc538080153d92dc242ed061e05654e9ef0cac92957629cd8467", "0x2349aa0e5a6b6e36938cb9cfb918f9a7a0e0be63df2568ed1cab7f906047318a", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x1cb2674bc340400344a373e37f3d4847fcf908ae37eb51de013f53d701cab586", "0x108f683abc35893a318d711dd34a97f1b79d3dbcda87e10d1027f9bdc9368931", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x01f42d35de62808bb52eff31791531d8547e4d340b5b6230f126281bee4d4218", "0x166405e03288c53d5751e27cbec0deb6dbcb3f8033c2352eb6733cf01c98111f", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f", "0x22ee780444a0b63efb6e774dfa594d1c99daf9f3e3e3658be4b2d6b952ab2362", "0x10d5bfb43d9f94f7ce290f60f2b8afba61cbdb3984fc08b4d4aecc30e3e598f9", "0x2b8cfd91b905cae31d41e7dedf4a927ee3bc429aad7e344d59d2810d82876c32", "0x2aaa6c24a758209e90aced1f10277b762a7c1115dbc0e16ac276fc2c671a861f"];
@@ -0,0 +1,20 @@
+const { expect } = require("chai");
+const fixture = require("./plonk_square.json");
+
+// The contract and the calldata are generated by the uzkge test `test_solidity_verifier_fixture`
+// for the circuit proving the knowledge of a square root of the public input 9.
+describe("Generated Plonk Verifier Contract", function () {
+  async function verify(calldata) {
+    const verifier = await ethers.deployContract("PlonkSquareVerifier");
+    const res = await ethers.provider.call({ to: await verifier.getAddress(), data: calldata });
+    return verifier.interface.decodeFunctionResult("verify", res)[0];
+  }
+
+  it("square proof verify must success", async function () {
+    expect(await verify(fixture.calldata)).to.equal(true);
+  });
+
+  it("square proof with a flipped evaluation must fail", async function () {
+    expect(await verify(fixture.flippedCalldata)).to.equal(false);
+  });
+});
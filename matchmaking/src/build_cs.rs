@@ -1,19 +1,24 @@
 use ark_bn254::Fr;
+use ark_ff::Zero;
 use ark_std::rand::{CryptoRng, RngCore};
 use uzkge::{
     anemoi::{AnemoiJive, AnemoiJive254},
-    errors::Result,
+    errors::{Result, UzkgeError},
     plonk::{
-        constraint_system::VarIndex, indexer::PlonkProof, prover::prover_with_lagrange,
+        constraint_system::{circuit::Circuit, VarIndex},
+        indexer::PlonkProof,
+        prover::prover_with_lagrange,
         verifier::verifier,
     },
     poly_commit::kzg_poly_commitment::KZGCommitmentSchemeBN254,
-    utils::transcript::Transcript,
+    utils::transcript::{Transcript, TranscriptScheme},
 };
 
 use crate::{
     gen_params::{ProverParams, VerifierParams},
     matchmaking::Matchmaking,
+    multi_party::MultiPartyMatchmaking,
+    team::{check_team_tags, TeamMatchmaking},
 };
 
 pub type Proof = PlonkProof<KZGCommitmentSchemeBN254>;
@@ -21,15 +26,86 @@ pub type TurboCS = uzkge::plonk::constraint_system::TurboCS<Fr>;
 
 const PLONK_PROOF_TRANSCRIPT: &[u8] = b"Plonk Matchmaking Proof";
 const N_TRANSCRIPT: &[u8] = b"Number inputs";
+const TEAM_PLONK_PROOF_TRANSCRIPT: &[u8] = b"Plonk Team Matchmaking Proof";
+const TEAM_SIZE_TRANSCRIPT: &[u8] = b"Team size";
+const MULTI_PARTY_PLONK_PROOF_TRANSCRIPT: &[u8] = b"Plonk Multi Party Matchmaking";
+const PARTIES_TRANSCRIPT: &[u8] = b"Number parties";
 
+/// The default pool size.
 pub const N: usize = 50;
 
-pub(crate) fn build_cs(
+/// The pool sizes with the embedded verifier parameters.
+pub const EMBEDDED_SIZES: [usize; 3] = [10, 20, 50];
+
+/// The largest pool size, whose circuits still fit in the largest SRS.
+pub const MAX_N: usize = 512;
+
+/// The largest number of the seed shares of the multi-party matchmaking.
+pub const MAX_PARTIES: usize = 64;
+
+/// The transcript of the matchmaking proof of `n` inputs.
+pub fn matchmaking_transcript(n: usize) -> Transcript {
+    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
+    transcript.append_u64(N_TRANSCRIPT, n as u64);
+    transcript
+}
+
+/// The transcript of the team matchmaking proof of `n` inputs in the teams of `k`.
+pub fn team_matchmaking_transcript(n: usize, k: usize) -> Transcript {
+    let mut transcript = Transcript::new(TEAM_PLONK_PROOF_TRANSCRIPT);
+    transcript.append_u64(N_TRANSCRIPT, n as u64);
+    transcript.append_u64(TEAM_SIZE_TRANSCRIPT, k as u64);
+    transcript
+}
+
+/// The transcript of the multi-party matchmaking proof of `n` inputs and `parties` seed shares.
+pub fn multi_party_matchmaking_transcript(n: usize, parties: usize) -> Transcript {
+    let mut transcript = Transcript::new(MULTI_PARTY_PLONK_PROOF_TRANSCRIPT);
+    transcript.append_u64(N_TRANSCRIPT, n as u64);
+    transcript.append_u64(PARTIES_TRANSCRIPT, parties as u64);
+    transcript
+}
+
+/// The matchmaking circuit of `n` inputs.
+pub struct MatchmakingCircuit {
+    pub n: usize,
+}
+
+/// The inputs of the matchmaking circuit.
+pub struct MatchmakingInputs {
+    pub inputs: Vec<Fr>,
+    pub committed_seed: Fr,
+    pub random_number: Fr,
+}
+
+impl Circuit<Fr> for MatchmakingCircuit {
+    type Inputs = MatchmakingInputs;
+    type Output = Vec<VarIndex>;
+
+    fn synthesize(&self, cs: &mut TurboCS, inputs: &MatchmakingInputs) -> Vec<VarIndex> {
+        build_cs(
+            cs,
+            &inputs.inputs,
+            &inputs.committed_seed,
+            &inputs.random_number,
+        )
+    }
+
+    fn dummy_inputs(&self) -> MatchmakingInputs {
+        MatchmakingInputs {
+            inputs: vec![Fr::zero(); self.n],
+            committed_seed: Fr::zero(),
+            random_number: Fr::zero(),
+        }
+    }
+}
+
+fn build_cs(
+    cs: &mut TurboCS,
     inputs: &[Fr],
     committed_seed: &Fr,
     random_number: &Fr,
-) -> (TurboCS, Vec<VarIndex>) {
-    let mut cs = TurboCS::new();
+) -> Vec<VarIndex> {
     cs.load_anemoi_parameters::<AnemoiJive254>();
 
     let input_vars = inputs
@@ -41,14 +117,14 @@ pub(crate) fn build_cs(
     let committed_input_var = cs.new_variable(*committed_seed);
     let committed_output_var = cs.new_variable(committed_trace.output);
 
-    let mut mm = Matchmaking::<N, Fr>::new(
+    let mut mm = Matchmaking::<Fr>::new(
         &input_vars,
         committed_input_var,
         committed_output_var,
         &committed_trace,
         random_number_var,
     );
-    mm.generate_constraints::<AnemoiJive254>(&mut cs);
+    mm.generate_constraints::<AnemoiJive254>(cs);
 
     // public IO value
     for x in input_vars {
@@ -62,7 +138,7 @@ pub(crate) fn build_cs(
 
     cs.pad();
 
-    (cs, mm.output_vars)
+    mm.output_vars
 }
 
 pub fn prove_matchmaking<R: CryptoRng + RngCore>(
@@ -72,20 +148,26 @@ pub fn prove_matchmaking<R: CryptoRng + RngCore>(
     random_number: &Fr,
     prover_params: &ProverParams,
 ) -> Result<(Proof, Vec<Fr>)> {
-    assert_eq!(inputs.len(), N);
+    if inputs.len() <= 2 {
+        return Err(UzkgeError::ParameterError);
+    }
 
-    let (mut cs, output_vars) = build_cs(inputs, committed_seed, random_number);
-    let witness = cs.get_and_clear_witness();
+    let circuit = MatchmakingCircuit { n: inputs.len() };
+    let inputs = MatchmakingInputs {
+        inputs: inputs.to_vec(),
+        committed_seed: *committed_seed,
+        random_number: *random_number,
+    };
+    let (witness, output_vars) = circuit.assign_witness(&prover_params.cs, &inputs)?;
 
-    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
-    transcript.append_u64(N_TRANSCRIPT, N as u64);
+    let mut transcript = matchmaking_transcript(circuit.n);
 
     let proof = prover_with_lagrange(
         prng,
         &mut transcript,
         &prover_params.pcs,
         prover_params.lagrange_pcs.as_ref(),
-        &cs,
+        &prover_params.cs,
         &prover_params.prover_params,
         &witness,
     )?;
@@ -106,24 +188,360 @@ pub fn verify_matchmaking(
     random_number: &Fr,
     proof: &Proof,
 ) -> Result<()> {
-    assert_eq!(inputs.len(), N);
-    assert_eq!(outputs.len(), N);
+    if inputs.len() != outputs.len() {
+        return Err(UzkgeError::ParameterError);
+    }
 
-    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
-    transcript.append_u64(N_TRANSCRIPT, N as u64);
+    let mut transcript = matchmaking_transcript(inputs.len());
+
+    let mut online_inputs = vec![];
+    online_inputs.extend_from_slice(inputs);
+    online_inputs.extend_from_slice(outputs);
+    online_inputs.push(*random_number);
+    online_inputs.push(*commitment);
+
+    verifier(
+        &mut transcript,
+        &verifier_params.shrunk_vk,
+        &verifier_params.shrunk_cs,
+        &verifier_params.verifier_params,
+        &online_inputs,
+        proof,
+    )
+}
+
+/// The team matchmaking circuit of `n` inputs in the teams of `team_size`.
+pub struct TeamMatchmakingCircuit {
+    pub n: usize,
+    pub team_size: usize,
+}
+
+/// The inputs of the team matchmaking circuit.
+pub struct TeamMatchmakingInputs {
+    pub inputs: Vec<Fr>,
+    pub tags: Vec<Fr>,
+    pub committed_seed: Fr,
+    pub random_number: Fr,
+}
+
+impl Circuit<Fr> for TeamMatchmakingCircuit {
+    type Inputs = TeamMatchmakingInputs;
+    type Output = Vec<VarIndex>;
+
+    fn synthesize(&self, cs: &mut TurboCS, inputs: &TeamMatchmakingInputs) -> Vec<VarIndex> {
+        build_team_cs(
+            cs,
+            &inputs.inputs,
+            &inputs.tags,
+            self.team_size,
+            &inputs.committed_seed,
+            &inputs.random_number,
+        )
+    }
+
+    fn dummy_inputs(&self) -> TeamMatchmakingInputs {
+        TeamMatchmakingInputs {
+            inputs: vec![Fr::zero(); self.n],
+            tags: vec![Fr::zero(); self.n],
+            committed_seed: Fr::zero(),
+            random_number: Fr::zero(),
+        }
+    }
+}
+
+fn build_team_cs(
+    cs: &mut TurboCS,
+    inputs: &[Fr],
+    tags: &[Fr],
+    team_size: usize,
+    committed_seed: &Fr,
+    random_number: &Fr,
+) -> Vec<VarIndex> {
+    cs.load_anemoi_parameters::<AnemoiJive254>();
+
+    let input_vars = inputs
+        .iter()
+        .map(|i| cs.new_variable(*i))
+        .collect::<Vec<_>>();
+    let tag_vars = tags.iter().map(|t| cs.new_variable(*t)).collect::<Vec<_>>();
+    let random_number_var = cs.new_variable(*random_number);
+    let committed_trace = AnemoiJive254::eval_variable_length_hash_with_trace(&[*committed_seed]);
+    let committed_input_var = cs.new_variable(*committed_seed);
+    let committed_output_var = cs.new_variable(committed_trace.output);
+
+    let mut mm = TeamMatchmaking::<Fr>::new(
+        &input_vars,
+        &tag_vars,
+        team_size,
+        committed_input_var,
+        committed_output_var,
+        &committed_trace,
+        random_number_var,
+    );
+    mm.generate_constraints::<AnemoiJive254>(cs);
+
+    // public IO value
+    for x in input_vars {
+        cs.prepare_pi_variable(x);
+    }
+    for x in tag_vars {
+        cs.prepare_pi_variable(x);
+    }
+    for x in mm.output_vars.iter() {
+        cs.prepare_pi_variable(*x);
+    }
+    cs.prepare_pi_variable(random_number_var);
+    cs.prepare_pi_variable(committed_output_var);
+
+    cs.pad();
+
+    mm.output_vars
+}
+
+/// Prove that the inputs are shuffled within their skill buckets and split into
+/// the teams of `team_size`. The inputs should be sorted by the `tags`, and every
+/// `team_size` consecutive players should have the same tag.
+pub fn prove_team_matchmaking<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    inputs: &[Fr],
+    tags: &[Fr],
+    team_size: usize,
+    committed_seed: &Fr,
+    random_number: &Fr,
+    prover_params: &ProverParams,
+) -> Result<(Proof, Vec<Fr>)> {
+    if inputs.len() != tags.len() || !check_team_tags(tags, team_size) {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let circuit = TeamMatchmakingCircuit {
+        n: inputs.len(),
+        team_size,
+    };
+    let inputs = TeamMatchmakingInputs {
+        inputs: inputs.to_vec(),
+        tags: tags.to_vec(),
+        committed_seed: *committed_seed,
+        random_number: *random_number,
+    };
+    let (witness, output_vars) = circuit.assign_witness(&prover_params.cs, &inputs)?;
+
+    let mut transcript = team_matchmaking_transcript(circuit.n, team_size);
+
+    let proof = prover_with_lagrange(
+        prng,
+        &mut transcript,
+        &prover_params.pcs,
+        prover_params.lagrange_pcs.as_ref(),
+        &prover_params.cs,
+        &prover_params.prover_params,
+        &witness,
+    )?;
+
+    let mut outputs = vec![];
+    for i in output_vars {
+        outputs.push(witness[i])
+    }
+
+    Ok((proof, outputs))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn verify_team_matchmaking(
+    verifier_params: &VerifierParams,
+    inputs: &[Fr],
+    tags: &[Fr],
+    team_size: usize,
+    outputs: &[Fr],
+    commitment: &Fr,
+    random_number: &Fr,
+    proof: &Proof,
+) -> Result<()> {
+    if inputs.len() != outputs.len()
+        || inputs.len() != tags.len()
+        || !check_team_tags(tags, team_size)
+    {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let mut transcript = team_matchmaking_transcript(inputs.len(), team_size);
 
     let mut online_inputs = vec![];
     online_inputs.extend_from_slice(inputs);
+    online_inputs.extend_from_slice(tags);
     online_inputs.extend_from_slice(outputs);
     online_inputs.push(*random_number);
     online_inputs.push(*commitment);
 
-    Ok(verifier(
+    verifier(
+        &mut transcript,
+        &verifier_params.shrunk_vk,
+        &verifier_params.shrunk_cs,
+        &verifier_params.verifier_params,
+        &online_inputs,
+        proof,
+    )
+}
+
+/// The multi-party matchmaking circuit of `n` inputs and the seed shares of `parties`.
+pub struct MultiPartyMatchmakingCircuit {
+    pub n: usize,
+    pub parties: usize,
+}
+
+/// The inputs of the multi-party matchmaking circuit.
+pub struct MultiPartyMatchmakingInputs {
+    pub inputs: Vec<Fr>,
+    pub seed_shares: Vec<Fr>,
+    pub random_number: Fr,
+}
+
+impl Circuit<Fr> for MultiPartyMatchmakingCircuit {
+    type Inputs = MultiPartyMatchmakingInputs;
+    type Output = Vec<VarIndex>;
+
+    fn synthesize(&self, cs: &mut TurboCS, inputs: &MultiPartyMatchmakingInputs) -> Vec<VarIndex> {
+        build_multi_party_cs(
+            cs,
+            &inputs.inputs,
+            &inputs.seed_shares,
+            &inputs.random_number,
+        )
+    }
+
+    fn dummy_inputs(&self) -> MultiPartyMatchmakingInputs {
+        MultiPartyMatchmakingInputs {
+            inputs: vec![Fr::zero(); self.n],
+            seed_shares: vec![Fr::zero(); self.parties],
+            random_number: Fr::zero(),
+        }
+    }
+}
+
+fn build_multi_party_cs(
+    cs: &mut TurboCS,
+    inputs: &[Fr],
+    seed_shares: &[Fr],
+    random_number: &Fr,
+) -> Vec<VarIndex> {
+    cs.load_anemoi_parameters::<AnemoiJive254>();
+
+    let input_vars = inputs
+        .iter()
+        .map(|i| cs.new_variable(*i))
+        .collect::<Vec<_>>();
+    let random_number_var = cs.new_variable(*random_number);
+
+    let share_traces = seed_shares
+        .iter()
+        .map(|s| AnemoiJive254::eval_variable_length_hash_with_trace(&[*s]))
+        .collect::<Vec<_>>();
+    let share_vars = seed_shares
+        .iter()
+        .map(|s| cs.new_variable(*s))
+        .collect::<Vec<_>>();
+    let commitment_vars = share_traces
+        .iter()
+        .map(|t| cs.new_variable(t.output))
+        .collect::<Vec<_>>();
+
+    let mut mm = MultiPartyMatchmaking::<Fr>::new(
+        &input_vars,
+        &share_vars,
+        &commitment_vars,
+        &share_traces,
+        random_number_var,
+    );
+    mm.generate_constraints::<AnemoiJive254>(cs);
+
+    // public IO value
+    for x in input_vars {
+        cs.prepare_pi_variable(x);
+    }
+    for x in mm.output_vars.iter() {
+        cs.prepare_pi_variable(*x);
+    }
+    cs.prepare_pi_variable(random_number_var);
+    for x in commitment_vars {
+        cs.prepare_pi_variable(x);
+    }
+
+    cs.pad();
+
+    mm.output_vars
+}
+
+/// Prove the matchmaking with the seed combined from the opened `seed_shares`
+/// of all the participants.
+pub fn prove_multi_party_matchmaking<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    inputs: &[Fr],
+    seed_shares: &[Fr],
+    random_number: &Fr,
+    prover_params: &ProverParams,
+) -> Result<(Proof, Vec<Fr>)> {
+    if inputs.len() <= 2 || seed_shares.is_empty() {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let circuit = MultiPartyMatchmakingCircuit {
+        n: inputs.len(),
+        parties: seed_shares.len(),
+    };
+    let inputs = MultiPartyMatchmakingInputs {
+        inputs: inputs.to_vec(),
+        seed_shares: seed_shares.to_vec(),
+        random_number: *random_number,
+    };
+    let (witness, output_vars) = circuit.assign_witness(&prover_params.cs, &inputs)?;
+
+    let mut transcript = multi_party_matchmaking_transcript(circuit.n, circuit.parties);
+
+    let proof = prover_with_lagrange(
+        prng,
+        &mut transcript,
+        &prover_params.pcs,
+        prover_params.lagrange_pcs.as_ref(),
+        &prover_params.cs,
+        &prover_params.prover_params,
+        &witness,
+    )?;
+
+    let mut outputs = vec![];
+    for i in output_vars {
+        outputs.push(witness[i])
+    }
+
+    Ok((proof, outputs))
+}
+
+/// Verify the matchmaking against the seed share `commitments` of all the participants.
+pub fn verify_multi_party_matchmaking(
+    verifier_params: &VerifierParams,
+    inputs: &[Fr],
+    outputs: &[Fr],
+    commitments: &[Fr],
+    random_number: &Fr,
+    proof: &Proof,
+) -> Result<()> {
+    if inputs.len() != outputs.len() || commitments.is_empty() {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let mut transcript = multi_party_matchmaking_transcript(inputs.len(), commitments.len());
+
+    let mut online_inputs = vec![];
+    online_inputs.extend_from_slice(inputs);
+    online_inputs.extend_from_slice(outputs);
+    online_inputs.push(*random_number);
+    online_inputs.extend_from_slice(commitments);
+
+    verifier(
         &mut transcript,
         &verifier_params.shrunk_vk,
         &verifier_params.shrunk_cs,
         &verifier_params.verifier_params,
         &online_inputs,
         proof,
-    )?)
+    )
 }
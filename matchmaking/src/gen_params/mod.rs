@@ -5,9 +5,27 @@ pub use params::*;
 
 #[cfg(not(feature = "no_vk"))]
 /// The specific part of the verifier parameters.
-pub static VERIFIER_SPECIFIC_PARAMS: Option<&'static [u8]> =
-    Some(include_bytes!("../../parameters/vk-specific.bin"));
+pub static VERIFIER_SPECIFIC_PARAMS_10: Option<&'static [u8]> =
+    Some(include_bytes!("../../parameters/vk-specific-10.bin"));
 
 #[cfg(feature = "no_vk")]
 /// The specific part of the verifier parameters.
-pub static VERIFIER_SPECIFIC_PARAMS: Option<&'static [u8]> = None;
+pub static VERIFIER_SPECIFIC_PARAMS_10: Option<&'static [u8]> = None;
+
+#[cfg(not(feature = "no_vk"))]
+/// The specific part of the verifier parameters.
+pub static VERIFIER_SPECIFIC_PARAMS_20: Option<&'static [u8]> =
+    Some(include_bytes!("../../parameters/vk-specific-20.bin"));
+
+#[cfg(feature = "no_vk")]
+/// The specific part of the verifier parameters.
+pub static VERIFIER_SPECIFIC_PARAMS_20: Option<&'static [u8]> = None;
+
+#[cfg(not(feature = "no_vk"))]
+/// The specific part of the verifier parameters.
+pub static VERIFIER_SPECIFIC_PARAMS_50: Option<&'static [u8]> =
+    Some(include_bytes!("../../parameters/vk-specific-50.bin"));
+
+#[cfg(feature = "no_vk")]
+/// The specific part of the verifier parameters.
+pub static VERIFIER_SPECIFIC_PARAMS_50: Option<&'static [u8]> = None;
@@ -1,36 +1,110 @@
-use ark_bn254::Fr;
-use ark_ff::Zero;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use uzkge::{
     errors::UzkgeError,
     gen_params::{
         load_lagrange_params, load_srs_params, VerifierParamsSplitCommon,
         VerifierParamsSplitSpecific, VERIFIER_COMMON_PARAMS,
     },
-    plonk::{constraint_system::ConstraintSystem, indexer::indexer_with_lagrange},
+    plonk::{
+        constraint_system::{circuit::Circuit, ConstraintSystem},
+        indexer::indexer_with_lagrange,
+    },
 };
 
 use crate::{
-    build_cs::{build_cs, N},
-    gen_params::VERIFIER_SPECIFIC_PARAMS,
+    build_cs::{
+        MatchmakingCircuit, MultiPartyMatchmakingCircuit, TeamMatchmakingCircuit, MAX_N,
+        MAX_PARTIES,
+    },
+    gen_params::{
+        VERIFIER_SPECIFIC_PARAMS_10, VERIFIER_SPECIFIC_PARAMS_20, VERIFIER_SPECIFIC_PARAMS_50,
+    },
 };
 
 // re-export
 pub use uzkge::gen_params::{ProverParams, VerifierParams};
 
-/// Obtain the parameters for prover.
-pub fn gen_prover_params() -> Result<ProverParams, UzkgeError> {
-    let (cs, _) = build_cs(&[Fr::zero(); N], &Fr::zero(), &Fr::zero());
+/// The circuits whose prover parameters are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CircuitKey {
+    Single(usize),
+    Team(usize, usize),
+    MultiParty(usize, usize),
+}
+
+/// The number of the prover parameters kept in the cache.
+const MAX_CACHED_PARAMS: usize = 8;
+
+/// The prover parameters already generated, as the indexer is slow for large pools.
+static PROVER_PARAMS: Lazy<Mutex<HashMap<CircuitKey, Arc<ProverParams>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cached_prover_params(
+    key: CircuitKey,
+    gen: impl FnOnce() -> Result<ProverParams, UzkgeError>,
+) -> Result<Arc<ProverParams>, UzkgeError> {
+    if let Some(pp) = PROVER_PARAMS.lock().unwrap().get(&key) {
+        return Ok(pp.clone());
+    }
+
+    // the lock is not held while indexing, so that the other circuits are not blocked.
+    // A circuit requested concurrently may be indexed twice, and the first one is kept.
+    let pp = Arc::new(gen()?);
+
+    let mut params = PROVER_PARAMS.lock().unwrap();
+    if let Some(cached) = params.get(&key) {
+        return Ok(cached.clone());
+    }
+    if params.len() < MAX_CACHED_PARAMS {
+        params.insert(key, pp.clone());
+    }
+    Ok(pp)
+}
+
+/// Get the parameters for prover with `n` inputs, generated at the first call for `n`.
+pub fn get_prover_params(n: usize) -> Result<Arc<ProverParams>, UzkgeError> {
+    cached_prover_params(CircuitKey::Single(n), || gen_prover_params(n))
+}
+
+/// Get the parameters for prover with `n` inputs in the teams of `k`, generated at the
+/// first call for `(n, k)`.
+pub fn get_team_prover_params(n: usize, k: usize) -> Result<Arc<ProverParams>, UzkgeError> {
+    cached_prover_params(CircuitKey::Team(n, k), || gen_team_prover_params(n, k))
+}
+
+/// Get the parameters for prover with `n` inputs and the seed shares of `parties`,
+/// generated at the first call for `(n, parties)`.
+pub fn get_multi_party_prover_params(
+    n: usize,
+    parties: usize,
+) -> Result<Arc<ProverParams>, UzkgeError> {
+    cached_prover_params(CircuitKey::MultiParty(n, parties), || {
+        gen_multi_party_prover_params(n, parties)
+    })
+}
+
+/// Obtain the parameters for prover with `n` inputs.
+pub fn gen_prover_params(n: usize) -> Result<ProverParams, UzkgeError> {
+    if n <= 2 || n > MAX_N {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let cs = MatchmakingCircuit { n }.synthesize_shape();
     let pcs = load_srs_params(cs.size())?;
     let lagrange_pcs = load_lagrange_params(cs.size());
 
-    let verifier_params = if let Ok(v) = load_verifier_params() {
+    let verifier_params = if let Ok(v) = load_verifier_params(n) {
         Some(v.verifier_params)
     } else {
         None
     };
 
     let prover_params =
-        indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None, verifier_params).unwrap();
+        indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None, verifier_params)?;
 
     Ok(ProverParams {
         pcs,
@@ -40,17 +114,79 @@ pub fn gen_prover_params() -> Result<ProverParams, UzkgeError> {
     })
 }
 
-/// Get the verifier parameters.
-pub fn get_verifier_params() -> Result<VerifierParams, UzkgeError> {
-    match load_verifier_params() {
+/// Obtain the parameters for prover with `n` inputs in the teams of `k`.
+pub fn gen_team_prover_params(n: usize, k: usize) -> Result<ProverParams, UzkgeError> {
+    if n <= 2 || n > MAX_N || k == 0 || !n.is_multiple_of(k) {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let cs = TeamMatchmakingCircuit { n, team_size: k }.synthesize_shape();
+    let pcs = load_srs_params(cs.size())?;
+    let lagrange_pcs = load_lagrange_params(cs.size());
+
+    let prover_params = indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None, None)?;
+
+    Ok(ProverParams {
+        pcs,
+        lagrange_pcs,
+        cs,
+        prover_params,
+    })
+}
+
+/// Get the verifier parameters with `n` inputs in the teams of `k`.
+pub fn get_team_verifier_params(n: usize, k: usize) -> Result<VerifierParams, UzkgeError> {
+    Ok(VerifierParams::from(get_team_prover_params(n, k)?.as_ref()))
+}
+
+/// Obtain the parameters for prover with `n` inputs and the seed shares of `parties`.
+pub fn gen_multi_party_prover_params(n: usize, parties: usize) -> Result<ProverParams, UzkgeError> {
+    if n <= 2 || n > MAX_N || parties == 0 || parties > MAX_PARTIES {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let cs = MultiPartyMatchmakingCircuit { n, parties }.synthesize_shape();
+    let pcs = load_srs_params(cs.size())?;
+    let lagrange_pcs = load_lagrange_params(cs.size());
+
+    let prover_params = indexer_with_lagrange(&cs, &pcs, lagrange_pcs.as_ref(), None, None)?;
+
+    Ok(ProverParams {
+        pcs,
+        lagrange_pcs,
+        cs,
+        prover_params,
+    })
+}
+
+/// Get the verifier parameters with `n` inputs and the seed shares of `parties`.
+pub fn get_multi_party_verifier_params(
+    n: usize,
+    parties: usize,
+) -> Result<VerifierParams, UzkgeError> {
+    Ok(VerifierParams::from(
+        get_multi_party_prover_params(n, parties)?.as_ref(),
+    ))
+}
+
+/// Get the verifier parameters with `n` inputs.
+pub fn get_verifier_params(n: usize) -> Result<VerifierParams, UzkgeError> {
+    match load_verifier_params(n) {
         Ok(vk) => Ok(vk),
-        Err(_e) => Ok(VerifierParams::from(gen_prover_params()?)),
+        Err(_e) => Ok(VerifierParams::from(get_prover_params(n)?.as_ref())),
     }
 }
 
 /// Load the verifier parameters from prepare.
-pub fn load_verifier_params() -> Result<VerifierParams, UzkgeError> {
-    match (VERIFIER_COMMON_PARAMS, VERIFIER_SPECIFIC_PARAMS) {
+pub fn load_verifier_params(n: usize) -> Result<VerifierParams, UzkgeError> {
+    let specific = match n {
+        10 => VERIFIER_SPECIFIC_PARAMS_10,
+        20 => VERIFIER_SPECIFIC_PARAMS_20,
+        50 => VERIFIER_SPECIFIC_PARAMS_50,
+        _ => return Err(UzkgeError::DeserializationError),
+    };
+
+    match (VERIFIER_COMMON_PARAMS, specific) {
         (Some(c_bytes), Some(s_bytes)) => {
             let common: VerifierParamsSplitCommon =
                 bincode::deserialize(c_bytes).map_err(|_| UzkgeError::DeserializationError)?;
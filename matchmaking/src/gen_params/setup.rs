@@ -11,38 +11,65 @@ use std::{
     sync::{Arc, Mutex},
 };
 use structopt::StructOpt;
-use zmatchmaking::gen_params::get_verifier_params;
+use uzkge::gen_params::solidity::gen_solidity_verifier;
+use zmatchmaking::{
+    build_cs::{matchmaking_transcript, EMBEDDED_SIZES},
+    gen_params::get_verifier_params,
+};
 
 #[derive(StructOpt, Debug)]
 #[structopt(about = "Tool to generate verifier params", rename_all = "kebab-case")]
 enum Actions {
     /// Generates the verifying key
-    MATCHMAKING { directory: PathBuf },
+    MATCHMAKING { num: usize, directory: PathBuf },
+
+    /// Generates the verifying keys of all the embedded sizes
+    ALL { directory: PathBuf },
+
+    /// Generates the self-contained Solidity verifier
+    SOLIDITY_VERIFIER { num: usize, directory: PathBuf },
 }
 
 fn main() {
     use Actions::*;
     let action = Actions::from_args();
     match action {
-        MATCHMAKING { directory } => gen_vk_specific(directory),
+        MATCHMAKING { num, directory } => gen_vk_specific(num, directory),
+
+        ALL { directory } => {
+            for num in EMBEDDED_SIZES {
+                gen_vk_specific(num, directory.clone())
+            }
+        }
+
+        SOLIDITY_VERIFIER { num, directory } => gen_solidity_matchmaking_verifier(num, directory),
     };
 }
 
-// cargo run --release --features="gen no_vk" --bin gen-params matchmaking "./parameters"
-fn gen_vk_specific(directory: PathBuf) {
-    let params = get_verifier_params().unwrap();
+// cargo run --release --features="gen no_vk" --bin gen-params matchmaking 50 "./parameters"
+fn gen_vk_specific(num: usize, directory: PathBuf) {
+    let params = get_verifier_params(num).unwrap();
     println!(
-        "the size of the constraint system of shuffle: {}",
-        params.shrunk_cs.size
+        "the size of the constraint system of matchmaking {}: {}",
+        num, params.shrunk_cs.size
     );
 
     let (_, special) = params.split().unwrap();
     let specials_ser = bincode::serialize(&special).unwrap();
     let mut specials_path: PathBuf = directory.clone();
-    specials_path.push("vk-specific.bin");
+    specials_path.push(format!("vk-specific-{}.bin", num));
     save_to_file(&specials_ser, specials_path);
 }
 
+// cargo run --release --features="gen" --bin gen-params solidity-verifier 50 "./parameters"
+fn gen_solidity_matchmaking_verifier(num: usize, mut directory: PathBuf) {
+    let params = get_verifier_params(num).unwrap();
+    let name = format!("MatchmakingPlonkVerifier_{}", num);
+    let contract = gen_solidity_verifier(&params, matchmaking_transcript(num), &name).unwrap();
+    directory.push(format!("{}.sol", name));
+    save_to_file(contract.as_bytes(), directory);
+}
+
 fn save_to_file(params_ser: &[u8], out_filename: ark_std::path::PathBuf) {
     use ark_std::io::Write;
     let filename = out_filename.to_str().unwrap();
@@ -1,6 +1,12 @@
 /// Module for match making.
 pub mod matchmaking;
 
+/// Module for team match making with skill buckets.
+pub mod team;
+
+/// Module for match making with the seed shares of all the participants.
+pub mod multi_party;
+
 /// Module for build match making cs.
 pub mod build_cs;
 
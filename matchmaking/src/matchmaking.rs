@@ -6,7 +6,7 @@ use uzkge::{
     plonk::constraint_system::{TurboCS, VarIndex},
 };
 
-pub struct Matchmaking<const N: usize, F: PrimeField> {
+pub struct Matchmaking<F: PrimeField> {
     pub input_vars: Vec<VarIndex>,
 
     pub committed_input_var: VarIndex,
@@ -18,7 +18,7 @@ pub struct Matchmaking<const N: usize, F: PrimeField> {
     pub output_vars: Vec<VarIndex>,
 }
 
-impl<const N: usize, F: PrimeField> Matchmaking<N, F> {
+impl<F: PrimeField> Matchmaking<F> {
     pub fn new(
         input_vars: &[VarIndex],
         committed_input_var: VarIndex,
@@ -26,8 +26,7 @@ impl<const N: usize, F: PrimeField> Matchmaking<N, F> {
         committed_trace: &AnemoiVLHTrace<F, 2, 14>,
         random_number_var: VarIndex,
     ) -> Self {
-        assert!(N > 2);
-        assert_eq!(input_vars.len(), N);
+        assert!(input_vars.len() > 2);
 
         Self {
             input_vars: input_vars.to_vec(),
@@ -40,19 +39,7 @@ impl<const N: usize, F: PrimeField> Matchmaking<N, F> {
     }
 
     pub fn generate_constraints<P: AnemoiJive<F, 2, 14>>(&mut self, cs: &mut TurboCS<F>) {
-        let zero = F::zero();
-        let one = F::one();
-        let minus_one = one.neg();
-
-        let mut indexes = vec![zero, one];
-        let mut index_vars = vec![cs.zero_var(), cs.one_var()];
-        for i in 2..N {
-            let index = F::from(i as u64);
-            let index_var = cs.new_variable(index);
-            cs.insert_constant_gate(index_var, index);
-            indexes.push(index);
-            index_vars.push(index_var)
-        }
+        let index_vars = new_index_vars(cs, self.input_vars.len());
 
         cs.anemoi_variable_length_hash::<P>(
             &self.committed_trace,
@@ -60,171 +47,248 @@ impl<const N: usize, F: PrimeField> Matchmaking<N, F> {
             self.committed_ouput_var,
         );
 
-        let stream_cipher_trace = P::eval_stream_cipher_with_trace(
-            &[
-                self.committed_trace.input[0],
-                cs.witness[self.random_number_var],
-            ],
-            N - 1,
-        );
-        let stream_cipher_trace_output_vars = stream_cipher_trace
-            .output
-            .iter()
-            .map(|x| cs.new_variable(*x))
-            .collect::<Vec<_>>();
-        cs.anemoi_stream_cipher::<P>(
-            &stream_cipher_trace,
-            &[self.committed_input_var, self.random_number_var],
-            &stream_cipher_trace_output_vars,
+        self.output_vars = shuffle_with_seed::<F, P>(
+            cs,
+            &self.input_vars,
+            &index_vars,
+            self.committed_input_var,
+            self.random_number_var,
         );
+    }
+}
+
+/// The bit length of the truncation of the quotients of the stream cipher outputs, and of
+/// the low part of the outputs that is reduced in the team matchmaking.
+pub(crate) const REDUCED_BITS: usize = 128;
+
+/// The constant variables of the indices `0..size`.
+pub(crate) fn new_index_vars<F: PrimeField>(cs: &mut TurboCS<F>, size: usize) -> Vec<VarIndex> {
+    let mut index_vars = vec![cs.zero_var(), cs.one_var()];
+    for i in 2..size {
+        let index = F::from(i as u64);
+        let index_var = cs.new_variable(index);
+        cs.insert_constant_gate(index_var, index);
+        index_vars.push(index_var)
+    }
+    index_vars
+}
+
+/// Shuffle the inputs with the Anemoi stream cipher of the seed and the random number,
+/// and return the outputs.
+pub(crate) fn shuffle_with_seed<F: PrimeField, P: AnemoiJive<F, 2, 14>>(
+    cs: &mut TurboCS<F>,
+    input_vars: &[VarIndex],
+    index_vars: &[VarIndex],
+    seed_var: VarIndex,
+    random_number_var: VarIndex,
+) -> Vec<VarIndex> {
+    let size = input_vars.len();
+    let zero = F::zero();
+    let one = F::one();
+    let minus_one = one.neg();
+
+    let stream_cipher_trace = P::eval_stream_cipher_with_trace(
+        &[cs.witness[seed_var], cs.witness[random_number_var]],
+        size - 1,
+    );
+    let stream_cipher_trace_output_vars = stream_cipher_trace
+        .output
+        .iter()
+        .map(|x| cs.new_variable(*x))
+        .collect::<Vec<_>>();
+    cs.anemoi_stream_cipher::<P>(
+        &stream_cipher_trace,
+        &[seed_var, random_number_var],
+        &stream_cipher_trace_output_vars,
+    );
 
-        let mut output_vars = self.input_vars.clone();
+    let mut output_vars = input_vars.to_vec();
 
-        for i in 1..N {
-            let n: BigUint = stream_cipher_trace.output[i - 1].into();
-            let m = BigUint::from(i + 1);
-            let (quotient, remainder) = n.div_rem(&m);
-            let quotient = F::from(quotient);
-            let remainder = F::from(remainder);
+    for i in 1..size {
+        let (_, remainder_var) = div_rem(cs, stream_cipher_trace_output_vars[i - 1], i + 1);
+        let remainder = cs.witness[remainder_var];
 
-            let n_var = cs.new_variable(stream_cipher_trace.output[i - 1]);
-            let quotient_var = cs.new_variable(quotient);
-            let remainder_var = cs.new_variable(remainder);
+        let relative_indices = (0..=i)
+            .map(|j| F::from(j as u64).sub(&remainder))
+            .collect::<Vec<F>>();
 
-            cs.push_add_selectors(F::from((i + 1) as u64), one, zero, zero);
-            cs.push_mul_selectors(zero, zero);
+        let mut bits: Vec<F> = Vec::new();
+        for x in relative_indices.iter() {
+            if x.is_zero() {
+                bits.push(one);
+            } else {
+                bits.push(zero);
+            }
+        }
+        let bits_vars = bits.iter().map(|x| cs.new_variable(*x)).collect::<Vec<_>>();
+
+        // Enforce that the sum of bits equals 1.
+        let sum_bits_var = sum_vars(cs, &bits_vars, true);
+        cs.insert_constant_gate(sum_bits_var, one);
+
+        for i in 0..bits_vars.len() {
+            cs.push_add_selectors(zero, zero, zero, zero);
+            cs.push_mul_selectors(one, minus_one);
             cs.push_constant_selector(zero);
             cs.push_ecc_selector(zero);
-            cs.push_out_selector(one);
+            cs.push_out_selector(zero);
 
-            cs.wiring[0].push(quotient_var);
-            cs.wiring[1].push(remainder_var);
-            cs.wiring[2].push(cs.zero_var());
-            cs.wiring[3].push(cs.zero_var());
-            cs.wiring[4].push(n_var);
+            cs.wiring[0].push(index_vars[i]);
+            cs.wiring[1].push(bits_vars[i]);
+            cs.wiring[2].push(remainder_var);
+            cs.wiring[3].push(bits_vars[i]);
+            cs.wiring[4].push(cs.zero_var());
 
             cs.finish_new_gate();
+        }
 
-            let relative_indices = indexes
-                .iter()
-                .take(i + 1)
-                .map(|x| x.sub(&remainder))
-                .collect::<Vec<F>>();
-
-            let mut bits: Vec<F> = Vec::new();
-            for x in relative_indices.iter() {
-                if x.is_zero() {
-                    bits.push(one);
-                } else {
-                    bits.push(zero);
-                }
-            }
-            let bits_vars = bits.iter().map(|x| cs.new_variable(*x)).collect::<Vec<_>>();
-
-            {
-                // Enforce that the sum of bits equals 1.
-                let mut sum_bits_var = cs.zero_var();
-                for c in bits_vars.chunks(3) {
-                    match c.len() {
-                        3 => {
-                            sum_bits_var = cs.linear_combine(
-                                &[sum_bits_var, c[0], c[1], c[2]],
-                                one,
-                                one,
-                                one,
-                                one,
-                            );
-                            cs.attach_boolean_constraint_to_gate();
-                        }
-
-                        2 => {
-                            sum_bits_var = cs.linear_combine(
-                                &[sum_bits_var, c[0], c[1], cs.zero_var()],
-                                one,
-                                one,
-                                one,
-                                zero,
-                            );
-                            cs.attach_boolean_constraint_to_gate();
-                        }
-
-                        1 => {
-                            sum_bits_var = cs.linear_combine(
-                                &[sum_bits_var, c[0], cs.zero_var(), cs.zero_var()],
-                                one,
-                                one,
-                                zero,
-                                zero,
-                            );
-                            cs.attach_boolean_constraint_to_gate();
-                        }
-
-                        _ => unreachable!(),
-                    }
-                }
-                cs.insert_constant_gate(sum_bits_var, one);
-            }
+        swap_with_bits(cs, &mut output_vars, &bits_vars, i);
+    }
+
+    output_vars
+}
 
-            for i in 0..bits_vars.len() {
-                cs.push_add_selectors(zero, zero, zero, zero);
-                cs.push_mul_selectors(one, minus_one);
-                cs.push_constant_selector(zero);
-                cs.push_ecc_selector(zero);
-                cs.push_out_selector(zero);
+/// Divide `witness[n_var]` by `m`, and return the variables of the `REDUCED_BITS`-bit
+/// truncation of the quotient and of the remainder. The remainder is not range-checked.
+///
+/// The dividend is split into `high * m * 2^REDUCED_BITS + quotient * m + remainder`, so
+/// only small range checks are needed. The split does not wrap around as `high` is below
+/// `p / (m * 2^REDUCED_BITS)`, which an honest prover only misses for the negligible
+/// fraction of the dividends above `p - m * 2^REDUCED_BITS`.
+pub(crate) fn div_rem<F: PrimeField>(
+    cs: &mut TurboCS<F>,
+    n_var: VarIndex,
+    m: usize,
+) -> (VarIndex, VarIndex) {
+    let zero = F::zero();
+    let one = F::one();
+    let modulus: BigUint = F::MODULUS.into();
+    let shift = BigUint::from(m) << REDUCED_BITS;
 
-                cs.wiring[0].push(index_vars[i]);
-                cs.wiring[1].push(bits_vars[i]);
-                cs.wiring[2].push(remainder_var);
-                cs.wiring[3].push(bits_vars[i]);
-                cs.wiring[4].push(cs.zero_var());
+    let n: BigUint = cs.witness[n_var].into();
+    let (high, low) = n.div_rem(&shift);
+    let (quotient, remainder) = low.div_rem(&BigUint::from(m));
+    let high_var = cs.new_variable(F::from(high));
+    let quotient_var = cs.new_variable(F::from(quotient));
+    let remainder_var = cs.new_variable(F::from(remainder));
 
-                cs.finish_new_gate();
+    enforce_upper_bound(cs, high_var, &(modulus / &shift - 1u32));
+    cs.range_check(quotient_var, REDUCED_BITS);
+
+    // Enforce that `high * m * 2^REDUCED_BITS + quotient * m + remainder = n`.
+    cs.push_add_selectors(F::from(shift), F::from(m as u64), one, zero);
+    cs.push_mul_selectors(zero, zero);
+    cs.push_constant_selector(zero);
+    cs.push_ecc_selector(zero);
+    cs.push_out_selector(one);
+
+    cs.wiring[0].push(high_var);
+    cs.wiring[1].push(quotient_var);
+    cs.wiring[2].push(remainder_var);
+    cs.wiring[3].push(cs.zero_var());
+    cs.wiring[4].push(n_var);
+
+    cs.finish_new_gate();
+
+    (quotient_var, remainder_var)
+}
+
+/// Sum the variables with linear combination gates, with the Boolean constraints
+/// on the summands if `boolean`.
+pub(crate) fn sum_vars<F: PrimeField>(
+    cs: &mut TurboCS<F>,
+    vars: &[VarIndex],
+    boolean: bool,
+) -> VarIndex {
+    let zero = F::zero();
+    let one = F::one();
+
+    let mut sum_var = cs.zero_var();
+    for c in vars.chunks(3) {
+        match c.len() {
+            3 => {
+                sum_var = cs.linear_combine(&[sum_var, c[0], c[1], c[2]], one, one, one, one);
             }
 
-            let output_i_var = output_vars[i];
-
-            let bit_mul_output_vars = bits_vars
-                .iter()
-                .zip(output_vars.iter())
-                .map(|(x, y)| cs.mul(*x, *y))
-                .collect::<Vec<_>>();
-            let mut swap_var = cs.zero_var();
-            for c in bit_mul_output_vars.chunks(3) {
-                match c.len() {
-                    3 => {
-                        swap_var =
-                            cs.linear_combine(&[swap_var, c[0], c[1], c[2]], one, one, one, one);
-                    }
-
-                    2 => {
-                        swap_var = cs.linear_combine(
-                            &[swap_var, c[0], c[1], cs.zero_var()],
-                            one,
-                            one,
-                            one,
-                            zero,
-                        );
-                    }
-
-                    1 => {
-                        swap_var = cs.linear_combine(
-                            &[swap_var, c[0], cs.zero_var(), cs.zero_var()],
-                            one,
-                            one,
-                            zero,
-                            zero,
-                        );
-                    }
-                    _ => unreachable!(),
-                }
+            2 => {
+                sum_var =
+                    cs.linear_combine(&[sum_var, c[0], c[1], cs.zero_var()], one, one, one, zero);
             }
-            output_vars[i] = swap_var;
 
-            for j in 0..i {
-                output_vars[j] = cs.select(output_vars[j], output_i_var, bits_vars[j])
+            1 => {
+                sum_var = cs.linear_combine(
+                    &[sum_var, c[0], cs.zero_var(), cs.zero_var()],
+                    one,
+                    one,
+                    zero,
+                    zero,
+                );
             }
+
+            _ => unreachable!(),
         }
+        if boolean {
+            cs.attach_boolean_constraint_to_gate();
+        }
+    }
+    sum_var
+}
+
+/// Swap `output_vars[i]` with the output selected by the one-hot `bits_vars`.
+pub(crate) fn swap_with_bits<F: PrimeField>(
+    cs: &mut TurboCS<F>,
+    output_vars: &mut [VarIndex],
+    bits_vars: &[VarIndex],
+    i: usize,
+) {
+    let output_i_var = output_vars[i];
+
+    let bit_mul_output_vars = bits_vars
+        .iter()
+        .zip(output_vars.iter())
+        .map(|(x, y)| cs.mul(*x, *y))
+        .collect::<Vec<_>>();
+    output_vars[i] = sum_vars(cs, &bit_mul_output_vars, false);
 
-        self.output_vars = output_vars;
+    for j in 0..i {
+        output_vars[j] = cs.select(output_vars[j], output_i_var, bits_vars[j])
     }
 }
+
+/// Enforce that `witness[var] <= bound` with the range checks of `var` and `bound - var`
+/// in the bit length of `bound`. A `var` above the bound would make `bound - var` wrap to
+/// at least `p + bound - 2^bits + 1`, so this is sound when that is at least `2^bits`.
+///
+/// The range checks use the bit gates instead of lookups, as the generated Solidity
+/// verifier of the matchmaking proofs does not support the lookup argument.
+pub(crate) fn enforce_upper_bound<F: PrimeField>(
+    cs: &mut TurboCS<F>,
+    var: VarIndex,
+    bound: &BigUint,
+) {
+    let n_bits = (bound.bits() as usize).max(2);
+    let modulus: BigUint = F::MODULUS.into();
+    assert!(modulus + bound + 1u32 >= BigUint::from(1u32) << (n_bits + 1));
+
+    let zero = F::zero();
+    let bound = F::from(bound.clone());
+    let diff_var = cs.new_variable(bound - cs.witness[var]);
+
+    // Enforce that `bound - var = diff`.
+    cs.push_add_selectors(F::one().neg(), zero, zero, zero);
+    cs.push_mul_selectors(zero, zero);
+    cs.push_constant_selector(bound);
+    cs.push_ecc_selector(zero);
+    cs.push_out_selector(F::one());
+
+    cs.wiring[0].push(var);
+    cs.wiring[1].push(cs.zero_var());
+    cs.wiring[2].push(cs.zero_var());
+    cs.wiring[3].push(cs.zero_var());
+    cs.wiring[4].push(diff_var);
+
+    cs.finish_new_gate();
+
+    cs.range_check(var, n_bits);
+    cs.range_check(diff_var, n_bits);
+}
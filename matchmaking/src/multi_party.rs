@@ -0,0 +1,95 @@
+use ark_ff::PrimeField;
+use uzkge::{
+    anemoi::{AnemoiJive, AnemoiVLHTrace},
+    plonk::constraint_system::{TurboCS, VarIndex},
+};
+
+use crate::matchmaking::{new_index_vars, shuffle_with_seed, sum_vars};
+
+/// The matchmaking with the seed combined from the shares of all the participants.
+///
+/// Every participant commits to a random seed share with the Anemoi hash before the
+/// matchmaking, and opens it afterwards. The circuit proves that every share matches its
+/// commitment and shuffles the inputs with the sum of the shares, so that the result is
+/// not biased as long as one of the participants is honest.
+pub struct MultiPartyMatchmaking<F: PrimeField> {
+    pub input_vars: Vec<VarIndex>,
+
+    pub share_vars: Vec<VarIndex>,
+    pub commitment_vars: Vec<VarIndex>,
+    pub share_traces: Vec<AnemoiVLHTrace<F, 2, 14>>,
+
+    pub random_number_var: VarIndex,
+
+    pub output_vars: Vec<VarIndex>,
+}
+
+impl<F: PrimeField> MultiPartyMatchmaking<F> {
+    pub fn new(
+        input_vars: &[VarIndex],
+        share_vars: &[VarIndex],
+        commitment_vars: &[VarIndex],
+        share_traces: &[AnemoiVLHTrace<F, 2, 14>],
+        random_number_var: VarIndex,
+    ) -> Self {
+        assert!(input_vars.len() > 2);
+        assert!(!share_vars.is_empty());
+        assert_eq!(share_vars.len(), commitment_vars.len());
+        assert_eq!(share_vars.len(), share_traces.len());
+
+        Self {
+            input_vars: input_vars.to_vec(),
+            share_vars: share_vars.to_vec(),
+            commitment_vars: commitment_vars.to_vec(),
+            share_traces: share_traces.to_vec(),
+            random_number_var,
+            output_vars: Vec::new(),
+        }
+    }
+
+    pub fn generate_constraints<P: AnemoiJive<F, 2, 14>>(&mut self, cs: &mut TurboCS<F>) {
+        let index_vars = new_index_vars(cs, self.input_vars.len());
+
+        for ((trace, share_var), commitment_var) in self
+            .share_traces
+            .iter()
+            .zip(self.share_vars.iter())
+            .zip(self.commitment_vars.iter())
+        {
+            cs.anemoi_variable_length_hash::<P>(trace, &[*share_var], *commitment_var);
+        }
+
+        let seed_var = sum_vars(cs, &self.share_vars, false);
+
+        self.output_vars = shuffle_with_seed::<F, P>(
+            cs,
+            &self.input_vars,
+            &index_vars,
+            seed_var,
+            self.random_number_var,
+        );
+    }
+}
+
+/// The commitment of a seed share, published before the matchmaking.
+pub fn commit_seed_share<F: PrimeField, P: AnemoiJive<F, 2, 14>>(share: &F) -> F {
+    P::eval_variable_length_hash(&[*share])
+}
+
+/// Check the opened seed shares against their commitments.
+pub fn verify_seed_shares<F: PrimeField, P: AnemoiJive<F, 2, 14>>(
+    commitments: &[F],
+    shares: &[F],
+) -> bool {
+    !shares.is_empty()
+        && commitments.len() == shares.len()
+        && commitments
+            .iter()
+            .zip(shares.iter())
+            .all(|(c, s)| *c == commit_seed_share::<F, P>(s))
+}
+
+/// The seed combined from the opened shares.
+pub fn combine_seed_shares<F: PrimeField>(shares: &[F]) -> F {
+    shares.iter().sum()
+}
@@ -0,0 +1,235 @@
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use uzkge::{
+    anemoi::{AnemoiJive, AnemoiVLHTrace},
+    plonk::constraint_system::{TurboCS, VarIndex},
+};
+
+use crate::matchmaking::{enforce_upper_bound, sum_vars, swap_with_bits, REDUCED_BITS};
+
+/// The matchmaking of the teams of `team_size` players.
+///
+/// The inputs are grouped by the skill bucket tags, which are sorted so that the players
+/// of a bucket are contiguous. Every step of the shuffle swaps the player `i` with a player
+/// selected by the committed randomness among the players `0..=i` of the same bucket, so
+/// the players are only permuted within their buckets. Then the outputs are split into the
+/// teams of `team_size` consecutive players, and every team is checked to be in one bucket.
+pub struct TeamMatchmaking<F: PrimeField> {
+    pub input_vars: Vec<VarIndex>,
+    pub tag_vars: Vec<VarIndex>,
+    pub team_size: usize,
+
+    pub committed_input_var: VarIndex,
+    pub committed_ouput_var: VarIndex,
+    pub committed_trace: AnemoiVLHTrace<F, 2, 14>,
+
+    pub random_number_var: VarIndex,
+
+    pub output_vars: Vec<VarIndex>,
+}
+
+impl<F: PrimeField> TeamMatchmaking<F> {
+    pub fn new(
+        input_vars: &[VarIndex],
+        tag_vars: &[VarIndex],
+        team_size: usize,
+        committed_input_var: VarIndex,
+        committed_ouput_var: VarIndex,
+        committed_trace: &AnemoiVLHTrace<F, 2, 14>,
+        random_number_var: VarIndex,
+    ) -> Self {
+        assert!(input_vars.len() > 2);
+        assert_eq!(input_vars.len(), tag_vars.len());
+        assert!(team_size > 0 && input_vars.len().is_multiple_of(team_size));
+        assert!(F::MODULUS_BIT_SIZE as usize > REDUCED_BITS + usize::BITS as usize);
+
+        Self {
+            input_vars: input_vars.to_vec(),
+            tag_vars: tag_vars.to_vec(),
+            team_size,
+            committed_input_var,
+            committed_ouput_var,
+            committed_trace: committed_trace.clone(),
+            random_number_var,
+            output_vars: Vec::new(),
+        }
+    }
+
+    pub fn generate_constraints<P: AnemoiJive<F, 2, 14>>(&mut self, cs: &mut TurboCS<F>) {
+        let size = self.input_vars.len();
+        let zero = F::zero();
+        let one = F::one();
+        let minus_one = one.neg();
+
+        let mut index_vars = vec![cs.zero_var(), cs.one_var()];
+        for i in 2..size {
+            let index = F::from(i as u64);
+            let index_var = cs.new_variable(index);
+            cs.insert_constant_gate(index_var, index);
+            index_vars.push(index_var)
+        }
+
+        cs.anemoi_variable_length_hash::<P>(
+            &self.committed_trace,
+            &[self.committed_input_var],
+            self.committed_ouput_var,
+        );
+
+        let stream_cipher_trace = P::eval_stream_cipher_with_trace(
+            &[
+                self.committed_trace.input[0],
+                cs.witness[self.random_number_var],
+            ],
+            size - 1,
+        );
+        let stream_cipher_trace_output_vars = stream_cipher_trace
+            .output
+            .iter()
+            .map(|x| cs.new_variable(*x))
+            .collect::<Vec<_>>();
+        cs.anemoi_stream_cipher::<P>(
+            &stream_cipher_trace,
+            &[self.committed_input_var, self.random_number_var],
+            &stream_cipher_trace_output_vars,
+        );
+
+        let mut output_vars = self.input_vars.clone();
+        let shift = BigUint::from(1u32) << REDUCED_BITS;
+        let modulus: BigUint = F::MODULUS.into();
+        let high_bound = (modulus >> REDUCED_BITS) - 1u32;
+
+        // The first index of the bucket of the player `i`.
+        let mut start_var = cs.zero_var();
+
+        for i in 1..size {
+            let same_bucket_var = cs.is_equal(self.tag_vars[i], self.tag_vars[i - 1]);
+            start_var = cs.select(index_vars[i], start_var, same_bucket_var);
+
+            // The number of the players `0..=i` in the bucket.
+            let modulus_var = cs.linear_combine(
+                &[index_vars[i], start_var, cs.one_var(), cs.zero_var()],
+                one,
+                minus_one,
+                one,
+                zero,
+            );
+
+            // Split the output `n` into `high * 2^REDUCED_BITS + low`, where `high` is
+            // bounded so that the split is unique.
+            let n: BigUint = stream_cipher_trace.output[i - 1].into();
+            let (high, low) = n.div_rem(&shift);
+            let high_var = cs.new_variable(F::from(high));
+            let low_var = cs.new_variable(F::from(low.clone()));
+
+            cs.push_add_selectors(F::from(shift.clone()), one, zero, zero);
+            cs.push_mul_selectors(zero, zero);
+            cs.push_constant_selector(zero);
+            cs.push_ecc_selector(zero);
+            cs.push_out_selector(one);
+
+            cs.wiring[0].push(high_var);
+            cs.wiring[1].push(low_var);
+            cs.wiring[2].push(cs.zero_var());
+            cs.wiring[3].push(cs.zero_var());
+            cs.wiring[4].push(stream_cipher_trace_output_vars[i - 1]);
+
+            cs.finish_new_gate();
+
+            enforce_upper_bound(cs, high_var, &high_bound);
+            cs.range_check(low_var, REDUCED_BITS);
+
+            let m: BigUint = cs.witness[modulus_var].into();
+            let (quotient, remainder) = low.div_rem(&m);
+            let quotient_var = cs.new_variable(F::from(quotient));
+            let remainder_var = cs.new_variable(F::from(remainder));
+
+            // Enforce that `quotient * modulus + remainder = low`, which does not wrap
+            // around as the quotient is below `2^REDUCED_BITS`.
+            cs.range_check(quotient_var, REDUCED_BITS);
+
+            cs.push_add_selectors(zero, zero, one, zero);
+            cs.push_mul_selectors(one, zero);
+            cs.push_constant_selector(zero);
+            cs.push_ecc_selector(zero);
+            cs.push_out_selector(one);
+
+            cs.wiring[0].push(quotient_var);
+            cs.wiring[1].push(modulus_var);
+            cs.wiring[2].push(remainder_var);
+            cs.wiring[3].push(cs.zero_var());
+            cs.wiring[4].push(low_var);
+
+            cs.finish_new_gate();
+
+            let selected_var = cs.add(start_var, remainder_var);
+            let selected = cs.witness[selected_var];
+
+            let bits_vars = (0..=i)
+                .map(|j| {
+                    let bit = if F::from(j as u64) == selected {
+                        one
+                    } else {
+                        zero
+                    };
+                    cs.new_variable(bit)
+                })
+                .collect::<Vec<_>>();
+
+            // Enforce that the sum of bits equals 1.
+            let sum_bits_var = sum_vars(cs, &bits_vars, true);
+            cs.insert_constant_gate(sum_bits_var, one);
+
+            // Enforce that the selected player is at `start + remainder` and in the same
+            // bucket, which bounds the remainder by the modulus.
+            for j in 0..bits_vars.len() {
+                for (var, target_var) in [
+                    (index_vars[j], selected_var),
+                    (self.tag_vars[j], self.tag_vars[i]),
+                ] {
+                    cs.push_add_selectors(zero, zero, zero, zero);
+                    cs.push_mul_selectors(one, minus_one);
+                    cs.push_constant_selector(zero);
+                    cs.push_ecc_selector(zero);
+                    cs.push_out_selector(zero);
+
+                    cs.wiring[0].push(var);
+                    cs.wiring[1].push(bits_vars[j]);
+                    cs.wiring[2].push(target_var);
+                    cs.wiring[3].push(bits_vars[j]);
+                    cs.wiring[4].push(cs.zero_var());
+
+                    cs.finish_new_gate();
+                }
+            }
+
+            swap_with_bits(cs, &mut output_vars, &bits_vars, i);
+        }
+
+        // The players of a team are in the same bucket.
+        for team in self.tag_vars.chunks(self.team_size) {
+            for tag_var in team.iter().skip(1) {
+                cs.equal(team[0], *tag_var);
+            }
+        }
+
+        self.output_vars = output_vars;
+    }
+}
+
+/// Check that the tags are sorted and that every team of `team_size` consecutive
+/// players is in one bucket, which the circuit relies on.
+pub fn check_team_tags<F: PrimeField>(tags: &[F], team_size: usize) -> bool {
+    if tags.len() <= 2 || team_size == 0 || !tags.len().is_multiple_of(team_size) {
+        return false;
+    }
+
+    let sorted = tags
+        .windows(2)
+        .all(|w| w[0].into_bigint() <= w[1].into_bigint());
+    let grouped = tags
+        .chunks(team_size)
+        .all(|team| team.iter().all(|t| *t == team[0]));
+
+    sorted && grouped
+}
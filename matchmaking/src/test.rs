@@ -1,17 +1,43 @@
 use crate::{
-    build_cs::{prove_matchmaking, verify_matchmaking, N},
-    gen_params::{gen_prover_params, get_verifier_params},
+    build_cs::{
+        prove_matchmaking, prove_multi_party_matchmaking, prove_team_matchmaking,
+        verify_matchmaking, verify_multi_party_matchmaking, verify_team_matchmaking, TurboCS,
+        MAX_N, MAX_PARTIES, N,
+    },
+    gen_params::{
+        get_multi_party_prover_params, get_multi_party_verifier_params, get_prover_params,
+        get_team_prover_params, get_team_verifier_params, get_verifier_params,
+    },
+    matchmaking::div_rem,
+    multi_party::{combine_seed_shares, commit_seed_share, verify_seed_shares},
 };
 use ark_bn254::Fr;
-use ark_ff::UniformRand;
+use ark_ff::{Field, PrimeField, UniformRand};
 use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
+use std::sync::Arc;
 use uzkge::anemoi::{AnemoiJive, AnemoiJive254};
 
 #[test]
 fn test_matchmaking() {
+    matchmaking(N);
+}
+
+#[test]
+fn test_matchmaking_runtime_size() {
+    // an embedded size and a generated one.
+    matchmaking(10);
+    matchmaking(7);
+
+    // the pool sizes are bounded, so that no circuit is synthesized for them.
+    assert!(get_prover_params(MAX_N + 1).is_err());
+    assert!(get_team_prover_params(MAX_N + 1, 1).is_err());
+    assert!(get_multi_party_prover_params(10, MAX_PARTIES + 1).is_err());
+}
+
+fn matchmaking(n: usize) {
     let mut rng = ChaChaRng::from_entropy();
 
-    let inputs = (1..=N)
+    let inputs = (1..=n)
         .into_iter()
         .map(|i| Fr::from(i as u64))
         .collect::<Vec<_>>();
@@ -20,8 +46,10 @@ fn test_matchmaking() {
 
     let committment = AnemoiJive254::eval_variable_length_hash(&[committed_seed]);
 
-    let prover_params = gen_prover_params().unwrap();
-    let verifier_params = get_verifier_params().unwrap();
+    let prover_params = get_prover_params(n).unwrap();
+    let verifier_params = get_verifier_params(n).unwrap();
+    // the prover parameters are generated once per pool size.
+    assert!(Arc::ptr_eq(&prover_params, &get_prover_params(n).unwrap()));
 
     let (proof, outout) = prove_matchmaking(
         &mut rng,
@@ -42,3 +70,162 @@ fn test_matchmaking() {
     )
     .unwrap()
 }
+
+#[test]
+fn test_matchmaking_cheated_remainder() {
+    let mut rng = ChaChaRng::from_entropy();
+    let m = 10u64;
+
+    let mut cs = TurboCS::new();
+    let n = Fr::rand(&mut rng);
+    let n_var = cs.new_variable(n);
+    let (quotient_var, remainder_var) = div_rem(&mut cs, n_var, m as usize);
+    cs.prepare_pi_variable(n_var);
+    cs.prepare_pi_variable(remainder_var);
+    cs.pad();
+
+    let mut witness = cs.get_and_clear_witness();
+    let remainder = witness[remainder_var];
+    assert!(cs.verify_witness(&witness, &[n, remainder]).is_ok());
+
+    // the remainder is shifted, with the truncated quotient that wraps around the modulus.
+    let remainder: u64 = remainder.into_bigint().as_ref()[0];
+    let cheated_remainder = Fr::from((remainder + 1) % m);
+    witness[remainder_var] = cheated_remainder;
+    witness[quotient_var] +=
+        (Fr::from(remainder) - cheated_remainder) * Fr::from(m).inverse().unwrap();
+    assert!(cs
+        .verify_witness(&witness, &[n, cheated_remainder])
+        .is_err());
+}
+
+#[test]
+fn test_team_matchmaking() {
+    // 3v3 with two buckets, and 5v5 with one bucket.
+    let tags = [0u64, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1];
+    team_matchmaking(&tags, 3);
+    team_matchmaking(&[7u64; 10], 5);
+}
+
+fn team_matchmaking(tags: &[u64], k: usize) {
+    let mut rng = ChaChaRng::from_entropy();
+    let n = tags.len();
+
+    let inputs = (1..=n).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+    let tags = tags.iter().map(|t| Fr::from(*t)).collect::<Vec<_>>();
+    let committed_seed = Fr::rand(&mut rng);
+    let random_number = Fr::rand(&mut rng);
+
+    let committment = AnemoiJive254::eval_variable_length_hash(&[committed_seed]);
+
+    let prover_params = get_team_prover_params(n, k).unwrap();
+    let verifier_params = get_team_verifier_params(n, k).unwrap();
+
+    let (proof, outout) = prove_team_matchmaking(
+        &mut rng,
+        &inputs,
+        &tags,
+        k,
+        &committed_seed,
+        &random_number,
+        &prover_params,
+    )
+    .unwrap();
+
+    // the players stay in their buckets.
+    for (input, output) in inputs.iter().zip(outout.iter()) {
+        let i: u64 = (*input).into_bigint().as_ref()[0];
+        let o: u64 = (*output).into_bigint().as_ref()[0];
+        assert_eq!(tags[i as usize - 1], tags[o as usize - 1]);
+    }
+
+    verify_team_matchmaking(
+        &verifier_params,
+        &inputs,
+        &tags,
+        k,
+        &outout,
+        &committment,
+        &random_number,
+        &proof,
+    )
+    .unwrap();
+
+    let mut wrong_outout = outout.clone();
+    wrong_outout.swap(0, n - 1);
+    assert!(verify_team_matchmaking(
+        &verifier_params,
+        &inputs,
+        &tags,
+        k,
+        &wrong_outout,
+        &committment,
+        &random_number,
+        &proof,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_multi_party_matchmaking() {
+    let mut rng = ChaChaRng::from_entropy();
+    let n = 10;
+    let parties = 3;
+
+    let inputs = (1..=n).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+    let random_number = Fr::rand(&mut rng);
+
+    // every participant commits to a share, and opens it after all the commitments.
+    let shares = (0..parties).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitments = shares
+        .iter()
+        .map(commit_seed_share::<Fr, AnemoiJive254>)
+        .collect::<Vec<_>>();
+    assert!(verify_seed_shares::<Fr, AnemoiJive254>(
+        &commitments,
+        &shares
+    ));
+
+    let prover_params = get_multi_party_prover_params(n, parties).unwrap();
+    let verifier_params = get_multi_party_verifier_params(n, parties).unwrap();
+
+    let (proof, outout) =
+        prove_multi_party_matchmaking(&mut rng, &inputs, &shares, &random_number, &prover_params)
+            .unwrap();
+
+    verify_multi_party_matchmaking(
+        &verifier_params,
+        &inputs,
+        &outout,
+        &commitments,
+        &random_number,
+        &proof,
+    )
+    .unwrap();
+
+    // the permutation is the one of the single party matchmaking with the combined seed.
+    let seed = combine_seed_shares(&shares);
+    let single_party_params = get_prover_params(n).unwrap();
+    let (_, single_party_outout) = prove_matchmaking(
+        &mut rng,
+        &inputs,
+        &seed,
+        &random_number,
+        &single_party_params,
+    )
+    .unwrap();
+    assert_eq!(outout, single_party_outout);
+
+    // a participant cannot replace its commitment.
+    let mut wrong_commitments = commitments.clone();
+    wrong_commitments[1] = commit_seed_share::<Fr, AnemoiJive254>(&Fr::rand(&mut rng));
+    assert!(verify_multi_party_matchmaking(
+        &verifier_params,
+        &inputs,
+        &outout,
+        &wrong_commitments,
+        &random_number,
+        &proof,
+    )
+    .is_err());
+}
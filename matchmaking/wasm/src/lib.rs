@@ -0,0 +1,148 @@
+mod utils;
+
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Mutex};
+use uzkge::anemoi::{AnemoiJive, AnemoiJive254};
+use wasm_bindgen::prelude::*;
+use zmatchmaking::{
+    build_cs::{
+        prove_matchmaking as core_prove_matchmaking, verify_matchmaking as core_verify_matchmaking,
+    },
+    gen_params::{get_prover_params, get_verifier_params, VerifierParams},
+};
+
+use utils::error_to_jsvalue;
+pub use utils::{
+    default_prng, hex_to_scalar, matchmaking_proof_from_hex, matchmaking_proof_to_hex,
+    scalar_to_hex,
+};
+
+static VERIFIER_PARAMS: Lazy<Mutex<HashMap<usize, VerifierParams>>> = Lazy::new(|| {
+    let m = HashMap::new();
+    Mutex::new(m)
+});
+
+#[derive(Serialize, Deserialize)]
+pub struct Seed {
+    /// 0xHex (U256), keep it secret until the matchmaking
+    pub seed: String,
+    /// 0xHex (U256), the Anemoi hash of the seed
+    pub commitment: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MatchmakingWithProof {
+    /// 0xHex (U256)
+    pub outputs: Vec<String>,
+    /// hex string
+    pub proof: String,
+}
+
+fn hex_to_scalars(values: JsValue) -> Result<Vec<Fr>, JsValue> {
+    let values: Vec<String> = serde_wasm_bindgen::from_value(values)?;
+    values.iter().map(|v| hex_to_scalar(v)).collect()
+}
+
+/// generate a random seed and its commitment
+#[wasm_bindgen]
+pub fn generate_seed() -> Result<JsValue, JsValue> {
+    let mut prng = default_prng();
+    let seed = Fr::rand(&mut prng);
+    let commitment = AnemoiJive254::eval_variable_length_hash(&[seed]);
+
+    let ret = Seed {
+        seed: scalar_to_hex(&seed, true),
+        commitment: scalar_to_hex(&commitment, true),
+    };
+
+    Ok(serde_wasm_bindgen::to_value(&ret)?)
+}
+
+/// commit the seed, the commitment is published before the matchmaking
+#[wasm_bindgen]
+pub fn commit_seed(seed: String) -> Result<String, JsValue> {
+    let seed: Fr = hex_to_scalar(&seed)?;
+    let commitment = AnemoiJive254::eval_variable_length_hash(&[seed]);
+    Ok(scalar_to_hex(&commitment, true))
+}
+
+/// Initialize the prover key
+#[wasm_bindgen]
+pub fn init_prover_key(num: i32) -> Result<(), JsValue> {
+    let n = num as usize;
+
+    get_prover_params(n).map_err(error_to_jsvalue)?;
+    Ok(())
+}
+
+/// shuffle the inputs with the committed seed and the random number, return the outputs and proof
+#[wasm_bindgen]
+pub fn prove_matchmaking(
+    inputs: JsValue,
+    committed_seed: String,
+    random_number: String,
+) -> Result<JsValue, JsValue> {
+    let inputs = hex_to_scalars(inputs)?;
+    let committed_seed = hex_to_scalar(&committed_seed)?;
+    let random_number = hex_to_scalar(&random_number)?;
+    let n = inputs.len();
+
+    let mut prng = default_prng();
+
+    let prover_params = get_prover_params(n).map_err(error_to_jsvalue)?;
+
+    let (proof, outputs) = core_prove_matchmaking(
+        &mut prng,
+        &inputs,
+        &committed_seed,
+        &random_number,
+        &prover_params,
+    )
+    .map_err(error_to_jsvalue)?;
+
+    let ret = MatchmakingWithProof {
+        outputs: outputs.iter().map(|x| scalar_to_hex(x, true)).collect(),
+        proof: matchmaking_proof_to_hex(&proof),
+    };
+
+    Ok(serde_wasm_bindgen::to_value(&ret)?)
+}
+
+/// verify the outputs of the matchmaking against the seed commitment and the random number
+#[wasm_bindgen]
+pub fn verify_matchmaking(
+    inputs: JsValue,
+    outputs: JsValue,
+    commitment: String,
+    random_number: String,
+    proof: String,
+) -> Result<bool, JsValue> {
+    let inputs = hex_to_scalars(inputs)?;
+    let outputs = hex_to_scalars(outputs)?;
+    let commitment = hex_to_scalar(&commitment)?;
+    let random_number = hex_to_scalar(&random_number)?;
+    let proof = matchmaking_proof_from_hex(&proof)?;
+    let n = inputs.len();
+
+    let mut params = VERIFIER_PARAMS.lock().unwrap();
+    let verifier_params = if let Some(param) = params.get(&n) {
+        param
+    } else {
+        let vp = get_verifier_params(n).map_err(error_to_jsvalue)?;
+        params.insert(n, vp);
+        params.get(&n).unwrap()
+    };
+
+    Ok(core_verify_matchmaking(
+        verifier_params,
+        &inputs,
+        &outputs,
+        &commitment,
+        &random_number,
+        &proof,
+    )
+    .is_ok())
+}
@@ -0,0 +1,49 @@
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use rand_chacha::{
+    rand_core::{CryptoRng, RngCore, SeedableRng},
+    ChaChaRng,
+};
+use std::fmt::Display;
+use uzkge::plonk::constraint_system::TurboCS;
+use wasm_bindgen::prelude::*;
+use zmatchmaking::build_cs::Proof;
+
+#[inline(always)]
+pub(crate) fn error_to_jsvalue<T: Display>(e: T) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+pub fn default_prng() -> impl RngCore + CryptoRng {
+    ChaChaRng::from_entropy()
+}
+
+pub fn hex_to_scalar<F: PrimeField>(hex: &str) -> Result<F, JsValue> {
+    let hex = hex.trim_start_matches("0x");
+    let bytes = hex::decode(hex).map_err(error_to_jsvalue)?;
+    if bytes.len() != 32 {
+        return Err(error_to_jsvalue("Bytes length not 32"));
+    }
+    Ok(F::from_be_bytes_mod_order(&bytes))
+}
+
+pub fn scalar_to_hex<F: PrimeField>(scalar: &F, with_start: bool) -> String {
+    let bytes = scalar.into_bigint().to_bytes_be();
+    let s = hex::encode(bytes);
+    if with_start {
+        format!("0x{}", s)
+    } else {
+        s
+    }
+}
+
+pub fn matchmaking_proof_from_hex(s: &str) -> Result<Proof, JsValue> {
+    let hex = s.trim_start_matches("0x");
+    let bytes = hex::decode(hex).map_err(error_to_jsvalue)?;
+    Proof::from_bytes_be::<TurboCS<Fr>>(&bytes).map_err(error_to_jsvalue)
+}
+
+pub fn matchmaking_proof_to_hex(proof: &Proof) -> String {
+    let bytes = proof.to_bytes_be();
+    format!("0x{}", hex::encode(bytes))
+}
@@ -0,0 +1,40 @@
+//! Test suite for the Web and headless browsers.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use zmatchmaking_wasm::*;
+
+const N: i32 = 10;
+
+#[wasm_bindgen_test]
+fn pass() {
+    init_prover_key(N).unwrap();
+
+    let seed = generate_seed().unwrap();
+    let seed: Seed = serde_wasm_bindgen::from_value(seed).unwrap();
+    assert_eq!(commit_seed(seed.seed.clone()).unwrap(), seed.commitment);
+
+    let random_number = format!("0x{:064x}", 42);
+    let inputs: Vec<String> = (1..=N).map(|i| format!("0x{:064x}", i)).collect();
+    let inputs = serde_wasm_bindgen::to_value(&inputs).unwrap();
+
+    let res = prove_matchmaking(inputs.clone(), seed.seed, random_number.clone()).unwrap();
+    let res: MatchmakingWithProof = serde_wasm_bindgen::from_value(res).unwrap();
+    let outputs = serde_wasm_bindgen::to_value(&res.outputs).unwrap();
+
+    assert!(verify_matchmaking(
+        inputs.clone(),
+        outputs.clone(),
+        seed.commitment.clone(),
+        random_number.clone(),
+        res.proof.clone(),
+    )
+    .unwrap());
+
+    let wrong_commitment = format!("0x{:064x}", 1);
+    assert!(
+        !verify_matchmaking(inputs, outputs, wrong_commitment, random_number, res.proof,).unwrap()
+    );
+}
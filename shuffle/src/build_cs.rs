@@ -1,15 +1,21 @@
 use ark_bn254::Fr;
 use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective};
-use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{
+    rand::{CryptoRng, RngCore, SeedableRng},
+    UniformRand,
+};
+use rand_chacha::ChaChaRng;
 use uzkge::{
     errors::Result,
     plonk::{
-        constraint_system::shuffle::CardVar, indexer::PlonkProof, prover::prover_with_lagrange,
+        constraint_system::{circuit::Circuit, shuffle::CardVar, turbo::N_WIRE_SELECTORS},
+        indexer::PlonkProof,
+        prover::{prover_low_memory, prover_with_lagrange, ProverMemoryConfig},
         verifier::verifier,
     },
     poly_commit::kzg_poly_commitment::KZGCommitmentSchemeBN254,
     shuffle::{BabyJubjubShuffle, Permutation, Remark},
-    utils::transcript::Transcript,
+    utils::transcript::{Transcript, TranscriptScheme},
 };
 
 use crate::{
@@ -23,62 +29,145 @@ pub type TurboCS = uzkge::plonk::constraint_system::TurboCS<Fr>;
 const PLONK_PROOF_TRANSCRIPT: &[u8] = b"Plonk shuffle Proof";
 const N_CARDS_TRANSCRIPT: &[u8] = b"Number of cards";
 
-pub(crate) fn build_cs<R: CryptoRng + RngCore>(
+/// The transcript of the shuffle proof of `n` cards.
+pub fn shuffle_transcript(n: usize) -> Transcript {
+    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
+    transcript.append_u64(N_CARDS_TRANSCRIPT, n as u64);
+    transcript
+}
+
+/// The shuffle circuit of `n` cards, which remasks and permutes the cards.
+pub struct ShuffleCircuit {
+    pub n: usize,
+}
+
+/// The inputs of the shuffle circuit.
+pub struct ShuffleInputs {
+    pub aggregate_public_key: EdwardsProjective,
+    pub input_cards: Vec<MaskedCard>,
+    pub bits: Vec<Vec<[bool; N_WIRE_SELECTORS]>>,
+    pub permutation: Permutation<Fr>,
+}
+
+impl ShuffleInputs {
+    /// Sample the remasking bits and the permutation of the shuffle.
+    pub fn sample<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        aggregate_public_key: &EdwardsProjective,
+        input_cards: &[MaskedCard],
+    ) -> Self {
+        let n = input_cards.len();
+        let bits = (0..n)
+            .map(|_| BabyJubjubShuffle::sample_random_scalar_bits(prng))
+            .collect();
+        let permutation = Permutation::rand(prng, n);
+
+        Self {
+            aggregate_public_key: *aggregate_public_key,
+            input_cards: input_cards.to_vec(),
+            bits,
+            permutation,
+        }
+    }
+}
+
+impl Circuit<Fr> for ShuffleCircuit {
+    type Inputs = ShuffleInputs;
+    type Output = Vec<CardVar>;
+
+    fn synthesize(&self, cs: &mut TurboCS, inputs: &ShuffleInputs) -> Vec<CardVar> {
+        let apk = &inputs.aggregate_public_key;
+        cs.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(apk);
+
+        let mut remark_card_vars = Vec::with_capacity(self.n);
+
+        for (i, (input, bits)) in inputs.input_cards.iter().zip(&inputs.bits).enumerate() {
+            let trace = BabyJubjubShuffle::eval_remark_with_trace(input, bits, apk);
+            cs.push_namespace(format_args!("remark card {}", i));
+            let input_var = cs.new_card_variable(input);
+            cs.prepare_pi_card_variable(&input_var);
+            let output_var = cs.eval_card_remark(&trace, &input_var);
+            cs.pop_namespace();
+            remark_card_vars.push(output_var);
+        }
+
+        let shuffle_card_vars = cs.with_namespace("shuffle", |cs| {
+            cs.shuffle_card(&remark_card_vars, &inputs.permutation)
+        });
+        for card_var in shuffle_card_vars.iter() {
+            cs.prepare_pi_card_variable(card_var);
+        }
+
+        cs.pad();
+
+        shuffle_card_vars
+    }
+
+    fn dummy_inputs(&self) -> ShuffleInputs {
+        let mut rng = ChaChaRng::from_seed([0u8; 32]);
+        let apk = EdwardsProjective::rand(&mut rng);
+        let cards = vec![MaskedCard::rand(&mut rng); self.n];
+        ShuffleInputs::sample(&mut rng, &apk, &cards)
+    }
+}
+
+pub fn prove_shuffle<R: CryptoRng + RngCore>(
     prng: &mut R,
     aggregate_public_key: &EdwardsProjective,
     input_cards: &[MaskedCard],
-) -> (TurboCS, Vec<CardVar>) {
+    prover_params: &ProverParams,
+) -> Result<(ShuffleProof, Vec<MaskedCard>)> {
     let n = input_cards.len();
-    let mut cs = TurboCS::new();
-    cs.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(aggregate_public_key);
-
-    let mut remark_card_vars = Vec::with_capacity(n);
-
-    for input in input_cards.iter() {
-        let bits = BabyJubjubShuffle::sample_random_scalar_bits(prng);
-        let trace = BabyJubjubShuffle::eval_remark_with_trace(input, &bits, &aggregate_public_key);
-        let input_var = cs.new_card_variable(input);
-        cs.prepare_pi_card_variable(&input_var);
-        let output_var = cs.eval_card_remark(&trace, &input_var);
-        remark_card_vars.push(output_var);
-    }
+    let inputs = ShuffleInputs::sample(prng, aggregate_public_key, input_cards);
+    let (witness, output_vars) = ShuffleCircuit { n }.assign_witness(&prover_params.cs, &inputs)?;
 
-    let permutation = Permutation::rand(prng, n);
-    let shuffle_card_vars = cs.shuffle_card(&remark_card_vars, &permutation);
-    for card_var in shuffle_card_vars.iter() {
-        cs.prepare_pi_card_variable(card_var);
-    }
+    let mut transcript = shuffle_transcript(n);
 
-    cs.pad();
+    let proof = prover_with_lagrange(
+        prng,
+        &mut transcript,
+        &prover_params.pcs,
+        prover_params.lagrange_pcs.as_ref(),
+        &prover_params.cs,
+        &prover_params.prover_params,
+        &witness,
+    )?;
 
-    (cs, shuffle_card_vars)
+    Ok((proof, output_cards(&witness, &output_vars)))
 }
 
-pub fn prove_shuffle<R: CryptoRng + RngCore>(
+/// Prove the shuffle as `prove_shuffle` with the low-memory prover, which fails if the prover
+/// needs more memory than the budget in `config`. The coset evaluations in the prover parameters
+/// are not needed, call `PlonkProverParams::drop_coset_evals` beforehand to release them.
+pub fn prove_shuffle_low_memory<R: CryptoRng + RngCore>(
     prng: &mut R,
     aggregate_public_key: &EdwardsProjective,
     input_cards: &[MaskedCard],
     prover_params: &ProverParams,
+    config: &ProverMemoryConfig,
 ) -> Result<(ShuffleProof, Vec<MaskedCard>)> {
     let n = input_cards.len();
-    // FIXME check n eq prover_params
+    let inputs = ShuffleInputs::sample(prng, aggregate_public_key, input_cards);
+    let (witness, output_vars) = ShuffleCircuit { n }.assign_witness(&prover_params.cs, &inputs)?;
 
-    let (mut cs, output_vars) = build_cs(prng, aggregate_public_key, input_cards);
-    let witness = cs.get_and_clear_witness();
-
-    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
-    transcript.append_u64(N_CARDS_TRANSCRIPT, n as u64);
+    let mut transcript = shuffle_transcript(n);
 
-    let proof = prover_with_lagrange(
+    let (proof, _) = prover_low_memory(
         prng,
         &mut transcript,
         &prover_params.pcs,
         prover_params.lagrange_pcs.as_ref(),
-        &cs,
+        &prover_params.cs,
         &prover_params.prover_params,
         &witness,
+        config,
     )?;
 
+    Ok((proof, output_cards(&witness, &output_vars)))
+}
+
+/// Read the shuffled cards from the witness.
+fn output_cards(witness: &[Fr], output_vars: &[CardVar]) -> Vec<MaskedCard> {
     let mut outputs = vec![];
 
     for output in output_vars.iter() {
@@ -93,7 +182,7 @@ pub fn prove_shuffle<R: CryptoRng + RngCore>(
         outputs.push(MaskedCard::new(e1, e2))
     }
 
-    Ok((proof, outputs))
+    outputs
 }
 
 pub fn verify_shuffle(
@@ -105,8 +194,7 @@ pub fn verify_shuffle(
     let n = input_cards.len();
     // FIXME check n eq verifier_params
 
-    let mut transcript = Transcript::new(PLONK_PROOF_TRANSCRIPT);
-    transcript.append_u64(N_CARDS_TRANSCRIPT, n as u64);
+    let mut transcript = shuffle_transcript(n);
 
     let mut online_inputs = vec![];
 
@@ -118,12 +206,12 @@ pub fn verify_shuffle(
         online_inputs.extend_from_slice(&card.flatten());
     }
 
-    Ok(verifier(
+    verifier(
         &mut transcript,
         &verifier_params.shrunk_vk,
         &verifier_params.shrunk_cs,
         &verifier_params.verifier_params,
         &online_inputs,
         proof,
-    )?)
+    )
 }
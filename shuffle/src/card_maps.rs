@@ -1,4 +1,17 @@
-pub const CARD_MAPS: [&'static str; 54] = [
+use ark_ec::AffineRepr;
+use ark_ed_on_bn254::{EdwardsAffine, Fq};
+use ark_ff::{PrimeField, Zero};
+use once_cell::sync::Lazy;
+use sha3::{Digest, Keccak256};
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::{
+    error::{ShuffleError, ShuffleResult},
+    Card,
+};
+
+/// The y-coordinates of the first cards, kept so that the existing decks do not change.
+pub const CARD_MAPS: [&str; 54] = [
     "0x0e7e20b3cb30785b64cd6972e2ddf919db64d03d6cf01456243c5ef2fb766a65",
     "0x2d7690deeaa77c9d89b0ceb3c25f7bb09c44f40b4b8cf5d6fcb512c7be8fcba9",
     "0x13a50334ef174fd8160bb22e5f150b0ce7656c5c4a19b0ad6bc8f93fdf5fab7c",
@@ -54,3 +67,135 @@ pub const CARD_MAPS: [&'static str; 54] = [
     "0x1464429b0e93a259cec0b660c0bb6df28cb408706eee28f4e77a5e61c931f6f5",
     "0x142fb87f6d0974097206facac23ea38ffa01e3e1e45003e3ec238b6516eb0b2e",
 ];
+
+/// The largest deck of the default cards that can be initialized and decoded.
+pub const MAX_CARDS: usize = 1024;
+
+/// The reverse lookup table of the default cards, extended to `MAX_CARDS` at the first miss.
+pub static CARD_ENCODER: Lazy<Mutex<CardEncoder>> =
+    Lazy::new(|| Mutex::new(CardEncoder::new(CARD_MAPS.len())));
+
+/// The index of the point in the default cards, with `CARD_ENCODER`.
+pub fn default_point_to_index(point: &Card) -> ShuffleResult<usize> {
+    let mut encoder = CARD_ENCODER.lock().unwrap();
+    if encoder.point_to_index(point).is_none() && encoder.len() < MAX_CARDS {
+        *encoder = CardEncoder::new(MAX_CARDS);
+    }
+
+    encoder
+        .point_to_index(point)
+        .ok_or_else(|| ShuffleError::RangeError("Point not map to a card".to_owned()))
+}
+
+/// Deterministically map the card bytes to a point in the prime-order subgroup,
+/// by hashing them with a counter until the hash is the y-coordinate of a point.
+pub fn hash_to_card(bytes: &[u8]) -> Card {
+    let mut counter = 0u32;
+    loop {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"zshuffle card");
+        hasher.update(bytes);
+        hasher.update(counter.to_be_bytes());
+        let y = Fq::from_be_bytes_mod_order(&hasher.finalize());
+
+        if let Some(point) = EdwardsAffine::get_point_from_y_unchecked(y, true) {
+            let card = point.mul_by_cofactor_to_group();
+            if !card.is_zero() {
+                return card;
+            }
+        }
+        counter += 1;
+    }
+}
+
+/// The card of the index, from `CARD_MAPS` for the first cards and
+/// `hash_to_card` of the big-endian index for the others.
+pub fn index_to_point(index: usize) -> Card {
+    match CARD_MAPS.get(index) {
+        Some(y_hex) => {
+            let y_bytes = hex::decode(y_hex.trim_start_matches("0x")).unwrap();
+            let y = Fq::from_be_bytes_mod_order(&y_bytes);
+            EdwardsAffine::get_point_from_y_unchecked(y, true)
+                .unwrap()
+                .into()
+        }
+        None => hash_to_card(&(index as u64).to_be_bytes()),
+    }
+}
+
+/// A deck of cards with the reverse lookup table from the points to the indices.
+#[derive(Clone, Debug, Default)]
+pub struct CardEncoder {
+    cards: Vec<Card>,
+    indices: HashMap<EdwardsAffine, usize>,
+}
+
+impl CardEncoder {
+    /// The deck of the cards `0..num`, encoded by `index_to_point`.
+    pub fn new(num: usize) -> Self {
+        let mut encoder = Self::default();
+        for index in 0..num {
+            encoder.push(index_to_point(index));
+        }
+        encoder
+    }
+
+    /// A custom deck, the card `i` is `hash_to_card(cards[i])`.
+    pub fn from_cards<B: AsRef<[u8]>>(cards: &[B]) -> ShuffleResult<Self> {
+        let mut encoder = Self::default();
+        for card in cards.iter() {
+            let point = hash_to_card(card.as_ref());
+            if encoder.indices.contains_key(&point.into()) {
+                return Err(ShuffleError::RangeError("Duplicated card".to_owned()));
+            }
+            encoder.push(point);
+        }
+        Ok(encoder)
+    }
+
+    fn push(&mut self, card: Card) {
+        self.indices.insert(card.into(), self.cards.len());
+        self.cards.push(card);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn index_to_point(&self, index: usize) -> Option<Card> {
+        self.cards.get(index).copied()
+    }
+
+    pub fn point_to_index(&self, point: &Card) -> Option<usize> {
+        self.indices.get(&(*point).into()).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_card_encoder() {
+        let encoder = CardEncoder::new(136);
+        for index in 0..136 {
+            let card = encoder.index_to_point(index).unwrap();
+            assert!(EdwardsAffine::from(card).is_in_correct_subgroup_assuming_on_curve());
+            assert_eq!(card, index_to_point(index));
+            assert_eq!(encoder.point_to_index(&card), Some(index));
+        }
+        assert_eq!(encoder.point_to_index(&hash_to_card(b"joker")), None);
+
+        let uno: Vec<String> = (0..108).map(|i| format!("uno-{}", i)).collect();
+        let encoder = CardEncoder::from_cards(&uno).unwrap();
+        assert_eq!(encoder.point_to_index(&hash_to_card(b"uno-42")), Some(42));
+        assert!(CardEncoder::from_cards(&["a", "b", "a"]).is_err());
+
+        assert_eq!(default_point_to_index(&index_to_point(500)).unwrap(), 500);
+        assert!(default_point_to_index(&index_to_point(MAX_CARDS)).is_err());
+    }
+}
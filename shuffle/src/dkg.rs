@@ -0,0 +1,338 @@
+use ark_ec::PrimeGroup;
+use ark_ed_on_bn254::{EdwardsProjective, Fr};
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_std::rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use uzkge::{
+    chaum_pedersen::eq::{prove, verify, ChaumPedersenEqProof},
+    errors::{Result, UzkgeError},
+    utils::{
+        serialization::{ark_deserialize, ark_serialize},
+        transcript::{Transcript, TranscriptScheme},
+    },
+};
+
+use crate::keygen::{Keypair, PublicKey, SecretKey};
+
+/// The threshold `t` and the number of players `n`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DkgParameters {
+    pub threshold: usize,
+    pub num_players: usize,
+}
+
+impl DkgParameters {
+    pub fn new(threshold: usize, num_players: usize) -> Result<Self> {
+        if threshold == 0 || threshold > num_players {
+            return Err(UzkgeError::ParameterError);
+        }
+
+        Ok(Self {
+            threshold,
+            num_players,
+        })
+    }
+
+    pub(crate) fn check_index(&self, index: u64) -> Result<()> {
+        if index == 0 || index > self.num_players as u64 {
+            return Err(UzkgeError::ParameterError);
+        }
+        Ok(())
+    }
+}
+
+/// The broadcast message of a dealer.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DealerCommitment {
+    pub dealer: u64,
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub coefficients: Vec<PublicKey>,
+    pub proof: ChaumPedersenEqProof,
+}
+
+/// The secret polynomial `f_i` of the player `i` (indexed from 1), of degree `t - 1`.
+///
+/// The dealer broadcasts the Feldman commitments `f_i[k] * G` and sends `f_i(j)` to the
+/// player `j` privately. The joint key is `sum_i f_i(0) * G` and the key share of the
+/// player `j` is `x_j = sum_i f_i(j)`, a regular [`Keypair`] whose reveal tokens are
+/// proved with `reveal` and combined by `threshold_unmask`.
+pub struct Dealer {
+    index: u64,
+    polynomial: Vec<SecretKey>,
+}
+
+impl Dealer {
+    pub fn new<R: CryptoRng + RngCore>(
+        prng: &mut R,
+        parameters: &DkgParameters,
+        index: u64,
+    ) -> Result<Self> {
+        parameters.check_index(index)?;
+        let polynomial = (0..parameters.threshold).map(|_| Fr::rand(prng)).collect();
+
+        Ok(Self { index, polynomial })
+    }
+
+    /// The Feldman commitments of the polynomial, with a proof of knowledge of
+    /// the constant term against rogue-key attacks.
+    pub fn commitment<R: CryptoRng + RngCore>(&self, prng: &mut R) -> Result<DealerCommitment> {
+        let g = EdwardsProjective::generator();
+        let coefficients: Vec<PublicKey> = self.polynomial.iter().map(|a| g * a).collect();
+
+        let mut transcript = dealer_transcript(self.index);
+        let proof = prove(
+            prng,
+            &mut transcript,
+            &self.polynomial[0],
+            &[g],
+            &coefficients[..1],
+        )?;
+
+        Ok(DealerCommitment {
+            dealer: self.index,
+            coefficients,
+            proof,
+        })
+    }
+
+    /// The share sent privately to the player `index`.
+    pub fn share(&self, index: u64) -> SecretKey {
+        let x = Fr::from(index);
+        self.polynomial
+            .iter()
+            .rev()
+            .fold(Fr::zero(), |acc, a| acc * x + a)
+    }
+}
+
+fn dealer_transcript(dealer: u64) -> Transcript {
+    let mut transcript = Transcript::new(b"DKG");
+    transcript.append_u64(b"dealer", dealer);
+    transcript
+}
+
+/// Evaluate the committed polynomial at `index` in the exponent.
+fn eval_commitment(coefficients: &[PublicKey], index: u64) -> PublicKey {
+    let x = Fr::from(index);
+    coefficients
+        .iter()
+        .rev()
+        .fold(EdwardsProjective::zero(), |acc, c| acc * x + c)
+}
+
+/// Verify the broadcast message of a dealer.
+pub fn verify_commitment(parameters: &DkgParameters, commitment: &DealerCommitment) -> Result<()> {
+    parameters.check_index(commitment.dealer)?;
+    if commitment.coefficients.len() != parameters.threshold {
+        return Err(UzkgeError::VerificationError);
+    }
+
+    let mut transcript = dealer_transcript(commitment.dealer);
+    verify(
+        &mut transcript,
+        &[EdwardsProjective::generator()],
+        &commitment.coefficients[..1],
+        &commitment.proof,
+    )
+}
+
+/// Verify the share received by the player `index` against the commitment of its dealer.
+pub fn verify_share(commitment: &DealerCommitment, index: u64, share: &SecretKey) -> Result<()> {
+    if EdwardsProjective::generator() * share != eval_commitment(&commitment.coefficients, index) {
+        return Err(UzkgeError::VerificationError);
+    }
+    Ok(())
+}
+
+/// The commitments of the qualified dealers: at least `threshold` distinct dealers whose
+/// commitments pass `verify_commitment`.
+///
+/// The players should agree on the qualified set, leaving out the dealers that sent a
+/// share failing `verify_share` to any of them. Each player checks its own shares again
+/// in `combine_shares`. The joint key and the verification keys are only derived from
+/// a qualified set.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QualifiedCommitments(Vec<DealerCommitment>);
+
+impl QualifiedCommitments {
+    pub fn new(parameters: &DkgParameters, commitments: Vec<DealerCommitment>) -> Result<Self> {
+        if commitments.len() < parameters.threshold {
+            return Err(UzkgeError::ParameterError);
+        }
+
+        for (i, commitment) in commitments.iter().enumerate() {
+            if commitments[..i]
+                .iter()
+                .any(|c| c.dealer == commitment.dealer)
+            {
+                return Err(UzkgeError::ParameterError);
+            }
+            verify_commitment(parameters, commitment)?;
+        }
+
+        Ok(Self(commitments))
+    }
+
+    pub fn commitments(&self) -> &[DealerCommitment] {
+        &self.0
+    }
+}
+
+/// The joint public key, used to `mask` and shuffle the cards.
+pub fn joint_public_key(qualified: &QualifiedCommitments) -> PublicKey {
+    qualified.0.iter().map(|c| c.coefficients[0]).sum()
+}
+
+/// The public verification key of the player `index`, used to verify its reveal tokens.
+pub fn verification_key(qualified: &QualifiedCommitments, index: u64) -> PublicKey {
+    qualified
+        .0
+        .iter()
+        .map(|c| eval_commitment(&c.coefficients, index))
+        .sum()
+}
+
+/// Verify the shares received by the player `index` and combine them into its key
+/// share. `shares[i]` is sent by the dealer of `qualified.commitments()[i]`.
+pub fn combine_shares(
+    parameters: &DkgParameters,
+    index: u64,
+    qualified: &QualifiedCommitments,
+    shares: &[SecretKey],
+) -> Result<Keypair> {
+    parameters.check_index(index)?;
+    if qualified.0.len() != shares.len() {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    for (commitment, share) in qualified.0.iter().zip(shares.iter()) {
+        verify_share(commitment, index, share)?;
+    }
+
+    let secret: SecretKey = shares.iter().sum();
+    let keypair = Keypair::from_secret(secret);
+    debug_assert_eq!(keypair.public, verification_key(qualified, index));
+
+    Ok(keypair)
+}
+
+/// The Lagrange coefficient at zero of the player `index` in the set `indices`.
+/// The indices should be distinct and non-zero, and include `index`.
+pub fn lagrange_coefficient(index: u64, indices: &[u64]) -> Result<Fr> {
+    if !indices.contains(&index) {
+        return Err(UzkgeError::ParameterError);
+    }
+    for (i, j) in indices.iter().enumerate() {
+        if *j == 0 || indices[..i].contains(j) {
+            return Err(UzkgeError::ParameterError);
+        }
+    }
+
+    let x = Fr::from(index);
+    let mut num = Fr::one();
+    let mut den = Fr::one();
+    for j in indices.iter().filter(|j| **j != index) {
+        let xj = Fr::from(*j);
+        num *= xj;
+        den *= xj - x;
+    }
+
+    let den_inv = den.inverse().ok_or(UzkgeError::DivisionByZero)?;
+    Ok(num * den_inv)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ff::UniformRand;
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+    use crate::{mask::mask, reveal::*, Card};
+
+    #[test]
+    fn test_threshold_reveal() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let parameters = DkgParameters::new(3, 5).unwrap();
+
+        let dealers: Vec<Dealer> = (1..=5)
+            .map(|i| Dealer::new(&mut prng, &parameters, i).unwrap())
+            .collect();
+        let commitments: Vec<DealerCommitment> = dealers
+            .iter()
+            .map(|d| d.commitment(&mut prng).unwrap())
+            .collect();
+
+        // a dealer cannot be counted twice, nor with an invalid commitment.
+        let mut duplicated = commitments.clone();
+        duplicated[1] = duplicated[0].clone();
+        assert!(QualifiedCommitments::new(&parameters, duplicated).is_err());
+        let mut forged = commitments.clone();
+        forged[1].coefficients[0] = forged[0].coefficients[0];
+        assert!(QualifiedCommitments::new(&parameters, forged).is_err());
+        assert!(QualifiedCommitments::new(&parameters, commitments[..2].to_vec()).is_err());
+        let commitments = QualifiedCommitments::new(&parameters, commitments).unwrap();
+
+        let keypairs: Vec<Keypair> = (1..=5)
+            .map(|i| {
+                let shares: Vec<_> = dealers.iter().map(|d| d.share(i)).collect();
+                combine_shares(&parameters, i, &commitments, &shares).unwrap()
+            })
+            .collect();
+
+        // a wrong share is detected.
+        let mut shares: Vec<_> = dealers.iter().map(|d| d.share(1)).collect();
+        shares[2] += Fr::one();
+        assert!(combine_shares(&parameters, 1, &commitments, &shares).is_err());
+
+        let joint_pk = joint_public_key(&commitments);
+        let card = Card::rand(&mut prng);
+        let r = Fr::rand(&mut prng);
+        let (masked_card, _) = mask(&mut prng, &joint_pk, &card, &r).unwrap();
+
+        // players 2, 4 and 5 reveal the card.
+        let mut reveals = vec![];
+        for i in [2u64, 4, 5] {
+            let keypair = &keypairs[i as usize - 1];
+            let (token, proof) = reveal(&mut prng, keypair, &masked_card).unwrap();
+            let vk = verification_key(&commitments, i);
+            verify_reveal(&vk, &masked_card, &token, &proof).unwrap();
+            reveals.push((i, token));
+        }
+
+        assert_eq!(
+            threshold_unmask(&parameters, &masked_card, &reveals).unwrap(),
+            card
+        );
+
+        // fewer than t tokens are rejected.
+        assert_eq!(
+            threshold_unmask(&parameters, &masked_card, &reveals[..2]),
+            Err(UzkgeError::ParameterError)
+        );
+
+        // the indices are distinct and in 1..=n.
+        let mut duplicated = reveals.clone();
+        duplicated[2] = duplicated[1];
+        assert_eq!(
+            threshold_unmask(&parameters, &masked_card, &duplicated),
+            Err(UzkgeError::ParameterError)
+        );
+        let mut zero = reveals.clone();
+        zero[0].0 = 0;
+        assert_eq!(
+            threshold_unmask(&parameters, &masked_card, &zero),
+            Err(UzkgeError::ParameterError)
+        );
+        let mut out_of_range = reveals.clone();
+        out_of_range[0].0 = 6;
+        assert_eq!(
+            threshold_unmask(&parameters, &masked_card, &out_of_range),
+            Err(UzkgeError::ParameterError)
+        );
+        assert_eq!(
+            lagrange_coefficient(3, &[2, 4, 5]),
+            Err(UzkgeError::ParameterError)
+        );
+    }
+}
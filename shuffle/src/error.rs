@@ -9,6 +9,8 @@ pub enum ShuffleError {
     HexError(#[from] hex::FromHexError),
     #[error("{0}")]
     UzkgeError(uzkge::errors::UzkgeError),
+    #[error("Missing prover params of {0} cards, need init & refresh pk")]
+    MissingParams(usize),
     #[error("Range Error : {0}")]
     RangeError(String),
     #[error("Bincode Error : {0}")]
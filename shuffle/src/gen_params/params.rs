@@ -2,24 +2,24 @@ use ark_bn254::G1Projective;
 use ark_ed_on_bn254::EdwardsProjective;
 use ark_groth16::ProvingKey;
 use ark_serialize::CanonicalDeserialize;
-use ark_std::{rand::SeedableRng, UniformRand};
-use rand_chacha::ChaChaRng;
 use uzkge::{
     errors::UzkgeError,
     gen_params::{
         load_lagrange_params, load_srs_params, VerifierParamsSplitCommon,
         VerifierParamsSplitSpecific, VERIFIER_COMMON_PARAMS,
     },
-    plonk::{constraint_system::ConstraintSystem, indexer::indexer_with_lagrange},
+    plonk::{
+        constraint_system::{circuit::Circuit, ConstraintSystem},
+        indexer::indexer_with_lagrange,
+    },
 };
 
 use crate::{
-    build_cs::build_cs,
+    build_cs::ShuffleCircuit,
     gen_params::{
         GROTH16_PK_52, VERIFIER_SPECIFIC_PARAMS_48, VERIFIER_SPECIFIC_PARAMS_52,
         VERIFIER_SPECIFIC_PARAMS_54,
     },
-    MaskedCard,
 };
 
 // re-export
@@ -27,10 +27,7 @@ pub use uzkge::gen_params::{ProverParams, VerifierParams};
 
 /// Obtain the parameters for shuffle.
 pub fn gen_shuffle_prover_params(n: usize) -> Result<ProverParams, UzkgeError> {
-    let mut rng = ChaChaRng::from_seed([0u8; 32]);
-    let apk = EdwardsProjective::rand(&mut rng);
-    let cards = vec![MaskedCard::rand(&mut rng); n];
-    let (cs, _) = build_cs(&mut rng, &apk, &cards);
+    let cs = ShuffleCircuit { n }.synthesize_shape();
 
     let cs_size = cs.size();
     let pcs = load_srs_params(cs_size)?;
@@ -54,19 +51,36 @@ pub fn gen_shuffle_prover_params(n: usize) -> Result<ProverParams, UzkgeError> {
 }
 
 /// Refresh the public key for shuffle.
+///
+/// Only the public key selectors are updated, and they are committed by the MSMs of
+/// the precomputed `shuffle_public_key_bases`, so neither the SRS nor the generators
+/// are loaded again. Return the new commitments of the public key selectors.
 pub fn refresh_prover_params_public_key(
     params: &mut ProverParams,
     shuffle_pk: &EdwardsProjective,
 ) -> Result<Vec<G1Projective>, UzkgeError> {
     use ark_bn254::Fr;
+    use ark_ec::{CurveGroup, VariableBaseMSM};
     use uzkge::{
-        poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme},
+        poly_commit::{field_polynomial::FpPolynomial, kzg_poly_commitment::KZGCommitment},
         shuffle::BabyJubjubShuffle,
     };
 
+    let bases = G1Projective::normalize_batch(
+        &params
+            .prover_params
+            .shuffle_public_key_bases
+            .iter()
+            .map(|c| c.0)
+            .collect::<Vec<_>>(),
+    );
+    if bases.len() != params.cs.n_iteration_shuffle_scalar_mul() {
+        return Err(UzkgeError::ParameterError);
+    }
+
     params
         .cs
-        .load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(shuffle_pk);
+        .load_shuffle_public_key::<_, BabyJubjubShuffle>(shuffle_pk);
 
     let n = params.cs.size();
     let m = params.cs.quot_eval_dom_size();
@@ -74,48 +88,49 @@ pub fn refresh_prover_params_public_key(
         return Err(UzkgeError::ParameterError);
     }
 
-    let pcs = load_srs_params(n)?;
-    let lagrange_pcs = load_lagrange_params(n);
-    let lagrange_pcs = lagrange_pcs.as_ref();
-    let lagrange_pcs = if lagrange_pcs.is_some() && lagrange_pcs.unwrap().max_degree() + 1 == n {
-        lagrange_pcs
-    } else {
-        None
-    };
-
     let domain = FpPolynomial::evaluation_domain(n).ok_or(UzkgeError::ParameterError)?;
     let domain_m = FpPolynomial::quotient_evaluation_domain(m).ok_or(UzkgeError::ParameterError)?;
 
-    let q_shuffle_public_key_evals = params.cs.compute_shuffle_public_key_selectors();
+    // the values of the selectors in each iteration of the remark, in the order of
+    // `compute_shuffle_public_key_selectors`.
+    let mut iteration_values = vec![];
+    for keys in [
+        &params.cs.shuffle_public_keys_x,
+        &params.cs.shuffle_public_keys_y,
+        &params.cs.shuffle_public_keys_dxy,
+    ] {
+        for i in 0..keys[0].len() {
+            iteration_values.push(keys.iter().map(|k| k[i]).collect::<Vec<Fr>>());
+        }
+    }
 
-    let q_shuffle_public_key_polys: Vec<FpPolynomial<Fr>> = q_shuffle_public_key_evals
+    let cm_shuffle_public_key_vec = iteration_values
+        .iter()
+        .map(|values| {
+            G1Projective::msm(&bases, values)
+                .map(KZGCommitment)
+                .map_err(|_| UzkgeError::ParameterError)
+        })
+        .collect::<Result<Vec<_>, UzkgeError>>()?;
+
+    let q_shuffle_public_key_polys: Vec<FpPolynomial<Fr>> = params
+        .cs
+        .compute_shuffle_public_key_selectors()
         .iter()
-        .map(|p| FpPolynomial::ifft_with_domain(&domain, &p))
+        .map(|p| FpPolynomial::ifft_with_domain(&domain, p))
         .collect::<Vec<FpPolynomial<Fr>>>();
 
-    let q_shuffle_public_key_coset_evals = q_shuffle_public_key_polys
-        .iter()
-        .map(|p| p.coset_fft_with_domain(&domain_m, &params.prover_params.verifier_params.k[1]))
-        .collect::<Vec<Vec<Fr>>>();
-
-    let mut cm_shuffle_public_key_vec = vec![];
-    for (q_shuffle_public_key_eval, q_shuffle_public_key_poly) in q_shuffle_public_key_evals
-        .into_iter()
-        .zip(q_shuffle_public_key_polys.iter())
-    {
-        if let Some(lagrange_pcs) = lagrange_pcs {
-            let eval_poly = FpPolynomial::from_coefs(q_shuffle_public_key_eval);
-            let cm = lagrange_pcs
-                .commit(&eval_poly)
-                .map_err(|_| UzkgeError::ParameterError)?;
-            cm_shuffle_public_key_vec.push(cm)
-        } else {
-            let cm = pcs
-                .commit(&q_shuffle_public_key_poly)
-                .map_err(|_| UzkgeError::ParameterError)?;
-            cm_shuffle_public_key_vec.push(cm)
-        }
-    }
+    // the coset evaluations are only kept if the prover parameters have them.
+    let q_shuffle_public_key_coset_evals = if params.prover_params.has_coset_evals() {
+        let k = &params.prover_params.verifier_params.k[1];
+        q_shuffle_public_key_polys
+            .iter()
+            .map(|p| p.quotient_coset_fft_with_domain(&domain, &domain_m, k))
+            .collect::<Vec<Vec<Fr>>>()
+    } else {
+        vec![]
+    };
+
     let res: Vec<_> = cm_shuffle_public_key_vec.iter().map(|c| c.0).collect();
 
     params.prover_params.q_shuffle_public_key_polys = q_shuffle_public_key_polys;
@@ -4,8 +4,8 @@
 
 use std::path::PathBuf;
 use structopt::StructOpt;
-use uzkge::gen_params::solidity::gen_solidity_vk;
-use zshuffle::gen_params::get_shuffle_verifier_params;
+use uzkge::gen_params::solidity::{gen_solidity_verifier, gen_solidity_vk};
+use zshuffle::{build_cs::shuffle_transcript, gen_params::get_shuffle_verifier_params};
 
 #[derive(StructOpt, Debug)]
 #[structopt(
@@ -22,6 +22,9 @@ enum Actions {
         full: String,
     },
 
+    /// Generates the self-contained Solidity verifier for shuffle
+    SOLIDITY_VERIFIER { num: usize, directory: PathBuf },
+
     /// Generates all necessary parameters
     ALL { directory: PathBuf },
 }
@@ -41,6 +44,8 @@ fn main() {
             gen_solidity_vk(params, num, directory, &full == "true")
         }
 
+        SOLIDITY_VERIFIER { num, directory } => gen_solidity_shuffle_verifier(num, directory),
+
         ALL { directory } => gen_all(directory),
     };
 }
@@ -68,6 +73,15 @@ fn gen_shuffle_vk(num: usize, directory: PathBuf, full: bool) {
     }
 }
 
+// cargo run --release --features="gen" --bin gen-params solidity-verifier 52 "./parameters"
+fn gen_solidity_shuffle_verifier(num: usize, mut directory: PathBuf) {
+    let params = get_shuffle_verifier_params(num).unwrap();
+    let name = format!("ShufflePlonkVerifier_{}", num);
+    let contract = gen_solidity_verifier(&params, shuffle_transcript(num), &name).unwrap();
+    directory.push(format!("{}.sol", name));
+    save_to_file(contract.as_bytes(), directory);
+}
+
 // cargo run --release --features="gen no_vk" --bin gen-params all "./parameters"
 fn gen_all(directory: PathBuf) {
     gen_shuffle_vk(48, directory.clone(), false);
@@ -2,12 +2,19 @@ use ark_ec::PrimeGroup;
 use ark_ed_on_bn254::{EdwardsProjective, Fr};
 use ark_ff::UniformRand;
 use ark_std::rand::{CryptoRng, RngCore};
-use uzkge::errors::Result;
+use uzkge::{
+    chaum_pedersen::eq::{prove, verify, ChaumPedersenEqProof},
+    errors::{Result, UzkgeError},
+    utils::transcript::{Transcript, TranscriptScheme},
+};
 
 pub type PublicKey = EdwardsProjective;
 
 pub type SecretKey = Fr;
 
+/// A Schnorr proof of possession of the secret key, bound to a game/session id.
+pub type KeyOwnershipProof = ChaumPedersenEqProof;
+
 pub struct Keypair {
     pub secret: SecretKey,
     pub public: PublicKey,
@@ -23,8 +30,102 @@ impl Keypair {
         let secret = Fr::rand(prng);
         Self::from_secret(secret)
     }
+
+    /// Prove the possession of the secret key in the game/session `session`,
+    /// so that the public key can be aggregated with `aggregate_keys_checked`.
+    pub fn prove_ownership<R: CryptoRng + RngCore>(
+        &self,
+        prng: &mut R,
+        session: &[u8],
+    ) -> Result<KeyOwnershipProof> {
+        let mut transcript = ownership_transcript(session);
+        prove(
+            prng,
+            &mut transcript,
+            &self.secret,
+            &[EdwardsProjective::generator()],
+            &[self.public],
+        )
+    }
+}
+
+fn ownership_transcript(session: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(b"Key Ownership");
+    transcript.append_message(b"session", session);
+    transcript
+}
+
+pub fn verify_key_ownership(
+    pk: &PublicKey,
+    session: &[u8],
+    proof: &KeyOwnershipProof,
+) -> Result<()> {
+    let mut transcript = ownership_transcript(session);
+    verify(
+        &mut transcript,
+        &[EdwardsProjective::generator()],
+        &[*pk],
+        proof,
+    )
 }
 
 pub fn aggregate_keys(keys: &[PublicKey]) -> Result<PublicKey> {
     Ok(keys.iter().sum())
 }
+
+/// Aggregate the public keys, rejecting the keys without a valid proof of possession
+/// in `session`, e.g. a rogue key `pk_evil - sum(others)` chosen by the last player.
+pub fn aggregate_keys_checked(
+    keys: &[(PublicKey, KeyOwnershipProof)],
+    session: &[u8],
+) -> Result<PublicKey> {
+    for (i, (pk, proof)) in keys.iter().enumerate() {
+        if keys[..i].iter().any(|(other, _)| other == pk) {
+            return Err(UzkgeError::ParameterError);
+        }
+        verify_key_ownership(pk, session, proof)?;
+    }
+
+    Ok(keys.iter().map(|(pk, _)| pk).sum())
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ed_on_bn254::EdwardsProjective;
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregate_keys_checked() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let session = b"game 1";
+
+        let keys: Vec<_> = (0..4)
+            .map(|_| {
+                let keypair = Keypair::generate(&mut prng);
+                let proof = keypair.prove_ownership(&mut prng, session).unwrap();
+                (keypair.public, proof)
+            })
+            .collect();
+
+        let joint = aggregate_keys_checked(&keys, session).unwrap();
+        let pks: Vec<_> = keys.iter().map(|(pk, _)| *pk).collect();
+        assert_eq!(joint, aggregate_keys(&pks).unwrap());
+
+        // the proofs are bound to the session.
+        assert!(aggregate_keys_checked(&keys, b"game 2").is_err());
+
+        // a rogue key can not be proved.
+        let evil = Keypair::generate(&mut prng);
+        let rogue = evil.public - pks[..3].iter().sum::<EdwardsProjective>();
+        let mut rogue_keys = keys[..3].to_vec();
+        rogue_keys.push((rogue, evil.prove_ownership(&mut prng, session).unwrap()));
+        assert!(aggregate_keys_checked(&rogue_keys, session).is_err());
+
+        let mut duplicated = keys.clone();
+        duplicated.push(keys[0].clone());
+        assert!(aggregate_keys_checked(&duplicated, session).is_err());
+    }
+}
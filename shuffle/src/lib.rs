@@ -1,6 +1,9 @@
 /// Module for keygen & Keypair.
 pub mod keygen;
 
+/// Module for the t-of-n distributed key generation.
+pub mod dkg;
+
 /// Module for mask card.
 pub mod mask;
 
@@ -4,7 +4,7 @@ use ark_std::rand::{CryptoRng, RngCore};
 use uzkge::{
     chaum_pedersen::dl::{prove, verify, ChaumPedersenDLParameters, ChaumPedersenDLProof},
     errors::Result,
-    utils::transcript::Transcript,
+    utils::transcript::{Transcript, TranscriptScheme},
 };
 
 use crate::{keygen::PublicKey, Card, MaskedCard};
@@ -3,14 +3,16 @@ use ark_ed_on_bn254::EdwardsProjective;
 use ark_std::rand::{CryptoRng, RngCore};
 use uzkge::{
     anemoi::AnemoiJive254,
-    chaum_pedersen::dl::{
-        prove, prove0, verify, verify0, ChaumPedersenDLParameters, ChaumPedersenDLProof,
+    chaum_pedersen::{
+        dl::{prove, prove0, verify, verify0, ChaumPedersenDLParameters, ChaumPedersenDLProof},
+        eq::{self, ChaumPedersenEqProof},
     },
-    errors::Result,
-    utils::transcript::Transcript,
+    errors::{Result, UzkgeError},
+    utils::transcript::{Transcript, TranscriptScheme},
 };
 
 use crate::{
+    dkg::{lagrange_coefficient, DkgParameters},
     keygen::{Keypair, PublicKey},
     Card, MaskedCard, RevealCard,
 };
@@ -89,9 +91,84 @@ pub fn verify_reveal0(
     verify0::<AnemoiJive254>(&parameters, &reveal_card, pk, proof)
 }
 
+// Reveal many cards with one proof that all of them use the same key.
+pub fn reveal_all<R: CryptoRng + RngCore>(
+    prng: &mut R,
+    keypair: &Keypair,
+    masked_cards: &[MaskedCard],
+) -> Result<(Vec<RevealCard>, ChaumPedersenEqProof)> {
+    let reveals: Vec<RevealCard> = masked_cards
+        .iter()
+        .map(|card| card.e1 * keypair.secret)
+        .collect();
+
+    let (bases, values) = reveal_all_statement(&keypair.public, masked_cards, &reveals);
+    let mut transcript = Transcript::new(b"Revealing");
+
+    let proof = eq::prove(prng, &mut transcript, &keypair.secret, &bases, &values)?;
+
+    Ok((reveals, proof))
+}
+
+pub fn verify_reveal_all(
+    pk: &PublicKey,
+    masked_cards: &[MaskedCard],
+    reveal_cards: &[RevealCard],
+    proof: &ChaumPedersenEqProof,
+) -> Result<()> {
+    if masked_cards.len() != reveal_cards.len() {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let (bases, values) = reveal_all_statement(pk, masked_cards, reveal_cards);
+    let mut transcript = Transcript::new(b"Revealing");
+
+    eq::verify(&mut transcript, &bases, &values, proof)
+}
+
+fn reveal_all_statement(
+    pk: &PublicKey,
+    masked_cards: &[MaskedCard],
+    reveal_cards: &[RevealCard],
+) -> (Vec<EdwardsProjective>, Vec<EdwardsProjective>) {
+    let mut bases = vec![EdwardsProjective::generator()];
+    bases.extend(masked_cards.iter().map(|card| card.e1));
+
+    let mut values = vec![*pk];
+    values.extend(reveal_cards.iter().copied());
+
+    (bases, values)
+}
+
 #[inline]
 pub fn unmask(masked_card: &MaskedCard, reveal_cards: &[RevealCard]) -> Result<Card> {
     let aggregate: EdwardsProjective = reveal_cards.iter().sum();
 
     Ok(masked_card.e2 - aggregate)
 }
+
+/// Unmask a card with the reveal tokens of at least `t` distinct players of a t-of-n DKG,
+/// given as `(player index, token)`.
+pub fn threshold_unmask(
+    parameters: &DkgParameters,
+    masked_card: &MaskedCard,
+    reveal_cards: &[(u64, RevealCard)],
+) -> Result<Card> {
+    if reveal_cards.len() < parameters.threshold {
+        return Err(UzkgeError::ParameterError);
+    }
+    let indices: Vec<u64> = reveal_cards.iter().map(|(i, _)| *i).collect();
+    for (k, i) in indices.iter().enumerate() {
+        parameters.check_index(*i)?;
+        if indices[..k].contains(i) {
+            return Err(UzkgeError::ParameterError);
+        }
+    }
+
+    let mut aggregate = EdwardsProjective::default();
+    for (i, reveal_card) in reveal_cards.iter() {
+        aggregate += *reveal_card * lagrange_coefficient(*i, &indices)?;
+    }
+
+    Ok(masked_card.e2 - aggregate)
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use uzkge::{
     gen_params::{ProverParams, VerifierParams},
+    plonk::prover::ProverMemoryConfig,
 };
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
@@ -9,13 +10,18 @@ use crate::error::{ShuffleResult,ShuffleError};
 use crate::utils::{default_prng,point_to_hex,
     point_to_uncompress,hex_to_point,hex_to_scalar,
     shuffle_proof_to_hex,shuffle_proof_from_hex,scalar_to_hex,uncompress_to_point,};
-use crate::card_maps::CARD_MAPS;
-use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
-use ark_ff::{BigInteger, One, PrimeField};
+use crate::card_maps::{self, default_point_to_index, CardEncoder, MAX_CARDS};
+use ark_ed_on_bn254::{EdwardsProjective, Fr};
+use ark_ff::One;
 use crate::{
     mask::mask,
-    build_cs::{prove_shuffle, verify_shuffle},
-    keygen::{aggregate_keys as core_aggregate_keys, Keypair as CoreKeypair},
+    build_cs::{prove_shuffle, prove_shuffle_low_memory, verify_shuffle},
+    keygen::{
+        aggregate_keys as core_aggregate_keys,
+        aggregate_keys_checked as core_aggregate_keys_checked,
+        verify_key_ownership as core_verify_key_ownership, KeyOwnershipProof,
+        Keypair as CoreKeypair,
+    },
     reveal::*,
     reveal_with_snark::RevealCircuit,
     Groth16,ProvingKey,gen_params::load_groth16_pk,SNARK,
@@ -85,20 +91,16 @@ pub struct ShuffledCardsWithProof {
     
 
 fn index_to_point(index: i32) -> EdwardsProjective {
-    let y_hex = CARD_MAPS[index as usize].trim_start_matches("0x");
-    let y_bytes = hex::decode(y_hex).unwrap();
-    let y = Fq::from_be_bytes_mod_order(&y_bytes);
-
-    let affine = EdwardsAffine::get_point_from_y_unchecked(y, true).unwrap();
-    affine.into()
+    card_maps::index_to_point(index as usize)
 }
 
 fn point_to_index(point: EdwardsProjective) -> ShuffleResult<i32> {
-    let affine = EdwardsAffine::from(point);
-    let y_bytes = affine.y.into_bigint().to_bytes_be();
-    let bytes = format!("0x{}", hex::encode(&y_bytes));
+    default_point_to_index(&point).map(|pos| pos as i32)
+}
 
-    if let Some(pos) = CARD_MAPS.iter().position(|y| y == &bytes) {
+fn custom_point_to_index(point: EdwardsProjective, cards: &[String]) -> ShuffleResult<i32> {
+    let encoder = CardEncoder::from_cards(cards)?;
+    if let Some(pos) = encoder.point_to_index(&point) {
         Ok(pos as i32)
     } else {
         Err(ShuffleError::RangeError("Point not map to a card".to_owned()))
@@ -130,6 +132,25 @@ pub fn init_prover_key(num: i32)->ShuffleResult<()> {
     Ok(())
 }
 
+/// Initialize the prover key without the coset evaluations, for `shuffle_cards_low_memory`
+/// on the memory-constrained devices. It also replaces the key of `init_prover_key`, then
+/// `shuffle_cards` evaluates the quotient polynomial without them, which is slower.
+pub fn init_prover_key_low_memory(num: i32) -> ShuffleResult<()> {
+    let n = num as usize;
+
+    let mut params = PARAMS.lock().unwrap();
+    match params.get_mut(&n) {
+        Some(pp) => pp.prover_params.drop_coset_evals(),
+        None => {
+            let mut pp = gen_shuffle_prover_params(n).map_err(ShuffleError::UzkgeError)?;
+            pp.prover_params.drop_coset_evals();
+            params.insert(n, pp);
+        }
+    }
+    drop(params);
+    Ok(())
+}
+
 pub fn aggregate_keys(publics:  Vec<String>) -> ShuffleResult<String> {
     let mut pks = vec![];
     for bytes in publics {
@@ -139,6 +160,40 @@ pub fn aggregate_keys(publics:  Vec<String>) -> ShuffleResult<String> {
     Ok(point_to_hex(&pk, true))
 }
 
+fn key_ownership_proof_from_hex(proof: &str) -> ShuffleResult<KeyOwnershipProof> {
+    let bytes = hex::decode(proof.trim_start_matches("0x"))?;
+    KeyOwnershipProof::from_uncompress(&bytes).map_err(|e| ShuffleError::UzkgeError(e))
+}
+
+/// prove the possession of the secret key in the game/session
+pub fn prove_key_ownership(sk: String, session: String) -> ShuffleResult<String> {
+    let mut prng = default_prng();
+    let keypair = CoreKeypair::from_secret(hex_to_scalar(&sk)?);
+
+    let proof = keypair
+        .prove_ownership(&mut prng, session.as_bytes())
+        .map_err(|e| ShuffleError::UzkgeError(e))?;
+
+    Ok(format!("0x{}", hex::encode(&proof.to_uncompress())))
+}
+
+pub fn verify_key_ownership(pk: String, session: String, proof: String) -> ShuffleResult<bool> {
+    let pk = hex_to_point(&pk)?;
+    let proof = key_ownership_proof_from_hex(&proof)?;
+
+    Ok(core_verify_key_ownership(&pk, session.as_bytes(), &proof).is_ok())
+}
+
+/// aggregate the (public key, ownership proof) pairs, rejecting the unproven keys
+pub fn aggregate_keys_checked(publics: Vec<(String, String)>, session: String) -> ShuffleResult<String> {
+    let mut keys = vec![];
+    for (pk, proof) in publics {
+        keys.push((hex_to_point(&pk)?, key_ownership_proof_from_hex(&proof)?));
+    }
+    let pk = core_aggregate_keys_checked(&keys, session.as_bytes()).map_err(|e| ShuffleError::UzkgeError(e))?;
+    Ok(point_to_hex(&pk, true))
+}
+
 
 pub fn refresh_joint_key(joint: String, num: i32) -> ShuffleResult<Vec<String>> {
     let joint_pk = hex_to_point(&joint)?;
@@ -167,19 +222,32 @@ pub fn refresh_joint_key(joint: String, num: i32) -> ShuffleResult<Vec<String>>
 }
 
 pub fn init_masked_cards(joint: String, num: i32) -> ShuffleResult<Vec<MaskedCardWithProof>> {
-    if CARD_MAPS.len() < num as usize {
+    if MAX_CARDS < num as usize {
         return Err(ShuffleError::RangeError("The number of cards exceeds the maximum".to_owned()));
     }
 
+    let points: Vec<EdwardsProjective> = (0..num).map(index_to_point).collect();
+    mask_deck(joint, &points)
+}
+
+/// Init a custom deck, the card `i` is encoded from the bytes of `cards[i]`
+/// by `CardEncoder::from_cards`. The same `cards` decode them in `unmask_custom_card`.
+pub fn init_custom_masked_cards(joint: String, cards: Vec<String>) -> ShuffleResult<Vec<MaskedCardWithProof>> {
+    let encoder = CardEncoder::from_cards(&cards)?;
+    let points: Vec<EdwardsProjective> = (0..encoder.len())
+        .map(|i| encoder.index_to_point(i).unwrap())
+        .collect();
+    mask_deck(joint, &points)
+}
+
+fn mask_deck(joint: String, points: &[EdwardsProjective]) -> ShuffleResult<Vec<MaskedCardWithProof>> {
     let mut prng = default_prng();
     let joint_pk = hex_to_point(&joint)?;
 
     let mut deck = vec![];
-    for n in 0..num {
-        let point = index_to_point(n);
-
+    for point in points {
         let (masked_card, masked_proof) =
-            mask(&mut prng, &joint_pk, &point, &Fr::one()).map_err(|e| ShuffleError::UzkgeError(e))?;
+            mask(&mut prng, &joint_pk, point, &Fr::one()).map_err(ShuffleError::UzkgeError)?;
 
         deck.push(MaskedCardWithProof {
             card: masked_card_serialize(&masked_card),
@@ -207,7 +275,7 @@ pub fn shuffle_cards(joint: String, deck: Vec<MaskedCard>) -> ShuffleResult<Shuf
     let params = PARAMS.lock().unwrap();
     let prover_params = params
         .get(&n)
-        .expect("Missing PARAMS, need init & refresh pk");
+        .ok_or(ShuffleError::MissingParams(n))?;
 
     let (shuffled_proof, new_deck) =
         prove_shuffle(&mut prng, &joint_pk, &masked_deck, &prover_params).map_err(|e| ShuffleError::UzkgeError(e))?;
@@ -226,6 +294,43 @@ pub fn shuffle_cards(joint: String, deck: Vec<MaskedCard>) -> ShuffleResult<Shuf
     Ok(ret)
 }
 
+/// shuffle the cards with the low-memory prover, e.g., on mobile, which fails if it needs
+/// more than `budget` bytes. Init the key with `init_prover_key_low_memory` to also drop the
+/// coset evaluations of the prover key.
+pub fn shuffle_cards_low_memory(joint: String, deck: Vec<MaskedCard>, budget: Option<usize>) -> ShuffleResult<ShuffledCardsWithProof> {
+    let n = deck.len();
+
+    let mut prng = default_prng();
+    let joint_pk = hex_to_point(&joint)?;
+
+    let mut masked_deck = vec![];
+    for card in deck {
+        masked_deck.push(masked_card_deserialize(&card)?);
+    }
+
+    let params = PARAMS.lock().unwrap();
+    let prover_params = params
+        .get(&n)
+        .ok_or(ShuffleError::MissingParams(n))?;
+
+    let config = ProverMemoryConfig { budget };
+    let (shuffled_proof, new_deck) =
+        prove_shuffle_low_memory(&mut prng, &joint_pk, &masked_deck, prover_params, &config).map_err(ShuffleError::UzkgeError)?;
+    drop(params);
+
+    let masked_cards: Vec<_> = new_deck
+        .iter()
+        .map(masked_card_serialize)
+        .collect();
+
+    let ret = ShuffledCardsWithProof {
+        cards: masked_cards,
+        proof: shuffle_proof_to_hex(&shuffled_proof),
+    };
+
+    Ok(ret)
+}
+
 pub fn verify_shuffled_cards(
     deck1: Vec<MaskedCard>,
     deck2: Vec<MaskedCard>,
@@ -246,7 +351,7 @@ pub fn verify_shuffled_cards(
     let params = PARAMS.lock().unwrap();
     let prover_params = params
         .get(&n)
-        .expect("Missing PARAMS, need init & refresh pk");
+        .ok_or(ShuffleError::MissingParams(n))?;
     let verifier_params = VerifierParams::from(prover_params);
 
     Ok(verify_shuffle(
@@ -327,7 +432,15 @@ pub fn reveal_card_with_snark(sk: String, card: MaskedCard) -> ShuffleResult<Rev
 }
 
 pub fn unmask_card(sk: String, card: MaskedCard, reveals:  Vec<(String, String)>) -> ShuffleResult<i32> {
-    
+    point_to_index(unmask_point(sk, card, reveals)?)
+}
+
+/// Unmask a card of the custom deck of `init_custom_masked_cards`.
+pub fn unmask_custom_card(sk: String, card: MaskedCard, reveals: Vec<(String, String)>, cards: Vec<String>) -> ShuffleResult<i32> {
+    custom_point_to_index(unmask_point(sk, card, reveals)?, &cards)
+}
+
+fn unmask_point(sk: String, card: MaskedCard, reveals: Vec<(String, String)>) -> ShuffleResult<EdwardsProjective> {
     let mut prng = default_prng();
     let keypair = CoreKeypair::from_secret(hex_to_scalar(&sk)?);
     let masked = masked_card_deserialize(&card)?;
@@ -340,8 +453,7 @@ pub fn unmask_card(sk: String, card: MaskedCard, reveals:  Vec<(String, String)>
     let (reveal_card, _proof) = reveal(&mut prng, &keypair, &masked).map_err(|e| ShuffleError::UzkgeError(e))?;
     reveal_cards.push(reveal_card);
 
-    let unmasked_card = unmask(&masked, &reveal_cards).map_err(|e| ShuffleError::UzkgeError(e))?;
-    point_to_index(unmasked_card)
+    unmask(&masked, &reveal_cards).map_err(ShuffleError::UzkgeError)
 }
 
 
@@ -385,6 +497,17 @@ pub mod tests{
         let res =
             verify_shuffled_cards(deck_cards.clone(), cards.clone(), proof.proof.clone()).unwrap();
         assert_eq!(res, true);
+
+        let low_memory_proof = shuffle_cards_low_memory(joint_pk.clone(), cards.clone(), None).unwrap();
+        let res = verify_shuffled_cards(cards.clone(), low_memory_proof.cards.clone(), low_memory_proof.proof.clone()).unwrap();
+        assert!(res);
+        assert!(shuffle_cards_low_memory(joint_pk.clone(), cards.clone(), Some(1)).is_err());
+        assert!(shuffle_cards_low_memory(joint_pk.clone(), cards[1..].to_vec(), None).is_err());
+
+        init_prover_key_low_memory(CARD_NUM).unwrap();
+        let low_memory_proof = shuffle_cards_low_memory(joint_pk.clone(), cards.clone(), None).unwrap();
+        let res = verify_shuffled_cards(cards.clone(), low_memory_proof.cards.clone(), low_memory_proof.proof.clone()).unwrap();
+        assert!(res);
  
         init_reveal_key();
         
@@ -393,7 +516,39 @@ pub mod tests{
         
         let unmasked_card = unmask_card(key1.sk.clone(), reveal_item.clone(),vec![key2_reveal_proof.card]).unwrap();
         println!("unmasked_card={:?}",unmasked_card);
-        
 
+        // a custom deck is decoded with the same cards.
+        let uno: Vec<String> = (0..108).map(|i| format!("uno-{}", i)).collect();
+        let custom_decks = init_custom_masked_cards(joint_pk.clone(), uno.clone()).unwrap();
+        assert_eq!(custom_decks.len(), 108);
+        let custom_card = custom_decks[42].card.clone();
+        let key2_reveal = reveal_card(key2.sk.clone(), custom_card.clone()).unwrap();
+        let index = unmask_custom_card(key1.sk.clone(), custom_card.clone(), vec![key2_reveal.card.clone()], uno).unwrap();
+        assert_eq!(index, 42);
+        assert!(unmask_card(key1.sk.clone(), custom_card, vec![key2_reveal.card]).is_err());
+
+    }
+
+    #[test]
+    fn key_ownership(){
+        let key = generate_key_preset("0x020b31a672b203b71241031c8ea5e5a4ef133c57bcde822ac514e8a1c7f89124".to_owned(),
+        "0xada2d401ec3113060a049b5472550965f59423eaaeec3133dd33628e5df50491".to_owned(),
+        "0x27f9bc87a7fe674c14532699864907156753a8271a6e97b8f8b99a474ad2afdd".to_owned(),
+        "0x1104f55d8e6233dd3331ecaeea2394f565095572549b040a061331ec01d4a2ad".to_owned(),
+        ).unwrap();
+        let session = "session-0".to_owned();
+
+        let proof = prove_key_ownership(key.sk.clone(), session.clone()).unwrap();
+        assert!(verify_key_ownership(key.pk.clone(), session.clone(), proof.clone()).unwrap());
+        assert!(!verify_key_ownership(key.pk.clone(), "session-1".to_owned(), proof.clone()).unwrap());
+
+        // a malformed proof is rejected without panicking.
+        let mut bytes = hex::decode(proof.trim_start_matches("0x")).unwrap();
+        bytes[63] ^= 1;
+        let off_curve = format!("0x{}", hex::encode(&bytes));
+        assert!(verify_key_ownership(key.pk.clone(), session.clone(), off_curve).is_err());
+        let truncated = format!("0x{}", hex::encode(&bytes[..100]));
+        assert!(verify_key_ownership(key.pk.clone(), session.clone(), truncated.clone()).is_err());
+        assert!(aggregate_keys_checked(vec![(key.pk, truncated)], session).is_err());
     }
 }
\ No newline at end of file
@@ -362,3 +362,83 @@ fn test_poker() {
         println!("David: {:?}", real_d_card);
     }
 }
+
+#[test]
+fn test_reveal_all() {
+    let mut rng = ChaChaRng::from_seed([0u8; 32]);
+
+    let keypair = Keypair::generate(&mut rng);
+    let deck: Vec<MaskedCard> = (0..N_CARDS)
+        .map(|_| {
+            let card = Card::rand(&mut rng);
+            let r = Fr::rand(&mut rng);
+            mask(&mut rng, &keypair.public, &card, &r).unwrap().0
+        })
+        .collect();
+
+    let (reveals, proof) = reveal_all(&mut rng, &keypair, &deck).unwrap();
+    verify_reveal_all(&keypair.public, &deck, &reveals, &proof).unwrap();
+
+    for (card, reveal_card) in deck.iter().zip(reveals.iter()) {
+        assert_eq!(card.e1 * keypair.secret, *reveal_card);
+    }
+
+    let mut wrong_reveals = reveals.clone();
+    wrong_reveals.swap(0, 1);
+    assert!(verify_reveal_all(&keypair.public, &deck, &wrong_reveals, &proof).is_err());
+}
+
+#[test]
+fn test_refresh_public_key() {
+    use uzkge::{
+        gen_params::load_srs_params,
+        plonk::constraint_system::ConstraintSystem,
+        poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme},
+    };
+
+    let mut rng = ChaChaRng::from_entropy();
+    let mut prover_params = gen_shuffle_prover_params(4).unwrap();
+
+    let joint_pk = EdwardsProjective::rand(&mut rng);
+    let res = refresh_prover_params_public_key(&mut prover_params, &joint_pk).unwrap();
+
+    // the refreshed selectors match the ones committed from scratch.
+    let n = prover_params.cs.size();
+    let pcs = load_srs_params(n).unwrap();
+    let domain = FpPolynomial::evaluation_domain(n).unwrap();
+    let polys = prover_params
+        .cs
+        .compute_shuffle_public_key_selectors()
+        .iter()
+        .map(|p| FpPolynomial::ifft_with_domain(&domain, p))
+        .collect::<Vec<_>>();
+    let cm_vec = polys
+        .iter()
+        .map(|p| pcs.commit(p).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(res, cm_vec.iter().map(|c| c.0).collect::<Vec<_>>());
+    assert_eq!(
+        prover_params
+            .prover_params
+            .verifier_params
+            .cm_shuffle_public_key_vec,
+        cm_vec
+    );
+    assert_eq!(
+        prover_params.prover_params.q_shuffle_public_key_polys,
+        polys
+    );
+
+    let m = prover_params.cs.quot_eval_dom_size();
+    let domain_m = FpPolynomial::quotient_evaluation_domain(m).unwrap();
+    let k = &prover_params.prover_params.verifier_params.k[1];
+    let coset_evals = polys
+        .iter()
+        .map(|p| p.coset_fft_with_domain(&domain_m, k))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        prover_params.prover_params.q_shuffle_public_key_coset_evals,
+        coset_evals
+    );
+}
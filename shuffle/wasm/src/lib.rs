@@ -4,26 +4,32 @@ mod poker;
 use ark_ec::AffineRepr;
 pub use poker::*;
 
-use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
-use ark_ff::{BigInteger, One, PrimeField};
+use ark_ed_on_bn254::{EdwardsProjective, Fr};
+use ark_ff::One;
 use once_cell::sync::Lazy;
 use std::{collections::HashMap, sync::Mutex};
 use uzkge::{
     chaum_pedersen::dl::ChaumPedersenDLProof,
     gen_params::{ProverParams, VerifierParams},
+    plonk::prover::ProverMemoryConfig,
 };
 use wasm_bindgen::prelude::*;
 use zshuffle::{
-    build_cs::{prove_shuffle, verify_shuffle},
+    build_cs::{prove_shuffle, prove_shuffle_low_memory, verify_shuffle},
     gen_params::{
         gen_shuffle_prover_params, load_groth16_pk, params::refresh_prover_params_public_key,
     },
-    keygen::{aggregate_keys as core_aggregate_keys, Keypair as CoreKeypair},
+    keygen::{
+        aggregate_keys as core_aggregate_keys,
+        aggregate_keys_checked as core_aggregate_keys_checked,
+        verify_key_ownership as core_verify_key_ownership, KeyOwnershipProof,
+        Keypair as CoreKeypair,
+    },
     mask::*,
     reveal::*,
     reveal_with_snark::RevealCircuit,
     Groth16, MaskedCard as Masked, ProvingKey, SNARK,
-    card_maps::CARD_MAPS,
+    card_maps::{self, default_point_to_index, CardEncoder, MAX_CARDS},
     sdk::{Keypair,MaskedCard,MaskedCardWithProof,RevealedCardWithProof,RevealedCardWithSnarkProof,ShuffledCardsWithProof}
 };
 
@@ -139,22 +145,76 @@ pub fn aggregate_keys(publics: JsValue) -> Result<String, JsValue> {
     Ok(point_to_hex(&pk, true))
 }
 
+fn key_ownership_proof_from_hex(proof: &str) -> Result<KeyOwnershipProof, JsValue> {
+    let bytes = hex::decode(proof.trim_start_matches("0x")).map_err(error_to_jsvalue)?;
+    KeyOwnershipProof::from_uncompress(&bytes).map_err(error_to_jsvalue)
+}
+
+/// prove the possession of the secret key in the game/session
+#[wasm_bindgen]
+pub fn prove_key_ownership(sk: String, session: String) -> Result<String, JsValue> {
+    let mut prng = default_prng();
+    let keypair = CoreKeypair::from_secret(hex_to_scalar(&sk)?);
+
+    let proof = keypair
+        .prove_ownership(&mut prng, session.as_bytes())
+        .map_err(error_to_jsvalue)?;
+
+    Ok(format!("0x{}", hex::encode(&proof.to_uncompress())))
+}
+
+/// verify the key ownership proof of the public key in the game/session
+#[wasm_bindgen]
+pub fn verify_key_ownership(pk: String, session: String, proof: String) -> Result<bool, JsValue> {
+    let pk = hex_to_point(&pk)?;
+    let proof = key_ownership_proof_from_hex(&proof)?;
+
+    Ok(core_verify_key_ownership(&pk, session.as_bytes(), &proof).is_ok())
+}
+
+/// aggregate all (pk, ownership proof) to joint pk, rejecting the unproven keys
+#[wasm_bindgen]
+pub fn aggregate_keys_checked(publics: JsValue, session: String) -> Result<String, JsValue> {
+    let publics: Vec<(String, String)> = serde_wasm_bindgen::from_value(publics)?;
+    let mut keys = vec![];
+    for (pk, proof) in publics {
+        keys.push((hex_to_point(&pk)?, key_ownership_proof_from_hex(&proof)?));
+    }
+    let pk = core_aggregate_keys_checked(&keys, session.as_bytes()).map_err(error_to_jsvalue)?;
+    Ok(point_to_hex(&pk, true))
+}
+
 /// mask the card, return the masked card and masked proof
 #[wasm_bindgen]
 pub fn init_masked_cards(joint: String, num: i32) -> Result<JsValue, JsValue> {
-    if CARD_MAPS.len() < num as usize {
+    if MAX_CARDS < num as usize {
         return Err(error_to_jsvalue("The number of cards exceeds the maximum"));
     }
 
+    let points: Vec<EdwardsProjective> = (0..num).map(index_to_point).collect();
+    mask_deck(joint, &points)
+}
+
+/// mask the custom deck, the card `i` is encoded from the bytes of `cards[i]`,
+/// return the masked cards and masked proofs
+#[wasm_bindgen]
+pub fn init_custom_masked_cards(joint: String, cards: JsValue) -> Result<JsValue, JsValue> {
+    let cards: Vec<String> = serde_wasm_bindgen::from_value(cards)?;
+    let encoder = CardEncoder::from_cards(&cards).map_err(error_to_jsvalue)?;
+    let points: Vec<EdwardsProjective> = (0..encoder.len())
+        .map(|i| encoder.index_to_point(i).unwrap())
+        .collect();
+    mask_deck(joint, &points)
+}
+
+fn mask_deck(joint: String, points: &[EdwardsProjective]) -> Result<JsValue, JsValue> {
     let mut prng = default_prng();
     let joint_pk = hex_to_point(&joint)?;
 
     let mut deck = vec![];
-    for n in 0..num {
-        let point = index_to_point(n);
-
+    for point in points {
         let (masked_card, masked_proof) =
-            mask(&mut prng, &joint_pk, &point, &Fr::one()).map_err(error_to_jsvalue)?;
+            mask(&mut prng, &joint_pk, point, &Fr::one()).map_err(error_to_jsvalue)?;
 
         deck.push(MaskedCardWithProof {
             card: masked_card_serialize(&masked_card),
@@ -224,6 +284,26 @@ pub fn init_prover_key(num: i32) {
     drop(params);
 }
 
+/// Initialize the prover key without the coset evaluations, for `shuffle_cards_low_memory`
+/// on the memory-constrained devices. It also replaces the key of `init_prover_key`, then
+/// `shuffle_cards` evaluates the quotient polynomial without them, which is slower.
+#[wasm_bindgen]
+pub fn init_prover_key_low_memory(num: i32) -> Result<(), JsValue> {
+    let n = num as usize;
+
+    let mut params = PARAMS.lock().unwrap();
+    match params.get_mut(&n) {
+        Some(pp) => pp.prover_params.drop_coset_evals(),
+        None => {
+            let mut pp = gen_shuffle_prover_params(n).map_err(error_to_jsvalue)?;
+            pp.prover_params.drop_coset_evals();
+            params.insert(n, pp);
+        }
+    }
+    drop(params);
+    Ok(())
+}
+
 /// Initialize the reveal key
 #[wasm_bindgen]
 pub fn init_reveal_key() {
@@ -285,7 +365,7 @@ pub fn shuffle_cards(joint: String, deck: JsValue) -> Result<JsValue, JsValue> {
     let params = PARAMS.lock().unwrap();
     let prover_params = params
         .get(&n)
-        .expect("Missing PARAMS, need init & refresh pk");
+        .ok_or_else(|| error_to_jsvalue("Missing PARAMS, need init & refresh pk"))?;
 
     let (shuffled_proof, new_deck) =
         prove_shuffle(&mut prng, &joint_pk, &masked_deck, &prover_params)
@@ -305,6 +385,50 @@ pub fn shuffle_cards(joint: String, deck: JsValue) -> Result<JsValue, JsValue> {
     Ok(serde_wasm_bindgen::to_value(&ret)?)
 }
 
+/// shuffle the cards with the low-memory prover, e.g., on mobile, which fails if it needs
+/// more than `budget` bytes. Init the key with `init_prover_key_low_memory` to also drop the
+/// coset evaluations of the prover key.
+#[wasm_bindgen]
+pub fn shuffle_cards_low_memory(
+    joint: String,
+    deck: JsValue,
+    budget: Option<usize>,
+) -> Result<JsValue, JsValue> {
+    let deck: Vec<MaskedCard> = serde_wasm_bindgen::from_value(deck)?;
+    let n = deck.len();
+
+    let mut prng = default_prng();
+    let joint_pk = hex_to_point(&joint)?;
+
+    let mut masked_deck = vec![];
+    for card in deck {
+        masked_deck.push(masked_card_deserialize(&card)?);
+    }
+
+    let params = PARAMS.lock().unwrap();
+    let prover_params = params
+        .get(&n)
+        .ok_or_else(|| error_to_jsvalue("Missing PARAMS, need init & refresh pk"))?;
+
+    let config = ProverMemoryConfig { budget };
+    let (shuffled_proof, new_deck) =
+        prove_shuffle_low_memory(&mut prng, &joint_pk, &masked_deck, prover_params, &config)
+            .map_err(error_to_jsvalue)?;
+    drop(params);
+
+    let masked_cards: Vec<_> = new_deck
+        .iter()
+        .map(masked_card_serialize)
+        .collect();
+
+    let ret = ShuffledCardsWithProof {
+        cards: masked_cards,
+        proof: shuffle_proof_to_hex(&shuffled_proof),
+    };
+
+    Ok(serde_wasm_bindgen::to_value(&ret)?)
+}
+
 /// verify the shuffled cards
 #[wasm_bindgen]
 pub fn verify_shuffled_cards(
@@ -329,7 +453,7 @@ pub fn verify_shuffled_cards(
     let params = PARAMS.lock().unwrap();
     let prover_params = params
         .get(&n)
-        .expect("Missing PARAMS, need init & refresh pk");
+        .ok_or_else(|| error_to_jsvalue("Missing PARAMS, need init & refresh pk"))?;
     let verifier_params = VerifierParams::from(prover_params);
 
     Ok(verify_shuffle(
@@ -425,6 +549,21 @@ pub fn verify_revealed_card(pk: String, card: JsValue, reveal: JsValue) -> Resul
 /// unmask the card use others' reveals
 #[wasm_bindgen]
 pub fn unmask_card(sk: String, card: JsValue, reveals: JsValue) -> Result<i32, JsValue> {
+    point_to_index(unmask_point(sk, card, reveals)?)
+}
+
+/// unmask the card of the custom deck `cards` use others' reveals
+#[wasm_bindgen]
+pub fn unmask_custom_card(
+    sk: String,
+    card: JsValue,
+    reveals: JsValue,
+    cards: JsValue,
+) -> Result<i32, JsValue> {
+    custom_point_to_index(unmask_point(sk, card, reveals)?, cards)
+}
+
+fn unmask_point(sk: String, card: JsValue, reveals: JsValue) -> Result<EdwardsProjective, JsValue> {
     let card: MaskedCard = serde_wasm_bindgen::from_value(card)?;
     let reveals: Vec<(String, String)> = serde_wasm_bindgen::from_value(reveals)?;
 
@@ -440,13 +579,22 @@ pub fn unmask_card(sk: String, card: JsValue, reveals: JsValue) -> Result<i32, J
     let (reveal_card, _proof) = reveal(&mut prng, &keypair, &masked).map_err(error_to_jsvalue)?;
     reveal_cards.push(reveal_card);
 
-    let unmasked_card = unmask(&masked, &reveal_cards).map_err(error_to_jsvalue)?;
-    point_to_index(unmasked_card)
+    unmask(&masked, &reveal_cards).map_err(error_to_jsvalue)
 }
 
 /// decode masked to card use all reveals
 #[wasm_bindgen]
 pub fn decode_point(card: JsValue, reveals: JsValue) -> Result<i32, JsValue> {
+    point_to_index(decode_unmasked_point(card, reveals)?)
+}
+
+/// decode masked to the card of the custom deck `cards` use all reveals
+#[wasm_bindgen]
+pub fn decode_custom_point(card: JsValue, reveals: JsValue, cards: JsValue) -> Result<i32, JsValue> {
+    custom_point_to_index(decode_unmasked_point(card, reveals)?, cards)
+}
+
+fn decode_unmasked_point(card: JsValue, reveals: JsValue) -> Result<EdwardsProjective, JsValue> {
     let card: MaskedCard = serde_wasm_bindgen::from_value(card)?;
     let reveals: Vec<(String, String)> = serde_wasm_bindgen::from_value(reveals)?;
 
@@ -456,25 +604,23 @@ pub fn decode_point(card: JsValue, reveals: JsValue) -> Result<i32, JsValue> {
         reveal_cards.push(uncompress_to_point(&reveal.0, &reveal.1)?);
     }
 
-    let unmasked_card = unmask(&masked, &reveal_cards).map_err(error_to_jsvalue)?;
-    point_to_index(unmasked_card)
+    unmask(&masked, &reveal_cards).map_err(error_to_jsvalue)
 }
 
 fn index_to_point(index: i32) -> EdwardsProjective {
-    let y_hex = CARD_MAPS[index as usize].trim_start_matches("0x");
-    let y_bytes = hex::decode(y_hex).unwrap();
-    let y = Fq::from_be_bytes_mod_order(&y_bytes);
-
-    let affine = EdwardsAffine::get_point_from_y_unchecked(y, true).unwrap();
-    affine.into()
+    card_maps::index_to_point(index as usize)
 }
 
 fn point_to_index(point: EdwardsProjective) -> Result<i32, JsValue> {
-    let affine = EdwardsAffine::from(point);
-    let y_bytes = affine.y.into_bigint().to_bytes_be();
-    let bytes = format!("0x{}", hex::encode(&y_bytes));
+    default_point_to_index(&point)
+        .map(|pos| pos as i32)
+        .map_err(error_to_jsvalue)
+}
 
-    if let Some(pos) = CARD_MAPS.iter().position(|y| y == &bytes) {
+fn custom_point_to_index(point: EdwardsProjective, cards: JsValue) -> Result<i32, JsValue> {
+    let cards: Vec<String> = serde_wasm_bindgen::from_value(cards)?;
+    let encoder = CardEncoder::from_cards(&cards).map_err(error_to_jsvalue)?;
+    if let Some(pos) = encoder.point_to_index(&point) {
         Ok(pos as i32)
     } else {
         Err(error_to_jsvalue("Point not map to  a card"))
@@ -23,9 +23,28 @@ fn pass() {
     let key3: Keypair = serde_wasm_bindgen::from_value(key3).unwrap();
     let key4: Keypair = serde_wasm_bindgen::from_value(key4).unwrap();
 
+    let session = "game-1".to_owned();
+    let mut proved = vec![];
+    for key in [&key1, &key2, &key3, &key4] {
+        let proof = prove_key_ownership(key.sk.clone(), session.clone()).unwrap();
+        assert!(verify_key_ownership(key.pk.clone(), session.clone(), proof.clone()).unwrap());
+        proved.push((key.pk.clone(), proof));
+    }
+
+    // a malformed proof is rejected without panicking.
+    let mut off_curve = proved[0].1.clone();
+    let last = if off_curve.as_bytes()[129] == b'0' { "1" } else { "0" };
+    off_curve.replace_range(129..130, last);
+    assert!(verify_key_ownership(key1.pk.clone(), session.clone(), off_curve).is_err());
+    let truncated = proved[0].1[..proved[0].1.len() - 2].to_owned();
+    assert!(verify_key_ownership(key1.pk.clone(), session.clone(), truncated).is_err());
+    let proved_values = serde_wasm_bindgen::to_value(&proved).unwrap();
+    let checked_pk = aggregate_keys_checked(proved_values, session).unwrap();
+
     let joint = [key1.pk, key2.pk, key3.pk, key4.pk];
     let joint_values = serde_wasm_bindgen::to_value(&joint).unwrap();
     let joint_pk = aggregate_keys(joint_values).unwrap();
+    assert_eq!(checked_pk, joint_pk);
 
     // must do it before prov & verify.
     // when joint pk changed, must do it again !!!
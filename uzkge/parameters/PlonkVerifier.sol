@@ -0,0 +1,636 @@
+// SPDX-License-Identifier: UNLICENSED
+// Generated file from uzkge/gen-params, DONOT edit!
+pragma solidity ^0.8.20;
+
+contract {{ contract_name }} {
+    error InvalidInput();
+    error PrecompileFailed();
+
+    /// The opening of a batch of polynomials at one point.
+    struct Opening {
+        uint256[2] cm;
+        uint256 eval;
+    }
+
+    struct Transcript {
+        bytes data;
+        uint256 len;
+    }
+
+    struct Challenges {
+        uint256 beta;
+        uint256 gamma;
+        uint256 alpha;
+        uint256 zeta;
+        uint256 u;
+        uint256 zH;
+        uint256 l1;
+        uint256[17] alphaPows;
+    }
+
+    /// The scalar field of BN254.
+    uint256 private constant R = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+    /// The base field of BN254.
+    uint256 private constant Q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+
+    uint256 private constant CS_SIZE = {{ cs_size }};
+    uint256 private constant MAX_DEGREE = {{ max_degree }};
+    uint256 private constant ROOT = {{ root }};
+    uint256 private constant ANEMOI_GENERATOR = {{ anemoi_generator }};
+    uint256 private constant ANEMOI_GENERATOR_INV = {{ anemoi_generator_inv }};
+{%- for k in ks %}
+    uint256 private constant K_{{ loop.index0 }} = {{ k }};
+{%- endfor %}
+{%- if shuffle %}
+    uint256 private constant EDWARDS_A = {{ edwards_a }};
+{%- endif %}
+
+    /// The SRS: the generator of G1, and [1]_2, [x]_2 with the imaginary parts first.
+    uint256 private constant G1_X = {{ g1.0 }};
+    uint256 private constant G1_Y = {{ g1.1 }};
+    uint256 private constant G2_X1 = {{ g2.0 }};
+    uint256 private constant G2_X0 = {{ g2.1 }};
+    uint256 private constant G2_Y1 = {{ g2.2 }};
+    uint256 private constant G2_Y0 = {{ g2.3 }};
+    uint256 private constant G2_TAU_X1 = {{ g2_tau.0 }};
+    uint256 private constant G2_TAU_X0 = {{ g2_tau.1 }};
+    uint256 private constant G2_TAU_Y1 = {{ g2_tau.2 }};
+    uint256 private constant G2_TAU_Y0 = {{ g2_tau.3 }};
+
+    /// The layout of the proof, in words of `PlonkProof::to_bytes_be` after the layout tag.
+    uint256 private constant PROOF_TAG = {{ proof.tag }};
+    uint256 private constant PROOF_WORDS = {{ proof.words }};
+    uint256 private constant P_CM_W = {{ proof.cm_w }};
+{%- if shuffle %}
+    uint256 private constant P_CM_W_SEL = {{ proof.cm_w_sel }};
+{%- endif %}
+    uint256 private constant P_CM_T = {{ proof.cm_t }};
+    uint256 private constant P_CM_Z = {{ proof.cm_z }};
+    uint256 private constant P_PRK_3 = {{ proof.prk_3 }};
+    uint256 private constant P_PRK_4 = {{ proof.prk_4 }};
+    uint256 private constant P_W = {{ proof.w }};
+    uint256 private constant P_W_OMEGA = {{ proof.w_omega }};
+    uint256 private constant P_Z_OMEGA = {{ proof.z_omega }};
+    uint256 private constant P_S = {{ proof.s }};
+{%- if shuffle %}
+    uint256 private constant P_Q_ECC = {{ proof.q_ecc }};
+    uint256 private constant P_W_SEL = {{ proof.w_sel }};
+{%- endif %}
+    uint256 private constant P_OPENING_ZETA = {{ proof.opening_zeta }};
+    uint256 private constant P_OPENING_ZETA_OMEGA = {{ proof.opening_zeta_omega }};
+
+    /// The layout of the verifier key, in points of `VK_POINTS`.
+    uint256 private constant N_Q = {{ vk.n_q }};
+    uint256 private constant VK_Q = {{ vk.q }};
+    uint256 private constant VK_S = {{ vk.s }};
+    uint256 private constant VK_QB = {{ vk.qb }};
+    uint256 private constant VK_PRK = {{ vk.prk }};
+{%- if shuffle %}
+    uint256 private constant VK_Q_ECC = {{ vk.q_ecc }};
+    uint256 private constant VK_SHUFFLE_GENERATOR = {{ vk.shuffle_generator }};
+    uint256 private constant VK_SHUFFLE_PUBLIC_KEY = {{ vk.shuffle_public_key }};
+{%- endif %}
+    bytes private constant VK_POINTS = hex"{{ vk_points }}";
+
+    /// The public inputs: \omega^j and the lagrange constant c_j of their constraints.
+    uint256 private constant NUM_PUBLIC_INPUTS = {{ num_public_inputs }};
+    bytes private constant PI_ROOTS = hex"{{ pi_roots }}";
+    bytes private constant PI_LAGRANGE_CONSTANTS = hex"{{ pi_lagrange_constants }}";
+
+    /// The transcript after the domain separator and the verifier key.
+    bytes private constant TRANSCRIPT_PREFIX = hex"{{ transcript_prefix }}";
+    uint256 private constant TRANSCRIPT_PREFIX_LENGTH = {{ transcript_prefix_length }};
+    uint256 private constant TRANSCRIPT_CAPACITY = {{ transcript_capacity }};
+    /// "New PCS-Batch-Eval Protocol" in one slot.
+    uint256 private constant PCS_BATCH_DOMAIN = {{ pcs_batch_domain }};
+
+    /// Verify a Plonk proof, encoded by `PlonkProof::to_bytes_be`.
+    function verify(bytes calldata proof, uint256[] calldata publicInputs) external view returns (bool) {
+        if (
+            proof.length != PROOF_WORDS * 32 + 1 || uint8(proof[0]) != PROOF_TAG
+                || publicInputs.length != NUM_PUBLIC_INPUTS
+        ) {
+            revert InvalidInput();
+        }
+        uint256[] memory p = new uint256[](PROOF_WORDS);
+        assembly {
+            calldatacopy(add(p, 0x20), add(proof.offset, 1), mul(PROOF_WORDS, 32))
+        }
+        for (uint256 i = P_PRK_3; i < P_OPENING_ZETA; i++) {
+            if (p[i] >= R) {
+                revert InvalidInput();
+            }
+        }
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            if (publicInputs[i] >= R) {
+                revert InvalidInput();
+            }
+        }
+
+        // 1. compute all challenges such as gamma, beta, alpha, zeta and u.
+        Transcript memory t = _initTranscript(publicInputs);
+        Challenges memory c = _computeChallenges(t, p);
+
+        // 2. compute Z_h(\zeta) and L_1(\zeta).
+        c.zH = _sub(_pow(c.zeta, CS_SIZE), 1);
+        c.l1 = _mul(c.zH, _inverse(_sub(c.zeta, 1)));
+
+        // 3. compute PI(\zeta).
+        uint256 piEval = _evalPublicInputs(publicInputs, c.zeta, c.zH);
+
+        // 4. derive the linearization polynomial commitment.
+        bytes memory vk = VK_POINTS;
+        Opening memory r;
+        r.eval = _rEvalZeta(p, c, piEval);
+        _rCommitment(r.cm, p, c, vk);
+
+        // 5. verify opening proofs.
+        Opening memory atZeta = _batchZeta(p, vk, r, _batchChallenge(t, c.zeta));
+        Opening memory atZetaOmega = _batchZetaOmega(t, p, c);
+        return _verifyOpenings(p, c, atZeta, atZetaOmega);
+    }
+
+    function _initTranscript(uint256[] calldata publicInputs) private pure returns (Transcript memory t) {
+        t.data = bytes.concat(TRANSCRIPT_PREFIX, new bytes(TRANSCRIPT_CAPACITY));
+        t.len = TRANSCRIPT_PREFIX_LENGTH;
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            _append(t, publicInputs[i]);
+        }
+    }
+
+    function _computeChallenges(Transcript memory t, uint256[] memory p) private pure returns (Challenges memory c) {
+        // the witness commitments, followed by the witness selector commitments.
+        _appendWords(t, p, P_CM_W, P_CM_T - P_CM_W);
+        c.beta = _challenge(t);
+        _appendByte(t, 0x01);
+        c.gamma = _challenge(t);
+
+        _appendWords(t, p, P_CM_Z, 2);
+        c.alpha = _challenge(t);
+        _appendWords(t, p, P_CM_T, P_CM_Z - P_CM_T);
+
+        c.zeta = _challenge(t);
+        _appendWords(t, p, P_W, 5);
+        _appendWords(t, p, P_S, 4);
+{%- if shuffle %}
+        _appendWords(t, p, P_W_SEL, 3);
+{%- endif %}
+        _append(t, p[P_PRK_3]);
+        _append(t, p[P_PRK_4]);
+        _append(t, p[P_Z_OMEGA]);
+{%- if shuffle %}
+        _append(t, p[P_Q_ECC]);
+{%- endif %}
+        _appendWords(t, p, P_W_OMEGA, 3);
+        c.u = _challenge(t);
+
+        c.alphaPows[0] = 1;
+        for (uint256 i = 1; i < 17; i++) {
+            c.alphaPows[i] = _mul(c.alphaPows[i - 1], c.alpha);
+        }
+    }
+
+    /// Compute sum_i pi_i c_j (\zeta^n - 1) / (\zeta - \omega^j), where j is the constraint of pi_i.
+    function _evalPublicInputs(uint256[] calldata publicInputs, uint256 zeta, uint256 zH)
+        private
+        view
+        returns (uint256 r)
+    {
+        bytes memory roots = PI_ROOTS;
+        bytes memory constants = PI_LAGRANGE_CONSTANTS;
+
+        // batch inversion of the denominators.
+        uint256[] memory prods = new uint256[](NUM_PUBLIC_INPUTS);
+        uint256 acc = 1;
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            prods[i] = acc;
+            acc = _mul(acc, _sub(zeta, _word(roots, i)));
+        }
+        acc = _inverse(acc);
+        for (uint256 i = NUM_PUBLIC_INPUTS; i > 0; i--) {
+            // acc * prods[i - 1] is the inverse of the i-th denominator.
+            r = _add(r, _mul(_mul(_word(constants, i - 1), _mul(acc, prods[i - 1])), publicInputs[i - 1]));
+            acc = _mul(acc, _sub(zeta, _word(roots, i - 1)));
+        }
+        r = _mul(r, zH);
+    }
+
+    /// Evaluate the r polynomial at point \zeta.
+    function _rEvalZeta(uint256[] memory p, Challenges memory c, uint256 piEval) private pure returns (uint256 r) {
+        uint256 term1 = _mul(c.alpha, p[P_Z_OMEGA]);
+        for (uint256 i = 0; i < 4; i++) {
+            term1 = _mul(term1, _add(_add(p[P_W + i], _mul(c.beta, p[P_S + i])), c.gamma));
+        }
+        term1 = _mul(term1, _add(p[P_W + 4], c.gamma));
+
+        r = _add(term1, _mul(c.l1, c.alphaPows[2]));
+        r = _sub(r, piEval);
+        r = _add(r, _anemoiTerms(p, c.alphaPows));
+        r = _add(r, _anemoiNextTerms(p, c.alphaPows));
+{%- if shuffle %}
+        r = _sub(r, _shuffleTerms(p, c.alphaPows));
+{%- endif %}
+    }
+
+    /// The Anemoi terms of the r polynomial that use the third round key.
+    function _anemoiTerms(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 r) {
+        uint256 w3w0 = _add(p[P_W + 3], p[P_W]);
+        uint256 w2w1 = _add(p[P_W + 2], p[P_W + 1]);
+        uint256 tmp = _add(_add(w3w0, _mul(ANEMOI_GENERATOR, w2w1)), p[P_PRK_3]);
+        uint256 pow5 = _pow5(_sub(tmp, p[P_W_OMEGA + 2]));
+        // w3 + 2 * w0 + g * (w2 + 2 * w1)
+        uint256 rhs = _add(_add(w3w0, p[P_W]), _mul(ANEMOI_GENERATOR, _add(w2w1, p[P_W + 1])));
+
+        r = _mul(_mul(a[6], p[P_PRK_3]), _sub(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), rhs));
+        tmp = p[P_W_OMEGA + 2];
+        rhs = _add(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), ANEMOI_GENERATOR_INV);
+        r = _add(r, _mul(_mul(a[8], p[P_PRK_3]), _sub(rhs, p[P_W_OMEGA])));
+    }
+
+    /// The Anemoi terms of the r polynomial that use the fourth round key.
+    function _anemoiNextTerms(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 r) {
+        uint256 gSquarePlusOne = _add(_mul(ANEMOI_GENERATOR, ANEMOI_GENERATOR), 1);
+        uint256 w3w0 = _add(p[P_W + 3], p[P_W]);
+        uint256 w2w1 = _add(p[P_W + 2], p[P_W + 1]);
+        uint256 tmp = _add(_add(_mul(ANEMOI_GENERATOR, w3w0), _mul(gSquarePlusOne, w2w1)), p[P_PRK_4]);
+        uint256 pow5 = _pow5(_sub(tmp, p[P_W + 4]));
+        // g * (w3 + 2 * w0) + (g^2 + 1) * (w2 + 2 * w1)
+        uint256 rhs = _add(
+            _mul(ANEMOI_GENERATOR, _add(w3w0, p[P_W])),
+            _mul(gSquarePlusOne, _add(w2w1, p[P_W + 1]))
+        );
+
+        r = _mul(_mul(a[7], p[P_PRK_3]), _sub(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), rhs));
+        tmp = p[P_W + 4];
+        rhs = _add(_add(pow5, _mul(ANEMOI_GENERATOR, _mul(tmp, tmp))), ANEMOI_GENERATOR_INV);
+        r = _add(r, _mul(_mul(a[9], p[P_PRK_3]), _sub(rhs, p[P_W_OMEGA + 1])));
+    }
+{%- if shuffle %}
+
+    /// The shuffle terms of the r polynomial, to be subtracted.
+    function _shuffleTerms(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 r) {
+        uint256 s0 = p[P_W_SEL];
+        uint256 s1 = p[P_W_SEL + 1];
+        uint256 s2 = p[P_W_SEL + 2];
+        uint256 qEcc = p[P_Q_ECC];
+
+        // the four selectors of the bits (s0, s1) add up to q_ecc.
+        r = _mul(
+            _mul(s2, qEcc),
+            _add(
+                _add(_mul(a[10], p[P_W_OMEGA]), _mul(a[11], p[P_W_OMEGA + 1])),
+                _add(_mul(a[12], p[P_W_OMEGA + 2]), _mul(a[13], p[P_W + 4]))
+            )
+        );
+        r = _add(r, _mul(a[14], _add(_mul(_mul(qEcc, s0), _sub(1, s0)), _mul(_sub(1, qEcc), s0))));
+        r = _add(r, _mul(a[15], _add(_mul(_mul(qEcc, s1), _sub(1, s1)), _mul(_sub(1, qEcc), s1))));
+        r = _add(r, _mul(_mul(a[16], qEcc), _mul(_sub(1, s2), _add(1, s2))));
+    }
+{%- endif %}
+
+    /// Compute the commitment of the r polynomial.
+    function _rCommitment(uint256[2] memory acc, uint256[] memory p, Challenges memory c, bytes memory vk)
+        private
+        view
+    {
+        uint256[N_Q] memory m = _selectorMultipliers(p);
+        for (uint256 i = 0; i < N_Q; i++) {
+            _ecMulAddVk(acc, vk, VK_Q + i, m[i]);
+        }
+
+        _ecMulAdd(acc, p[P_CM_Z], p[P_CM_Z + 1], _zScalar(p, c));
+        _ecMulAddVk(acc, vk, VK_S + 4, _neg(_sLastScalar(p, c)));
+        _ecMulAddVk(acc, vk, VK_QB, _qbScalar(p, c.alphaPows));
+        _ecMulAddVk(acc, vk, VK_PRK, _mul(p[P_PRK_3], c.alphaPows[6]));
+        _ecMulAddVk(acc, vk, VK_PRK + 1, _mul(p[P_PRK_3], c.alphaPows[7]));
+{%- if shuffle %}
+        _shuffleCommitment(acc, p, c.alphaPows, vk);
+{%- endif %}
+        _tCommitment(acc, p, c);
+    }
+
+    /// - Z_h(\zeta) * (t_1(X) + \zeta^{n+2} * t_2(X) + \zeta^{2(n+2)} * t_3(X) + ...)
+    function _tCommitment(uint256[2] memory acc, uint256[] memory p, Challenges memory c) private view {
+        uint256 factor = _pow(c.zeta, MAX_DEGREE);
+        uint256 exponent = _neg(c.zH);
+        for (uint256 i = P_CM_T; i < P_CM_Z; i += 2) {
+            _ecMulAdd(acc, p[i], p[i + 1], exponent);
+            exponent = _mul(exponent, factor);
+        }
+    }
+
+    /// The coefficients of the selectors in the gate identity.
+    function _selectorMultipliers(uint256[] memory p) private pure returns (uint256[N_Q] memory m) {
+        uint256 w0 = p[P_W];
+        uint256 w1 = p[P_W + 1];
+        uint256 w2 = p[P_W + 2];
+        uint256 w3 = p[P_W + 3];
+        uint256 w4 = p[P_W + 4];
+        m[0] = w0;
+        m[1] = w1;
+        m[2] = w2;
+        m[3] = w3;
+        m[4] = mulmod(w0, w1, R);
+        m[5] = mulmod(w2, w3, R);
+        m[6] = 1;
+        m[7] = mulmod(mulmod(m[4], m[5], R), w4, R);
+        m[8] = _neg(w4);
+{%- for e in custom_multipliers %}
+        m[{{ loop.index0 + 9 }}] = {{ e }};
+{%- endfor %}
+    }
+
+    /// alpha * prod_i (w_i(\zeta) + beta * k_i * \zeta + gamma) + alpha^2 * L_1(\zeta)
+    function _zScalar(uint256[] memory p, Challenges memory c) private pure returns (uint256 z) {
+        uint256 betaZeta = _mul(c.beta, c.zeta);
+        z = c.alpha;
+{%- for k in ks %}
+        z = _mul(z, _add(_add(p[P_W + {{ loop.index0 }}], _mul(K_{{ loop.index0 }}, betaZeta)), c.gamma));
+{%- endfor %}
+        z = _add(z, _mul(c.l1, c.alphaPows[2]));
+    }
+
+    /// alpha * z(\zeta\omega) * beta * prod_{i < 4} (w_i(\zeta) + beta * s_i(\zeta) + gamma)
+    function _sLastScalar(uint256[] memory p, Challenges memory c) private pure returns (uint256 s) {
+        s = _mul(_mul(c.alpha, p[P_Z_OMEGA]), c.beta);
+        for (uint256 i = 0; i < 4; i++) {
+            s = _mul(s, _add(_add(p[P_W + i], _mul(c.beta, p[P_S + i])), c.gamma));
+        }
+    }
+    /// w1 * (w1 - 1) * alpha^3 + w2 * (w2 - 1) * alpha^4 + w3 * (w3 - 1) * alpha^5
+    function _qbScalar(uint256[] memory p, uint256[17] memory a) private pure returns (uint256 s) {
+        uint256 w1 = p[P_W + 1];
+        uint256 w2 = p[P_W + 2];
+        uint256 w3 = p[P_W + 3];
+        s = _mul(_mul(w1, _sub(w1, 1)), a[3]);
+        s = _add(s, _mul(_mul(w2, _sub(w2, 1)), a[4]));
+        s = _add(s, _mul(_mul(w3, _sub(w3, 1)), a[5]));
+    }
+{%- if shuffle %}
+
+    /// Add the shuffle selectors to the r commitment.
+    function _shuffleCommitment(uint256[2] memory acc, uint256[] memory p, uint256[17] memory a, bytes memory vk)
+        private
+        view
+    {
+        uint256[6] memory coeffs = _shuffleCoefficients(p, a);
+        uint256[4] memory sels = _shuffleSelectors(p);
+        // the selectors are ordered as x, y, dxy, each of them for the bits 00, 01, 10, 11.
+        for (uint256 j = 0; j < 3; j++) {
+            for (uint256 b = 0; b < 4; b++) {
+                _ecMulAddVk(acc, vk, VK_SHUFFLE_PUBLIC_KEY + 4 * j + b, _mul(sels[b], coeffs[j]));
+                _ecMulAddVk(acc, vk, VK_SHUFFLE_GENERATOR + 4 * j + b, _mul(sels[b], coeffs[3 + j]));
+            }
+        }
+    }
+
+    /// The selectors of the bits 00, 01, 10, 11 of the witness selectors.
+    function _shuffleSelectors(uint256[] memory p) private pure returns (uint256[4] memory sels) {
+        uint256 s0 = p[P_W_SEL];
+        uint256 s1 = p[P_W_SEL + 1];
+        sels[0] = _sub(_add(_mul(_sub(1, s0), _sub(1, s1)), p[P_Q_ECC]), 1);
+        sels[1] = _mul(s0, _sub(1, s1));
+        sels[2] = _mul(_sub(1, s0), s1);
+        sels[3] = _mul(s0, s1);
+    }
+
+    /// The coefficients of the public key selectors (x, y, dxy), then of the generator selectors.
+    function _shuffleCoefficients(uint256[] memory p, uint256[17] memory a)
+        private
+        pure
+        returns (uint256[6] memory coeffs)
+    {
+        uint256 s2 = p[P_W_SEL + 2];
+        uint256 w0 = p[P_W];
+        uint256 w1 = p[P_W + 1];
+        uint256 w2 = p[P_W + 2];
+        uint256 w3 = p[P_W + 3];
+
+        coeffs[0] = _sub(_mul(_mul(a[11], EDWARDS_A), w0), _mul(a[10], w1));
+        coeffs[1] = _neg(_mul(s2, _add(_mul(a[10], w0), _mul(a[11], w1))));
+        coeffs[2] = _mul(_mul(w0, w1), _sub(_mul(a[10], p[P_W_OMEGA]), _mul(a[11], p[P_W_OMEGA + 1])));
+        coeffs[3] = _sub(_mul(_mul(a[13], EDWARDS_A), w2), _mul(a[12], w3));
+        coeffs[4] = _neg(_mul(s2, _add(_mul(a[12], w2), _mul(a[13], w3))));
+        coeffs[5] = _mul(_mul(w2, w3), _sub(_mul(a[12], p[P_W_OMEGA + 2]), _mul(a[13], p[P_W + 4])));
+    }
+{%- endif %}
+
+    /// Batch the openings at \zeta.
+    function _batchZeta(uint256[] memory p, bytes memory vk, Opening memory r, uint256 alpha)
+        private
+        view
+        returns (Opening memory o)
+    {
+        uint256 m = 1;
+        for (uint256 i = 0; i < 5; i++) {
+            _accumulate(o, p[P_CM_W + 2 * i], p[P_CM_W + 2 * i + 1], p[P_W + i], m);
+            m = _mul(m, alpha);
+        }
+        for (uint256 i = 0; i < 4; i++) {
+            _accumulateVk(o, vk, VK_S + i, p[P_S + i], m);
+            m = _mul(m, alpha);
+        }
+        _accumulateVk(o, vk, VK_PRK + 2, p[P_PRK_3], m);
+        m = _mul(m, alpha);
+        _accumulateVk(o, vk, VK_PRK + 3, p[P_PRK_4], m);
+        m = _mul(m, alpha);
+{%- if shuffle %}
+        _accumulateVk(o, vk, VK_Q_ECC, p[P_Q_ECC], m);
+        m = _mul(m, alpha);
+        for (uint256 i = 0; i < 3; i++) {
+            _accumulate(o, p[P_CM_W_SEL + 2 * i], p[P_CM_W_SEL + 2 * i + 1], p[P_W_SEL + i], m);
+            m = _mul(m, alpha);
+        }
+{%- endif %}
+        _accumulate(o, r.cm[0], r.cm[1], r.eval, m);
+    }
+
+    /// Batch the openings at \zeta\omega.
+    function _batchZetaOmega(Transcript memory t, uint256[] memory p, Challenges memory c)
+        private
+        view
+        returns (Opening memory o)
+    {
+        uint256 alpha = _batchChallenge(t, _mul(c.zeta, ROOT));
+        _accumulate(o, p[P_CM_Z], p[P_CM_Z + 1], p[P_Z_OMEGA], 1);
+        uint256 m = alpha;
+        for (uint256 i = 0; i < 3; i++) {
+            _accumulate(o, p[P_CM_W + 2 * i], p[P_CM_W + 2 * i + 1], p[P_W_OMEGA + i], m);
+            m = _mul(m, alpha);
+        }
+    }
+
+    function _batchChallenge(Transcript memory t, uint256 point) private pure returns (uint256) {
+        _append(t, PCS_BATCH_DOMAIN);
+        _append(t, R);
+        _append(t, MAX_DEGREE);
+        _append(t, point);
+        return _challenge(t);
+    }
+
+    /// Check both openings with the random combination of u, in a single pairing.
+    function _verifyOpenings(uint256[] memory p, Challenges memory c, Opening memory atZeta, Opening memory atZetaOmega)
+        private
+        view
+        returns (bool)
+    {
+        uint256[2] memory left = [p[P_OPENING_ZETA], p[P_OPENING_ZETA + 1]];
+        _ecMulAdd(left, p[P_OPENING_ZETA_OMEGA], p[P_OPENING_ZETA_OMEGA + 1], c.u);
+        uint256[2] memory right = _openingsRight(p, c, atZeta, atZetaOmega);
+
+        // e(left, [x]_2) * e(-right, [1]_2) == 1
+        uint256[12] memory input = [
+            left[0],
+            left[1],
+            G2_TAU_X1,
+            G2_TAU_X0,
+            G2_TAU_Y1,
+            G2_TAU_Y0,
+            right[0],
+            (Q - right[1]) % Q,
+            G2_X1,
+            G2_X0,
+            G2_Y1,
+            G2_Y0
+        ];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x08, input, 0x180, out, 0x20)
+        }
+        return ok && out[0] == 1;
+    }
+
+    /// cm + u * cm' + \zeta * W + u * \zeta\omega * W' - (eval + u * eval') * [1]_1
+    function _openingsRight(uint256[] memory p, Challenges memory c, Opening memory atZeta, Opening memory atZetaOmega)
+        private
+        view
+        returns (uint256[2] memory right)
+    {
+        uint256 uZetaOmega = _mul(c.u, _mul(c.zeta, ROOT));
+        right[0] = atZeta.cm[0];
+        right[1] = atZeta.cm[1];
+        _ecMulAdd(right, atZetaOmega.cm[0], atZetaOmega.cm[1], c.u);
+        _ecMulAdd(right, p[P_OPENING_ZETA], p[P_OPENING_ZETA + 1], c.zeta);
+        _ecMulAdd(right, p[P_OPENING_ZETA_OMEGA], p[P_OPENING_ZETA_OMEGA + 1], uZetaOmega);
+        _ecMulAdd(right, G1_X, G1_Y, _neg(_add(atZeta.eval, _mul(c.u, atZetaOmega.eval))));
+    }
+
+    function _accumulate(Opening memory o, uint256 x, uint256 y, uint256 eval, uint256 m) private view {
+        _ecMulAdd(o.cm, x, y, m);
+        o.eval = _add(o.eval, _mul(eval, m));
+    }
+
+    function _accumulateVk(Opening memory o, bytes memory vk, uint256 i, uint256 eval, uint256 m) private view {
+        _ecMulAddVk(o.cm, vk, i, m);
+        o.eval = _add(o.eval, _mul(eval, m));
+    }
+
+    function _ecMulAddVk(uint256[2] memory acc, bytes memory vk, uint256 i, uint256 s) private view {
+        _ecMulAdd(acc, _word(vk, 2 * i), _word(vk, 2 * i + 1), s);
+    }
+
+    /// acc += s * (x, y)
+    function _ecMulAdd(uint256[2] memory acc, uint256 x, uint256 y, uint256 s) private view {
+        uint256[3] memory mulInput = [x, y, s];
+        uint256[4] memory addInput;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x07, mulInput, 0x60, add(addInput, 0x40), 0x40)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+        addInput[0] = acc[0];
+        addInput[1] = acc[1];
+        assembly {
+            ok := staticcall(gas(), 0x06, addInput, 0x80, acc, 0x40)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+    }
+
+    function _append(Transcript memory t, uint256 v) private pure {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        assembly {
+            mstore(add(add(data, 0x20), len), v)
+        }
+        t.len = len + 32;
+    }
+
+    function _appendWords(Transcript memory t, uint256[] memory p, uint256 start, uint256 n) private pure {
+        for (uint256 i = start; i < start + n; i++) {
+            _append(t, p[i]);
+        }
+    }
+
+    function _appendByte(Transcript memory t, uint8 b) private pure {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        assembly {
+            mstore8(add(add(data, 0x20), len), b)
+        }
+        t.len = len + 1;
+    }
+
+    /// Hash the transcript into a challenge, and restart the transcript from the challenge.
+    function _challenge(Transcript memory t) private pure returns (uint256 c) {
+        bytes memory data = t.data;
+        uint256 len = t.len;
+        bytes32 h;
+        assembly {
+            h := keccak256(add(data, 0x20), len)
+        }
+        c = uint256(h) % R;
+        assembly {
+            mstore(add(data, 0x20), c)
+        }
+        t.len = 32;
+    }
+
+    function _word(bytes memory b, uint256 i) private pure returns (uint256 w) {
+        assembly {
+            w := mload(add(b, mul(add(i, 1), 0x20)))
+        }
+    }
+
+    function _add(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a, b, R);
+    }
+
+    function _sub(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a, R - b, R);
+    }
+
+    function _mul(uint256 a, uint256 b) private pure returns (uint256) {
+        return mulmod(a, b, R);
+    }
+
+    function _neg(uint256 a) private pure returns (uint256) {
+        return (R - a) % R;
+    }
+
+    function _pow5(uint256 a) private pure returns (uint256) {
+        uint256 a2 = mulmod(a, a, R);
+        return mulmod(mulmod(a2, a2, R), a, R);
+    }
+
+    function _pow(uint256 base, uint256 e) private view returns (uint256 r) {
+        uint256[6] memory input = [uint256(0x20), 0x20, 0x20, base, e, R];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x05, input, 0xc0, out, 0x20)
+        }
+        if (!ok) {
+            revert PrecompileFailed();
+        }
+        r = out[0];
+    }
+
+    function _inverse(uint256 a) private view returns (uint256) {
+        return _pow(a, R - 2);
+    }
+}
@@ -0,0 +1,12 @@
+// The `MontConfig` derive checks the `asm` feature of the crate using it.
+#![allow(unexpected_cfgs)]
+
+use ark_ff::fields::{Fp256, MontBackend, MontConfig};
+
+#[derive(MontConfig)]
+#[modulus = "52435875175126190479447740508185965837690552500527637822603658699938581184513"]
+#[generator = "7"]
+pub struct Bls12381FrConfig;
+
+/// The scalar field of BLS12-381.
+pub type Bls12381Fr = Fp256<MontBackend<Bls12381FrConfig, 4>>;
@@ -1,3 +1,5 @@
+/// The module for the AnemoiJive381 data structure.
+mod bls12_381;
 /// The module for the AnemoiJive254 data structure.
 mod bn254;
 /// The module for the MDS matrices.
@@ -12,6 +14,7 @@ pub use traces::*;
 mod tests;
 
 use ark_ff::PrimeField;
+pub use bls12_381::*;
 pub use bn254::*;
 
 use crate::anemoi::mds::{ApplicableMDSMatrix, MDSMatrix};
@@ -169,6 +172,72 @@ where
         trace
     }
 
+    /// Eval the Anemoi-Jive compression, which maps `2 * N` elements to one element,
+    /// as the sum of the input and the output of the permutation.
+    fn eval_jive(x: &[F; N], y: &[F; N]) -> F {
+        let sum_before_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
+
+        let mut x = *x;
+        let mut y = *y;
+        Self::anemoi_permutation(&mut x, &mut y);
+
+        let sum_after_perm: F = x.iter().sum::<F>() + y.iter().sum::<F>();
+        sum_before_perm + sum_after_perm
+    }
+
+    /// Eval the Anemoi-Jive compression and return the trace.
+    fn eval_jive_with_trace(x: &[F; N], y: &[F; N]) -> AnemoiJiveTrace<F, N, NUM_ROUNDS> {
+        let mds = MDSMatrix::<F, N>(Self::MDS_MATRIX);
+        let alpha_inv = Self::get_alpha_inv();
+
+        let input_x = *x;
+        let input_y = *y;
+        let mut x = *x;
+        let mut y = *y;
+
+        let mut intermediate_values_before_constant_additions =
+            ([[F::ZERO; N]; NUM_ROUNDS], [[F::ZERO; N]; NUM_ROUNDS]);
+        for r in 0..NUM_ROUNDS {
+            for i in 0..N {
+                x[i] += &Self::ROUND_KEYS_X[r][i];
+                y[i] += &Self::ROUND_KEYS_Y[r][i];
+            }
+            mds.permute_in_place(&mut x, &mut y);
+            for i in 0..N {
+                y[i] += &x[i];
+                x[i] += &y[i];
+            }
+            for i in 0..N {
+                x[i] -= &(Self::GENERATOR * y[i].square());
+                y[i] -= &x[i].pow(&alpha_inv);
+                x[i] += &(Self::GENERATOR * y[i].square() + Self::GENERATOR_INV);
+            }
+
+            intermediate_values_before_constant_additions.0[r] = x;
+            intermediate_values_before_constant_additions.1[r] = y;
+        }
+
+        mds.permute_in_place(&mut x, &mut y);
+        for i in 0..N {
+            y[i] += &x[i];
+            x[i] += &y[i];
+        }
+
+        let output = input_x.iter().sum::<F>()
+            + input_y.iter().sum::<F>()
+            + x.iter().sum::<F>()
+            + y.iter().sum::<F>();
+
+        AnemoiJiveTrace {
+            input_x,
+            input_y,
+            intermediate_values_before_constant_additions,
+            final_x: x,
+            final_y: y,
+            output,
+        }
+    }
+
     /// Eval the the Anemoi-Jive stream cipher.
     fn eval_stream_cipher(input: &[F], output_len: usize) -> Vec<F> {
         let mut input = input.to_vec();
@@ -1,12 +1,73 @@
 use ark_bn254::Fr;
-use ark_ff::{AdditiveGroup, Field, MontFp, One, Zero};
+use ark_ff::{AdditiveGroup, Field, MontFp, One, PrimeField, Zero};
+use num_bigint::BigUint;
 
 use crate::anemoi::{
+    bls12_381::AnemoiJive381,
     bn254::AnemoiJive254,
     mds::{ApplicableMDSMatrix, MDSMatrix},
     AnemoiJive, N_ANEMOI_ROUNDS,
 };
 
+/// The first and the second hundred digits of pi, from which the Anemoi specification
+/// derives the round keys.
+const PI_0: &str = "1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679";
+const PI_1: &str = "8214808651328230664709384460955058223172535940812848111745028410270193852110555964462294895493038196";
+
+/// Derive the constants of the instance from the field as in the Anemoi specification,
+/// and check them against the hardcoded ones.
+fn check_anemoi_constants<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>() {
+    // alpha is the smallest integer that `x^alpha` is a permutation of the field,
+    // the generator is the smallest generator of the multiplicative group.
+    let alpha = [3u64, 5, 7, 9, 11]
+        .into_iter()
+        .find(|a| {
+            F::from(*a).pow(P::get_alpha_inv()).pow([*a]) == F::from(*a) && {
+                let x = F::from(123456789u64);
+                x.pow(P::get_alpha_inv()).pow([*a]) == x
+            }
+        })
+        .unwrap();
+    assert_eq!(P::ALPHA as u64, alpha);
+    assert_eq!(P::GENERATOR, F::GENERATOR);
+
+    let g = P::GENERATOR;
+    let delta = g.inverse().unwrap();
+    assert_eq!(P::GENERATOR_INV, delta);
+    assert_eq!(P::GENERATOR_SQUARE_PLUS_ONE, g.square() + F::ONE);
+
+    let mds = MDSMatrix::<F, 2>::from_generator(&g);
+    assert_eq!(P::MDS_MATRIX, mds.0);
+
+    let pi_0 = F::from(BigUint::parse_bytes(PI_0.as_bytes(), 10).unwrap());
+    let pi_1 = F::from(BigUint::parse_bytes(PI_1.as_bytes(), 10).unwrap());
+    for r in 0..N_ANEMOI_ROUNDS {
+        let pi_0_r = pi_0.pow([r as u64]);
+        for i in 0..2 {
+            let pi_1_i = pi_1.pow([i as u64]);
+            let pow_alpha = (pi_0_r + pi_1_i).pow([alpha]);
+            assert_eq!(P::ROUND_KEYS_X[r][i], g * pi_0_r.square() + pow_alpha);
+            assert_eq!(
+                P::ROUND_KEYS_Y[r][i],
+                g * pi_1_i.square() + pow_alpha + delta
+            );
+        }
+
+        // The preprocessed round keys are the round keys after the linear layer.
+        let (x, y) = mds.permute(&P::ROUND_KEYS_X[r], &P::ROUND_KEYS_Y[r]);
+        for i in 0..2 {
+            assert_eq!(P::PREPROCESSED_ROUND_KEYS_X[r][i], x[i].double() + y[i]);
+            assert_eq!(P::PREPROCESSED_ROUND_KEYS_Y[r][i], x[i] + y[i]);
+        }
+    }
+}
+
+#[test]
+fn test_anemoi_constants() {
+    check_anemoi_constants::<Fr, AnemoiJive254>();
+    check_anemoi_constants::<crate::anemoi::Bls12381Fr, AnemoiJive381>();
+}
+
 #[test]
 fn test_anemoi_variable_length_hash() {
     type F = Fr;
@@ -182,3 +182,20 @@ impl<F: PrimeField, const N: usize, const NUM_ROUNDS: usize> ark_std::fmt::Debug
         Ok(())
     }
 }
+
+/// The structure for the trace of the Anemoi-Jive compression function.
+#[derive(Clone, Debug)]
+pub struct AnemoiJiveTrace<F: PrimeField, const N: usize, const NUM_ROUNDS: usize> {
+    /// The first half of the input.
+    pub input_x: [F; N],
+    /// The second half of the input.
+    pub input_y: [F; N],
+    /// The intermediate values of the permutation.
+    pub intermediate_values_before_constant_additions: ([[F; N]; NUM_ROUNDS], [[F; N]; NUM_ROUNDS]),
+    /// The first half of the state after the permutation.
+    pub final_x: [F; N],
+    /// The second half of the state after the permutation.
+    pub final_y: [F; N],
+    /// The output.
+    pub output: F,
+}
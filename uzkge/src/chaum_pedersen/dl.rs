@@ -1,5 +1,5 @@
 use ark_ec::{AffineRepr, CurveGroup};
-use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fr};
 use ark_ff::{BigInteger, PrimeField};
 use ark_std::{
     ops::Mul,
@@ -10,11 +10,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     anemoi::AnemoiJive,
+    chaum_pedersen::point_from_uncompress,
     errors::{Result, UzkgeError},
     poly_commit::pcs::ToBytes,
     utils::{
         serialization::{ark_deserialize, ark_serialize},
-        transcript::Transcript,
+        transcript::TranscriptScheme,
     },
 };
 
@@ -58,13 +59,8 @@ impl ChaumPedersenDLProof {
             return Err(UzkgeError::SerializationError);
         }
 
-        let ax = Fq::from_be_bytes_mod_order(&bytes[..32]);
-        let ay = Fq::from_be_bytes_mod_order(&bytes[32..64]);
-        let a = EdwardsAffine::new(ax, ay).into();
-
-        let bx = Fq::from_be_bytes_mod_order(&bytes[64..96]);
-        let by = Fq::from_be_bytes_mod_order(&bytes[96..128]);
-        let b = EdwardsAffine::new(bx, by).into();
+        let a = point_from_uncompress(&bytes[..64])?;
+        let b = point_from_uncompress(&bytes[64..128])?;
 
         let r = Fr::from_be_bytes_mod_order(&bytes[128..160]);
 
@@ -72,10 +68,10 @@ impl ChaumPedersenDLProof {
     }
 }
 
-pub fn prove<R: CryptoRng + RngCore>(
+pub fn prove<R: CryptoRng + RngCore, T: TranscriptScheme>(
     prng: &mut R,
     parameters: &ChaumPedersenDLParameters,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     witness: &Fr,
     c1: &EdwardsProjective,
     c2: &EdwardsProjective,
@@ -162,9 +158,9 @@ pub fn prove0<R: CryptoRng + RngCore, H: AnemoiJive<ark_bn254::Fr, 2, 14>>(
     Ok(ChaumPedersenDLProof { a, b, r })
 }
 
-pub fn verify(
+pub fn verify<T: TranscriptScheme>(
     parameters: &ChaumPedersenDLParameters,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     c1: &EdwardsProjective,
     c2: &EdwardsProjective,
     proof: &ChaumPedersenDLProof,
@@ -0,0 +1,255 @@
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::{
+    ops::Mul,
+    rand::{CryptoRng, RngCore},
+    UniformRand, Zero,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chaum_pedersen::point_from_uncompress,
+    errors::{Result, UzkgeError},
+    poly_commit::pcs::ToBytes,
+    utils::{
+        serialization::{ark_deserialize, ark_serialize},
+        transcript::TranscriptScheme,
+    },
+};
+
+/// The proof that `bases[i] * x = values[i]` for all `i` with the same secret `x`.
+///
+/// The statements are folded with a random challenge `rho` into the combined base
+/// `G = sum_i rho^i * bases[i]` and value `Y = sum_i rho^i * values[i]`, so that the proof
+/// is a DLEQ proof for `(bases[0], values[0])` and `(G, Y)`, of constant size: the
+/// commitments `a = bases[0] * omega` and `b = G * omega` and a single response.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct ChaumPedersenEqProof {
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub a: EdwardsProjective,
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub b: EdwardsProjective,
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub r: Fr,
+}
+
+impl ChaumPedersenEqProof {
+    pub fn to_uncompress(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        let aa = EdwardsAffine::from(self.a);
+        bytes.extend(aa.x.into_bigint().to_bytes_be());
+        bytes.extend(aa.y.into_bigint().to_bytes_be());
+
+        let ab = EdwardsAffine::from(self.b);
+        bytes.extend(ab.x.into_bigint().to_bytes_be());
+        bytes.extend(ab.y.into_bigint().to_bytes_be());
+
+        bytes.extend(self.r.into_bigint().to_bytes_be());
+
+        bytes
+    }
+
+    pub fn from_uncompress(bytes: &[u8]) -> core::result::Result<Self, UzkgeError> {
+        if bytes.len() != 160 {
+            return Err(UzkgeError::SerializationError);
+        }
+
+        let a = point_from_uncompress(&bytes[..64])?;
+        let b = point_from_uncompress(&bytes[64..128])?;
+
+        let r = Fr::from_be_bytes_mod_order(&bytes[128..160]);
+
+        Ok(Self { a, b, r })
+    }
+}
+
+/// Append the statement to the transcript and return the folding challenge `rho`.
+fn init_transcript<T: TranscriptScheme>(
+    transcript: &mut T,
+    bases: &[EdwardsProjective],
+    values: &[EdwardsProjective],
+) -> Result<Fr> {
+    if bases.is_empty() || bases.len() != values.len() {
+        return Err(UzkgeError::ParameterError);
+    }
+    // the first base fixes the secret, and the small order components of the points
+    // would not be bound by the folded statement.
+    if bases[0].is_zero() || !in_prime_order_subgroup(bases) || !in_prime_order_subgroup(values) {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    transcript.append_message(b"Chaum Pedersen", b"EQ");
+    transcript.append_u64(b"num bases", bases.len() as u64);
+    for (g, y) in bases.iter().zip(values.iter()) {
+        transcript.append_message(b"append commitment", &g.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &y.to_transcript_bytes());
+    }
+
+    Ok(transcript.get_challenge_field_elem(b"Chaum Pedersen Rho"))
+}
+
+/// Return true if all the points are in the prime order subgroup.
+pub(crate) fn in_prime_order_subgroup(points: &[EdwardsProjective]) -> bool {
+    EdwardsProjective::normalize_batch(points)
+        .iter()
+        .all(|p| p.is_in_correct_subgroup_assuming_on_curve())
+}
+
+fn powers(rho: Fr, n: usize) -> Vec<Fr> {
+    let mut rho_pow = Fr::from(1u64);
+    (0..n)
+        .map(|_| {
+            let r = rho_pow;
+            rho_pow *= rho;
+            r
+        })
+        .collect()
+}
+
+pub fn prove<R: CryptoRng + RngCore, T: TranscriptScheme>(
+    prng: &mut R,
+    transcript: &mut T,
+    witness: &Fr,
+    bases: &[EdwardsProjective],
+    values: &[EdwardsProjective],
+) -> Result<ChaumPedersenEqProof> {
+    let rho = init_transcript(transcript, bases, values)?;
+    for (g, y) in bases.iter().zip(values.iter()) {
+        assert_eq!(g.mul(witness), *y);
+    }
+
+    let rho_powers = powers(rho, bases.len());
+    let combined_base =
+        EdwardsProjective::msm(&EdwardsProjective::normalize_batch(bases), &rho_powers).unwrap();
+
+    let omega = Fr::rand(prng);
+
+    let a = bases[0].mul(&omega);
+    let b = combined_base.mul(&omega);
+    transcript.append_message(b"append commitment", &a.to_transcript_bytes());
+    transcript.append_message(b"append commitment", &b.to_transcript_bytes());
+
+    let c: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen C");
+
+    let r = omega + c * witness;
+
+    Ok(ChaumPedersenEqProof { a, b, r })
+}
+
+pub fn verify<T: TranscriptScheme>(
+    transcript: &mut T,
+    bases: &[EdwardsProjective],
+    values: &[EdwardsProjective],
+    proof: &ChaumPedersenEqProof,
+) -> Result<()> {
+    let rho = init_transcript(transcript, bases, values)?;
+    if !in_prime_order_subgroup(&[proof.a, proof.b]) {
+        return Err(UzkgeError::VerificationError);
+    }
+
+    transcript.append_message(b"append commitment", &proof.a.to_transcript_bytes());
+    transcript.append_message(b"append commitment", &proof.b.to_transcript_bytes());
+
+    let c: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen C");
+
+    // Check `bases[0] * r == a + values[0] * c` and `G * r == b + Y * c` at once,
+    // with a random linear combination, in one multi-scalar multiplication.
+    let mu: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen Batch");
+
+    let n = bases.len();
+    let mut points = Vec::with_capacity(2 * n + 4);
+    let mut scalars = Vec::with_capacity(2 * n + 4);
+    points.extend([bases[0], proof.a, values[0], proof.b]);
+    scalars.extend([proof.r, -Fr::from(1u64), -c, -mu]);
+    for ((g, y), rho_pow) in bases.iter().zip(values.iter()).zip(powers(rho, n)) {
+        points.extend([*g, *y]);
+        scalars.extend([mu * rho_pow * proof.r, -mu * rho_pow * c]);
+    }
+
+    let points = EdwardsProjective::normalize_batch(&points);
+    let sum = EdwardsProjective::msm(&points, &scalars).unwrap();
+    if sum != EdwardsProjective::default() {
+        return Err(UzkgeError::VerificationError);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use ark_ec::PrimeGroup;
+    use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use super::{prove, verify, ChaumPedersenEqProof};
+    use crate::{
+        errors::UzkgeError,
+        utils::transcript::{Transcript, TranscriptScheme},
+    };
+
+    /// The point `(0, -1)` of order 2.
+    pub(crate) fn small_order_point() -> EdwardsProjective {
+        EdwardsAffine::new_unchecked(Fq::from(0u64), -Fq::from(1u64)).into()
+    }
+
+    #[test]
+    fn test_chaum_pedersen_eq() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+
+        let witness = Fr::rand(&mut prng);
+        let mut bases = vec![EdwardsProjective::generator()];
+        bases.extend((0..52).map(|_| EdwardsProjective::rand(&mut prng)));
+        let values: Vec<_> = bases.iter().map(|g| *g * witness).collect();
+
+        let proof = prove(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &witness,
+            &bases,
+            &values,
+        )
+        .unwrap();
+        assert!(verify(&mut Transcript::new(b"Test"), &bases, &values, &proof).is_ok());
+
+        let bytes = proof.to_uncompress();
+        assert_eq!(bytes.len(), 160);
+        let decoded = ChaumPedersenEqProof::from_uncompress(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+
+        // a point off the curve or outside the prime-order subgroup fails to decode.
+        let mut off_curve = bytes.clone();
+        off_curve[63] ^= 1;
+        assert_eq!(
+            ChaumPedersenEqProof::from_uncompress(&off_curve),
+            Err(UzkgeError::DeserializationError)
+        );
+        let mut small_order = proof.clone();
+        small_order.b = small_order_point();
+        assert_eq!(
+            ChaumPedersenEqProof::from_uncompress(&small_order.to_uncompress()),
+            Err(UzkgeError::DeserializationError)
+        );
+
+        let mut wrong_values = values.clone();
+        wrong_values[7] = bases[7] * (witness + Fr::from(1u64));
+        assert!(verify(&mut Transcript::new(b"Test"), &bases, &wrong_values, &proof).is_err());
+        assert!(verify(
+            &mut Transcript::new(b"Test"),
+            &bases[1..],
+            &values[1..],
+            &proof
+        )
+        .is_err());
+
+        // a value with a small order component is rejected.
+        let mut wrong_values = values.clone();
+        wrong_values[7] += small_order_point();
+        assert_eq!(
+            verify(&mut Transcript::new(b"Test"), &bases, &wrong_values, &proof),
+            Err(UzkgeError::ParameterError)
+        );
+    }
+}
@@ -1 +1,25 @@
+use ark_ec::AffineRepr;
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq};
+use ark_ff::PrimeField;
+
+use crate::errors::UzkgeError;
+
 pub mod dl;
+pub mod eq;
+pub mod multiple;
+
+/// Decode a point from the 64 big-endian bytes of its coordinates `x || y`, and return
+/// [UzkgeError::DeserializationError] if the point is not on the curve or not in the
+/// prime-order subgroup.
+pub(crate) fn point_from_uncompress(bytes: &[u8]) -> Result<EdwardsProjective, UzkgeError> {
+    if bytes.len() != 64 {
+        return Err(UzkgeError::DeserializationError);
+    }
+    let x = Fq::from_be_bytes_mod_order(&bytes[..32]);
+    let y = Fq::from_be_bytes_mod_order(&bytes[32..]);
+    let point = EdwardsAffine::new_unchecked(x, y);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(UzkgeError::DeserializationError);
+    }
+    Ok(point.into_group())
+}
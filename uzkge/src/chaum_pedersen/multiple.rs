@@ -0,0 +1,295 @@
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::{
+    ops::Mul,
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chaum_pedersen::{
+        dl::ChaumPedersenDLParameters, eq::in_prime_order_subgroup, point_from_uncompress,
+    },
+    errors::{Result, UzkgeError},
+    poly_commit::pcs::ToBytes,
+    utils::{
+        serialization::{ark_deserialize, ark_serialize},
+        transcript::TranscriptScheme,
+    },
+};
+
+/// The aggregated proof of many independent DLEQ statements
+/// `parameters[j].g * x_j = c1[j]` and `parameters[j].h * x_j = c2[j]`.
+/// All the statements share one challenge and are verified with one
+/// multi-scalar multiplication.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct ChaumPedersenMultipleProof {
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub a: Vec<EdwardsProjective>,
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub b: Vec<EdwardsProjective>,
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub r: Vec<Fr>,
+}
+
+impl ChaumPedersenMultipleProof {
+    pub fn to_uncompress(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        for ((a, b), r) in self.a.iter().zip(self.b.iter()).zip(self.r.iter()) {
+            let aa = EdwardsAffine::from(*a);
+            bytes.extend(aa.x.into_bigint().to_bytes_be());
+            bytes.extend(aa.y.into_bigint().to_bytes_be());
+
+            let ab = EdwardsAffine::from(*b);
+            bytes.extend(ab.x.into_bigint().to_bytes_be());
+            bytes.extend(ab.y.into_bigint().to_bytes_be());
+
+            bytes.extend(r.into_bigint().to_bytes_be());
+        }
+
+        bytes
+    }
+
+    pub fn from_uncompress(bytes: &[u8]) -> core::result::Result<Self, UzkgeError> {
+        if !bytes.len().is_multiple_of(160) {
+            return Err(UzkgeError::SerializationError);
+        }
+
+        let mut proof = Self::default();
+        for chunk in bytes.chunks(160) {
+            proof.a.push(point_from_uncompress(&chunk[..64])?);
+            proof.b.push(point_from_uncompress(&chunk[64..128])?);
+
+            proof.r.push(Fr::from_be_bytes_mod_order(&chunk[128..160]));
+        }
+
+        Ok(proof)
+    }
+}
+
+fn init_transcript<T: TranscriptScheme>(
+    transcript: &mut T,
+    parameters: &[ChaumPedersenDLParameters],
+    c1: &[EdwardsProjective],
+    c2: &[EdwardsProjective],
+) -> Result<()> {
+    if parameters.is_empty() || parameters.len() != c1.len() || parameters.len() != c2.len() {
+        return Err(UzkgeError::ParameterError);
+    }
+    // the small order components of the points would not be bound by the batched check.
+    let bases: Vec<EdwardsProjective> = parameters.iter().flat_map(|p| [p.g, p.h]).collect();
+    if !in_prime_order_subgroup(&bases)
+        || !in_prime_order_subgroup(c1)
+        || !in_prime_order_subgroup(c2)
+    {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    transcript.append_message(b"Chaum Pedersen", b"Multiple");
+    transcript.append_u64(b"num statements", parameters.len() as u64);
+    for ((p, c1), c2) in parameters.iter().zip(c1.iter()).zip(c2.iter()) {
+        transcript.append_message(b"append commitment", &p.g.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &p.h.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &c1.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &c2.to_transcript_bytes());
+    }
+
+    Ok(())
+}
+
+pub fn prove<R: CryptoRng + RngCore, T: TranscriptScheme>(
+    prng: &mut R,
+    parameters: &[ChaumPedersenDLParameters],
+    transcript: &mut T,
+    witnesses: &[Fr],
+    c1: &[EdwardsProjective],
+    c2: &[EdwardsProjective],
+) -> Result<ChaumPedersenMultipleProof> {
+    init_transcript(transcript, parameters, c1, c2)?;
+    if witnesses.len() != parameters.len() {
+        return Err(UzkgeError::ParameterError);
+    }
+
+    let mut omegas = Vec::with_capacity(parameters.len());
+    let mut proof = ChaumPedersenMultipleProof::default();
+    for (((p, w), c1), c2) in parameters
+        .iter()
+        .zip(witnesses.iter())
+        .zip(c1.iter())
+        .zip(c2.iter())
+    {
+        assert_eq!(p.g.mul(w), *c1);
+        assert_eq!(p.h.mul(w), *c2);
+
+        let omega = Fr::rand(prng);
+        let a = p.g.mul(&omega);
+        let b = p.h.mul(&omega);
+        transcript.append_message(b"append commitment", &a.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &b.to_transcript_bytes());
+
+        omegas.push(omega);
+        proof.a.push(a);
+        proof.b.push(b);
+    }
+
+    let c: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen C");
+
+    proof.r = omegas
+        .iter()
+        .zip(witnesses.iter())
+        .map(|(omega, w)| *omega + c * w)
+        .collect();
+
+    Ok(proof)
+}
+
+pub fn verify<T: TranscriptScheme>(
+    parameters: &[ChaumPedersenDLParameters],
+    transcript: &mut T,
+    c1: &[EdwardsProjective],
+    c2: &[EdwardsProjective],
+    proof: &ChaumPedersenMultipleProof,
+) -> Result<()> {
+    init_transcript(transcript, parameters, c1, c2)?;
+    let n = parameters.len();
+    if proof.a.len() != n || proof.b.len() != n || proof.r.len() != n {
+        return Err(UzkgeError::VerificationError);
+    }
+    if !in_prime_order_subgroup(&proof.a) || !in_prime_order_subgroup(&proof.b) {
+        return Err(UzkgeError::VerificationError);
+    }
+
+    for (a, b) in proof.a.iter().zip(proof.b.iter()) {
+        transcript.append_message(b"append commitment", &a.to_transcript_bytes());
+        transcript.append_message(b"append commitment", &b.to_transcript_bytes());
+    }
+
+    let c: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen C");
+
+    // Check `g_j * r_j == a_j + c1_j * c` and `h_j * r_j == b_j + c2_j * c`
+    // for all `j` at once, with a random linear combination.
+    let rho: Fr = transcript.get_challenge_field_elem(b"Chaum Pedersen Batch");
+
+    let mut points = Vec::with_capacity(6 * n);
+    let mut scalars = Vec::with_capacity(6 * n);
+    let mut rho_pow = Fr::from(1u64);
+    for j in 0..n {
+        let p = &parameters[j];
+        let rho_next = rho_pow * rho;
+
+        points.extend([p.g, proof.a[j], c1[j]]);
+        scalars.extend([rho_pow * proof.r[j], -rho_pow, -rho_pow * c]);
+
+        points.extend([p.h, proof.b[j], c2[j]]);
+        scalars.extend([rho_next * proof.r[j], -rho_next, -rho_next * c]);
+
+        rho_pow = rho_next * rho;
+    }
+
+    let points = EdwardsProjective::normalize_batch(&points);
+    let sum = EdwardsProjective::msm(&points, &scalars).unwrap();
+    if sum != EdwardsProjective::default() {
+        return Err(UzkgeError::VerificationError);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::PrimeGroup;
+    use ark_ed_on_bn254::{EdwardsProjective, Fr};
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use super::{prove, verify, ChaumPedersenMultipleProof};
+    use crate::{
+        chaum_pedersen::{dl::ChaumPedersenDLParameters, eq::test::small_order_point},
+        errors::UzkgeError,
+        utils::transcript::{Transcript, TranscriptScheme},
+    };
+
+    #[test]
+    fn test_chaum_pedersen_multiple() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+
+        let n = 10;
+        let parameters: Vec<_> = (0..n)
+            .map(|_| ChaumPedersenDLParameters {
+                g: EdwardsProjective::rand(&mut prng),
+                h: EdwardsProjective::generator(),
+            })
+            .collect();
+        let witnesses: Vec<_> = (0..n).map(|_| Fr::rand(&mut prng)).collect();
+        let c1: Vec<_> = parameters
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(p, w)| p.g * w)
+            .collect();
+        let c2: Vec<_> = parameters
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(p, w)| p.h * w)
+            .collect();
+
+        let proof = prove(
+            &mut prng,
+            &parameters,
+            &mut Transcript::new(b"Test"),
+            &witnesses,
+            &c1,
+            &c2,
+        )
+        .unwrap();
+        assert!(verify(&parameters, &mut Transcript::new(b"Test"), &c1, &c2, &proof).is_ok());
+
+        let decoded = ChaumPedersenMultipleProof::from_uncompress(&proof.to_uncompress()).unwrap();
+        assert_eq!(decoded, proof);
+
+        let mut off_curve = proof.to_uncompress();
+        off_curve[160 + 31] ^= 1;
+        assert_eq!(
+            ChaumPedersenMultipleProof::from_uncompress(&off_curve),
+            Err(UzkgeError::DeserializationError)
+        );
+
+        let mut wrong_c2 = c2.clone();
+        wrong_c2[3] = c2[4];
+        assert!(verify(
+            &parameters,
+            &mut Transcript::new(b"Test"),
+            &c1,
+            &wrong_c2,
+            &proof
+        )
+        .is_err());
+
+        // a statement with a small order component is rejected.
+        let mut wrong_c1 = c1.clone();
+        wrong_c1[2] += small_order_point();
+        assert_eq!(
+            verify(
+                &parameters,
+                &mut Transcript::new(b"Test"),
+                &wrong_c1,
+                &c2,
+                &proof
+            ),
+            Err(UzkgeError::ParameterError)
+        );
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.r.swap(0, 1);
+        assert!(verify(
+            &parameters,
+            &mut Transcript::new(b"Test"),
+            &c1,
+            &c2,
+            &wrong_proof
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use ark_ec::{AffineRepr, CurveGroup, PrimeGroup};
+use ark_ed_on_bn254::{EdwardsProjective, Fq, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::{
+    ops::Mul,
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    errors::{Result, UzkgeError},
+    utils::serialization::{ark_deserialize, ark_serialize},
+};
+
+/// The secret key of EdDSA over BabyJubjub.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct EdDSASecretKey(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")] pub Fr,
+);
+
+/// The public key of EdDSA over BabyJubjub, i.e., `sk * G`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct EdDSAPublicKey(
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub  EdwardsProjective,
+);
+
+/// The EdDSA signature `(R, s)` over BabyJubjub.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct EdDSASignature {
+    /// The commitment of the nonce, `R = r * G`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub r: EdwardsProjective,
+    /// The response, `s = r + c * sk`.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub s: Fr,
+}
+
+/// Compute the challenge `H(R, A, msg)` of the signature, the Anemoi output is
+/// kept in the base field so that the gadget can recompute it.
+pub fn eddsa_challenge<H: AnemoiJive<Fq, 2, N_ANEMOI_ROUNDS>>(
+    r: &EdwardsProjective,
+    pk: &EdDSAPublicKey,
+    msg: &[Fq],
+) -> Fq {
+    let mut input = vec![];
+    let (x, y) = r.into_affine().xy().unwrap_or_default();
+    input.push(x);
+    input.push(y);
+
+    let (x, y) = pk.0.into_affine().xy().unwrap_or_default();
+    input.push(x);
+    input.push(y);
+
+    input.extend_from_slice(msg);
+
+    H::eval_variable_length_hash(&input)
+}
+
+impl EdDSASecretKey {
+    /// Sample a random secret key.
+    pub fn rand<R: CryptoRng + RngCore>(prng: &mut R) -> Self {
+        Self(Fr::rand(prng))
+    }
+
+    /// Compute the public key.
+    pub fn public_key(&self) -> EdDSAPublicKey {
+        EdDSAPublicKey(EdwardsProjective::generator().mul(&self.0))
+    }
+
+    /// Sign the message, the nonce is derived deterministically from the secret key and the message.
+    pub fn sign<H: AnemoiJive<Fq, 2, N_ANEMOI_ROUNDS>>(&self, msg: &[Fq]) -> EdDSASignature {
+        // The base field is about 8 times larger than the scalar field, so the nonce
+        // is reduced from two hash outputs to avoid a biased nonce.
+        let mut input = vec![Fq::from_le_bytes_mod_order(
+            &self.0.into_bigint().to_bytes_le(),
+        )];
+        input.extend_from_slice(msg);
+        let h1 = H::eval_variable_length_hash(&input);
+        let h2 = H::eval_variable_length_hash(&[h1]);
+
+        let mut bytes = h1.into_bigint().to_bytes_le();
+        bytes.extend(h2.into_bigint().to_bytes_le());
+        let nonce = Fr::from_le_bytes_mod_order(&bytes);
+
+        let r = EdwardsProjective::generator().mul(&nonce);
+        let c = eddsa_challenge::<H>(&r, &self.public_key(), msg);
+        let c: Fr = Fr::from_be_bytes_mod_order(&c.into_bigint().to_bytes_be());
+
+        EdDSASignature {
+            r,
+            s: nonce + c * self.0,
+        }
+    }
+}
+
+impl EdDSAPublicKey {
+    /// Verify the signature of the message.
+    pub fn verify<H: AnemoiJive<Fq, 2, N_ANEMOI_ROUNDS>>(
+        &self,
+        msg: &[Fq],
+        signature: &EdDSASignature,
+    ) -> Result<()> {
+        if !self
+            .0
+            .into_affine()
+            .is_in_correct_subgroup_assuming_on_curve()
+            || !signature
+                .r
+                .into_affine()
+                .is_in_correct_subgroup_assuming_on_curve()
+        {
+            return Err(UzkgeError::VerificationError);
+        }
+
+        let c = eddsa_challenge::<H>(&signature.r, self, msg);
+        let c: Fr = Fr::from_be_bytes_mod_order(&c.into_bigint().to_bytes_be());
+
+        if EdwardsProjective::generator().mul(&signature.s) != signature.r + self.0.mul(&c) {
+            return Err(UzkgeError::VerificationError);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ed_on_bn254::{Fq, Fr};
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        anemoi::AnemoiJive254,
+        eddsa::{EdDSASecretKey, EdDSASignature},
+    };
+
+    #[test]
+    fn test_eddsa() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+
+        let sk = EdDSASecretKey::rand(&mut prng);
+        let pk = sk.public_key();
+        let msg = (0..3).map(|_| Fq::rand(&mut prng)).collect::<Vec<_>>();
+
+        let signature = sk.sign::<AnemoiJive254>(&msg);
+        assert_eq!(signature, sk.sign::<AnemoiJive254>(&msg));
+        pk.verify::<AnemoiJive254>(&msg, &signature).unwrap();
+
+        let bytes = bincode::serialize(&signature).unwrap();
+        let decoded: EdDSASignature = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, signature);
+
+        // wrong message
+        let mut wrong_msg = msg.clone();
+        wrong_msg[0] += Fq::from(1u64);
+        assert!(pk.verify::<AnemoiJive254>(&wrong_msg, &signature).is_err());
+
+        // wrong public key
+        let other_pk = EdDSASecretKey::rand(&mut prng).public_key();
+        assert!(other_pk.verify::<AnemoiJive254>(&msg, &signature).is_err());
+
+        // wrong response
+        let mut wrong_signature = signature;
+        wrong_signature.s += Fr::from(1u64);
+        assert!(pk.verify::<AnemoiJive254>(&msg, &wrong_signature).is_err());
+    }
+}
@@ -15,6 +15,8 @@ pub enum UzkgeError {
     MissingSRSError,
     /// Params: Could not preprocess verifier.
     VerifierParamsError,
+    /// Params: The SRS is not made of the successive powers of the same tau.
+    InvalidSRSError,
     /// PolyComScheme: Cannot compute the proof as sumcheck fails.
     PCSProveEvalError,
     /// PolyComScheme: The degree of the polynomial is higher than the maximum supported.
@@ -39,6 +41,15 @@ pub enum UzkgeError {
     ProofError,
     /// Plonk: Verification error.
     VerificationError,
+    /// Plonk: Batch verification failed at the proof of index {0}.
+    BatchVerificationError(usize),
+    /// Plonk: A lookup query is not in the lookup table.
+    LookupError,
+    /// Plonk: The params commit {0} custom selectors, but the registered custom gates do not
+    /// match them. Custom gates are not serialized, register them again after loading.
+    CustomGateMismatch(usize),
+    /// Plonk: The prover exceeded the memory budget of {0} bytes.
+    MemoryBudgetExceeded(usize),
     /// Plonk: {0}
     Message(String),
 }
@@ -1,7 +1,16 @@
-use ark_bn254::{Fr, G1Projective};
+use ark_bn254::{Fr, G1Projective, G2Projective};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 use ark_std::collections::BTreeMap;
 use lazy_static::lazy_static;
+use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
 
 use crate::{
     errors::UzkgeError,
@@ -15,6 +24,9 @@ use crate::{
 #[cfg(feature = "gen")]
 pub mod solidity;
 
+/// Module for loading the SRS from Powers-of-Tau files.
+pub mod ptau;
+
 #[cfg(not(feature = "no_vk"))]
 /// The common part of the verifier parameters.
 pub static VERIFIER_COMMON_PARAMS: Option<&'static [u8]> =
@@ -141,20 +153,159 @@ impl From<&ProverParams> for VerifierParams {
     }
 }
 
+/// The largest circuit size that the embedded SRS supports.
+pub const EMBEDDED_SRS_SIZE: usize = 16384;
+
+/// The largest circuit size that the parameters can be loaded for, e.g., the outer circuit
+/// of `TurboCS::verify_plonk_proofs` for two proofs, with about 2^22 gates for every proof.
+pub const MAX_SRS_SIZE: usize = 1 << 23;
+
+/// The file name of the full SRS in the parameters directory.
+pub const FULL_SRS_FILE: &str = "srs-full.bin";
+
+lazy_static! {
+    /// The directory with the full SRS, where the derived Lagrange bases are cached.
+    static ref PARAMS_DIRECTORY: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+/// Set the directory for the circuits larger than `EMBEDDED_SRS_SIZE`.
+///
+/// The directory should contain the full SRS `srs-full.bin`, in the format of
+/// `KZGCommitmentSchemeBN254::to_unchecked_bytes`, with at least `size + 3` powers,
+/// e.g., stored by `store_full_srs` from the SRS of `ptau::load_ptau`.
+/// The SRS is checked to be a valid sequence of powers every time it is loaded, and the
+/// Lagrange bases are derived from it at the first use and cached in the directory.
+pub fn set_params_directory<P: AsRef<Path>>(directory: P) {
+    *PARAMS_DIRECTORY.write().unwrap() = Some(directory.as_ref().to_path_buf());
+}
+
+/// Store the full SRS into the parameters directory.
+pub fn store_full_srs(srs: &KZGCommitmentSchemeBN254) -> Result<(), UzkgeError> {
+    let path = params_file(FULL_SRS_FILE)?;
+    write_atomic(&path, &srs.to_unchecked_bytes()?)
+}
+
+/// Write the file through a temporary file in the same directory and rename it, so that
+/// a concurrent reader or an interrupted write never leaves a truncated file at `path`.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), UzkgeError> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, bytes)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+            UzkgeError::SerializationError
+        })
+}
+
+/// The parameters of the size are derived from the full SRS, instead of the embedded one.
+fn use_full_srs(size: usize) -> bool {
+    size > EMBEDDED_SRS_SIZE || SRS.is_none()
+}
+
+fn params_file(name: &str) -> Result<PathBuf, UzkgeError> {
+    let directory = PARAMS_DIRECTORY.read().unwrap();
+    let directory = directory.as_ref().ok_or(UzkgeError::MissingSRSError)?;
+    Ok(directory.join(name))
+}
+
+/// Load the first `size + 3` powers of the full SRS.
+fn load_full_srs_params(size: usize) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let bytes = fs::read(params_file(FULL_SRS_FILE)?).map_err(|_| UzkgeError::MissingSRSError)?;
+    let len_1 = size + 3;
+
+    let mut reader = &bytes[..];
+    let mut lens = [0u32; 2];
+    for len in lens.iter_mut() {
+        let mut buf = [0u8; 4];
+        reader
+            .read_exact(&mut buf)
+            .map_err(|_| UzkgeError::DeserializationError)?;
+        *len = u32::from_le_bytes(buf);
+    }
+    if (lens[0] as usize) < len_1 || lens[1] < 2 {
+        return Err(UzkgeError::DegreeError);
+    }
+
+    let n_1 = G1Projective::default().serialized_size(Compress::No);
+    let mut public_parameter_group_1 = Vec::with_capacity(len_1);
+    for _ in 0..len_1 {
+        let g1 = G1Projective::deserialize_with_mode(&mut reader, Compress::No, Validate::Yes)
+            .map_err(|_| UzkgeError::DeserializationError)?;
+        public_parameter_group_1.push(g1);
+    }
+
+    let mut reader = n_1
+        .checked_mul(lens[0] as usize)
+        .and_then(|n| n.checked_add(8))
+        .and_then(|offset| bytes.get(offset..))
+        .ok_or(UzkgeError::DeserializationError)?;
+    let mut public_parameter_group_2 = Vec::with_capacity(lens[1] as usize);
+    for _ in 0..lens[1] {
+        let g2 = G2Projective::deserialize_with_mode(&mut reader, Compress::No, Validate::Yes)
+            .map_err(|_| UzkgeError::DeserializationError)?;
+        public_parameter_group_2.push(g2);
+    }
+
+    // the file is from a directory set by the user, so the powers are checked at every load,
+    // with the randomness derived from the whole file.
+    let srs = KZGCommitmentSchemeBN254 {
+        public_parameter_group_1,
+        public_parameter_group_2,
+    };
+    let seed: [u8; 32] = Keccak256::digest(&bytes).into();
+    ptau::check_srs(&srs, &mut ChaChaRng::from_seed(seed))?;
+    Ok(srs)
+}
+
+/// Load the Lagrange basis from the cache, or derive it from the full SRS and cache it.
+fn load_cached_lagrange_params(size: usize) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let path = params_file(&format!("lagrange-srs-{}.bin", size))?;
+    if let Ok(bytes) = fs::read(&path) {
+        let lagrange = KZGCommitmentSchemeBN254::from_unchecked_bytes(&bytes)?;
+        // a cache of another size, e.g., copied from another directory, is rejected.
+        if lagrange.public_parameter_group_1.len() != size {
+            return Err(UzkgeError::DeserializationError);
+        }
+        return Ok(lagrange);
+    }
+
+    let srs = load_full_srs_params(size)?;
+    let lagrange = ptau::compute_lagrange_basis(&srs, size)?;
+    // a failed cache only costs the time to derive it again.
+    let _ = write_atomic(&path, &lagrange.to_unchecked_bytes()?);
+    Ok(lagrange)
+}
+
 pub fn load_lagrange_params(size: usize) -> Option<KZGCommitmentSchemeBN254> {
+    if use_full_srs(size) {
+        if size > MAX_SRS_SIZE || !size.is_power_of_two() {
+            return None;
+        }
+        return load_cached_lagrange_params(size).ok();
+    }
+
     match LAGRANGE_BASES.get(&size) {
         None => None,
-        Some(bytes) => KZGCommitmentSchemeBN254::from_unchecked_bytes(&bytes).ok(),
+        Some(bytes) => KZGCommitmentSchemeBN254::from_unchecked_bytes(bytes).ok(),
     }
 }
 
 pub fn load_srs_params(size: usize) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    if size > MAX_SRS_SIZE {
+        return Err(UzkgeError::ParameterError);
+    }
+    if use_full_srs(size) {
+        return load_full_srs_params(size);
+    }
+
     let srs = SRS.ok_or(UzkgeError::MissingSRSError)?;
 
     let KZGCommitmentSchemeBN254 {
         public_parameter_group_1,
         public_parameter_group_2,
-    } = KZGCommitmentSchemeBN254::from_unchecked_bytes(&srs)
+    } = KZGCommitmentSchemeBN254::from_unchecked_bytes(srs)
         .map_err(|_| UzkgeError::DeserializationError)?;
 
     let mut new_group_1 = vec![G1Projective::default(); core::cmp::max(size + 3, 2051)];
@@ -172,12 +323,102 @@ pub fn load_srs_params(size: usize) -> Result<KZGCommitmentSchemeBN254, UzkgeErr
         new_group_1[16384..16387].copy_from_slice(&public_parameter_group_1[2057..2060]);
     }
 
-    if size > 16384 {
-        return Err(UzkgeError::ParameterError);
-    }
-
     Ok(KZGCommitmentSchemeBN254 {
         public_parameter_group_2,
         public_parameter_group_1: new_group_1,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{G1Projective, G2Projective};
+    use ark_ec::PrimeGroup;
+    use rand_chacha::{rand_core::SeedableRng, ChaChaRng};
+
+    use crate::{
+        errors::UzkgeError,
+        gen_params::{
+            load_lagrange_params, load_srs_params, set_params_directory, store_full_srs,
+            FULL_SRS_FILE, MAX_SRS_SIZE, SRS,
+        },
+        poly_commit::kzg_poly_commitment::KZGCommitmentSchemeBN254,
+    };
+
+    #[test]
+    fn test_params_directory() {
+        let size = 32768;
+        if SRS.is_some() {
+            assert_eq!(
+                load_srs_params(size).unwrap_err(),
+                UzkgeError::MissingSRSError
+            );
+        }
+
+        let directory = std::env::temp_dir().join(format!("uzkge-params-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        set_params_directory(&directory);
+
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let mut srs = KZGCommitmentSchemeBN254::new(size + 10, &mut prng);
+
+        // the powers of the full SRS are checked when loading it.
+        srs.public_parameter_group_1[7] += G1Projective::generator();
+        store_full_srs(&srs).unwrap();
+        assert_eq!(
+            load_srs_params(size).unwrap_err(),
+            UzkgeError::InvalidSRSError
+        );
+        srs.public_parameter_group_1[7] -= G1Projective::generator();
+        store_full_srs(&srs).unwrap();
+
+        let trimmed = load_srs_params(size).unwrap();
+        assert_eq!(trimmed.public_parameter_group_1.len(), size + 3);
+        assert_eq!(trimmed.public_parameter_group_2.len(), 2);
+        assert_eq!(
+            load_srs_params(size * 2).unwrap_err(),
+            UzkgeError::DegreeError
+        );
+        assert_eq!(
+            load_srs_params(MAX_SRS_SIZE * 2).unwrap_err(),
+            UzkgeError::ParameterError
+        );
+
+        // a cached Lagrange basis of another size is rejected.
+        let cache_path = directory.join(format!("lagrange-srs-{}.bin", size));
+        let wrong_cache = KZGCommitmentSchemeBN254 {
+            public_parameter_group_1: vec![G1Projective::generator(); 4],
+            public_parameter_group_2: vec![G2Projective::generator(); 2],
+        };
+        std::fs::write(&cache_path, wrong_cache.to_unchecked_bytes().unwrap()).unwrap();
+        assert!(load_lagrange_params(size).is_none());
+
+        // the cached Lagrange basis is loaded without deriving it again.
+        let cached = KZGCommitmentSchemeBN254 {
+            public_parameter_group_1: vec![G1Projective::generator(); size],
+            public_parameter_group_2: vec![G2Projective::generator(); 2],
+        };
+        std::fs::write(&cache_path, cached.to_unchecked_bytes().unwrap()).unwrap();
+        assert_eq!(load_lagrange_params(size), Some(cached));
+        assert!(load_lagrange_params(size + 1).is_none());
+
+        // a truncated full SRS is an error, not a panic.
+        let srs_path = directory.join(FULL_SRS_FILE);
+        let bytes = std::fs::read(&srs_path).unwrap();
+        std::fs::write(&srs_path, &bytes[..bytes.len() - 100]).unwrap();
+        assert_eq!(
+            load_srs_params(size).unwrap_err(),
+            UzkgeError::DeserializationError
+        );
+        std::fs::write(&srs_path, &bytes[..20]).unwrap();
+        assert!(load_srs_params(size).is_err());
+
+        // the files are written without leaving temporary files behind.
+        assert!(std::fs::read_dir(&directory).unwrap().all(|entry| !entry
+            .unwrap()
+            .path()
+            .to_string_lossy()
+            .ends_with(".tmp")));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,557 @@
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, PrimeGroup, VariableBaseMSM};
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use ark_poly::EvaluationDomain;
+use ark_std::{
+    cfg_into_iter,
+    rand::{CryptoRng, RngCore},
+    UniformRand, Zero,
+};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::{iter::IntoParallelIterator, prelude::ParallelIterator};
+
+use crate::{
+    errors::UzkgeError,
+    poly_commit::{field_polynomial::FpPolynomial, kzg_poly_commitment::KZGCommitmentSchemeBN254},
+};
+
+/// The magic number of the snarkjs `.ptau` files.
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+
+/// The size of the hash at the beginning of the raw Perpetual Powers of Tau files.
+const PPOT_HASH_SIZE: u64 = 64;
+
+/// The encoding of the points in the raw Perpetual Powers of Tau files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PpotEncoding {
+    /// The `challenge` files, with uncompressed points.
+    Uncompressed,
+    /// The `response` files, with compressed points.
+    Compressed,
+}
+
+/// Read the SRS of degree `max_degree` from a snarkjs `.ptau` file, and check it.
+pub fn load_ptau<P: AsRef<Path>, R: CryptoRng + RngCore>(
+    path: P,
+    max_degree: usize,
+    prng: &mut R,
+) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let file = File::open(path).map_err(|_| UzkgeError::MissingSRSError)?;
+    let srs = read_ptau(&mut BufReader::new(file), max_degree)?;
+    check_srs(&srs, prng)?;
+    Ok(srs)
+}
+
+/// Read the SRS of degree `max_degree` from a raw Perpetual Powers of Tau file of
+/// 2^`power` powers, e.g., `challenge_0072`, and check it.
+pub fn load_ppot<P: AsRef<Path>, R: CryptoRng + RngCore>(
+    path: P,
+    power: usize,
+    encoding: PpotEncoding,
+    max_degree: usize,
+    prng: &mut R,
+) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let file = File::open(path).map_err(|_| UzkgeError::MissingSRSError)?;
+    let srs = read_ppot(&mut BufReader::new(file), power, encoding, max_degree)?;
+    check_srs(&srs, prng)?;
+    Ok(srs)
+}
+
+/// Read the SRS of degree `max_degree` from a snarkjs `.ptau` file without checking it.
+///
+/// The file consists of sections, the header section (1) has the prime and the power,
+/// the tau G1 section (2) and the tau G2 section (3) have the points in Montgomery form.
+pub fn read_ptau<R: Read + Seek>(
+    reader: &mut R,
+    max_degree: usize,
+) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let mut magic = [0u8; 4];
+    read_exact(reader, &mut magic)?;
+    if &magic != PTAU_MAGIC {
+        return Err(UzkgeError::DeserializationError);
+    }
+    let _version = read_u32(reader)?;
+    let n_sections = read_u32(reader)?;
+
+    let mut sections = [None; 4];
+    for _ in 0..n_sections {
+        let section_type = read_u32(reader)? as usize;
+        let section_size = read_u64(reader)?;
+        let position = reader
+            .stream_position()
+            .map_err(|_| UzkgeError::DeserializationError)?;
+        if (1..=3).contains(&section_type) {
+            sections[section_type] = Some(position);
+        }
+        let end = position
+            .checked_add(section_size)
+            .ok_or(UzkgeError::DeserializationError)?;
+        seek(reader, end)?;
+    }
+    let [_, Some(header), Some(tau_g1), Some(tau_g2)] = sections else {
+        return Err(UzkgeError::DeserializationError);
+    };
+
+    seek(reader, header)?;
+    // the base field of BN254 is of 32 bytes, checked before allocating the buffer.
+    let n8 = read_u32(reader)? as usize;
+    if n8 != 32 {
+        return Err(UzkgeError::DeserializationError);
+    }
+    let mut q = vec![0u8; n8];
+    read_exact(reader, &mut q)?;
+    if q != Fq::MODULUS.to_bytes_le() {
+        return Err(UzkgeError::DeserializationError);
+    }
+    let power = read_u32(reader)? as usize;
+    if power >= usize::BITS as usize - 1 {
+        return Err(UzkgeError::DeserializationError);
+    }
+    if max_degree + 1 > (1 << (power + 1)) - 1 {
+        return Err(UzkgeError::DegreeError);
+    }
+
+    seek(reader, tau_g1)?;
+    let mut bytes = vec![0u8; 64 * (max_degree + 1)];
+    read_exact(reader, &mut bytes)?;
+    let public_parameter_group_1 = cfg_into_iter!(0..max_degree + 1)
+        .map(|i| {
+            let chunk = &bytes[64 * i..64 * (i + 1)];
+            let x = fq_from_montgomery_le(&chunk[..32])?;
+            let y = fq_from_montgomery_le(&chunk[32..])?;
+            g1_from_xy(x, y)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    seek(reader, tau_g2)?;
+    let mut bytes = [0u8; 256];
+    read_exact(reader, &mut bytes)?;
+    let public_parameter_group_2 = bytes
+        .chunks(128)
+        .map(|chunk| {
+            let x = Fq2::new(
+                fq_from_montgomery_le(&chunk[..32])?,
+                fq_from_montgomery_le(&chunk[32..64])?,
+            );
+            let y = Fq2::new(
+                fq_from_montgomery_le(&chunk[64..96])?,
+                fq_from_montgomery_le(&chunk[96..])?,
+            );
+            g2_from_xy(x, y)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(KZGCommitmentSchemeBN254 {
+        public_parameter_group_1,
+        public_parameter_group_2,
+    })
+}
+
+/// Read the SRS of degree `max_degree` from a raw Perpetual Powers of Tau file of
+/// 2^`power` powers without checking it.
+///
+/// The file starts with a 64-byte hash, followed by the 2^(`power` + 1) - 1 tau G1 points
+/// and the 2^`power` tau G2 points, in the big-endian encoding of bellman.
+pub fn read_ppot<R: Read + Seek>(
+    reader: &mut R,
+    power: usize,
+    encoding: PpotEncoding,
+    max_degree: usize,
+) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    let n_g1 = (1usize << (power + 1)) - 1;
+    if max_degree + 1 > n_g1 {
+        return Err(UzkgeError::DegreeError);
+    }
+    let (g1_size, g2_size) = match encoding {
+        PpotEncoding::Uncompressed => (64, 128),
+        PpotEncoding::Compressed => (32, 64),
+    };
+
+    seek(reader, PPOT_HASH_SIZE)?;
+    let mut bytes = vec![0u8; g1_size * (max_degree + 1)];
+    read_exact(reader, &mut bytes)?;
+    let public_parameter_group_1 = cfg_into_iter!(0..max_degree + 1)
+        .map(|i| g1_from_bellman(&bytes[g1_size * i..g1_size * (i + 1)], encoding))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    seek(reader, PPOT_HASH_SIZE + (g1_size * n_g1) as u64)?;
+    let mut bytes = vec![0u8; 2 * g2_size];
+    read_exact(reader, &mut bytes)?;
+    let public_parameter_group_2 = bytes
+        .chunks(g2_size)
+        .map(|chunk| g2_from_bellman(chunk, encoding))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(KZGCommitmentSchemeBN254 {
+        public_parameter_group_1,
+        public_parameter_group_2,
+    })
+}
+
+/// Check that the SRS consists of the successive powers of the same tau, i.e.,
+/// it starts with the generators and `e(g1[i + 1], g2[0]) = e(g1[i], g2[1])` for all `i`.
+/// The equations are batched with random coefficients.
+pub fn check_srs<R: CryptoRng + RngCore>(
+    srs: &KZGCommitmentSchemeBN254,
+    prng: &mut R,
+) -> Result<(), UzkgeError> {
+    let g1 = &srs.public_parameter_group_1;
+    let g2 = &srs.public_parameter_group_2;
+    if g1.len() < 2 || g2.len() < 2 {
+        return Err(UzkgeError::InvalidSRSError);
+    }
+    if g1[0] != G1Projective::generator()
+        || g2[0] != G2Projective::generator()
+        || g1[1].is_zero()
+        || g2[1].is_zero()
+    {
+        return Err(UzkgeError::InvalidSRSError);
+    }
+
+    let n = g1.len() - 1;
+    let scalars: Vec<Fr> = (0..n).map(|_| Fr::rand(prng)).collect();
+    let bases = G1Projective::normalize_batch(g1);
+    let left = G1Projective::msm(&bases[1..], &scalars).unwrap();
+    let right = G1Projective::msm(&bases[..n], &scalars).unwrap();
+
+    if Bn254::multi_pairing([left, -right], [g2[0], g2[1]]).is_zero() {
+        Ok(())
+    } else {
+        Err(UzkgeError::InvalidSRSError)
+    }
+}
+
+/// Compute the Lagrange basis of size `n` of the SRS, in the format that
+/// `load_lagrange_params` returns, by an inverse FFT over G1.
+pub fn compute_lagrange_basis(
+    srs: &KZGCommitmentSchemeBN254,
+    n: usize,
+) -> Result<KZGCommitmentSchemeBN254, UzkgeError> {
+    if srs.public_parameter_group_1.len() < n {
+        return Err(UzkgeError::DegreeError);
+    }
+    let domain = FpPolynomial::<Fr>::evaluation_domain(n).ok_or(UzkgeError::GroupNotFound(n))?;
+
+    // L_i(tau) = 1/n * \sum_j (tau * \omega^{-i})^j.
+    let mut public_parameter_group_1 = srs.public_parameter_group_1[..n].to_vec();
+    domain.ifft_in_place(&mut public_parameter_group_1);
+
+    Ok(KZGCommitmentSchemeBN254 {
+        public_parameter_group_1,
+        public_parameter_group_2: srs.public_parameter_group_2.clone(),
+    })
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), UzkgeError> {
+    reader
+        .read_exact(buf)
+        .map_err(|_| UzkgeError::DeserializationError)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, UzkgeError> {
+    let mut buf = [0u8; 4];
+    read_exact(reader, &mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, UzkgeError> {
+    let mut buf = [0u8; 8];
+    read_exact(reader, &mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn seek<R: Seek>(reader: &mut R, position: u64) -> Result<(), UzkgeError> {
+    reader
+        .seek(SeekFrom::Start(position))
+        .map(|_| ())
+        .map_err(|_| UzkgeError::DeserializationError)
+}
+
+fn bigint_from_le(bytes: &[u8]) -> Result<BigInt<4>, UzkgeError> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(buf);
+    }
+    let bigint = BigInt::new(limbs);
+    if bigint >= Fq::MODULUS {
+        return Err(UzkgeError::DeserializationError);
+    }
+    Ok(bigint)
+}
+
+fn fq_from_montgomery_le(bytes: &[u8]) -> Result<Fq, UzkgeError> {
+    Ok(Fq::new_unchecked(bigint_from_le(bytes)?))
+}
+
+fn fq_from_be(bytes: &[u8]) -> Result<Fq, UzkgeError> {
+    let mut le = bytes.to_vec();
+    le.reverse();
+    Ok(Fq::from_bigint(bigint_from_le(&le)?).unwrap())
+}
+
+fn g1_from_xy(x: Fq, y: Fq) -> Result<G1Projective, UzkgeError> {
+    if x.is_zero() && y.is_zero() {
+        return Ok(G1Projective::zero());
+    }
+    let point = G1Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        return Err(UzkgeError::DeserializationError);
+    }
+    Ok(point.into_group())
+}
+
+fn g2_from_xy(x: Fq2, y: Fq2) -> Result<G2Projective, UzkgeError> {
+    if x.is_zero() && y.is_zero() {
+        return Ok(G2Projective::zero());
+    }
+    let point = G2Affine::new_unchecked(x, y);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(UzkgeError::DeserializationError);
+    }
+    Ok(point.into_group())
+}
+
+/// The flags of bellman are in the two most significant bits of the first byte,
+/// the point at infinity and, for the compressed points, the greatest y-coordinate.
+fn bellman_flags(bytes: &[u8], encoding: PpotEncoding) -> Result<(bool, bool), UzkgeError> {
+    let infinity = bytes[0] & 0x40 != 0;
+    let greatest = bytes[0] & 0x80 != 0;
+    if encoding == PpotEncoding::Uncompressed && greatest {
+        return Err(UzkgeError::DeserializationError);
+    }
+    Ok((infinity, greatest))
+}
+
+fn g1_from_bellman(bytes: &[u8], encoding: PpotEncoding) -> Result<G1Projective, UzkgeError> {
+    let (infinity, greatest) = bellman_flags(bytes, encoding)?;
+    if infinity {
+        return Ok(G1Projective::zero());
+    }
+    let mut bytes = bytes.to_vec();
+    bytes[0] &= 0x3f;
+
+    let x = fq_from_be(&bytes[..32])?;
+    match encoding {
+        PpotEncoding::Uncompressed => g1_from_xy(x, fq_from_be(&bytes[32..])?),
+        PpotEncoding::Compressed => G1Affine::get_point_from_x_unchecked(x, greatest)
+            .map(|p| p.into_group())
+            .ok_or(UzkgeError::DeserializationError),
+    }
+}
+
+fn g2_from_bellman(bytes: &[u8], encoding: PpotEncoding) -> Result<G2Projective, UzkgeError> {
+    let (infinity, greatest) = bellman_flags(bytes, encoding)?;
+    if infinity {
+        return Ok(G2Projective::zero());
+    }
+    let mut bytes = bytes.to_vec();
+    bytes[0] &= 0x3f;
+
+    // c1 goes before c0.
+    let x = Fq2::new(fq_from_be(&bytes[32..64])?, fq_from_be(&bytes[..32])?);
+    match encoding {
+        PpotEncoding::Uncompressed => g2_from_xy(
+            x,
+            Fq2::new(fq_from_be(&bytes[96..])?, fq_from_be(&bytes[64..96])?),
+        ),
+        PpotEncoding::Compressed => {
+            let point = G2Affine::get_point_from_x_unchecked(x, greatest)
+                .ok_or(UzkgeError::DeserializationError)?;
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(UzkgeError::DeserializationError);
+            }
+            Ok(point.into_group())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{Fq, Fr, G1Projective, G2Projective};
+    use ark_ec::CurveGroup;
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_std::{io::Cursor, rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        errors::UzkgeError,
+        gen_params::ptau::{check_srs, compute_lagrange_basis, read_ppot, read_ptau, PpotEncoding},
+        poly_commit::{
+            field_polynomial::FpPolynomial, kzg_poly_commitment::KZGCommitmentSchemeBN254,
+            pcs::PolyComScheme,
+        },
+    };
+
+    fn montgomery_le(x: &Fq) -> Vec<u8> {
+        x.0.to_bytes_le()
+    }
+
+    fn be(x: &Fq) -> Vec<u8> {
+        x.into_bigint().to_bytes_be()
+    }
+
+    fn write_section(bytes: &mut Vec<u8>, section_type: u32, data: &[u8]) {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.extend(data);
+    }
+
+    /// Encode the SRS as a `.ptau` file of 2^`power` powers.
+    fn to_ptau(srs: &KZGCommitmentSchemeBN254, power: u32) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend(32u32.to_le_bytes());
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+
+        let mut tau_g1 = vec![];
+        for p in G1Projective::normalize_batch(&srs.public_parameter_group_1) {
+            tau_g1.extend(montgomery_le(&p.x));
+            tau_g1.extend(montgomery_le(&p.y));
+        }
+        let mut tau_g2 = vec![];
+        for p in G2Projective::normalize_batch(&srs.public_parameter_group_2) {
+            tau_g2.extend(montgomery_le(&p.x.c0));
+            tau_g2.extend(montgomery_le(&p.x.c1));
+            tau_g2.extend(montgomery_le(&p.y.c0));
+            tau_g2.extend(montgomery_le(&p.y.c1));
+        }
+
+        let mut bytes = b"ptau".to_vec();
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend(4u32.to_le_bytes());
+        write_section(&mut bytes, 1, &header);
+        write_section(&mut bytes, 3, &tau_g2);
+        write_section(&mut bytes, 7, &[0u8; 10]);
+        write_section(&mut bytes, 2, &tau_g1);
+        bytes
+    }
+
+    /// Encode the SRS as a raw Perpetual Powers of Tau file.
+    fn to_ppot(srs: &KZGCommitmentSchemeBN254, encoding: PpotEncoding) -> Vec<u8> {
+        let mut bytes = vec![0u8; 64];
+        for p in G1Projective::normalize_batch(&srs.public_parameter_group_1) {
+            let mut x = be(&p.x);
+            match encoding {
+                PpotEncoding::Uncompressed => {
+                    bytes.extend(x);
+                    bytes.extend(be(&p.y));
+                }
+                PpotEncoding::Compressed => {
+                    if p.y > -p.y {
+                        x[0] |= 0x80;
+                    }
+                    bytes.extend(x);
+                }
+            }
+        }
+        for p in G2Projective::normalize_batch(&srs.public_parameter_group_2) {
+            let mut x = be(&p.x.c1);
+            x.extend(be(&p.x.c0));
+            match encoding {
+                PpotEncoding::Uncompressed => {
+                    bytes.extend(x);
+                    bytes.extend(be(&p.y.c1));
+                    bytes.extend(be(&p.y.c0));
+                }
+                PpotEncoding::Compressed => {
+                    if p.y > -p.y {
+                        x[0] |= 0x80;
+                    }
+                    bytes.extend(x);
+                }
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_read_ptau() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let srs = KZGCommitmentSchemeBN254::new(14, &mut prng);
+        let bytes = to_ptau(&srs, 3);
+
+        let loaded = read_ptau(&mut Cursor::new(&bytes), 10).unwrap();
+        assert_eq!(
+            loaded.public_parameter_group_1,
+            srs.public_parameter_group_1[..11]
+        );
+        assert_eq!(
+            loaded.public_parameter_group_2,
+            srs.public_parameter_group_2
+        );
+        assert!(check_srs(&loaded, &mut prng).is_ok());
+
+        assert_eq!(
+            read_ptau(&mut Cursor::new(&bytes), 15).unwrap_err(),
+            UzkgeError::DegreeError
+        );
+        assert!(read_ptau(&mut Cursor::new(&bytes[1..]), 10).is_err());
+
+        // a header with a huge field size is rejected before allocating.
+        let mut bad_bytes = bytes.clone();
+        bad_bytes[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            read_ptau(&mut Cursor::new(&bad_bytes), 10).unwrap_err(),
+            UzkgeError::DeserializationError
+        );
+
+        // so is a section size that overflows the position.
+        let mut bad_bytes = bytes.clone();
+        bad_bytes[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            read_ptau(&mut Cursor::new(&bad_bytes), 10).unwrap_err(),
+            UzkgeError::DeserializationError
+        );
+    }
+
+    #[test]
+    fn test_read_ppot() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let srs = KZGCommitmentSchemeBN254::new(14, &mut prng);
+
+        for encoding in [PpotEncoding::Uncompressed, PpotEncoding::Compressed] {
+            let bytes = to_ppot(&srs, encoding);
+            let loaded = read_ppot(&mut Cursor::new(&bytes), 3, encoding, 14).unwrap();
+            assert_eq!(loaded, srs);
+            assert!(check_srs(&loaded, &mut prng).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_check_srs() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let mut srs = KZGCommitmentSchemeBN254::new(14, &mut prng);
+        assert!(check_srs(&srs, &mut prng).is_ok());
+
+        srs.public_parameter_group_1[5] += G1Projective::rand(&mut prng);
+        assert_eq!(
+            check_srs(&srs, &mut prng).unwrap_err(),
+            UzkgeError::InvalidSRSError
+        );
+    }
+
+    #[test]
+    fn test_compute_lagrange_basis() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let srs = KZGCommitmentSchemeBN254::new(18, &mut prng);
+        let lagrange = compute_lagrange_basis(&srs, 16).unwrap();
+        assert_eq!(lagrange.max_degree() + 1, 16);
+
+        let evals: Vec<Fr> = (0..16).map(|_| Fr::rand(&mut prng)).collect();
+        let domain = FpPolynomial::<Fr>::evaluation_domain(16).unwrap();
+        let coefs = FpPolynomial::ifft_with_domain(&domain, &evals);
+        assert_eq!(
+            lagrange.commit(&FpPolynomial::from_coefs(evals)).unwrap(),
+            srs.commit(&coefs).unwrap()
+        );
+    }
+}
@@ -1,18 +1,28 @@
-use ark_bn254::Fr;
-use ark_ff::{Field, One};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, Field, One, PrimeField};
 use ark_serialize::CanonicalSerialize;
+use serde::Serialize;
 use std::path::PathBuf;
 use tera::{Context, Tera};
 
 use crate::{
+    errors::UzkgeError,
     gen_params::VerifierParams,
+    plonk::{
+        constraint_system::{ConstraintSystem, PlonkLayout, TurboCS},
+        transcript::transcript_init_plonk,
+    },
     poly_commit::field_polynomial::FpPolynomial,
-    utils::serialization::{point_to_uncompress_be, scalar_to_bytes_be},
+    utils::{
+        serialization::{point_to_uncompress_be, scalar_to_bytes_be},
+        transcript::Transcript,
+    },
 };
 
 const VKE1: &str = include_str!("../../parameters/VerifierKeyExtra1.sol");
 const VKE2: &str = include_str!("../../parameters/VerifierKeyExtra2.sol");
 const VK: &str = include_str!("../../parameters/VerifierKey.sol");
+const PLONK_VERIFIER: &str = include_str!("../../parameters/PlonkVerifier.sol");
 
 pub fn gen_solidity_vk(vk: VerifierParams, num: usize, directory: PathBuf, full: bool) {
     let params = vk.verifier_params;
@@ -145,6 +155,229 @@ pub fn gen_solidity_vk(vk: VerifierParams, num: usize, directory: PathBuf, full:
     println!("VerifierKey genereated directory: {:?}!", directory);
 }
 
+/// The layout of a proof, in the 32-byte words of `PlonkProof::to_bytes_be` after the layout tag.
+#[derive(Serialize, Default)]
+struct ProofLayout {
+    tag: u8,
+    words: usize,
+    cm_w: usize,
+    cm_w_sel: usize,
+    cm_t: usize,
+    cm_z: usize,
+    prk_3: usize,
+    prk_4: usize,
+    w: usize,
+    w_omega: usize,
+    z_omega: usize,
+    s: usize,
+    q_ecc: usize,
+    w_sel: usize,
+    opening_zeta: usize,
+    opening_zeta_omega: usize,
+}
+
+impl ProofLayout {
+    fn new(plonk_layout: PlonkLayout) -> Self {
+        let n_wires = TurboCS::<Fr>::n_wires_per_gate();
+        let mut layout = ProofLayout {
+            tag: plonk_layout.to_tag(),
+            ..Default::default()
+        };
+        let mut offset = 0;
+        let mut take = |len: usize| {
+            offset += len;
+            offset - len
+        };
+
+        layout.cm_w = take(2 * n_wires);
+        if plonk_layout.has_shuffle() {
+            layout.cm_w_sel = take(2 * TurboCS::<Fr>::num_wire_selectors());
+        }
+        layout.cm_t = take(2 * n_wires);
+        layout.cm_z = take(2);
+        layout.prk_3 = take(1);
+        layout.prk_4 = take(1);
+        layout.w = take(n_wires);
+        layout.w_omega = take(3);
+        layout.z_omega = take(1);
+        layout.s = take(n_wires - 1);
+        if plonk_layout.has_shuffle() {
+            layout.q_ecc = take(1);
+            layout.w_sel = take(TurboCS::<Fr>::num_wire_selectors());
+        }
+        layout.opening_zeta = take(2);
+        layout.opening_zeta_omega = take(2);
+        layout.words = take(0);
+        layout
+    }
+}
+
+/// The layout of the verifier key, in the points of `VK_POINTS`.
+#[derive(Serialize, Default)]
+struct VkLayout {
+    n_q: usize,
+    q: usize,
+    s: usize,
+    qb: usize,
+    prk: usize,
+    q_ecc: usize,
+    shuffle_generator: usize,
+    shuffle_public_key: usize,
+}
+
+/// Generate a self-contained Solidity verifier contract for the verifier parameters.
+///
+/// `transcript` should be in the same state as the one given to the prover, so that the
+/// contract can start from the transcript after the verifier key is appended. The custom
+/// gates of the circuit should be registered in `vk.shrunk_cs`, each of them with the
+/// Solidity translation of its selector multipliers. The proofs are passed to the contract
+/// as the bytes of `PlonkProof::to_bytes_be`, see `PlonkProof::to_solidity_calldata`.
+pub fn gen_solidity_verifier(
+    vk: &VerifierParams,
+    mut transcript: Transcript,
+    contract_name: &str,
+) -> Result<String, UzkgeError> {
+    let params = &vk.verifier_params;
+    if params.layout().has_lookup() {
+        return Err(UzkgeError::Message(
+            "the Solidity verifier does not support the lookup argument".to_string(),
+        ));
+    }
+    let cs = &vk.shrunk_cs;
+    if params.cm_q_vec.len() != TurboCS::<Fr>::num_selectors() + cs.num_custom_selectors() {
+        return Err(UzkgeError::VerifierParamsError);
+    }
+    let mut custom_multipliers = vec![];
+    for gate in cs.custom_gates().iter() {
+        let multipliers = gate.solidity_selector_multipliers().ok_or_else(|| {
+            UzkgeError::Message(format!(
+                "the custom gate {} has no Solidity translation",
+                gate.name()
+            ))
+        })?;
+        if multipliers.len() != gate.num_selectors() {
+            return Err(UzkgeError::Message(format!(
+                "the Solidity translation of the custom gate {} has a wrong number of multipliers",
+                gate.name()
+            )));
+        }
+        custom_multipliers.extend(multipliers);
+    }
+
+    let domain = FpPolynomial::<Fr>::evaluation_domain(params.cs_size)
+        .ok_or(UzkgeError::GroupNotFound(params.cs_size))?;
+    let root = domain.group_gen;
+
+    // the public inputs are appended by the contract.
+    transcript_init_plonk(&mut transcript, params, &[], &root);
+    let transcript_prefix = transcript.state();
+
+    let mut vk_points = vec![];
+    let mut vk_layout = VkLayout {
+        n_q: params.cm_q_vec.len(),
+        ..Default::default()
+    };
+    vk_points.extend(params.cm_q_vec.iter());
+    vk_layout.s = vk_points.len();
+    vk_points.extend(params.cm_s_vec.iter());
+    vk_layout.qb = vk_points.len();
+    vk_points.push(&params.cm_qb);
+    vk_layout.prk = vk_points.len();
+    vk_points.extend(params.cm_prk_vec.iter());
+    if params.layout().has_shuffle() {
+        vk_layout.q_ecc = vk_points.len();
+        vk_points.push(&params.cm_q_ecc);
+        vk_layout.shuffle_generator = vk_points.len();
+        vk_points.extend(params.cm_shuffle_generator_vec.iter());
+        vk_layout.shuffle_public_key = vk_points.len();
+        vk_points.extend(params.cm_shuffle_public_key_vec.iter());
+    }
+    let vk_points: String = vk_points
+        .iter()
+        .map(|c| hex::encode(point_to_uncompress_be(&c.0)))
+        .collect();
+
+    let pi_roots: String = params
+        .public_vars_constraint_indices
+        .iter()
+        .map(|c| hex::encode(scalar_to_bytes_be(&root.pow([*c as u64]))))
+        .collect();
+    let pi_lagrange_constants: String = params
+        .lagrange_constants
+        .iter()
+        .map(|c| hex::encode(scalar_to_bytes_be(c)))
+        .collect();
+
+    let domain_separator = b"New PCS-Batch-Eval Protocol";
+    let mut pcs_batch_domain = [0u8; 32];
+    pcs_batch_domain[32 - domain_separator.len()..].copy_from_slice(domain_separator);
+
+    let g1: G1Affine = vk.shrunk_vk.public_parameter_group_1[0].into();
+    let g2: G2Affine = vk.shrunk_vk.public_parameter_group_2[0].into();
+    let g2_tau: G2Affine = vk.shrunk_vk.public_parameter_group_2[1].into();
+
+    let mut context = Context::new();
+    context.insert("contract_name", contract_name);
+    context.insert("shuffle", &params.layout().has_shuffle());
+    context.insert("cs_size", &params.cs_size);
+    context.insert("max_degree", &(params.cs_size + 2));
+    context.insert("root", &fmt_scalar(&root));
+    context.insert("anemoi_generator", &fmt_scalar(&params.anemoi_generator));
+    context.insert(
+        "anemoi_generator_inv",
+        &fmt_scalar(&params.anemoi_generator_inv),
+    );
+    let ks: Vec<String> = params.k.iter().map(fmt_scalar).collect();
+    context.insert("ks", &ks);
+    context.insert("edwards_a", &fmt_scalar(&params.edwards_a));
+    context.insert("g1", &[fmt_scalar(&g1.x), fmt_scalar(&g1.y)]);
+    context.insert(
+        "g2",
+        &[
+            fmt_scalar(&g2.x.c1),
+            fmt_scalar(&g2.x.c0),
+            fmt_scalar(&g2.y.c1),
+            fmt_scalar(&g2.y.c0),
+        ],
+    );
+    context.insert(
+        "g2_tau",
+        &[
+            fmt_scalar(&g2_tau.x.c1),
+            fmt_scalar(&g2_tau.x.c0),
+            fmt_scalar(&g2_tau.y.c1),
+            fmt_scalar(&g2_tau.y.c0),
+        ],
+    );
+    context.insert("proof", &ProofLayout::new(params.layout()));
+    context.insert("vk", &vk_layout);
+    context.insert("vk_points", &vk_points);
+    context.insert(
+        "num_public_inputs",
+        &params.public_vars_constraint_indices.len(),
+    );
+    context.insert("pi_roots", &pi_roots);
+    context.insert("pi_lagrange_constants", &pi_lagrange_constants);
+    context.insert("transcript_prefix", &hex::encode(transcript_prefix));
+    context.insert("transcript_prefix_length", &transcript_prefix.len());
+    context.insert(
+        "transcript_capacity",
+        &(32 * params.public_vars_constraint_indices.len() + 1024),
+    );
+    context.insert("pcs_batch_domain", &fmt_s(&hex::encode(pcs_batch_domain)));
+    context.insert("custom_multipliers", &custom_multipliers);
+
+    let mut tera = Tera::default();
+    tera.add_raw_template("plonk_verifier", PLONK_VERIFIER)
+        .map_err(|e| UzkgeError::Message(e.to_string()))?;
+    tera.render("plonk_verifier", &context)
+        .map_err(|e| UzkgeError::Message(e.to_string()))
+}
+
+fn fmt_scalar<F: PrimeField>(s: &F) -> String {
+    fmt_s(&hex::encode(s.into_bigint().to_bytes_be()))
+}
+
 fn hex_i(i: i32) -> String {
     format!("0x{:x}", i)
 }
@@ -152,3 +385,218 @@ fn hex_i(i: i32) -> String {
 fn fmt_s(s: &str) -> String {
     format!("0x{}", s)
 }
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{rand::SeedableRng, sync::Arc, One};
+    use rand_chacha::ChaChaRng;
+    use std::{env, fs, path::PathBuf};
+
+    use crate::{
+        gen_params::{solidity::gen_solidity_verifier, VerifierParams},
+        plonk::{
+            constraint_system::{custom_gate::CustomGate, ConstraintSystem, TurboCS},
+            indexer::{indexer, PlonkProof},
+            prover::prover,
+        },
+        poly_commit::{
+            kzg_poly_commitment::{KZGCommitment, KZGCommitmentScheme, KZGCommitmentSchemeBN254},
+            pcs::PolyComScheme,
+        },
+        utils::{
+            serialization::{point_to_uncompress_be, scalar_to_bytes_be},
+            transcript::{Transcript, TranscriptScheme},
+        },
+    };
+
+    /// The gate `q_square * (w1^2 - wo) = 0`.
+    #[derive(Debug)]
+    struct SquareGate;
+
+    impl CustomGate<Fr> for SquareGate {
+        fn name(&self) -> &str {
+            "square"
+        }
+
+        fn num_selectors(&self) -> usize {
+            1
+        }
+
+        fn degree(&self) -> usize {
+            2
+        }
+
+        fn eval_selector_multipliers(&self, wire_vals: &[&Fr]) -> Vec<Fr> {
+            vec![*wire_vals[0] * wire_vals[0] - wire_vals[4]]
+        }
+
+        fn solidity_selector_multipliers(&self) -> Option<Vec<String>> {
+            Some(vec!["addmod(mulmod(w0, w0, R), R - w4, R)".to_string()])
+        }
+    }
+
+    fn prove_square() -> (VerifierParams, PlonkProof<KZGCommitmentSchemeBN254>) {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+
+        let mut cs = TurboCS::<Fr>::new();
+        let gate_id = cs.add_custom_gate(Arc::new(SquareGate));
+        let zero = cs.zero_var();
+        let a = cs.new_variable(Fr::from(3u64));
+        let b = cs.new_variable(Fr::from(9u64));
+        cs.insert_custom_gate(gate_id, &[a, zero, zero, zero, b], &[Fr::one()]);
+        cs.prepare_pi_variable(b);
+        cs.pad();
+        let witness = cs.get_and_clear_witness();
+
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let proof = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        let vk = VerifierParams {
+            shrunk_vk: pcs.shrink_to_verifier_only().unwrap(),
+            shrunk_cs: cs.shrink_to_verifier_only(),
+            verifier_params: prover_params.verifier_params.clone(),
+        };
+        (vk, proof)
+    }
+
+    #[test]
+    fn test_gen_solidity_verifier() {
+        let (vk, proof) = prove_square();
+        let contract =
+            gen_solidity_verifier(&vk, Transcript::new(b"Test"), "TestVerifier").unwrap();
+        assert!(contract.contains("contract TestVerifier {"));
+        assert!(contract.contains("m[9] = addmod(mulmod(w0, w0, R), R - w4, R);"));
+        assert!(contract.contains("uint256 private constant NUM_PUBLIC_INPUTS = 1;"));
+        assert!(contract.contains(&format!(
+            "uint256 private constant PROOF_WORDS = {};",
+            proof.to_bytes_be().len() / 32
+        )));
+        assert!(!contract.contains("{{"));
+
+        // the custom gates should be registered and translated.
+        let mut vk_without_gates = vk.clone();
+        vk_without_gates.shrunk_cs = TurboCS::new().shrink_to_verifier_only();
+        assert!(
+            gen_solidity_verifier(&vk_without_gates, Transcript::new(b"Test"), "TestVerifier")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_solidity_proof_layout() {
+        let (vk, proof) = prove_square();
+        let contract =
+            gen_solidity_verifier(&vk, Transcript::new(b"Test"), "TestVerifier").unwrap();
+        let constant = |name: &str| -> usize {
+            let prefix = format!("uint256 private constant {} = ", name);
+            let line = contract
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(prefix.as_str()))
+                .unwrap();
+            line.trim_end_matches(';').parse().unwrap()
+        };
+
+        // the contract reads the words of `to_bytes_be` after the layout tag.
+        let bytes = proof.to_bytes_be();
+        assert_eq!(bytes[0] as usize, constant("PROOF_TAG"));
+        assert_eq!(bytes.len(), 1 + 32 * constant("PROOF_WORDS"));
+        let words = |name: &str, len: usize| -> &[u8] {
+            let start = 1 + 32 * constant(name);
+            &bytes[start..start + 32 * len]
+        };
+
+        let point = |c: &KZGCommitment<G1Projective>| point_to_uncompress_be(&c.0);
+        let scalars = |s: &[Fr]| s.iter().flat_map(scalar_to_bytes_be).collect::<Vec<_>>();
+        let n_wires = proof.w_polys_eval_zeta.len();
+        let cm_w: Vec<u8> = proof.cm_w_vec.iter().flat_map(point).collect();
+        let cm_t: Vec<u8> = proof.cm_t_vec.iter().flat_map(point).collect();
+        assert_eq!(words("P_CM_W", 2 * n_wires), &cm_w[..]);
+        assert_eq!(words("P_CM_T", 2 * n_wires), &cm_t[..]);
+        assert_eq!(words("P_CM_Z", 2), &point(&proof.cm_z)[..]);
+        assert_eq!(
+            words("P_PRK_3", 1),
+            &scalar_to_bytes_be(&proof.prk_3_poly_eval_zeta)[..]
+        );
+        assert_eq!(
+            words("P_PRK_4", 1),
+            &scalar_to_bytes_be(&proof.prk_4_poly_eval_zeta)[..]
+        );
+        assert_eq!(
+            words("P_W", n_wires),
+            &scalars(&proof.w_polys_eval_zeta)[..]
+        );
+        assert_eq!(
+            words("P_W_OMEGA", 3),
+            &scalars(&proof.w_polys_eval_zeta_omega)[..]
+        );
+        assert_eq!(
+            words("P_Z_OMEGA", 1),
+            &scalar_to_bytes_be(&proof.z_eval_zeta_omega)[..]
+        );
+        assert_eq!(
+            words("P_S", n_wires - 1),
+            &scalars(&proof.s_polys_eval_zeta)[..]
+        );
+        assert_eq!(
+            words("P_OPENING_ZETA", 2),
+            &point(&proof.opening_witness_zeta)[..]
+        );
+        assert_eq!(
+            words("P_OPENING_ZETA_OMEGA", 2),
+            &point(&proof.opening_witness_zeta_omega)[..]
+        );
+        assert_eq!(
+            constant("P_OPENING_ZETA_OMEGA") + 2,
+            constant("PROOF_WORDS")
+        );
+
+        // the calldata carries the proof bytes as the first argument.
+        let calldata = proof.to_solidity_calldata(&[Fr::from(9u64)]);
+        assert_eq!(&calldata[100..100 + bytes.len()], &bytes[..]);
+    }
+
+    #[test]
+    fn test_solidity_verifier_fixture() {
+        // the hardhat test `contracts/solidity/test/plonk_square.js` deploys the contract and
+        // verifies the calldata, set `UPDATE_SOLIDITY_FIXTURE` to regenerate them.
+        let (vk, proof) = prove_square();
+        let contract =
+            gen_solidity_verifier(&vk, Transcript::new(b"Test"), "PlonkSquareVerifier").unwrap();
+
+        let public_inputs = [Fr::from(9u64)];
+        let mut flipped = proof.clone();
+        flipped.w_polys_eval_zeta[0] += Fr::one();
+        let fixture = format!(
+            "{{\n  \"calldata\": \"0x{}\",\n  \"flippedCalldata\": \"0x{}\"\n}}\n",
+            hex::encode(proof.to_solidity_calldata(&public_inputs)),
+            hex::encode(flipped.to_solidity_calldata(&public_inputs)),
+        );
+
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/solidity");
+        for (path, content) in [
+            (dir.join("contracts/test/PlonkSquareVerifier.sol"), contract),
+            (dir.join("test/plonk_square.json"), fixture),
+        ] {
+            if env::var("UPDATE_SOLIDITY_FIXTURE").is_ok() {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, content).unwrap();
+            } else {
+                let expected = fs::read_to_string(&path).unwrap();
+                assert!(
+                    expected == content,
+                    "{} is stale, set UPDATE_SOLIDITY_FIXTURE to regenerate it",
+                    path.display()
+                );
+            }
+        }
+    }
+}
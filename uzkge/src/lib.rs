@@ -13,9 +13,15 @@ pub mod errors;
 /// Module for anemoi hash.
 pub mod anemoi;
 
+/// Module for the Anemoi Merkle tree.
+pub mod merkle_tree;
+
 /// Module for chaum pedersen.
 pub mod chaum_pedersen;
 
+/// Module for EdDSA signatures over BabyJubjub.
+pub mod eddsa;
+
 /// Module for shuffle.
 pub mod shuffle;
 
@@ -0,0 +1,266 @@
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    errors::{Result, UzkgeError},
+    utils::serialization::{ark_deserialize, ark_serialize},
+};
+
+/// The salt of the Anemoi-Jive compression in the Merkle tree of `arity`,
+/// which separates the binary tree from the ternary tree. The leaves are hashed
+/// with the salt of `arity` 1, which separates them from the internal nodes.
+pub fn merkle_tree_salt<F: PrimeField>(arity: usize) -> F {
+    F::from(arity as u64)
+}
+
+/// Hash a leaf into the node at the bottom of the tree.
+pub fn hash_leaf<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>(leaf: &F) -> F {
+    P::eval_jive(&[*leaf, F::ZERO], &[F::ZERO, merkle_tree_salt(1)])
+}
+
+/// Compress the children of a node with the Anemoi-Jive compression, the missing
+/// child of a binary node is zero.
+pub fn hash_children<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>(children: &[F]) -> F {
+    assert!(children.len() == 2 || children.len() == 3);
+
+    let third = children.get(2).copied().unwrap_or(F::ZERO);
+    P::eval_jive(
+        &[children[0], children[1]],
+        &[third, merkle_tree_salt(children.len())],
+    )
+}
+
+/// The membership proof of a leaf, with the siblings of the nodes from the leaf to the root.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProof<F: PrimeField> {
+    /// The index of the leaf.
+    pub index: u64,
+    /// The siblings of every level, from the leaf to the root.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub siblings: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MerkleProof<F> {
+    /// The positions of the nodes of the path in their parents, from the leaf to the root.
+    pub fn positions(&self, arity: usize) -> Vec<usize> {
+        let mut index = self.index;
+        self.siblings
+            .iter()
+            .map(|_| {
+                let position = (index % arity as u64) as usize;
+                index /= arity as u64;
+                position
+            })
+            .collect()
+    }
+}
+
+/// The Merkle tree with the Anemoi-Jive compression, of `ARITY` 2 or 3.
+/// The hashed leaves are padded with zeros to a power of `ARITY`, and the
+/// padding is not a leaf of the tree.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>, const ARITY: usize> {
+    leaves: Vec<F>,
+    /// The nodes of every level, from the hashed leaves to the root.
+    layers: Vec<Vec<F>>,
+    _hash: PhantomData<P>,
+}
+
+/// The binary Merkle tree.
+pub type BinaryMerkleTree<F, P> = MerkleTree<F, P, 2>;
+
+/// The ternary Merkle tree.
+pub type TernaryMerkleTree<F, P> = MerkleTree<F, P, 3>;
+
+impl<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>, const ARITY: usize>
+    MerkleTree<F, P, ARITY>
+{
+    pub fn new(leaves: &[F]) -> Result<Self> {
+        if !(ARITY == 2 || ARITY == 3) || leaves.is_empty() {
+            return Err(UzkgeError::ParameterError);
+        }
+
+        let mut capacity = 1;
+        while capacity < leaves.len() {
+            capacity *= ARITY;
+        }
+
+        let mut layer: Vec<F> = leaves.iter().map(hash_leaf::<F, P>).collect();
+        layer.resize(capacity, F::ZERO);
+
+        let mut layers = vec![layer];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(ARITY)
+                .map(hash_children::<F, P>)
+                .collect();
+            layers.push(next);
+        }
+
+        Ok(Self {
+            leaves: leaves.to_vec(),
+            layers,
+            _hash: PhantomData,
+        })
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> F {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// The number of levels above the leaves.
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// The leaf at `index`.
+    pub fn leaf(&self, index: usize) -> Option<F> {
+        self.leaves.get(index).copied()
+    }
+
+    /// The membership proof of the leaf at `index`.
+    pub fn prove(&self, index: usize) -> Result<MerkleProof<F>> {
+        if index >= self.leaves.len() {
+            return Err(UzkgeError::ParameterError);
+        }
+
+        Ok(self.path(index))
+    }
+
+    /// The membership proof of the node at `index` of the bottom level, which may be
+    /// the padding.
+    fn path(&self, index: usize) -> MerkleProof<F> {
+        let mut position = index;
+        let mut siblings = Vec::with_capacity(self.depth());
+        for layer in self.layers.iter().take(self.depth()) {
+            let first = position - position % ARITY;
+            let node_siblings = (first..first + ARITY)
+                .filter(|i| *i != position)
+                .map(|i| layer[i])
+                .collect();
+            siblings.push(node_siblings);
+            position /= ARITY;
+        }
+
+        MerkleProof {
+            index: index as u64,
+            siblings,
+        }
+    }
+
+    /// Verify the membership proof of the `leaf` against the `root` of the tree of `depth`.
+    pub fn verify(root: &F, depth: usize, leaf: &F, proof: &MerkleProof<F>) -> Result<()> {
+        if proof.siblings.len() != depth
+            || proof.siblings.iter().any(|s| s.len() != ARITY - 1)
+            || proof.index >= (ARITY as u64).saturating_pow(depth as u32)
+        {
+            return Err(UzkgeError::VerificationError);
+        }
+
+        let mut node = hash_leaf::<F, P>(leaf);
+        for (siblings, position) in proof.siblings.iter().zip(proof.positions(ARITY)) {
+            let mut children = siblings.clone();
+            children.insert(position, node);
+            node = hash_children::<F, P>(&children);
+        }
+
+        if node != *root {
+            return Err(UzkgeError::VerificationError);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use super::{BinaryMerkleTree, MerkleTree, TernaryMerkleTree};
+    use crate::anemoi::AnemoiJive254;
+
+    fn check<const ARITY: usize>(num_leaves: usize) {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let leaves: Vec<Fr> = (0..num_leaves).map(|_| Fr::rand(&mut prng)).collect();
+
+        let tree = MerkleTree::<Fr, AnemoiJive254, ARITY>::new(&leaves).unwrap();
+        let root = tree.root();
+        let depth = tree.depth();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(i).unwrap();
+            assert_eq!(proof.siblings.len(), tree.depth());
+            assert!(
+                MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(&root, depth, leaf, &proof).is_ok()
+            );
+
+            let mut wrong_proof = proof.clone();
+            wrong_proof.index = (proof.index + 1) % leaves.len() as u64;
+            assert!(MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(
+                &root,
+                depth,
+                leaf,
+                &wrong_proof
+            )
+            .is_err());
+        }
+
+        let wrong_leaf = Fr::rand(&mut prng);
+        let proof = tree.prove(0).unwrap();
+        assert!(
+            MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(&root, depth, &wrong_leaf, &proof)
+                .is_err()
+        );
+    }
+
+    fn check_forged<const ARITY: usize>(num_leaves: usize) {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let leaves: Vec<Fr> = (0..num_leaves).map(|_| Fr::rand(&mut prng)).collect();
+
+        let tree = MerkleTree::<Fr, AnemoiJive254, ARITY>::new(&leaves).unwrap();
+        let root = tree.root();
+        let depth = tree.depth();
+
+        // an internal node, with the shorter path above it.
+        let mut proof = tree.prove(0).unwrap();
+        proof.siblings.remove(0);
+        let node = tree.layers[1][0];
+        assert!(
+            MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(&root, depth, &node, &proof).is_err()
+        );
+        assert!(
+            MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(&root, depth - 1, &node, &proof)
+                .is_err()
+        );
+
+        // the zero padding after the leaves.
+        assert!(tree.prove(num_leaves).is_err());
+        let proof = tree.path(num_leaves);
+        assert!(MerkleTree::<Fr, AnemoiJive254, ARITY>::verify(
+            &root,
+            depth,
+            &Fr::from(0u64),
+            &proof
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        check::<2>(13);
+        check::<3>(13);
+        check_forged::<2>(13);
+        check_forged::<3>(13);
+
+        let leaves = [Fr::from(1u64), Fr::from(2u64)];
+        let binary = BinaryMerkleTree::<Fr, AnemoiJive254>::new(&leaves).unwrap();
+        let ternary = TernaryMerkleTree::<Fr, AnemoiJive254>::new(&leaves).unwrap();
+        assert_ne!(binary.root(), ternary.root());
+    }
+}
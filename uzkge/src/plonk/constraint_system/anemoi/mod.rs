@@ -1,7 +1,9 @@
 use ark_ff::PrimeField;
 
 use crate::{
-    anemoi::{AnemoiJive, AnemoiStreamCipherTrace, AnemoiVLHTrace, N_ANEMOI_ROUNDS},
+    anemoi::{
+        AnemoiJive, AnemoiJiveTrace, AnemoiStreamCipherTrace, AnemoiVLHTrace, N_ANEMOI_ROUNDS,
+    },
     plonk::constraint_system::{TurboCS, VarIndex},
 };
 
@@ -312,6 +314,49 @@ impl<F: PrimeField> TurboCS<F> {
         }
     }
 
+    /// Create constraints for the Anemoi-Jive compression of three elements and a constant
+    /// salt, i.e., `input_x = [input_var[0], input_var[1]]` and `input_y = [input_var[2], salt]`.
+    pub fn jive_crh<P: AnemoiJive<F, 2usize, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        trace: &AnemoiJiveTrace<F, 2, N_ANEMOI_ROUNDS>,
+        input_var: &[VarIndex; 3],
+        salt: F,
+    ) -> VarIndex {
+        assert_eq!(trace.input_y[1], salt);
+
+        let zero = F::ZERO;
+        let one = F::ONE;
+
+        let salt_var = self.new_variable(salt);
+        let sum_after_perm = trace.final_x.iter().sum::<F>() + trace.final_y.iter().sum::<F>();
+        let sum_after_perm_var = self
+            .anemoi_permutation_round::<P>(
+                &([input_var[0], input_var[1]], [input_var[2], salt_var]),
+                &([None, None], [None, None]),
+                &trace.intermediate_values_before_constant_additions,
+                Some(sum_after_perm),
+                Some(salt),
+            )
+            .unwrap();
+
+        // output = input_var[0] + input_var[1] + input_var[2] + salt + sum_after_perm
+        let output_var = self.new_variable(trace.output);
+        self.push_add_selectors(one, one, one, one);
+        self.push_mul_selectors(zero, zero);
+        self.push_constant_selector(salt);
+        self.push_ecc_selector(zero);
+        self.push_out_selector(one);
+
+        self.wiring[0].push(input_var[0]);
+        self.wiring[1].push(input_var[1]);
+        self.wiring[2].push(input_var[2]);
+        self.wiring[3].push(sum_after_perm_var);
+        self.wiring[4].push(output_var);
+        self.finish_new_gate();
+
+        output_var
+    }
+
     /// Create constraints for the Anemoi stream cipher
     pub fn anemoi_stream_cipher<P: AnemoiJive<F, 2usize, 14usize>>(
         &mut self,
@@ -624,3 +669,69 @@ mod test_bn254 {
         }
     }
 }
+
+#[cfg(test)]
+mod test_bls12_381 {
+    use crate::{
+        anemoi::{AnemoiJive, AnemoiJive381, Bls12381Fr as Fr},
+        plonk::constraint_system::TurboCS,
+    };
+
+    #[test]
+    fn test_anemoi_variable_length_hash_constraint_system() {
+        let trace = AnemoiJive381::eval_variable_length_hash_with_trace(&[
+            Fr::from(1u64),
+            Fr::from(2u64),
+            Fr::from(3u64),
+            Fr::from(4u64),
+        ]);
+
+        let mut cs = TurboCS::new();
+        cs.load_anemoi_parameters::<AnemoiJive381>();
+
+        let one = cs.new_variable(Fr::from(1u64));
+        let two = cs.new_variable(Fr::from(2u64));
+        let three = cs.new_variable(Fr::from(3u64));
+        let four = cs.new_variable(Fr::from(4u64));
+
+        let output_var = cs.new_variable(trace.output);
+
+        cs.anemoi_variable_length_hash::<AnemoiJive381>(
+            &trace,
+            &[one, two, three, four],
+            output_var,
+        );
+
+        let witness = cs.get_and_clear_witness();
+        cs.verify_witness(&witness, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_jive_crh() {
+        let salt = Fr::from(3u64);
+        let trace = AnemoiJive381::eval_jive_with_trace(
+            &[Fr::from(1u64), Fr::from(2u64)],
+            &[Fr::from(3u64), salt],
+        );
+        assert_eq!(
+            trace.output,
+            AnemoiJive381::eval_jive(&[Fr::from(1u64), Fr::from(2u64)], &[Fr::from(3u64), salt])
+        );
+
+        let mut cs = TurboCS::new();
+        cs.load_anemoi_parameters::<AnemoiJive381>();
+
+        let one = cs.new_variable(Fr::from(1u64));
+        let two = cs.new_variable(Fr::from(2u64));
+        let three = cs.new_variable(Fr::from(3u64));
+
+        let output_var = cs.jive_crh::<AnemoiJive381>(&trace, &[one, two, three], salt);
+        cs.prepare_pi_variable(output_var);
+
+        let witness = cs.get_and_clear_witness();
+        cs.verify_witness(&witness, &[trace.output]).unwrap();
+        assert!(cs
+            .verify_witness(&witness, &[trace.output + Fr::from(1u64)])
+            .is_err());
+    }
+}
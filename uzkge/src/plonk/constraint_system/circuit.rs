@@ -0,0 +1,136 @@
+use ark_ff::PrimeField;
+
+use crate::{errors::UzkgeError, plonk::constraint_system::TurboCS};
+
+/// A circuit whose shape, i.e., the gates, the selectors and the wiring, does not depend
+/// on its inputs. The shape is synthesized once and cached, e.g., in the prover parameters,
+/// and each proof only assigns the witness.
+pub trait Circuit<F: PrimeField> {
+    /// The inputs of the circuit, including the randomness of the prover.
+    type Inputs;
+    /// The variables or values returned by the circuit.
+    type Output;
+
+    /// Add the gates of the circuit on `inputs` to `cs`, and pad it.
+    fn synthesize(&self, cs: &mut TurboCS<F>, inputs: &Self::Inputs) -> Self::Output;
+
+    /// The inputs used to synthesize the shape.
+    fn dummy_inputs(&self) -> Self::Inputs;
+
+    /// Synthesize the shape of the circuit on the dummy inputs.
+    fn synthesize_shape(&self) -> TurboCS<F> {
+        let mut cs = TurboCS::new();
+        self.synthesize(&mut cs, &self.dummy_inputs());
+        let _ = cs.get_and_clear_witness();
+        cs
+    }
+
+    /// Assign the witness of the circuit on `inputs`, without building the selectors again.
+    /// The witness is checked to fit the cached `shape`.
+    fn assign_witness(
+        &self,
+        shape: &TurboCS<F>,
+        inputs: &Self::Inputs,
+    ) -> Result<(Vec<F>, Self::Output), UzkgeError> {
+        let mut cs = TurboCS::new_witness_only();
+        let output = self.synthesize(&mut cs, inputs);
+        if cs.size != shape.size || cs.num_vars != shape.num_vars {
+            return Err(UzkgeError::ParameterError);
+        }
+        Ok((cs.get_and_clear_witness(), output))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::PrimeGroup;
+    use ark_ed_on_bn254::{EdwardsProjective, Fq, Fr};
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        plonk::constraint_system::{circuit::Circuit, shuffle::CardVar, ConstraintSystem, TurboCS},
+        shuffle::{BabyJubjubShuffle, Ciphertext, Remark},
+    };
+
+    struct RemarkCircuit {
+        n: usize,
+    }
+
+    struct RemarkInputs {
+        public: EdwardsProjective,
+        cards: Vec<Ciphertext<EdwardsProjective>>,
+        bits: Vec<Vec<[bool; 3]>>,
+    }
+
+    impl RemarkCircuit {
+        fn sample_inputs(&self, prng: &mut ChaChaRng) -> RemarkInputs {
+            let public = EdwardsProjective::generator() * Fr::rand(prng);
+            let cards = (0..self.n)
+                .map(|_| {
+                    let m = EdwardsProjective::rand(prng);
+                    Ciphertext::encrypt(prng, &m, &public)
+                })
+                .collect();
+            let bits = (0..self.n)
+                .map(|_| BabyJubjubShuffle::sample_random_scalar_bits(prng))
+                .collect();
+            RemarkInputs {
+                public,
+                cards,
+                bits,
+            }
+        }
+    }
+
+    impl Circuit<Fq> for RemarkCircuit {
+        type Inputs = RemarkInputs;
+        type Output = Vec<CardVar>;
+
+        fn synthesize(&self, cs: &mut TurboCS<Fq>, inputs: &RemarkInputs) -> Vec<CardVar> {
+            cs.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(&inputs.public);
+            let mut outputs = vec![];
+            for (card, bits) in inputs.cards.iter().zip(inputs.bits.iter()) {
+                let trace = BabyJubjubShuffle::eval_remark_with_trace(card, bits, &inputs.public);
+                let input_var = cs.new_card_variable(card);
+                let output_var = cs.eval_card_remark(&trace, &input_var);
+                cs.prepare_pi_card_variable(&output_var);
+                outputs.push(output_var);
+            }
+            cs.pad();
+            outputs
+        }
+
+        fn dummy_inputs(&self) -> RemarkInputs {
+            self.sample_inputs(&mut ChaChaRng::from_seed([0u8; 32]))
+        }
+    }
+
+    #[test]
+    fn test_assign_witness() {
+        let mut prng = ChaChaRng::from_entropy();
+        let circuit = RemarkCircuit { n: 2 };
+        let mut shape = circuit.synthesize_shape();
+
+        let inputs = circuit.sample_inputs(&mut prng);
+        let (witness, _) = circuit.assign_witness(&shape, &inputs).unwrap();
+        shape.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(&inputs.public);
+
+        let mut cs = TurboCS::new();
+        circuit.synthesize(&mut cs, &inputs);
+        assert_eq!(witness, cs.get_and_clear_witness());
+
+        let online_inputs = shape
+            .public_vars_witness_indices()
+            .iter()
+            .map(|i| witness[*i])
+            .collect::<Vec<_>>();
+        shape.verify_witness(&witness, &online_inputs).unwrap();
+
+        // a circuit of another size does not fit the shape.
+        let other = RemarkCircuit { n: 3 };
+        assert!(other
+            .assign_witness(&shape, &other.sample_inputs(&mut prng))
+            .is_err());
+    }
+}
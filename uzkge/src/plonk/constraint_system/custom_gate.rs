@@ -0,0 +1,295 @@
+use ark_ff::PrimeField;
+use ark_std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::UzkgeError,
+    plonk::constraint_system::{turbo::N_WIRES_PER_GATE, ConstraintSystem, TurboCS, VarIndex},
+};
+
+/// A user-defined gate of the Turbo CS.
+///
+/// A custom gate owns `num_selectors` extra selector columns and adds the identity
+/// ```text
+///     q_1(X) * m_1(w1, w2, w3, w4, wo) + ... + q_k(X) * m_k(w1, w2, w3, w4, wo) = 0
+/// ```
+/// to every gate. The identity is linear in the selectors, so that the verifier can
+/// linearize it with the selector commitments and the wire evaluations at zeta, in
+/// the same way as the built-in selectors. The multipliers can only depend on the
+/// wires of the current gate.
+pub trait CustomGate<F: PrimeField>: Debug + Send + Sync {
+    /// The name of the gate, used in error messages.
+    fn name(&self) -> &str;
+
+    /// The number of the selectors of the gate.
+    fn num_selectors(&self) -> usize;
+
+    /// The degree of the multipliers in the wires, which should not be larger than
+    /// `N_WIRES_PER_GATE`, so that the quotient polynomial fits in the evaluation domain.
+    fn degree(&self) -> usize;
+
+    /// Given the wires values of a gate, evaluate the coefficients of the selectors
+    /// in the gate identity.
+    fn eval_selector_multipliers(&self, wire_vals: &[&F]) -> Vec<F>;
+
+    /// Evaluate the gate identity given the values of the wires and the selectors.
+    fn eval_gate_func(&self, wire_vals: &[&F], sel_vals: &[&F]) -> F {
+        self.eval_selector_multipliers(wire_vals)
+            .iter()
+            .zip(sel_vals.iter())
+            .map(|(m, q)| *m * *q)
+            .sum()
+    }
+
+    /// The selector multipliers as Solidity expressions, used by the generated Solidity
+    /// verifier. The expressions are over the wire values `w0`, `w1`, `w2`, `w3`, `w4`
+    /// and should be reduced modulo the scalar field `R`, e.g. `mulmod(w0, w1, R)`.
+    /// A gate without a translation cannot be verified on chain.
+    fn solidity_selector_multipliers(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// Evaluate the sum of the identities of the custom gates,
+/// `sel_vals` are the values of the custom selectors only.
+pub fn eval_custom_gates<F: PrimeField>(
+    gates: &[Arc<dyn CustomGate<F>>],
+    wire_vals: &[&F],
+    sel_vals: &[&F],
+) -> F {
+    let mut offset = 0;
+    let mut r = F::ZERO;
+    for gate in gates.iter() {
+        let n = gate.num_selectors();
+        r += gate.eval_gate_func(wire_vals, &sel_vals[offset..offset + n]);
+        offset += n;
+    }
+    r
+}
+
+/// Check that the custom gates registered in `cs` match the `n_committed_selectors`
+/// committed selectors of the params. The gates are not serialized with the constraint
+/// system, so a loaded constraint system without its gates is rejected here.
+pub fn check_custom_gates<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: &CS,
+    n_committed_selectors: usize,
+) -> Result<(), UzkgeError> {
+    let n_custom_selectors: usize = cs.custom_gates().iter().map(|g| g.num_selectors()).sum();
+    let n_committed_custom_selectors = n_committed_selectors.saturating_sub(CS::num_selectors());
+    if n_committed_selectors != CS::num_selectors() + n_custom_selectors {
+        return Err(UzkgeError::CustomGateMismatch(n_committed_custom_selectors));
+    }
+    Ok(())
+}
+
+/// Concatenate the selector multipliers of the custom gates.
+pub fn eval_custom_selector_multipliers<F: PrimeField>(
+    gates: &[Arc<dyn CustomGate<F>>],
+    wire_vals: &[&F],
+) -> Vec<F> {
+    gates
+        .iter()
+        .flat_map(|gate| gate.eval_selector_multipliers(wire_vals))
+        .collect()
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Register a custom gate and return its id.
+    ///
+    /// The gates are not serialized with the constraint system, so a verifier that
+    /// loads the constraint system should register the same gates in the same order,
+    /// otherwise the prover and the verifier return [UzkgeError::CustomGateMismatch].
+    pub fn add_custom_gate(&mut self, gate: Arc<dyn CustomGate<F>>) -> usize {
+        assert!(
+            gate.degree() <= N_WIRES_PER_GATE,
+            "the degree of the custom gate {} is too large",
+            gate.name()
+        );
+        self.custom_gates.push(gate);
+        self.custom_gates.len() - 1
+    }
+
+    /// The number of the selectors of all the custom gates.
+    pub fn num_custom_selectors(&self) -> usize {
+        self.custom_gates.iter().map(|g| g.num_selectors()).sum()
+    }
+
+    /// Insert a gate that is only constrained by the custom gate `gate_id`.
+    pub fn insert_custom_gate(
+        &mut self,
+        gate_id: usize,
+        wires: &[VarIndex; N_WIRES_PER_GATE],
+        selectors: &[F],
+    ) {
+        assert!(gate_id < self.custom_gates.len(), "custom gate not found");
+        assert_eq!(
+            selectors.len(),
+            self.custom_gates[gate_id].num_selectors(),
+            "wrong number of the custom selectors"
+        );
+        for var in wires.iter() {
+            assert!(*var < self.num_vars, "variable index out of bound");
+        }
+
+        let zero = F::ZERO;
+        self.push_add_selectors(zero, zero, zero, zero);
+        self.push_mul_selectors(zero, zero);
+        self.push_constant_selector(zero);
+        self.push_ecc_selector(zero);
+        self.push_out_selector(zero);
+
+        for (i, var) in wires.iter().enumerate() {
+            self.wiring[i].push(*var);
+        }
+        self.custom_constraint_indices
+            .push((self.size, gate_id, selectors.to_vec()));
+        self.finish_new_gate();
+
+        // all the wires are used by the custom constraint, even with zero selectors.
+        #[cfg(feature = "debug")]
+        for var in wires.iter() {
+            self.witness_backtrace.remove(var);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::{rand::SeedableRng, sync::Arc, One, Zero};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        errors::UzkgeError,
+        plonk::{
+            constraint_system::{custom_gate::CustomGate, ConstraintSystem, TurboCS},
+            indexer::indexer,
+            prover::prover,
+            verifier::verifier,
+        },
+        poly_commit::kzg_poly_commitment::KZGCommitmentScheme,
+        utils::transcript::{Transcript, TranscriptScheme},
+    };
+
+    /// The gate `q_pow5 * (w1^5 - wo) + q_sq * (w1^2 + w2^2 - wo) = 0`.
+    #[derive(Debug)]
+    struct PowGate;
+
+    impl CustomGate<Fr> for PowGate {
+        fn name(&self) -> &str {
+            "pow"
+        }
+
+        fn num_selectors(&self) -> usize {
+            2
+        }
+
+        fn degree(&self) -> usize {
+            5
+        }
+
+        fn eval_selector_multipliers(&self, wire_vals: &[&Fr]) -> Vec<Fr> {
+            let w1 = *wire_vals[0];
+            let w2 = *wire_vals[1];
+            let wo = *wire_vals[4];
+            let w1_square = w1 * w1;
+            vec![w1_square * w1_square * w1 - wo, w1_square + w2 * w2 - wo]
+        }
+    }
+
+    fn build_cs(pow5: u64, square: u64) -> (TurboCS<Fr>, Vec<Fr>) {
+        let mut cs = TurboCS::<Fr>::new();
+        let gate_id = cs.add_custom_gate(Arc::new(PowGate));
+        let zero = cs.zero_var();
+
+        let a = cs.new_variable(Fr::from(3u64));
+        let b = cs.new_variable(Fr::from(4u64));
+        let c = cs.new_variable(Fr::from(pow5));
+        let d = cs.new_variable(Fr::from(square));
+        cs.insert_custom_gate(gate_id, &[a, zero, zero, zero, c], &[Fr::one(), Fr::zero()]);
+        cs.insert_custom_gate(gate_id, &[a, b, zero, zero, d], &[Fr::zero(), Fr::one()]);
+        cs.prepare_pi_variable(d);
+        cs.pad();
+
+        let witness = cs.get_and_clear_witness();
+        (cs, witness)
+    }
+
+    #[test]
+    fn test_custom_gate_verify_witness() {
+        let (cs, witness) = build_cs(243, 25);
+        assert!(cs.verify_witness(&witness, &[Fr::from(25u64)]).is_ok());
+
+        let (cs, witness) = build_cs(242, 25);
+        assert!(cs.verify_witness(&witness, &[Fr::from(25u64)]).is_err());
+    }
+
+    #[test]
+    fn test_custom_gate_prove_and_verify() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(40, &mut prng);
+
+        let (cs, witness) = build_cs(243, 25);
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+        let mut verifier_cs = cs.shrink_to_verifier_only();
+
+        let proof = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        assert!(verifier(
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &verifier_cs,
+            &verifier_params,
+            &[Fr::from(25u64)],
+            &proof,
+        )
+        .is_ok());
+        assert!(verifier(
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &verifier_cs,
+            &verifier_params,
+            &[Fr::from(26u64)],
+            &proof,
+        )
+        .is_err());
+
+        // a verifier that does not register the custom gate rejects the proof.
+        verifier_cs.custom_gates.clear();
+        assert_eq!(
+            verifier(
+                &mut Transcript::new(b"Test"),
+                &pcs,
+                &verifier_cs,
+                &verifier_params,
+                &[Fr::from(25u64)],
+                &proof,
+            ),
+            Err(UzkgeError::CustomGateMismatch(2))
+        );
+
+        // so does a constraint system loaded without its custom gates.
+        let bytes = bincode::serialize(&cs).unwrap();
+        let loaded_cs: TurboCS<Fr> = bincode::deserialize(&bytes).unwrap();
+        assert!(loaded_cs.custom_gates.is_empty());
+        assert_eq!(
+            prover(
+                &mut prng,
+                &mut Transcript::new(b"Test"),
+                &pcs,
+                &loaded_cs,
+                &prover_params,
+                &witness,
+            )
+            .unwrap_err(),
+            UzkgeError::CustomGateMismatch(2)
+        );
+    }
+}
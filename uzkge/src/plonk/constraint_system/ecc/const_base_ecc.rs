@@ -9,7 +9,7 @@ use crate::plonk::constraint_system::{TurboCS, VarIndex};
 /// The function compute
 /// {4^i * [G]}_{i=0..n-1}, {2 * 4^i * [G]}_{i=0..n-1}, and {3 * 4^i * [G]}_{i=0..n-1}
 /// [G] is represented in extended form because doubling/addition is more efficient.
-fn compute_base_multiples<T: TECurveConfig>(
+pub(crate) fn compute_base_multiples<T: TECurveConfig>(
     base: Projective<T>,
     n: usize,
 ) -> Vec<Vec<Projective<T>>> {
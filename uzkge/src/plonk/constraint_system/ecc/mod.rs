@@ -4,6 +4,9 @@ pub mod const_base_ecc;
 /// Module for ECC where the base is not a constant.
 pub mod nonconst_base_ecc;
 
+/// Module for ECC of a short Weierstrass curve over a non-native field.
+pub mod non_native_ecc;
+
 use ark_ec::twisted_edwards::{Affine, Projective, TECurveConfig};
 use ark_ff::PrimeField;
 
@@ -0,0 +1,386 @@
+use ark_ec::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{AdditiveGroup, Field, PrimeField};
+use ark_std::Zero;
+
+use crate::plonk::constraint_system::{non_native::NonNativeVar, TurboCS, VarIndex};
+
+/// The number of bits of a window of the scalars of [TurboCS::non_native_msm].
+pub const NON_NATIVE_MSM_WINDOW_BITS: usize = 4;
+
+/// The variables of an affine point of the short Weierstrass curve `C`, whose coordinates
+/// are elements of the non-native base field of `C`.
+///
+/// The identity has no affine coordinates, so a point variable is never the identity.
+pub struct NonNativePointVar<C: SWCurveConfig>
+where
+    C::BaseField: PrimeField,
+{
+    x: NonNativeVar<C::BaseField>,
+    y: NonNativeVar<C::BaseField>,
+}
+
+impl<C: SWCurveConfig> Clone for NonNativePointVar<C>
+where
+    C::BaseField: PrimeField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+        }
+    }
+}
+
+impl<C: SWCurveConfig> NonNativePointVar<C>
+where
+    C::BaseField: PrimeField,
+{
+    /// Create a point variable of the coordinates. The coordinates are not checked to be on
+    /// the curve, see [TurboCS::non_native_enforce_on_curve].
+    pub fn new(x: NonNativeVar<C::BaseField>, y: NonNativeVar<C::BaseField>) -> Self {
+        Self { x, y }
+    }
+
+    /// Return x-coordinate of the point variable.
+    pub fn get_x(&self) -> &NonNativeVar<C::BaseField> {
+        &self.x
+    }
+
+    /// Return y-coordinate of the point variable.
+    pub fn get_y(&self) -> &NonNativeVar<C::BaseField> {
+        &self.y
+    }
+
+    /// Return the point value.
+    pub fn get_point(&self) -> Affine<C> {
+        Affine::new_unchecked(self.x.get_value(), self.y.get_value())
+    }
+}
+
+/// A point of `C` of an unknown discrete logarithm in practice, derived from the label.
+fn offset_point<C: SWCurveConfig>(label: &[u8]) -> Affine<C> {
+    (C::GENERATOR * C::ScalarField::from_le_bytes_mod_order(label)).into_affine()
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Create variables for a point of `C`, and enforce that the point is on the curve.
+    pub fn new_non_native_point_variable<C: SWCurveConfig>(
+        &mut self,
+        point: &Affine<C>,
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        assert!(!point.is_zero(), "the identity has no affine coordinates");
+        let x = self.new_non_native_variable(point.x);
+        let y = self.new_non_native_variable(point.y);
+        let point_var = NonNativePointVar { x, y };
+        self.non_native_enforce_on_curve(&point_var);
+        point_var
+    }
+
+    /// Create variables for a constant point of `C`.
+    pub fn new_non_native_point_constant<C: SWCurveConfig>(
+        &mut self,
+        point: &Affine<C>,
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        assert!(!point.is_zero(), "the identity has no affine coordinates");
+        let x = self.new_non_native_constant(point.x);
+        let y = self.new_non_native_constant(point.y);
+        NonNativePointVar { x, y }
+    }
+
+    /// Enforce `y^2 = x^3 + b` for the point, where `a = 0` for the curve `C`.
+    pub fn non_native_enforce_on_curve<C: SWCurveConfig>(&mut self, point: &NonNativePointVar<C>)
+    where
+        C::BaseField: PrimeField,
+    {
+        assert!(C::COEFF_A.is_zero(), "the curve is not of a = 0");
+        let x_sq = self.non_native_mul(&point.x, &point.x);
+        self.non_native_enforce_relation(
+            &[(1, &point.y, &point.y), (-1, &x_sq, &point.x)],
+            &[],
+            &(-C::COEFF_B),
+        );
+    }
+
+    /// Return `b` if `bit` is one and `a` if `bit` is zero, for a boolean variable `bit`.
+    pub fn non_native_point_select<C: SWCurveConfig>(
+        &mut self,
+        a: &NonNativePointVar<C>,
+        b: &NonNativePointVar<C>,
+        bit: VarIndex,
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        let x = self.non_native_select(&a.x, &b.x, bit);
+        let y = self.non_native_select(&a.y, &b.y, bit);
+        NonNativePointVar { x, y }
+    }
+
+    /// Return `a + b` for the points `a` and `b` of distinct x-coordinates, which is
+    /// enforced, i.e., `a != b` and `a != -b`.
+    pub fn non_native_point_add<C: SWCurveConfig>(
+        &mut self,
+        a: &NonNativePointVar<C>,
+        b: &NonNativePointVar<C>,
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        let (x1, y1) = (a.x.get_value(), a.y.get_value());
+        let (x2, y2) = (b.x.get_value(), b.y.get_value());
+        let inv = (x2 - x1).inverse().unwrap_or(C::BaseField::ZERO);
+        let lambda = (y2 - y1) * inv;
+        let x3 = lambda.square() - x1 - x2;
+        let y3 = lambda * (x1 - x3) - y1;
+
+        let inv_var = self.new_non_native_variable(inv);
+        let lambda_var = self.new_non_native_variable(lambda);
+        let x3_var = self.new_non_native_variable(x3);
+        let y3_var = self.new_non_native_variable(y3);
+
+        // (x2 - x1) * inv = 1
+        self.non_native_enforce_relation(
+            &[(1, &b.x, &inv_var), (-1, &a.x, &inv_var)],
+            &[],
+            &(-C::BaseField::ONE),
+        );
+        // lambda * (x2 - x1) = y2 - y1
+        self.non_native_enforce_relation(
+            &[(1, &lambda_var, &b.x), (-1, &lambda_var, &a.x)],
+            &[(-1, &b.y), (1, &a.y)],
+            &C::BaseField::ZERO,
+        );
+        // x3 = lambda^2 - x1 - x2
+        self.non_native_enforce_relation(
+            &[(1, &lambda_var, &lambda_var)],
+            &[(-1, &a.x), (-1, &b.x), (-1, &x3_var)],
+            &C::BaseField::ZERO,
+        );
+        // y3 = lambda * (x1 - x3) - y1
+        self.non_native_enforce_relation(
+            &[(1, &lambda_var, &a.x), (-1, &lambda_var, &x3_var)],
+            &[(-1, &a.y), (-1, &y3_var)],
+            &C::BaseField::ZERO,
+        );
+
+        NonNativePointVar {
+            x: x3_var,
+            y: y3_var,
+        }
+    }
+
+    /// Return `2 * a` for a point `a` on the curve `C` of `a = 0`.
+    ///
+    /// The y-coordinate of a point on the curve is never zero when the order of the curve
+    /// is odd, so the slope is unique.
+    pub fn non_native_point_double<C: SWCurveConfig>(
+        &mut self,
+        a: &NonNativePointVar<C>,
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        let (x1, y1) = (a.x.get_value(), a.y.get_value());
+        let inv = y1.double().inverse().unwrap_or(C::BaseField::ZERO);
+        let lambda = x1.square() * C::BaseField::from(3u8) * inv;
+        let x3 = lambda.square() - x1.double();
+        let y3 = lambda * (x1 - x3) - y1;
+
+        let lambda_var = self.new_non_native_variable(lambda);
+        let x3_var = self.new_non_native_variable(x3);
+        let y3_var = self.new_non_native_variable(y3);
+
+        // 2 * lambda * y1 = 3 * x1^2
+        self.non_native_enforce_relation(
+            &[(2, &lambda_var, &a.y), (-3, &a.x, &a.x)],
+            &[],
+            &C::BaseField::ZERO,
+        );
+        // x3 = lambda^2 - 2 * x1
+        self.non_native_enforce_relation(
+            &[(1, &lambda_var, &lambda_var)],
+            &[(-2, &a.x), (-1, &x3_var)],
+            &C::BaseField::ZERO,
+        );
+        // y3 = lambda * (x1 - x3) - y1
+        self.non_native_enforce_relation(
+            &[(1, &lambda_var, &a.x), (-1, &lambda_var, &x3_var)],
+            &[(-1, &a.y), (-1, &y3_var)],
+            &C::BaseField::ZERO,
+        );
+
+        NonNativePointVar {
+            x: x3_var,
+            y: y3_var,
+        }
+    }
+
+    /// Return `sum_i scalars_i * points_i` for the points of the prime-order curve `C`
+    /// whose scalar field is the native field.
+    ///
+    /// The sum is computed by windows of [NON_NATIVE_MSM_WINDOW_BITS] bits. The tables
+    /// and the accumulator are shifted by constant points, which are removed at the end,
+    /// so that no addition hits the identity or a doubling except with a negligible
+    /// probability for the points independent of the constant points. Such an addition
+    /// makes the witness unsatisfiable, it never makes a wrong sum satisfiable.
+    pub fn non_native_msm<C: SWCurveConfig<ScalarField = F>>(
+        &mut self,
+        points: &[NonNativePointVar<C>],
+        scalars: &[VarIndex],
+    ) -> NonNativePointVar<C>
+    where
+        C::BaseField: PrimeField,
+    {
+        assert_eq!(points.len(), scalars.len());
+        assert!(!points.is_empty());
+        assert!(
+            C::COFACTOR.iter().skip(1).all(|x| *x == 0) && C::COFACTOR[0] == 1,
+            "the curve is not of a prime order"
+        );
+
+        let n_windows = (F::MODULUS_BIT_SIZE as usize).div_ceil(NON_NATIVE_MSM_WINDOW_BITS);
+        let table_offset = offset_point::<C>(b"uzkge non-native msm table");
+        let acc_offset = offset_point::<C>(b"uzkge non-native msm accumulator");
+
+        // tables[i][d] = table_offset + d * points[i]
+        let table_offset_var = self.new_non_native_point_constant(&table_offset);
+        let tables: Vec<Vec<NonNativePointVar<C>>> = points
+            .iter()
+            .map(|point| {
+                let mut table = vec![table_offset_var.clone()];
+                for _ in 1..1 << NON_NATIVE_MSM_WINDOW_BITS {
+                    let entry = self.non_native_point_add(table.last().unwrap(), point);
+                    table.push(entry);
+                }
+                table
+            })
+            .collect();
+        let bits: Vec<Vec<VarIndex>> = scalars
+            .iter()
+            .map(|scalar| self.range_check(*scalar, n_windows * NON_NATIVE_MSM_WINDOW_BITS))
+            .collect();
+
+        let mut acc = self.new_non_native_point_constant(&acc_offset);
+        for w in (0..n_windows).rev() {
+            if w != n_windows - 1 {
+                for _ in 0..NON_NATIVE_MSM_WINDOW_BITS {
+                    acc = self.non_native_point_double(&acc);
+                }
+            }
+            for (table, bits) in tables.iter().zip(bits.iter()) {
+                let window =
+                    &bits[w * NON_NATIVE_MSM_WINDOW_BITS..(w + 1) * NON_NATIVE_MSM_WINDOW_BITS];
+                let mut entries = table.clone();
+                for bit in window {
+                    entries = entries
+                        .chunks(2)
+                        .map(|pair| self.non_native_point_select(&pair[0], &pair[1], *bit))
+                        .collect();
+                }
+                acc = self.non_native_point_add(&acc, &entries[0]);
+            }
+        }
+
+        // remove acc_offset * 2^((n_windows - 1) * w) + table_offset * n * sum_k 2^(k * w)
+        let window_size = F::from(1u64 << NON_NATIVE_MSM_WINDOW_BITS);
+        let mut shift = F::ONE;
+        let mut window_sum = F::ZERO;
+        for _ in 0..n_windows {
+            window_sum += shift;
+            shift *= window_size;
+        }
+        let acc_shift = shift / window_size;
+        let correction =
+            acc_offset * acc_shift + table_offset * (window_sum * F::from(points.len() as u64));
+        let correction_var = self.new_non_native_point_constant(&(-correction).into_affine());
+        self.non_native_point_add(&acc, &correction_var)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_ec::CurveGroup;
+    use ark_ff::{Field, UniformRand};
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::plonk::constraint_system::TurboCS;
+
+    #[test]
+    fn test_non_native_point_ops() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let a = G1Projective::rand(&mut prng).into_affine();
+        let b = G1Projective::rand(&mut prng).into_affine();
+
+        let mut cs = TurboCS::<Fr>::new();
+        let a_var = cs.new_non_native_point_variable(&a);
+        let b_var = cs.new_non_native_point_variable(&b);
+        let sum_var = cs.non_native_point_add(&a_var, &b_var);
+        let double_var = cs.non_native_point_double(&a_var);
+        let bit = cs.new_variable(Fr::ONE);
+        cs.insert_boolean_gate(bit);
+        let selected_var = cs.non_native_point_select(&a_var, &b_var, bit);
+        assert_eq!(sum_var.get_point(), (a + b).into_affine());
+        assert_eq!(double_var.get_point(), (a + a).into_affine());
+        assert_eq!(selected_var.get_point(), b);
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        // a wrong sum.
+        let mut bad_witness = witness.clone();
+        bad_witness[sum_var.get_x().get_limbs()[0]] += Fr::ONE;
+        assert!(cs.verify_witness(&bad_witness, &[]).is_err());
+
+        // a point not on the curve.
+        let mut bad_witness = witness.clone();
+        bad_witness[a_var.get_y().get_limbs()[0]] += Fr::ONE;
+        assert!(cs.verify_witness(&bad_witness, &[]).is_err());
+
+        // the addition of a point to itself.
+        let mut cs = TurboCS::<Fr>::new();
+        let a_var = cs.new_non_native_point_variable(&a);
+        cs.non_native_point_add(&a_var, &a_var);
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+    }
+
+    #[test]
+    fn test_non_native_msm() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let points: Vec<G1Affine> = (0..2)
+            .map(|_| G1Projective::rand(&mut prng).into_affine())
+            .collect();
+        let scalars = [Fr::rand(&mut prng), Fr::from(0u64)];
+
+        let mut cs = TurboCS::<Fr>::new();
+        let point_vars: Vec<_> = points
+            .iter()
+            .map(|point| cs.new_non_native_point_variable(point))
+            .collect();
+        let scalar_vars: Vec<_> = scalars.iter().map(|s| cs.new_variable(*s)).collect();
+        let res_var = cs.non_native_msm(&point_vars, &scalar_vars);
+        assert_eq!(
+            res_var.get_point(),
+            (points[0] * scalars[0] + points[1] * scalars[1]).into_affine()
+        );
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        // a wrong scalar.
+        let mut bad_witness = witness.clone();
+        bad_witness[scalar_vars[1]] = Fr::ONE;
+        assert!(cs.verify_witness(&bad_witness, &[]).is_err());
+    }
+}
@@ -46,7 +46,17 @@ impl<F: PrimeField> TurboCS<F> {
         assert!(n_bits > 0, "n_bits is not positive");
 
         let b_scalar_var = self.range_check(scalar_var, n_bits);
+        self.scalar_mul_with_nonconst_base(base_var, base, &b_scalar_var)
+    }
 
+    /// Non-constant-base scalar multiplication with the scalar given by its
+    /// little-endian boolean variables `b_scalar_var`, which are not range-checked here.
+    pub fn scalar_mul_with_nonconst_base<T: TECurveConfig<BaseField = F>>(
+        &mut self,
+        base_var: PointVar,
+        base: Projective<T>,
+        b_scalar_var: &[VarIndex],
+    ) -> PointVar {
         let mut res_ext = self.get_identity();
         let identity = self.get_identity();
         let extended_point = ExtendedPointVar(base_var, base);
@@ -0,0 +1,256 @@
+use ark_ec::{twisted_edwards::TECurveConfig, CurveConfig, PrimeGroup};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsConfig, EdwardsProjective, Fq, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::ops::Mul;
+use num_bigint::BigUint;
+
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    eddsa::EdDSASignature,
+    plonk::constraint_system::{
+        ecc::{const_base_ecc::compute_base_multiples, PointVar},
+        TurboCS, VarIndex,
+    },
+};
+
+/// The number of bits of the challenge, which is an element of the base field.
+const CHALLENGE_BITS: usize = 254;
+
+/// The number of bits of the response, which is an element of the scalar field.
+const RESPONSE_BITS: usize = 252;
+
+/// The variables of an EdDSA signature.
+#[derive(Default, Clone, Copy)]
+pub struct EdDSASignatureVars {
+    /// The commitment of the nonce.
+    pub r: PointVar,
+    /// The response, as an element of the base field.
+    pub s: VarIndex,
+}
+
+impl TurboCS<Fq> {
+    /// Create variables for an EdDSA signature.
+    pub fn new_eddsa_signature_variable(
+        &mut self,
+        signature: &EdDSASignature,
+    ) -> EdDSASignatureVars {
+        let r = self.new_point_variable(signature.r);
+        let s = self.new_variable(Fq::from_le_bytes_mod_order(
+            &signature.s.into_bigint().to_bytes_le(),
+        ));
+        EdDSASignatureVars { r, s }
+    }
+
+    /// Verify the EdDSA signature `sig_vars` of the message `msg_vars` under the public key `pk_var`,
+    /// i.e., `s * G = R + H(R, A, msg) * A`.
+    /// The public key is enforced to be in the prime-order subgroup, and then so is `R` by the equation.
+    pub fn verify_eddsa_signature<H: AnemoiJive<Fq, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        pk_var: &PointVar,
+        msg_vars: &[VarIndex],
+        sig_vars: &EdDSASignatureVars,
+    ) {
+        self.enforce_on_curve(&sig_vars.r);
+        self.enforce_on_curve(pk_var);
+        self.enforce_in_prime_subgroup(pk_var);
+
+        let r = self.get_point(&sig_vars.r);
+        let pk = self.get_point(pk_var);
+
+        // the challenge
+        let mut input_vars = vec![
+            sig_vars.r.get_x(),
+            sig_vars.r.get_y(),
+            pk_var.get_x(),
+            pk_var.get_y(),
+        ];
+        input_vars.extend_from_slice(msg_vars);
+        let input = input_vars
+            .iter()
+            .map(|&var| self.witness[var])
+            .collect::<Vec<_>>();
+        let trace = H::eval_variable_length_hash_with_trace(&input);
+        let c_var = self.new_variable(trace.output);
+        self.anemoi_variable_length_hash::<H>(&trace, &input_vars, c_var);
+
+        // c * A, with the canonical bits of the challenge
+        let c_bits = self.range_check(c_var, CHALLENGE_BITS);
+        self.enforce_bits_le_constant(&c_bits, &(BigUint::from(Fq::MODULUS) - 1u64));
+        let c_pk_var = self.scalar_mul_with_nonconst_base(*pk_var, pk, &c_bits);
+        let c_pk = self.get_point(&c_pk_var);
+
+        // s * G, with the canonical bits of the response to prevent malleability
+        let s_bits = self.range_check(sig_vars.s, RESPONSE_BITS);
+        self.enforce_bits_le_constant(&s_bits, &(BigUint::from(Fr::MODULUS) - 1u64));
+        let bases = compute_base_multiples(EdwardsProjective::generator(), RESPONSE_BITS >> 1);
+        let s_g_var = self.scalar_mul_with_const_bases(&bases[0], &bases[1], &bases[2], &s_bits);
+
+        let rhs_var = self.ecc_add(&sig_vars.r, &c_pk_var, &r, &c_pk);
+        self.equal(s_g_var.get_x(), rhs_var.get_var().get_x());
+        self.equal(s_g_var.get_y(), rhs_var.get_var().get_y());
+    }
+
+    /// Read the value of a point variable from the witness.
+    fn get_point(&self, point_var: &PointVar) -> EdwardsProjective {
+        EdwardsAffine::new_unchecked(
+            self.witness[point_var.get_x()],
+            self.witness[point_var.get_y()],
+        )
+        .into()
+    }
+
+    /// Enforce `a * x^2 + y^2 = 1 + d * x^2 * y^2`.
+    fn enforce_on_curve(&mut self, point_var: &PointVar) {
+        let x2 = self.mul(point_var.get_x(), point_var.get_x());
+        let y2 = self.mul(point_var.get_y(), point_var.get_y());
+        let x2y2 = self.mul(x2, y2);
+        let zero_var = self.zero_var();
+        let one_var = self.one_var();
+        self.insert_lc_gate(
+            &[x2, y2, x2y2, zero_var],
+            one_var,
+            EdwardsConfig::COEFF_A,
+            Fq::from(1u64),
+            -EdwardsConfig::COEFF_D,
+            Fq::from(0u64),
+        );
+    }
+
+    /// Enforce that the point on the curve is in the prime-order subgroup, which is the image of
+    /// the multiplication by the cofactor, with a point `Q` on the curve such that `8 * Q` is the point.
+    fn enforce_in_prime_subgroup(&mut self, point_var: &PointVar) {
+        let cofactor_bits = EdwardsConfig::COFACTOR[0].trailing_zeros();
+        assert_eq!(EdwardsConfig::COFACTOR, &[1 << cofactor_bits]);
+
+        let mut q = self.get_point(point_var).mul(EdwardsConfig::COFACTOR_INV);
+        let mut q_var = self.new_point_variable(q);
+        self.enforce_on_curve(&q_var);
+        for _ in 0..cofactor_bits {
+            let double_var = self.ecc_add(&q_var, &q_var, &q, &q);
+            q = *double_var.get_point();
+            q_var = double_var.into_point_var();
+        }
+        self.equal(q_var.get_x(), point_var.get_x());
+        self.equal(q_var.get_y(), point_var.get_y());
+    }
+
+    /// Enforce that the integer of the little-endian boolean variables `bits` is at most `bound`.
+    /// From the most significant bit, `eq` is one iff all the bits so far equal those of `bound`,
+    /// and a bit must be zero wherever `eq` is one and the bit of `bound` is zero.
+    fn enforce_bits_le_constant(&mut self, bits: &[VarIndex], bound: &BigUint) {
+        assert!(bound.bits() as usize <= bits.len());
+
+        let zero_var = self.zero_var();
+        let mut eq = self.one_var();
+        for (i, &bit) in bits.iter().enumerate().rev() {
+            if bound.bit(i as u64) {
+                eq = self.mul(eq, bit);
+            } else {
+                self.insert_mul_gate(eq, bit, zero_var);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_ec::PrimeGroup;
+    use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fq, Fr};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_std::{ops::Mul, rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        anemoi::AnemoiJive254,
+        eddsa::{eddsa_challenge, EdDSAPublicKey, EdDSASecretKey},
+        plonk::constraint_system::{eddsa::EdDSASignatureVars, TurboCS},
+    };
+
+    fn build(
+        pk: &EdDSAPublicKey,
+        msg: &[Fq],
+        r: ark_ed_on_bn254::EdwardsProjective,
+        s: Fq,
+    ) -> bool {
+        let mut cs = TurboCS::new();
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+
+        let pk_var = cs.new_point_variable(pk.0);
+        let msg_vars = msg.iter().map(|m| cs.new_variable(*m)).collect::<Vec<_>>();
+        let sig_vars = EdDSASignatureVars {
+            r: cs.new_point_variable(r),
+            s: cs.new_variable(s),
+        };
+        cs.prepare_pi_point_variable(pk_var);
+        cs.verify_eddsa_signature::<AnemoiJive254>(&pk_var, &msg_vars, &sig_vars);
+
+        let witness = cs.get_and_clear_witness();
+        let (x, y) = (witness[pk_var.get_x()], witness[pk_var.get_y()]);
+        cs.verify_witness(&witness, &[x, y]).is_ok()
+    }
+
+    #[test]
+    fn test_verify_eddsa_signature() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+
+        let sk = EdDSASecretKey::rand(&mut prng);
+        let pk = sk.public_key();
+        let msg = (0..3).map(|_| Fq::rand(&mut prng)).collect::<Vec<_>>();
+        let signature = sk.sign::<AnemoiJive254>(&msg);
+        let s = Fq::from_le_bytes_mod_order(&signature.s.into_bigint().to_bytes_le());
+
+        assert!(build(&pk, &msg, signature.r, s));
+
+        // wrong message
+        let mut wrong_msg = msg.clone();
+        wrong_msg[0] += Fq::from(1u64);
+        assert!(!build(&pk, &wrong_msg, signature.r, s));
+
+        // wrong public key
+        let other_pk = EdDSASecretKey::rand(&mut prng).public_key();
+        assert!(!build(&other_pk, &msg, signature.r, s));
+
+        // wrong response
+        assert!(!build(&pk, &msg, signature.r, s + Fq::from(1u64)));
+
+        // the non-canonical response s + l
+        let l = Fq::from_le_bytes_mod_order(&Fr::MODULUS.to_bytes_le());
+        assert!(!build(&pk, &msg, signature.r, s + l));
+
+        // a public key with a component of order two, for which the equation holds with an even challenge
+        let torsion = EdwardsAffine::new_unchecked(Fq::from(0u64), -Fq::from(1u64));
+        let small_pk = EdDSAPublicKey(pk.0 + torsion);
+        let (r, nonce, c) = loop {
+            let nonce = Fr::rand(&mut prng);
+            let r = EdwardsProjective::generator().mul(nonce);
+            let c = eddsa_challenge::<AnemoiJive254>(&r, &small_pk, &msg);
+            if c.into_bigint().is_even() {
+                break (r, nonce, c);
+            }
+        };
+        let c = Fr::from_be_bytes_mod_order(&c.into_bigint().to_bytes_be());
+        let s = nonce + c * sk.0;
+        let s = Fq::from_le_bytes_mod_order(&s.into_bigint().to_bytes_le());
+        assert!(!build(&small_pk, &msg, r, s));
+    }
+
+    #[test]
+    fn test_new_eddsa_signature_variable() {
+        let mut prng = ChaChaRng::from_seed([1u8; 32]);
+
+        let sk = EdDSASecretKey::rand(&mut prng);
+        let pk = sk.public_key();
+        let msg = vec![Fq::rand(&mut prng)];
+        let signature = sk.sign::<AnemoiJive254>(&msg);
+
+        let mut cs = TurboCS::new();
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+        let pk_var = cs.new_point_variable(pk.0);
+        let msg_vars = vec![cs.new_variable(msg[0])];
+        let sig_vars = cs.new_eddsa_signature_variable(&signature);
+        cs.verify_eddsa_signature::<AnemoiJive254>(&pk_var, &msg_vars, &sig_vars);
+
+        let witness = cs.get_and_clear_witness();
+        cs.verify_witness(&witness, &[]).unwrap();
+    }
+}
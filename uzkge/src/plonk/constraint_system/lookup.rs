@@ -0,0 +1,444 @@
+use ark_ff::{BigInteger, PrimeField};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::UzkgeError,
+    plonk::constraint_system::{TurboCS, VarIndex},
+    utils::serialization::{ark_deserialize, ark_serialize},
+};
+
+/// The number of columns of a lookup table.
+pub const N_LOOKUP_COLUMNS: usize = 3;
+
+/// The base 2 logarithm of the maximum number of rows of a lookup table, as the tables
+/// fit in the evaluation domain of the circuit.
+pub const MAX_LOOKUP_TABLE_BITS: usize = 20;
+
+/// A lookup table. A gate with lookup constraint requires the values of its first three wires
+/// to be one of the rows.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LookupTable<F: PrimeField> {
+    /// the rows of the table.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub rows: Vec<[F; N_LOOKUP_COLUMNS]>,
+}
+
+impl<F: PrimeField> LookupTable<F> {
+    /// Create a table from its rows.
+    pub fn new(rows: Vec<[F; N_LOOKUP_COLUMNS]>) -> Self {
+        Self { rows }
+    }
+
+    /// Create the table with rows `(x, 0, 0)` for `0 <= x < 2^n_bits`.
+    /// Return an error if `n_bits` is above `MAX_LOOKUP_TABLE_BITS`.
+    pub fn range(n_bits: usize) -> Result<Self, UzkgeError> {
+        if n_bits > MAX_LOOKUP_TABLE_BITS {
+            return Err(UzkgeError::LookupError);
+        }
+        let rows = (0..1u64 << n_bits)
+            .map(|x| [F::from(x), F::ZERO, F::ZERO])
+            .collect();
+        Ok(Self { rows })
+    }
+
+    /// Create the table with rows `(a, b, a ^ b)` for `0 <= a, b < 2^n_bits`.
+    /// Return an error if `2 * n_bits` is above `MAX_LOOKUP_TABLE_BITS`.
+    pub fn xor(n_bits: usize) -> Result<Self, UzkgeError> {
+        if n_bits > MAX_LOOKUP_TABLE_BITS / 2 {
+            return Err(UzkgeError::LookupError);
+        }
+        let mut rows = Vec::with_capacity(1 << (2 * n_bits));
+        for a in 0..1u64 << n_bits {
+            for b in 0..1u64 << n_bits {
+                rows.push([F::from(a), F::from(b), F::from(a ^ b)]);
+            }
+        }
+        Ok(Self { rows })
+    }
+
+    /// Return the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return true if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Return true if `row` is one of the rows of the table.
+    pub fn contains(&self, row: &[F; N_LOOKUP_COLUMNS]) -> bool {
+        self.rows.iter().any(|x| x == row)
+    }
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Register a lookup table and return its id, which starts from one.
+    /// Return an error if the table is empty or has more than `2^MAX_LOOKUP_TABLE_BITS` rows.
+    pub fn register_lookup_table(&mut self, table: LookupTable<F>) -> Result<usize, UzkgeError> {
+        if table.is_empty() || table.len() > 1 << MAX_LOOKUP_TABLE_BITS {
+            return Err(UzkgeError::LookupError);
+        }
+        self.lookup_tables.push(table);
+        Ok(self.lookup_tables.len())
+    }
+
+    /// Return the total number of rows of the registered lookup tables.
+    pub fn lookup_table_rows(&self) -> usize {
+        self.lookup_tables.iter().map(|table| table.len()).sum()
+    }
+
+    /// Return the table `table_id`, or an error if no table is registered with this id.
+    pub fn lookup_table(&self, table_id: usize) -> Result<&LookupTable<F>, UzkgeError> {
+        table_id
+            .checked_sub(1)
+            .and_then(|i| self.lookup_tables.get(i))
+            .ok_or(UzkgeError::LookupError)
+    }
+
+    /// Add a lookup constraint to the last gate: the values of its first three wires
+    /// must be a row of the table `table_id`.
+    pub fn attach_lookup_constraint_to_gate(&mut self, table_id: usize) -> Result<(), UzkgeError> {
+        self.lookup_table(table_id)?;
+        self.push_lookup_constraint(table_id);
+        Ok(())
+    }
+
+    fn push_lookup_constraint(&mut self, table_id: usize) {
+        self.lookup_constraint_indices
+            .push((self.size - 1, table_id));
+
+        // The looked up wires are used even if all the selectors of the gate are zero.
+        #[cfg(feature = "debug")]
+        for i in 0..3 {
+            let var = self.wiring[i][self.size - 1];
+            self.witness_backtrace.remove(&var);
+        }
+    }
+
+    /// Add a gate constraining that `(witness[vars[0]], witness[vars[1]], witness[vars[2]])`
+    /// is a row of the table `table_id`.
+    pub fn insert_lookup_gate(
+        &mut self,
+        vars: &[VarIndex; N_LOOKUP_COLUMNS],
+        table_id: usize,
+    ) -> Result<(), UzkgeError> {
+        self.lookup_table(table_id)?;
+        assert!(
+            vars.iter().all(|&x| x < self.num_vars),
+            "input wire index out of bound"
+        );
+        let zero = F::ZERO;
+        self.push_add_selectors(zero, zero, zero, zero);
+        self.push_mul_selectors(zero, zero);
+        self.push_constant_selector(zero);
+        self.push_ecc_selector(zero);
+        self.push_out_selector(zero);
+
+        for (i, var) in vars.iter().enumerate() {
+            self.wiring[i].push(*var);
+        }
+        self.wiring[3].push(0);
+        self.wiring[4].push(0);
+        self.finish_new_gate();
+        self.push_lookup_constraint(table_id);
+        Ok(())
+    }
+
+    /// Create an output variable holding the third column of the row of the table `table_id`
+    /// that starts with `(witness[left_var], witness[right_var])`, and insert a lookup gate.
+    /// If there is no such row, the output is zero and the witness will not verify.
+    /// Return an error if no table is registered with the id `table_id`.
+    pub fn lookup(
+        &mut self,
+        left_var: VarIndex,
+        right_var: VarIndex,
+        table_id: usize,
+    ) -> Result<VarIndex, UzkgeError> {
+        assert!(left_var < self.num_vars, "left_var index out of bound");
+        assert!(right_var < self.num_vars, "right_var index out of bound");
+        let table = self.lookup_table(table_id)?;
+        let left = self.witness[left_var];
+        let right = self.witness[right_var];
+        let out = table
+            .rows
+            .iter()
+            .find(|row| row[0] == left && row[1] == right)
+            .map(|row| row[2])
+            .unwrap_or(F::ZERO);
+        let out_var = self.new_variable(out);
+        self.insert_lookup_gate(&[left_var, right_var, out_var], table_id)?;
+        Ok(out_var)
+    }
+
+    /// Enforce a range constraint `0 <= witness[var] < 2^n_bits` with lookups:
+    /// 1. Split `witness[var]` into limbs of `limb_bits` bits, each of them being looked up
+    ///    in the range table of `limb_bits` bits, which is registered when needed.
+    /// 2. Recompose `witness[var]` from the limbs, one gate per limb.
+    /// 3. If `n_bits` is not a multiple of `limb_bits`, also look up the most significant
+    ///    limb shifted to the top of the range.
+    /// 4. Return witness indices of the limbs, in little endian form.
+    ///
+    /// Return an error if `limb_bits` is above `MAX_LOOKUP_TABLE_BITS`.
+    pub fn lookup_range_check(
+        &mut self,
+        var: VarIndex,
+        n_bits: usize,
+        limb_bits: usize,
+    ) -> Result<Vec<VarIndex>, UzkgeError> {
+        assert!(var < self.num_vars, "var index out of bound");
+        assert!(n_bits > 0, "the number of bits is zero");
+        assert!(limb_bits > 0, "the number of bits of a limb is zero");
+
+        let range_table = LookupTable::range(limb_bits)?;
+        let table_id = match self.lookup_tables.iter().position(|t| *t == range_table) {
+            Some(pos) => pos + 1,
+            None => self.register_lookup_table(range_table)?,
+        };
+
+        let n_limbs = n_bits.div_ceil(limb_bits);
+        let bits = self.witness[var].into_bigint().to_bits_le();
+        let limbs: Vec<VarIndex> = (0..n_limbs)
+            .map(|i| {
+                let mut value = F::ZERO;
+                let mut base = F::ONE;
+                for bit in bits.iter().skip(i * limb_bits).take(limb_bits) {
+                    if *bit {
+                        value += base;
+                    }
+                    base.double_in_place();
+                }
+                self.new_variable(value)
+            })
+            .collect();
+
+        let zero = F::ZERO;
+        let one = F::ONE;
+        let two = one.double();
+
+        // Wires: (w1, w2, w3, w4, wo) = (limb * 2^{limb_bits - r}, 0, 0, limb, 0)
+        // Selectors: q1 = 1, q4 = -2^{limb_bits - r}
+        let remainder = n_bits % limb_bits;
+        if remainder != 0 {
+            let top_limb = limbs[n_limbs - 1];
+            let shift = two.pow([(limb_bits - remainder) as u64]);
+            let shifted_var = self.new_variable(self.witness[top_limb] * shift);
+            self.push_add_selectors(one, zero, zero, -shift);
+            self.push_mul_selectors(zero, zero);
+            self.push_constant_selector(zero);
+            self.push_ecc_selector(zero);
+            self.push_out_selector(zero);
+
+            self.wiring[0].push(shifted_var);
+            self.wiring[1].push(0);
+            self.wiring[2].push(0);
+            self.wiring[3].push(top_limb);
+            self.wiring[4].push(0);
+            self.finish_new_gate();
+            self.push_lookup_constraint(table_id);
+        }
+
+        // Wires: (w1, w2, w3, w4, wo) = (limb_i, 0, 0, acc_{i+1}, acc_i)
+        // Selectors: q1 = qo = 1, q4 = 2^{limb_bits}
+        let base = two.pow([limb_bits as u64]);
+        let mut acc = self.zero_var();
+        for (i, limb) in limbs.iter().enumerate().rev() {
+            let out_var = if i == 0 {
+                var
+            } else {
+                self.new_variable(self.witness[acc] * base + self.witness[*limb])
+            };
+            self.push_add_selectors(one, zero, zero, base);
+            self.push_mul_selectors(zero, zero);
+            self.push_constant_selector(zero);
+            self.push_ecc_selector(zero);
+            self.push_out_selector(one);
+
+            self.wiring[0].push(*limb);
+            self.wiring[1].push(0);
+            self.wiring[2].push(0);
+            self.wiring[3].push(acc);
+            self.wiring[4].push(out_var);
+            self.finish_new_gate();
+            self.push_lookup_constraint(table_id);
+
+            acc = out_var;
+        }
+
+        Ok(limbs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        errors::UzkgeError,
+        plonk::{
+            constraint_system::{
+                lookup::{LookupTable, MAX_LOOKUP_TABLE_BITS},
+                PlonkLayout, TurboCS,
+            },
+            indexer::{indexer, PlonkProof},
+            prover::prover,
+            verifier::verifier,
+        },
+        poly_commit::kzg_poly_commitment::KZGCommitmentScheme,
+        utils::transcript::{Transcript, TranscriptScheme},
+    };
+
+    #[test]
+    fn test_lookup_range_check() {
+        let mut cs = TurboCS::<Fr>::new();
+        let var = cs.new_variable(Fr::from(0x1234_5678u64));
+        let limbs = cs.lookup_range_check(var, 32, 8).unwrap();
+        assert_eq!(limbs.len(), 4);
+        assert_eq!(cs.witness[limbs[0]], Fr::from(0x78u64));
+        assert_eq!(cs.witness[limbs[3]], Fr::from(0x12u64));
+        assert_eq!(cs.lookup_tables.len(), 1);
+
+        // the range table is registered once.
+        let var = cs.new_variable(Fr::from(1000u64));
+        cs.lookup_range_check(var, 10, 8).unwrap();
+        assert_eq!(cs.lookup_tables.len(), 1);
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        let mut cs = TurboCS::<Fr>::new();
+        let var = cs.new_variable(Fr::from(1024u64));
+        cs.lookup_range_check(var, 10, 8).unwrap();
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_err());
+    }
+
+    #[test]
+    fn test_lookup_table_bounds() {
+        assert!(LookupTable::<Fr>::range(MAX_LOOKUP_TABLE_BITS + 1).is_err());
+        assert!(LookupTable::<Fr>::range(64).is_err());
+        assert!(LookupTable::<Fr>::xor(MAX_LOOKUP_TABLE_BITS / 2 + 1).is_err());
+        assert!(LookupTable::<Fr>::xor(32).is_err());
+        assert_eq!(LookupTable::<Fr>::xor(2).unwrap().len(), 16);
+
+        let mut cs = TurboCS::<Fr>::new();
+        assert_eq!(
+            cs.register_lookup_table(LookupTable::new(vec![])),
+            Err(UzkgeError::LookupError)
+        );
+        let var = cs.new_variable(Fr::from(1u64));
+        assert_eq!(
+            cs.lookup_range_check(var, 64, 64),
+            Err(UzkgeError::LookupError)
+        );
+        assert!(cs.lookup_tables.is_empty());
+    }
+
+    #[test]
+    fn test_lookup_xor() {
+        let mut cs = TurboCS::<Fr>::new();
+        let table_id = cs
+            .register_lookup_table(LookupTable::xor(4).unwrap())
+            .unwrap();
+        let a = cs.new_variable(Fr::from(0b1010u64));
+        let b = cs.new_variable(Fr::from(0b0110u64));
+        let c = cs.lookup(a, b, table_id).unwrap();
+        assert_eq!(cs.witness[c], Fr::from(0b1100u64));
+
+        // unregistered table ids are rejected.
+        assert_eq!(cs.lookup(a, b, 0), Err(UzkgeError::LookupError));
+        assert_eq!(cs.lookup(a, b, 2), Err(UzkgeError::LookupError));
+        assert_eq!(
+            cs.insert_lookup_gate(&[a, b, c], 2),
+            Err(UzkgeError::LookupError)
+        );
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+
+        let mut bad_witness = witness.clone();
+        bad_witness[c] = Fr::from(0b1101u64);
+        assert!(cs.verify_witness(&bad_witness, &[]).is_err());
+    }
+
+    #[test]
+    fn test_lookup_prove_and_verify() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(600, &mut prng);
+
+        let mut cs = TurboCS::<Fr>::new();
+        let xor_table_id = cs
+            .register_lookup_table(LookupTable::xor(3).unwrap())
+            .unwrap();
+        let a = cs.new_variable(Fr::from(5u64));
+        let b = cs.new_variable(Fr::from(3u64));
+        let c = cs.lookup(a, b, xor_table_id).unwrap();
+        let d_value = Fr::from(0xabcdu64);
+        let d = cs.new_variable(d_value);
+        let limbs = cs.lookup_range_check(d, 16, 8).unwrap();
+        cs.prepare_pi_variable(c);
+        cs.prepare_pi_variable(d);
+        cs.pad();
+
+        // the xor table of 64 rows and the range table of 256 rows.
+        assert_eq!(cs.size, 512);
+
+        let witness = cs.get_and_clear_witness();
+        assert!(cs
+            .verify_witness(&witness, &[Fr::from(6u64), d_value])
+            .is_ok());
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+
+        let proof = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        assert_eq!(verifier_params.layout(), PlonkLayout::Lookup);
+        assert_eq!(proof.layout, PlonkLayout::Lookup);
+        let bytes = proof.to_bytes_be();
+        assert_eq!(bytes[0], PlonkLayout::Lookup.to_tag());
+        assert_eq!(
+            PlonkProof::from_bytes_be::<TurboCS<Fr>>(&bytes).unwrap(),
+            proof
+        );
+        assert!(verifier(
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &verifier_params,
+            &[Fr::from(6u64), d_value],
+            &proof,
+        )
+        .is_ok());
+        assert!(verifier(
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &verifier_params,
+            &[Fr::from(7u64), d_value],
+            &proof,
+        )
+        .is_err());
+
+        // a limb out of the range table cannot be proven.
+        let mut bad_witness = witness.clone();
+        bad_witness[limbs[0]] = Fr::from(256u64);
+        let res = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &bad_witness,
+        );
+        assert_eq!(res.unwrap_err(), UzkgeError::LookupError);
+    }
+}
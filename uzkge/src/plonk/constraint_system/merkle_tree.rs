@@ -0,0 +1,165 @@
+use ark_ff::PrimeField;
+
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    merkle_tree::{merkle_tree_salt, MerkleProof},
+    plonk::constraint_system::{TurboCS, VarIndex},
+};
+
+/// The variables of a node in the Merkle path.
+#[derive(Debug, Clone)]
+pub struct MerkleNodeVars {
+    /// The siblings of the node, one for the binary tree and two for the ternary tree.
+    pub siblings: Vec<VarIndex>,
+    /// The boolean variable that the node is the left child.
+    pub is_left_child: VarIndex,
+    /// The boolean variable that the node is the right child.
+    pub is_right_child: VarIndex,
+}
+
+/// The variables of the Merkle path from the leaf to the root.
+#[derive(Debug, Clone)]
+pub struct MerklePathVars {
+    pub nodes: Vec<MerkleNodeVars>,
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Allocate the variables of the Merkle path for the proof in the tree of `arity`.
+    pub fn new_merkle_path_vars(&mut self, proof: &MerkleProof<F>, arity: usize) -> MerklePathVars {
+        assert!(arity == 2 || arity == 3);
+
+        let nodes = proof
+            .siblings
+            .iter()
+            .zip(proof.positions(arity))
+            .map(|(siblings, position)| {
+                assert_eq!(siblings.len(), arity - 1);
+                MerkleNodeVars {
+                    siblings: siblings.iter().map(|s| self.new_variable(*s)).collect(),
+                    is_left_child: self.new_variable(F::from((position == 0) as u64)),
+                    is_right_child: self.new_variable(F::from((position == arity - 1) as u64)),
+                }
+            })
+            .collect();
+
+        MerklePathVars { nodes }
+    }
+
+    /// Create constraints for the root of the Merkle tree from the leaf and the path,
+    /// and return the root variable. The leaf is hashed as in [`crate::merkle_tree::hash_leaf`].
+    pub fn compute_merkle_root<P: AnemoiJive<F, 2usize, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        leaf_var: VarIndex,
+        path: &MerklePathVars,
+    ) -> VarIndex {
+        let salt = merkle_tree_salt(1);
+        let trace = P::eval_jive_with_trace(&[self.witness[leaf_var], F::ZERO], &[F::ZERO, salt]);
+        let zero_var = self.zero_var();
+        let mut node_var = self.jive_crh::<P>(&trace, &[leaf_var, zero_var, zero_var], salt);
+
+        for node in path.nodes.iter() {
+            self.insert_boolean_gate(node.is_left_child);
+            self.insert_boolean_gate(node.is_right_child);
+
+            let children = match node.siblings.len() {
+                2 => {
+                    // exactly one of the positions, so that the node is not both.
+                    let zero_var = self.zero_var();
+                    self.insert_mul_gate(node.is_left_child, node.is_right_child, zero_var);
+
+                    let left = self.select(node.siblings[0], node_var, node.is_left_child);
+                    let mid_or_right = self.select(node_var, node.siblings[1], node.is_right_child);
+                    let mid = self.select(mid_or_right, node.siblings[0], node.is_left_child);
+                    let right = self.select(node.siblings[1], node_var, node.is_right_child);
+                    [left, mid, right]
+                }
+
+                1 => {
+                    // the node is the left or the right child.
+                    let one_var = self.one_var();
+                    self.insert_add_gate(node.is_left_child, node.is_right_child, one_var);
+
+                    let left = self.select(node.siblings[0], node_var, node.is_left_child);
+                    let right = self.select(node_var, node.siblings[0], node.is_left_child);
+                    [left, right, self.zero_var()]
+                }
+
+                _ => panic!("the arity of the Merkle tree should be 2 or 3"),
+            };
+
+            let salt = merkle_tree_salt(node.siblings.len() + 1);
+            let trace = P::eval_jive_with_trace(
+                &[self.witness[children[0]], self.witness[children[1]]],
+                &[self.witness[children[2]], salt],
+            );
+            node_var = self.jive_crh::<P>(&trace, &children, salt);
+        }
+
+        node_var
+    }
+
+    /// Create constraints for the membership of the leaf in the Merkle tree of the root.
+    pub fn merkle_tree_membership<P: AnemoiJive<F, 2usize, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        leaf_var: VarIndex,
+        path: &MerklePathVars,
+        root_var: VarIndex,
+    ) {
+        let computed_root_var = self.compute_merkle_root::<P>(leaf_var, path);
+        self.equal(computed_root_var, root_var);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        anemoi::AnemoiJive254, merkle_tree::MerkleTree, plonk::constraint_system::TurboCS,
+    };
+
+    fn check<const ARITY: usize>() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let leaves: Vec<Fr> = (0..20).map(|_| Fr::rand(&mut prng)).collect();
+        let tree = MerkleTree::<Fr, AnemoiJive254, ARITY>::new(&leaves).unwrap();
+        let root = tree.root();
+
+        for index in [0, 7, 19] {
+            let proof = tree.prove(index).unwrap();
+
+            let mut cs = TurboCS::<Fr>::new();
+            cs.load_anemoi_parameters::<AnemoiJive254>();
+            let leaf_var = cs.new_variable(leaves[index]);
+            let root_var = cs.new_variable(root);
+            let path = cs.new_merkle_path_vars(&proof, ARITY);
+            cs.merkle_tree_membership::<AnemoiJive254>(leaf_var, &path, root_var);
+            cs.prepare_pi_variable(root_var);
+
+            let witness = cs.get_and_clear_witness();
+            assert!(cs.verify_witness(&witness, &[root]).is_ok());
+            assert!(cs
+                .verify_witness(&witness, &[root + Fr::from(1u64)])
+                .is_err());
+
+            // a leaf that is not in the tree.
+            let mut cs = TurboCS::<Fr>::new();
+            cs.load_anemoi_parameters::<AnemoiJive254>();
+            let leaf_var = cs.new_variable(Fr::rand(&mut prng));
+            let root_var = cs.new_variable(root);
+            let path = cs.new_merkle_path_vars(&proof, ARITY);
+            cs.merkle_tree_membership::<AnemoiJive254>(leaf_var, &path, root_var);
+            cs.prepare_pi_variable(root_var);
+
+            let witness = cs.get_and_clear_witness();
+            assert!(cs.verify_witness(&witness, &[root]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_merkle_tree_membership() {
+        check::<2>();
+        check::<3>();
+    }
+}
@@ -4,13 +4,39 @@ pub mod turbo;
 /// Module for the Anemoi-Jive hash function.
 pub mod anemoi;
 
+/// Module for the Anemoi Merkle tree.
+pub mod merkle_tree;
+
 /// Module for shuffle.
 pub mod shuffle;
 
 /// Module for ECC.
 pub mod ecc;
 
+/// Module for non-native field arithmetic.
+pub mod non_native;
+
+/// Module for the EdDSA signature verification.
+pub mod eddsa;
+
+/// Module for user-defined custom gates.
+pub mod custom_gate;
+
+/// Module for the namespaces of gates, for diagnostics.
+pub mod namespace;
+
+/// Module for the circuits with a cached shape.
+pub mod circuit;
+
+/// Module for the recursive PLONK verifier.
+pub mod recursion;
+
+/// Module for lookup tables.
+pub mod lookup;
+
 use ark_ff::PrimeField;
+use ark_std::sync::Arc;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::UzkgeError;
 
@@ -18,7 +44,7 @@ use crate::errors::UzkgeError;
 #[doc(hidden)]
 pub use turbo::TurboCS;
 
-use self::turbo::N_WIRE_SELECTORS;
+use self::{custom_gate::CustomGate, turbo::N_WIRE_SELECTORS};
 
 /// Variable index
 pub type VarIndex = usize;
@@ -26,6 +52,65 @@ pub type VarIndex = usize;
 /// Constraint index
 pub type CsIndex = usize;
 
+/// The gate set of a circuit, which decides the layout of its parameters and proofs.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub enum PlonkLayout {
+    /// The standard gates, without the shuffle remark gates and the lookup gates.
+    #[default]
+    Standard,
+    /// The standard gates and the shuffle remark gates, the proofs additionally carry
+    /// the witness selectors and the ecc selector opening.
+    Shuffle,
+    /// The standard gates and the lookup gates, the proofs additionally carry
+    /// the lookup commitments and openings.
+    Lookup,
+    /// The standard gates, the shuffle remark gates and the lookup gates.
+    ShuffleLookup,
+}
+
+impl PlonkLayout {
+    /// Return the layout with the given gates.
+    pub fn new(shuffle: bool, lookup: bool) -> Self {
+        match (shuffle, lookup) {
+            (false, false) => PlonkLayout::Standard,
+            (true, false) => PlonkLayout::Shuffle,
+            (false, true) => PlonkLayout::Lookup,
+            (true, true) => PlonkLayout::ShuffleLookup,
+        }
+    }
+
+    /// Return the tag of the layout in the proof bytes.
+    pub fn to_tag(&self) -> u8 {
+        match self {
+            PlonkLayout::Standard => 0,
+            PlonkLayout::Shuffle => 1,
+            PlonkLayout::Lookup => 2,
+            PlonkLayout::ShuffleLookup => 3,
+        }
+    }
+
+    /// Decode the layout from its tag.
+    pub fn from_tag(tag: u8) -> Result<Self, UzkgeError> {
+        match tag {
+            0 => Ok(PlonkLayout::Standard),
+            1 => Ok(PlonkLayout::Shuffle),
+            2 => Ok(PlonkLayout::Lookup),
+            3 => Ok(PlonkLayout::ShuffleLookup),
+            _ => Err(UzkgeError::DeserializationError),
+        }
+    }
+
+    /// Return true if the shuffle remark gates are enabled.
+    pub fn has_shuffle(&self) -> bool {
+        matches!(self, PlonkLayout::Shuffle | PlonkLayout::ShuffleLookup)
+    }
+
+    /// Return true if the lookup gates are enabled.
+    pub fn has_lookup(&self) -> bool {
+        matches!(self, PlonkLayout::Lookup | PlonkLayout::ShuffleLookup)
+    }
+}
+
 /// Trait for PLONK constraint systems.
 pub trait ConstraintSystem<F: PrimeField>: Sized {
     /// Return the number of constraints in the system.
@@ -103,6 +188,18 @@ pub trait ConstraintSystem<F: PrimeField>: Sized {
     /// Compute the indices of the constraints that related to shuffle.
     fn shuffle_remark_constraint_indices(&self) -> Vec<CsIndex>;
 
+    /// Return true if the circuit has gates with lookup constraint.
+    fn has_lookup_constraints(&self) -> bool;
+
+    /// Return the gate set of the circuit, the shuffle and lookup gates are only enabled
+    /// when the circuit has shuffle remark constraints and lookup constraints.
+    fn layout(&self) -> PlonkLayout {
+        PlonkLayout::new(
+            !self.shuffle_remark_constraint_indices().is_empty(),
+            self.has_lookup_constraints(),
+        )
+    }
+
     /// Compute the Anemoi selectors.
     fn compute_anemoi_jive_selectors(&self) -> [Vec<F>; 4];
 
@@ -112,6 +209,20 @@ pub trait ConstraintSystem<F: PrimeField>: Sized {
     /// Compute the public key selectors that related to shuffle.
     fn compute_shuffle_public_key_selectors(&self) -> Vec<Vec<F>>;
 
+    /// Compute the lookup selectors, i.e., the flag of the gates with lookup constraint
+    /// and the id of the table they look up.
+    fn compute_lookup_selectors(&self) -> [Vec<F>; 2];
+
+    /// Compute the three columns and the table id column of all the registered lookup tables,
+    /// padded to the size of the constraint system by repeating the last row.
+    fn compute_lookup_table(&self) -> [Vec<F>; 4];
+
+    /// Borrow the registered custom gates.
+    fn custom_gates(&self) -> &[Arc<dyn CustomGate<F>>];
+
+    /// Compute the selectors of the custom gates, concatenated in the order of registration.
+    fn compute_custom_selectors(&self) -> Vec<Vec<F>>;
+
     /// Map the witnesses into the wires of the circuit.
     /// The (i * size + j)-th output element is the value of the i-th wire on the j-th gate.
     fn extend_witness(&self, witness: &[F]) -> Vec<F> {
@@ -124,8 +235,8 @@ pub trait ConstraintSystem<F: PrimeField>: Sized {
         extended
     }
 
-    /// Compute the witness selectors.
-    fn compute_witness_selectors(&self) -> [Vec<F>; N_WIRE_SELECTORS];
+    /// Compute the witness selectors from the values of their variables in `witness`.
+    fn compute_witness_selectors(&self, witness: &[F]) -> [Vec<F>; N_WIRE_SELECTORS];
 
     /// Borrow the (index)-th selector vector.
     fn selector(&self, index: usize) -> Result<&[F], UzkgeError>;
@@ -0,0 +1,146 @@
+use ark_ff::PrimeField;
+use ark_std::{collections::BTreeMap, fmt::Display};
+
+use crate::plonk::constraint_system::{CsIndex, TurboCS};
+
+/// The separator of the nested namespaces in a namespace path.
+pub const NAMESPACE_SEPARATOR: &str = "/";
+
+/// The namespaces are recorded only with the `debug` feature, otherwise entering and leaving
+/// them does nothing, and the name is never formatted, e.g., for `format_args!("card {}", i)`.
+impl<F: PrimeField> TurboCS<F> {
+    /// Enter a namespace, the gates added before the matching `pop_namespace` are labeled
+    /// with the path of all the entered namespaces, e.g., `shuffle/remark card 7`.
+    pub fn push_namespace<S: Display>(&mut self, name: S) {
+        #[cfg(feature = "debug")]
+        {
+            self.namespace_stack.push(name.to_string());
+            self.record_namespace_span();
+        }
+        #[cfg(not(feature = "debug"))]
+        let _ = name;
+    }
+
+    /// Leave the current namespace.
+    pub fn pop_namespace(&mut self) {
+        #[cfg(feature = "debug")]
+        {
+            assert!(!self.namespace_stack.is_empty(), "namespace stack is empty");
+            self.namespace_stack.pop();
+            self.record_namespace_span();
+        }
+    }
+
+    /// Add the gates of `f` in the namespace `name`.
+    pub fn with_namespace<S: Display, R>(&mut self, name: S, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.push_namespace(name);
+        let res = f(self);
+        self.pop_namespace();
+        res
+    }
+
+    /// Return the namespace path of the gate `cs_index`, empty if the gate is in no namespace.
+    #[cfg(feature = "debug")]
+    pub fn namespace_of(&self, cs_index: CsIndex) -> &str {
+        let i = self
+            .namespace_spans
+            .partition_point(|(start, _)| *start <= cs_index);
+        if i == 0 {
+            ""
+        } else {
+            &self.namespace_spans[i - 1].1
+        }
+    }
+
+    /// Return the namespace path of the gate `cs_index`, always empty without the `debug` feature.
+    #[cfg(not(feature = "debug"))]
+    pub fn namespace_of(&self, _cs_index: CsIndex) -> &str {
+        ""
+    }
+
+    /// Return the number of gates of each namespace path, including the gates
+    /// of the nested namespaces. The gates in no namespace are not counted.
+    #[cfg(feature = "debug")]
+    pub fn namespace_gate_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for (i, (start, path)) in self.namespace_spans.iter().enumerate() {
+            let end = self
+                .namespace_spans
+                .get(i + 1)
+                .map_or(self.size, |(next, _)| *next);
+            if path.is_empty() || end <= *start {
+                continue;
+            }
+
+            let mut prefix = String::new();
+            for name in path.split(NAMESPACE_SEPARATOR) {
+                if !prefix.is_empty() {
+                    prefix.push_str(NAMESPACE_SEPARATOR);
+                }
+                prefix.push_str(name);
+                *counts.entry(prefix.clone()).or_insert(0) += end - start;
+            }
+        }
+        counts
+    }
+
+    /// Return the number of gates of each namespace path, always empty without the `debug` feature.
+    #[cfg(not(feature = "debug"))]
+    pub fn namespace_gate_counts(&self) -> BTreeMap<String, usize> {
+        BTreeMap::new()
+    }
+
+    /// Record that the gates from now on are in the current namespace path.
+    #[cfg(feature = "debug")]
+    fn record_namespace_span(&mut self) {
+        let path = self.namespace_stack.join(NAMESPACE_SEPARATOR);
+        match self.namespace_spans.last_mut() {
+            // no gate has been added since the last change.
+            Some((start, last)) if *start == self.size => *last = path,
+            _ => self.namespace_spans.push((self.size, path)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "debug"))]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::plonk::constraint_system::TurboCS;
+
+    #[test]
+    fn test_namespace() {
+        let mut cs = TurboCS::<Fr>::new();
+        let a = cs.new_variable(Fr::from(2u64));
+        let b = cs.new_variable(Fr::from(3u64));
+
+        cs.push_namespace("shuffle");
+        let c = cs.with_namespace("remark card 7", |cs| {
+            let c = cs.mul(a, b);
+            cs.add(c, a)
+        });
+        cs.mul(c, b);
+        cs.pop_namespace();
+        cs.push_namespace("empty");
+        cs.pop_namespace();
+        let e = cs.add(a, b);
+
+        // the two constant gates of `TurboCS::new`.
+        assert_eq!(cs.namespace_of(0), "");
+        assert_eq!(cs.namespace_of(2), "shuffle/remark card 7");
+        assert_eq!(cs.namespace_of(3), "shuffle/remark card 7");
+        assert_eq!(cs.namespace_of(4), "shuffle");
+        assert_eq!(cs.namespace_of(5), "");
+
+        let counts = cs.namespace_gate_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["shuffle"], 3);
+        assert_eq!(counts["shuffle/remark card 7"], 2);
+
+        // the failure is reported with the namespace path of the gate.
+        cs.with_namespace("broken", |cs| cs.insert_mul_gate(a, b, e));
+        let witness = cs.get_and_clear_witness();
+        let err = cs.verify_witness(&witness, &[]).unwrap_err();
+        assert!(format!("{:?}", err).contains("cs index 6 in `broken`"));
+    }
+}
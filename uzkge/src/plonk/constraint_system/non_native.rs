@@ -0,0 +1,626 @@
+use ark_ff::PrimeField;
+use ark_std::{marker::PhantomData, One, Zero};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+
+use crate::plonk::constraint_system::{TurboCS, VarIndex};
+
+/// The number of bits of a limb of a non-native field element.
+pub const NON_NATIVE_LIMB_BITS: usize = 64;
+
+/// The number of limbs of an element of the non-native field `T`.
+pub fn non_native_n_limbs<T: PrimeField>() -> usize {
+    (T::MODULUS_BIT_SIZE as usize).div_ceil(NON_NATIVE_LIMB_BITS)
+}
+
+/// The limbs of an element of the non-native field `T` as elements of the native field `F`,
+/// i.e., the public inputs of a [NonNativeVar] prepared by
+/// [TurboCS::prepare_pi_non_native_variable].
+pub fn non_native_limbs<T: PrimeField, F: PrimeField>(value: &T) -> Vec<F> {
+    let value: BigUint = value.into_bigint().into();
+    to_limbs(&value, non_native_n_limbs::<T>())
+        .into_iter()
+        .map(F::from)
+        .collect()
+}
+
+/// The variables of an element of the non-native field `T`, as little-endian limbs of
+/// [NON_NATIVE_LIMB_BITS] bits, each of them range-checked.
+///
+/// The integer of the limbs is congruent to the element modulo the modulus of `T`,
+/// but it is not necessarily below the modulus, see [TurboCS::non_native_reduce].
+#[derive(Debug, Clone)]
+pub struct NonNativeVar<T: PrimeField> {
+    limbs: Vec<VarIndex>,
+    value: BigUint,
+    _field: PhantomData<T>,
+}
+
+impl<T: PrimeField> NonNativeVar<T> {
+    /// Return the limb variables.
+    pub fn get_limbs(&self) -> &[VarIndex] {
+        &self.limbs
+    }
+
+    /// Return the value of the element.
+    pub fn get_value(&self) -> T {
+        T::from(self.value.clone())
+    }
+}
+
+/// A limb of an integer expression, as a linear combination of variables and a bound
+/// of the absolute value of the integer.
+#[derive(Clone)]
+struct LimbExpr<F: PrimeField> {
+    terms: Vec<(VarIndex, F)>,
+    constant: F,
+    bound: BigUint,
+}
+
+impl<F: PrimeField> LimbExpr<F> {
+    fn zero() -> Self {
+        Self {
+            terms: vec![],
+            constant: F::ZERO,
+            bound: BigUint::zero(),
+        }
+    }
+
+    fn add_term(&mut self, var: VarIndex, coef: i8, bound: &BigUint) {
+        let coef_f = F::from(coef.unsigned_abs());
+        self.terms
+            .push((var, if coef < 0 { coef_f.neg() } else { coef_f }));
+        self.bound += bound;
+    }
+
+    fn add_scaled_term(&mut self, var: VarIndex, coef: &BigUint, bound: &BigUint, negative: bool) {
+        let coef_f = F::from(coef.clone());
+        self.terms
+            .push((var, if negative { coef_f.neg() } else { coef_f }));
+        self.bound += coef * bound;
+    }
+
+    fn add_constant(&mut self, constant: &BigUint, negative: bool) {
+        let constant_f = F::from(constant.clone());
+        if negative {
+            self.constant -= constant_f;
+        } else {
+            self.constant += constant_f;
+        }
+        self.bound += constant;
+    }
+}
+
+fn limb_bound() -> BigUint {
+    (BigUint::one() << NON_NATIVE_LIMB_BITS) - 1u8
+}
+
+fn to_limbs(value: &BigUint, n_limbs: usize) -> Vec<BigUint> {
+    let mask = limb_bound();
+    (0..n_limbs)
+        .map(|i| (value >> (i * NON_NATIVE_LIMB_BITS)) & &mask)
+        .collect()
+}
+
+fn modulus<T: PrimeField>() -> BigUint {
+    T::MODULUS.into()
+}
+
+/// The integer of a native field element, as a signed integer around zero.
+fn to_signed<F: PrimeField>(value: &F) -> BigInt {
+    let value: BigUint = value.into_bigint().into();
+    let modulus = modulus::<F>();
+    if value > &modulus >> 1 {
+        BigInt::from_biguint(Sign::Minus, modulus - value)
+    } else {
+        BigInt::from_biguint(Sign::Plus, value)
+    }
+}
+
+fn from_signed<F: PrimeField>(value: &BigInt) -> F {
+    let abs = F::from(value.magnitude().clone());
+    if value.sign() == Sign::Minus {
+        abs.neg()
+    } else {
+        abs
+    }
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Create variables for an element of the non-native field `T`.
+    pub fn new_non_native_variable<T: PrimeField>(&mut self, value: T) -> NonNativeVar<T> {
+        let value: BigUint = value.into_bigint().into();
+        self.new_non_native_limbs(value, non_native_n_limbs::<T>())
+    }
+
+    /// Create variables for a constant of the non-native field `T`.
+    pub fn new_non_native_constant<T: PrimeField>(&mut self, value: T) -> NonNativeVar<T> {
+        let value: BigUint = value.into_bigint().into();
+        let limbs = to_limbs(&value, non_native_n_limbs::<T>())
+            .into_iter()
+            .map(|limb| {
+                let limb = F::from(limb);
+                let var = self.new_variable(limb);
+                self.insert_constant_gate(var, limb);
+                var
+            })
+            .collect();
+        NonNativeVar {
+            limbs,
+            value,
+            _field: PhantomData,
+        }
+    }
+
+    /// Insert constraints for a public IO element of the non-native field `T`,
+    /// whose public inputs are given by [non_native_limbs].
+    pub fn prepare_pi_non_native_variable<T: PrimeField>(&mut self, var: &NonNativeVar<T>) {
+        for limb in var.limbs.iter() {
+            self.prepare_pi_variable(*limb);
+        }
+    }
+
+    /// Return `a + b` in the non-native field `T`.
+    pub fn non_native_add<T: PrimeField>(
+        &mut self,
+        a: &NonNativeVar<T>,
+        b: &NonNativeVar<T>,
+    ) -> NonNativeVar<T> {
+        let mut expr = self.non_native_expr(a, 1);
+        for (limb_expr, limb) in expr.iter_mut().zip(b.limbs.iter()) {
+            limb_expr.add_term(*limb, 1, &limb_bound());
+        }
+        self.non_native_reduce_expr(&expr, &a.value + &b.value, true)
+            .unwrap()
+    }
+
+    /// Return `a - b` in the non-native field `T`.
+    pub fn non_native_sub<T: PrimeField>(
+        &mut self,
+        a: &NonNativeVar<T>,
+        b: &NonNativeVar<T>,
+    ) -> NonNativeVar<T> {
+        let (expr, value) = self.non_native_sub_expr(a, b);
+        self.non_native_reduce_expr(&expr, value, true).unwrap()
+    }
+
+    /// Return `a * b` in the non-native field `T`.
+    pub fn non_native_mul<T: PrimeField>(
+        &mut self,
+        a: &NonNativeVar<T>,
+        b: &NonNativeVar<T>,
+    ) -> NonNativeVar<T> {
+        let n_limbs = a.limbs.len();
+        let product_bound = limb_bound() * limb_bound();
+        let mut expr = vec![LimbExpr::zero(); 2 * n_limbs - 1];
+        for (i, a_limb) in a.limbs.iter().enumerate() {
+            for (j, b_limb) in b.limbs.iter().enumerate() {
+                let product = self.mul(*a_limb, *b_limb);
+                expr[i + j].add_term(product, 1, &product_bound);
+            }
+        }
+        self.non_native_reduce_expr(&expr, &a.value * &b.value, true)
+            .unwrap()
+    }
+
+    /// Return the element congruent to `a` whose integer is below the modulus of `T`.
+    pub fn non_native_reduce<T: PrimeField>(&mut self, a: &NonNativeVar<T>) -> NonNativeVar<T> {
+        let expr = self.non_native_expr(a, 1);
+        let res = self
+            .non_native_reduce_expr(&expr, a.value.clone(), true)
+            .unwrap();
+        self.non_native_enforce_canonical(&res);
+        res
+    }
+
+    /// Enforce that the integer of `a` is below the modulus of `T`.
+    pub fn non_native_enforce_canonical<T: PrimeField>(&mut self, a: &NonNativeVar<T>) {
+        // a + d = p - 1 with the range-checked limbs of `d`.
+        let max = modulus::<T>() - 1u8;
+        let d_value = if a.value <= max {
+            &max - &a.value
+        } else {
+            BigUint::zero()
+        };
+        let d = self.new_non_native_limbs::<T>(d_value, a.limbs.len());
+
+        let mut expr = self.non_native_expr(a, 1);
+        for ((limb_expr, limb), max_limb) in expr
+            .iter_mut()
+            .zip(d.limbs.iter())
+            .zip(to_limbs(&max, a.limbs.len()).iter())
+        {
+            limb_expr.add_term(*limb, 1, &limb_bound());
+            limb_expr.add_constant(max_limb, true);
+        }
+        self.enforce_zero_integer(&expr);
+    }
+
+    /// Enforce that `a` and `b` are the same element of the non-native field `T`.
+    pub fn non_native_equal<T: PrimeField>(&mut self, a: &NonNativeVar<T>, b: &NonNativeVar<T>) {
+        let (expr, value) = self.non_native_sub_expr(a, b);
+        self.non_native_reduce_expr::<T>(&expr, value, false);
+    }
+
+    /// Return `b` if `bit` is one and `a` if `bit` is zero, for a boolean variable `bit`.
+    pub fn non_native_select<T: PrimeField>(
+        &mut self,
+        a: &NonNativeVar<T>,
+        b: &NonNativeVar<T>,
+        bit: VarIndex,
+    ) -> NonNativeVar<T> {
+        let limbs = a
+            .limbs
+            .iter()
+            .zip(b.limbs.iter())
+            .map(|(a_limb, b_limb)| self.select(*a_limb, *b_limb, bit))
+            .collect();
+        let value = if self.witness[bit].is_zero() {
+            a.value.clone()
+        } else {
+            b.value.clone()
+        };
+        NonNativeVar {
+            limbs,
+            value,
+            _field: PhantomData,
+        }
+    }
+
+    /// Return the element of the non-native field `T` of the little-endian boolean
+    /// variables `bits`, e.g., the bits returned by [TurboCS::range_check].
+    pub fn new_non_native_from_bits<T: PrimeField>(
+        &mut self,
+        bits: &[VarIndex],
+    ) -> NonNativeVar<T> {
+        let n_limbs = non_native_n_limbs::<T>();
+        assert!(bits.len() <= n_limbs * NON_NATIVE_LIMB_BITS);
+
+        let mut limbs = vec![self.zero_var(); n_limbs];
+        for (limb, chunk) in limbs.iter_mut().zip(bits.chunks(NON_NATIVE_LIMB_BITS)) {
+            let terms: Vec<(VarIndex, F)> = chunk
+                .iter()
+                .enumerate()
+                .map(|(i, bit)| (*bit, F::from(BigUint::one() << i)))
+                .collect();
+            *limb = self.linear_sum(&terms, F::ZERO);
+        }
+        let value = limbs.iter().rev().fold(BigUint::zero(), |acc, limb| {
+            let limb: BigUint = self.witness[*limb].into_bigint().into();
+            (acc << NON_NATIVE_LIMB_BITS) + limb
+        });
+        NonNativeVar {
+            limbs,
+            value,
+            _field: PhantomData,
+        }
+    }
+
+    /// Enforce `sum_i c_i * a_i * b_i + sum_j d_j * e_j + constant = 0` in the non-native
+    /// field `T`, for the `products` `(c_i, a_i, b_i)` and the `terms` `(d_j, e_j)`.
+    ///
+    /// This is cheaper than the chain of operations of the same relation, since the
+    /// relation is reduced modulo the modulus of `T` only once.
+    pub fn non_native_enforce_relation<T: PrimeField>(
+        &mut self,
+        products: &[(i8, &NonNativeVar<T>, &NonNativeVar<T>)],
+        terms: &[(i8, &NonNativeVar<T>)],
+        constant: &T,
+    ) {
+        let n_limbs = non_native_n_limbs::<T>();
+        let p = modulus::<T>();
+        let max = (BigUint::one() << (n_limbs * NON_NATIVE_LIMB_BITS)) - 1u8;
+        let product_bound = limb_bound() * limb_bound();
+
+        let n_terms = if products.is_empty() {
+            n_limbs
+        } else {
+            2 * n_limbs - 1
+        };
+        let mut expr = vec![LimbExpr::zero(); n_terms];
+        let mut value = BigInt::zero();
+        let mut neg_bound = BigUint::zero();
+        for (coef, a, b) in products.iter() {
+            for (i, a_limb) in a.limbs.iter().enumerate() {
+                for (j, b_limb) in b.limbs.iter().enumerate() {
+                    let product = self.mul(*a_limb, *b_limb);
+                    expr[i + j].add_scaled_term(
+                        product,
+                        &BigUint::from(coef.unsigned_abs()),
+                        &product_bound,
+                        *coef < 0,
+                    );
+                }
+            }
+            value += BigInt::from(*coef) * BigInt::from(&a.value * &b.value);
+            if *coef < 0 {
+                neg_bound += coef.unsigned_abs() * &max * &max;
+            }
+        }
+        for (coef, a) in terms.iter() {
+            for (limb_expr, limb) in expr.iter_mut().zip(a.limbs.iter()) {
+                limb_expr.add_scaled_term(
+                    *limb,
+                    &BigUint::from(coef.unsigned_abs()),
+                    &limb_bound(),
+                    *coef < 0,
+                );
+            }
+            value += BigInt::from(*coef) * BigInt::from(a.value.clone());
+            if *coef < 0 {
+                neg_bound += coef.unsigned_abs() * &max;
+            }
+        }
+
+        // add the least multiple of the modulus above the negative terms, so that the
+        // expression is nonnegative.
+        let constant: BigUint = constant.into_bigint().into();
+        let offset = neg_bound.div_ceil(&p) * &p + &constant;
+        let n_offset_limbs = (offset.bits() as usize).div_ceil(NON_NATIVE_LIMB_BITS);
+        if expr.len() < n_offset_limbs {
+            expr.resize(n_offset_limbs, LimbExpr::zero());
+        }
+        for (limb_expr, offset_limb) in expr.iter_mut().zip(to_limbs(&offset, n_offset_limbs)) {
+            limb_expr.add_constant(&offset_limb, false);
+        }
+        let value = (value + BigInt::from(offset)).to_biguint().unwrap();
+
+        self.non_native_reduce_expr::<T>(&expr, value, false);
+    }
+
+    /// Create range-checked limbs of the integer `value`.
+    fn new_non_native_limbs<T: PrimeField>(
+        &mut self,
+        value: BigUint,
+        n_limbs: usize,
+    ) -> NonNativeVar<T> {
+        let limbs = to_limbs(&value, n_limbs)
+            .into_iter()
+            .map(|limb| {
+                let var = self.new_variable(F::from(limb));
+                self.range_check(var, NON_NATIVE_LIMB_BITS);
+                var
+            })
+            .collect();
+        NonNativeVar {
+            limbs,
+            value,
+            _field: PhantomData,
+        }
+    }
+
+    /// The integer expression `sign * a`.
+    fn non_native_expr<T: PrimeField>(&self, a: &NonNativeVar<T>, sign: i8) -> Vec<LimbExpr<F>> {
+        a.limbs
+            .iter()
+            .map(|limb| {
+                let mut limb_expr = LimbExpr::zero();
+                limb_expr.add_term(*limb, sign, &limb_bound());
+                limb_expr
+            })
+            .collect()
+    }
+
+    /// The integer expression `a - b + m * p`, where `m * p` is the least multiple of the
+    /// modulus above the integers of the limbs, so that the expression is nonnegative.
+    fn non_native_sub_expr<T: PrimeField>(
+        &self,
+        a: &NonNativeVar<T>,
+        b: &NonNativeVar<T>,
+    ) -> (Vec<LimbExpr<F>>, BigUint) {
+        let n_limbs = a.limbs.len();
+        let p = modulus::<T>();
+        let m = (BigUint::one() << (n_limbs * NON_NATIVE_LIMB_BITS)).div_ceil(&p);
+        let offset = m * &p;
+
+        let mut expr = self.non_native_expr(a, 1);
+        expr.push(LimbExpr::zero());
+        for (limb_expr, limb) in expr.iter_mut().zip(b.limbs.iter()) {
+            limb_expr.add_term(*limb, -1, &limb_bound());
+        }
+        for (limb_expr, offset_limb) in expr.iter_mut().zip(to_limbs(&offset, n_limbs + 1)) {
+            limb_expr.add_constant(&offset_limb, false);
+        }
+        (expr, &a.value + offset - &b.value)
+    }
+
+    /// Enforce `expr = q * p + r` for the nonnegative integer expression `expr` of the
+    /// integer `value`, with the range-checked limbs of `q` and `r`, and return `r`. If
+    /// `with_remainder` is false, `r` is zero and is not returned.
+    fn non_native_reduce_expr<T: PrimeField>(
+        &mut self,
+        expr: &[LimbExpr<F>],
+        value: BigUint,
+        with_remainder: bool,
+    ) -> Option<NonNativeVar<T>> {
+        let p = modulus::<T>();
+        let n_limbs = non_native_n_limbs::<T>();
+        let p_limbs = to_limbs(&p, n_limbs);
+
+        let max_value = expr
+            .iter()
+            .enumerate()
+            .fold(BigUint::zero(), |acc, (i, limb_expr)| {
+                acc + (&limb_expr.bound << (i * NON_NATIVE_LIMB_BITS))
+            });
+        let q_bits = (&max_value / &p).bits() as usize;
+        let n_q_limbs = q_bits.max(1).div_ceil(NON_NATIVE_LIMB_BITS);
+
+        let (q_value, r_value) = value.div_rem(&p);
+        let q = self.new_non_native_limbs::<T>(q_value, n_q_limbs);
+
+        let n_terms = expr.len().max(n_q_limbs + n_limbs - 1).max(n_limbs);
+        let mut diff = expr.to_vec();
+        diff.resize(n_terms, LimbExpr::zero());
+        for (i, q_limb) in q.limbs.iter().enumerate() {
+            for (j, p_limb) in p_limbs.iter().enumerate() {
+                if !p_limb.is_zero() {
+                    diff[i + j].add_scaled_term(*q_limb, p_limb, &limb_bound(), true);
+                }
+            }
+        }
+
+        let r = if with_remainder {
+            let r = self.new_non_native_limbs::<T>(r_value, n_limbs);
+            for (limb_expr, limb) in diff.iter_mut().zip(r.limbs.iter()) {
+                limb_expr.add_term(*limb, -1, &limb_bound());
+            }
+            Some(r)
+        } else {
+            None
+        };
+
+        self.enforce_zero_integer(&diff);
+        r
+    }
+
+    /// Enforce that the integer `sum_k expr[k] * 2^(k * NON_NATIVE_LIMB_BITS)` is zero,
+    /// by the carries between the limbs.
+    ///
+    /// Every equation `expr[k] + carry[k - 1] = carry[k] * 2^NON_NATIVE_LIMB_BITS` is an
+    /// equation of integers since both sides are bounded far below the native modulus.
+    fn enforce_zero_integer(&mut self, expr: &[LimbExpr<F>]) {
+        let native_bound = modulus::<F>() >> 1;
+        let shift = F::from(BigUint::one() << NON_NATIVE_LIMB_BITS);
+        let zero_var = self.zero_var();
+
+        let mut carry_var = zero_var;
+        let mut carry_value = BigInt::zero();
+        let mut carry_bound = BigUint::zero();
+        for (k, limb_expr) in expr.iter().enumerate() {
+            let limb_var = self.linear_sum(&limb_expr.terms, limb_expr.constant);
+            let limb_value = to_signed(&self.witness[limb_var]) + &carry_value;
+
+            if k == expr.len() - 1 {
+                assert!(&limb_expr.bound + &carry_bound < native_bound);
+                self.insert_add_gate(limb_var, carry_var, zero_var);
+                break;
+            }
+
+            carry_bound = (&limb_expr.bound + &carry_bound) >> NON_NATIVE_LIMB_BITS;
+            let carry_bits = (carry_bound.bits() as usize + 1).max(2);
+            let offset = BigUint::one() << (carry_bits - 1);
+            assert!(
+                &limb_expr.bound + (BigUint::one() << (carry_bits + NON_NATIVE_LIMB_BITS + 1))
+                    < native_bound,
+                "the native field is too small for the non-native field"
+            );
+
+            carry_value = limb_value >> NON_NATIVE_LIMB_BITS;
+            let shifted_carry = from_signed::<F>(&carry_value) + F::from(offset.clone());
+            let shifted_carry_var = self.new_variable(shifted_carry);
+            self.range_check(shifted_carry_var, carry_bits);
+
+            // expr[k] + carry[k - 1] - (shifted_carry - offset) * 2^NON_NATIVE_LIMB_BITS = 0
+            let next_carry_var =
+                self.linear_sum(&[(shifted_carry_var, F::ONE)], F::from(offset).neg());
+            self.insert_lc_gate(
+                &[limb_var, carry_var, next_carry_var, zero_var],
+                zero_var,
+                F::ONE,
+                F::ONE,
+                shift.neg(),
+                F::ZERO,
+            );
+            carry_var = next_carry_var;
+        }
+    }
+
+    /// Create a variable of `sum_i coef_i * var_i + constant`.
+    fn linear_sum(&mut self, terms: &[(VarIndex, F)], constant: F) -> VarIndex {
+        let zero_var = self.zero_var();
+        let one_var = self.one_var();
+        let mut terms = terms.to_vec();
+        if !constant.is_zero() {
+            terms.push((one_var, constant));
+        }
+        let mut res = zero_var;
+        for chunk in terms.chunks(3) {
+            let mut wires = [res, zero_var, zero_var, zero_var];
+            let mut coefs = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+            for (i, (var, coef)) in chunk.iter().enumerate() {
+                wires[i + 1] = *var;
+                coefs[i + 1] = *coef;
+            }
+            res = self.linear_combine(&wires, coefs[0], coefs[1], coefs[2], coefs[3]);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{Fq, Fr};
+    use ark_ff::{Field, PrimeField};
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::plonk::constraint_system::{non_native::non_native_limbs, TurboCS};
+
+    fn check<T: PrimeField>() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        for _ in 0..5 {
+            let a = T::rand(&mut prng);
+            let b = T::rand(&mut prng);
+
+            let mut cs = TurboCS::<Fr>::new();
+            let a_var = cs.new_non_native_variable(a);
+            let b_var = cs.new_non_native_variable(b);
+            let c_var = cs.new_non_native_constant(T::from(7u64));
+
+            let add_var = cs.non_native_add(&a_var, &b_var);
+            let sub_var = cs.non_native_sub(&a_var, &b_var);
+            let mul_var = cs.non_native_mul(&a_var, &b_var);
+            let res_var = cs.non_native_mul(&add_var, &c_var);
+            let res_var = cs.non_native_sub(&res_var, &mul_var);
+            assert_eq!(add_var.get_value(), a + b);
+            assert_eq!(sub_var.get_value(), a - b);
+            assert_eq!(mul_var.get_value(), a * b);
+            assert_eq!(res_var.get_value(), (a + b) * T::from(7u64) - a * b);
+
+            let reduced_var = cs.non_native_reduce(&res_var);
+            let expected_var = cs.new_non_native_variable(reduced_var.get_value());
+            cs.non_native_equal(&reduced_var, &expected_var);
+            cs.non_native_enforce_canonical(&expected_var);
+            cs.prepare_pi_non_native_variable(&mul_var);
+
+            let pi = non_native_limbs::<T, Fr>(&(a * b));
+            let witness = cs.get_and_clear_witness();
+            assert!(cs.verify_witness(&witness, &pi).is_ok());
+
+            // a wrong limb of the product.
+            let mut bad_witness = witness.clone();
+            bad_witness[mul_var.get_limbs()[0]] += Fr::ONE;
+            assert!(cs.verify_witness(&bad_witness, &pi).is_err());
+
+            // a wrong element.
+            let mut cs = TurboCS::<Fr>::new();
+            let a_var = cs.new_non_native_variable(a);
+            let b_var = cs.new_non_native_variable(a + T::ONE);
+            cs.non_native_equal(&a_var, &b_var);
+            let witness = cs.get_and_clear_witness();
+            assert!(cs.verify_witness(&witness, &[]).is_err());
+        }
+
+        // a sum congruent to zero.
+        let a = T::rand(&mut prng);
+        let mut cs = TurboCS::<Fr>::new();
+        let a_var = cs.new_non_native_variable(a);
+        let neg_a_var = cs.new_non_native_variable(-a);
+        let sum_var = cs.non_native_add(&a_var, &neg_a_var);
+        let zero_var = cs.new_non_native_constant(T::ZERO);
+        cs.non_native_equal(&sum_var, &zero_var);
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_non_native_bn254_fq() {
+        check::<Fq>();
+    }
+
+    #[test]
+    fn test_non_native_babyjubjub_fr() {
+        check::<ark_ed_on_bn254::Fr>();
+    }
+}
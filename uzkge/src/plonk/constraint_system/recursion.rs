@@ -0,0 +1,1646 @@
+use ark_bn254::{g1::Config as G1Config, Fq, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::ptr;
+
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    errors::UzkgeError,
+    plonk::{
+        constraint_system::{
+            ecc::non_native_ecc::NonNativePointVar,
+            turbo::{N_SELECTORS, N_WIRES_PER_GATE, N_WIRE_SELECTORS},
+            PlonkLayout, TurboCS, VarIndex,
+        },
+        indexer::{PlonkProof, PlonkVerifierParams},
+        recursion::{verifier_params_commitments, KZGAccumulator},
+    },
+    poly_commit::{
+        field_polynomial::FpPolynomial,
+        kzg_poly_commitment::{KZGCommitment, KZGCommitmentSchemeBN254},
+        pcs::{PolyComScheme, ToBytes},
+    },
+    utils::transcript::pack_bytes,
+};
+
+/// The variables of a commitment, as the elements that the Anemoi transcript absorbs.
+#[derive(Debug, Clone, Default)]
+pub struct CommitmentVars {
+    /// The constant variable of the length of the bytes.
+    pub len_var: VarIndex,
+    /// The limbs of the bytes after the length, see [pack_bytes].
+    pub limbs: Vec<VarIndex>,
+}
+
+/// The Anemoi transcript in the constraint system, which follows
+/// [AnemoiTranscript](crate::utils::transcript::AnemoiTranscript) step by step.
+#[derive(Debug, Clone)]
+pub struct AnemoiTranscriptVars {
+    state: Vec<VarIndex>,
+}
+
+impl AnemoiTranscriptVars {
+    /// Create a new transcript with the domain separator `msg`.
+    pub fn new<F: PrimeField>(cs: &mut TurboCS<F>, msg: &[u8]) -> Self {
+        let mut t = Self { state: vec![] };
+        t.append_message(cs, msg);
+        t
+    }
+
+    /// Append the constant message to the transcript.
+    pub fn append_message<F: PrimeField>(&mut self, cs: &mut TurboCS<F>, msg: &[u8]) {
+        for elem in pack_bytes::<F>(msg) {
+            self.state.push(constant_var(cs, elem));
+        }
+    }
+
+    /// Append the constant u64 to the transcript.
+    pub fn append_u64<F: PrimeField>(&mut self, cs: &mut TurboCS<F>, a: u64) {
+        self.state.push(constant_var(cs, F::from(a)));
+    }
+
+    /// Append a constant byte to the transcript.
+    pub fn append_single_byte<F: PrimeField>(&mut self, cs: &mut TurboCS<F>, b: u8) {
+        self.state.push(constant_var(cs, F::from(b)));
+    }
+
+    /// Append a commitment to the transcript.
+    pub fn append_commitment(&mut self, cm: &CommitmentVars) {
+        self.state.push(cm.len_var);
+        self.state.extend_from_slice(&cm.limbs);
+    }
+
+    /// Append a field element to the transcript.
+    pub fn append_challenge(&mut self, var: VarIndex) {
+        self.state.push(var);
+    }
+
+    /// Generate the challenge for the current transcript,
+    /// and then append it to the transcript.
+    pub fn get_challenge<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        cs: &mut TurboCS<F>,
+    ) -> VarIndex {
+        let input: Vec<F> = self.state.iter().map(|var| cs.witness[*var]).collect();
+        let trace = P::eval_variable_length_hash_with_trace(&input);
+        let challenge_var = cs.new_variable(trace.output);
+        cs.anemoi_variable_length_hash::<P>(&trace, &self.state, challenge_var);
+        self.state = vec![challenge_var];
+        challenge_var
+    }
+}
+
+/// The variables of a PLONK proof, with the same fields as [PlonkProof].
+#[derive(Debug, Clone)]
+pub struct PlonkProofVars {
+    pub layout: PlonkLayout,
+    pub cm_w_vec: Vec<CommitmentVars>,
+    pub cm_w_sel_vec: Vec<CommitmentVars>,
+    pub cm_t_vec: Vec<CommitmentVars>,
+    pub cm_z: CommitmentVars,
+    pub cm_lookup_f: CommitmentVars,
+    pub cm_lookup_h_vec: Vec<CommitmentVars>,
+    pub cm_lookup_z: CommitmentVars,
+    pub prk_3_poly_eval_zeta: VarIndex,
+    pub prk_4_poly_eval_zeta: VarIndex,
+    pub w_polys_eval_zeta: Vec<VarIndex>,
+    pub w_polys_eval_zeta_omega: Vec<VarIndex>,
+    pub z_eval_zeta_omega: VarIndex,
+    pub s_polys_eval_zeta: Vec<VarIndex>,
+    pub q_ecc_poly_eval_zeta: VarIndex,
+    pub w_sel_polys_eval_zeta: Vec<VarIndex>,
+    pub lookup_f_eval_zeta: VarIndex,
+    pub lookup_table_eval_zeta: VarIndex,
+    pub lookup_table_eval_zeta_omega: VarIndex,
+    pub lookup_h_polys_eval_zeta: Vec<VarIndex>,
+    pub lookup_h1_eval_zeta_omega: VarIndex,
+    pub lookup_z_eval_zeta_omega: VarIndex,
+    pub q_lookup_polys_eval_zeta: Vec<VarIndex>,
+    pub opening_witness_zeta: CommitmentVars,
+    pub opening_witness_zeta_omega: CommitmentVars,
+}
+
+impl PlonkProofVars {
+    /// The commitments of the proof, in the order of
+    /// [proof_commitments](crate::plonk::recursion::proof_commitments).
+    pub fn commitments(&self) -> Vec<&CommitmentVars> {
+        let mut commitments: Vec<&CommitmentVars> = self.cm_w_vec.iter().collect();
+        commitments.extend(self.cm_w_sel_vec.iter());
+        commitments.extend(self.cm_t_vec.iter());
+        commitments.push(&self.cm_z);
+        if self.layout.has_lookup() {
+            commitments.push(&self.cm_lookup_f);
+            commitments.extend(self.cm_lookup_h_vec.iter());
+            commitments.push(&self.cm_lookup_z);
+        }
+        commitments.push(&self.opening_witness_zeta);
+        commitments.push(&self.opening_witness_zeta_omega);
+        commitments
+    }
+}
+
+/// The variables of the deferred pairing check, see [KZGAccumulator].
+#[derive(Clone)]
+pub struct KZGAccumulatorVars {
+    pub left: NonNativePointVar<G1Config>,
+    pub right: NonNativePointVar<G1Config>,
+    pub params: Vec<VarIndex>,
+    pub eval: VarIndex,
+}
+
+impl KZGAccumulatorVars {
+    /// The value of the accumulator in the witness of the constraint system.
+    pub fn get_value(&self, cs: &TurboCS<Fr>) -> KZGAccumulator<KZGCommitmentSchemeBN254> {
+        KZGAccumulator {
+            left: KZGCommitment(self.left.get_point().into()),
+            right: KZGCommitment(self.right.get_point().into()),
+            params: self.params.iter().map(|var| cs.witness[*var]).collect(),
+            eval: cs.witness[self.eval],
+        }
+    }
+
+    /// All the variables, which should be the public inputs of the outer proof, see
+    /// [KZGAccumulator::public_inputs].
+    pub fn vars(&self) -> Vec<VarIndex> {
+        let mut vars = vec![];
+        for point in [&self.left, &self.right] {
+            vars.extend_from_slice(point.get_x().get_limbs());
+            vars.extend_from_slice(point.get_y().get_limbs());
+        }
+        vars.extend_from_slice(&self.params);
+        vars.push(self.eval);
+        vars
+    }
+}
+
+/// The scalars of the deferred pairing check, with the scalars of the commitments of
+/// every proof in the order of [PlonkProofVars::commitments] and the scalars of its
+/// opening witnesses. A missing scalar is zero.
+struct DeferredScalarsVars {
+    params: Vec<VarIndex>,
+    left: Vec<[VarIndex; 2]>,
+    right: Vec<Vec<Option<VarIndex>>>,
+    eval: VarIndex,
+}
+
+/// The points and the scalars of the two multi-scalar multiplications of
+/// [KZGAccumulatorVars], with the scalars of the commitments of the verifier params.
+pub(crate) struct DeferredMsmVars {
+    pub(crate) left_points: Vec<NonNativePointVar<G1Config>>,
+    pub(crate) left_scalars: Vec<VarIndex>,
+    pub(crate) right_points: Vec<NonNativePointVar<G1Config>>,
+    pub(crate) right_scalars: Vec<VarIndex>,
+    pub(crate) params: Vec<VarIndex>,
+    pub(crate) eval: VarIndex,
+}
+
+/// A proof to be verified recursively, with its transcript and public inputs.
+pub type RecursiveInstance<'a> = (AnemoiTranscriptVars, &'a [VarIndex], &'a PlonkProofVars);
+
+/// The linear combination of the commitments of the verifier params and of one proof,
+/// with the coefficients as variables.
+struct MsmVars {
+    coefs: Vec<Option<VarIndex>>,
+}
+
+impl MsmVars {
+    fn new(len: usize) -> Self {
+        Self {
+            coefs: vec![None; len],
+        }
+    }
+
+    fn add_term<F: PrimeField>(&mut self, cs: &mut TurboCS<F>, index: usize, coef_var: VarIndex) {
+        self.coefs[index] = Some(match self.coefs[index] {
+            Some(var) => cs.add(var, coef_var),
+            None => coef_var,
+        });
+    }
+
+    fn sub_term<F: PrimeField>(&mut self, cs: &mut TurboCS<F>, index: usize, coef_var: VarIndex) {
+        let neg_var = mul_constant(cs, coef_var, F::ONE.neg());
+        self.add_term(cs, index, neg_var);
+    }
+
+    fn add_msm<F: PrimeField>(
+        &mut self,
+        cs: &mut TurboCS<F>,
+        other: &MsmVars,
+        factor_var: VarIndex,
+    ) {
+        for (index, coef) in other.coefs.iter().enumerate() {
+            if let Some(coef_var) = coef {
+                let scaled_var = cs.mul(*coef_var, factor_var);
+                self.add_term(cs, index, scaled_var);
+            }
+        }
+    }
+}
+
+/// The opening claims of one proof, as in the verifier.
+struct OpeningClaimsVars {
+    msm: MsmVars,
+    eval: VarIndex,
+    left: [VarIndex; 2],
+    u: VarIndex,
+}
+
+/// The position of `item` in `list`, compared by address.
+fn position<T>(list: &[&T], item: &T) -> usize {
+    list.iter().position(|x| ptr::eq(*x, item)).unwrap()
+}
+
+fn constant_var<F: PrimeField>(cs: &mut TurboCS<F>, value: F) -> VarIndex {
+    let var = cs.new_variable(value);
+    cs.insert_constant_gate(var, value);
+    var
+}
+
+fn mul_constant<F: PrimeField>(cs: &mut TurboCS<F>, var: VarIndex, c: F) -> VarIndex {
+    let zero_var = cs.zero_var();
+    cs.linear_combine(
+        &[var, zero_var, zero_var, zero_var],
+        c,
+        F::ZERO,
+        F::ZERO,
+        F::ZERO,
+    )
+}
+
+fn add_constant<F: PrimeField>(cs: &mut TurboCS<F>, var: VarIndex, c: F) -> VarIndex {
+    let zero_var = cs.zero_var();
+    let one_var = cs.one_var();
+    cs.linear_combine(
+        &[var, one_var, zero_var, zero_var],
+        F::ONE,
+        c,
+        F::ZERO,
+        F::ZERO,
+    )
+}
+
+/// Compute `q` with `q * b = a`, which requires `b` to be nonzero.
+fn div<F: PrimeField>(cs: &mut TurboCS<F>, a: VarIndex, b: VarIndex) -> VarIndex {
+    let b_inv = cs.witness[b].inverse().unwrap_or(F::ZERO);
+    let q = cs.new_variable(cs.witness[a] * b_inv);
+    cs.insert_mul_gate(q, b, a);
+    q
+}
+
+fn pow<F: PrimeField>(cs: &mut TurboCS<F>, var: VarIndex, exp: u64) -> VarIndex {
+    let mut res = cs.one_var();
+    for i in (0..64 - exp.leading_zeros()).rev() {
+        res = cs.mul(res, res);
+        if (exp >> i) & 1 == 1 {
+            res = cs.mul(res, var);
+        }
+    }
+    res
+}
+
+fn product<F: PrimeField>(cs: &mut TurboCS<F>, vars: &[VarIndex]) -> VarIndex {
+    let mut res = vars[0];
+    for var in vars.iter().skip(1) {
+        res = cs.mul(res, *var);
+    }
+    res
+}
+
+/// Compute `sum_i coef_i * var_i`.
+fn sum_with_coefs<F: PrimeField>(cs: &mut TurboCS<F>, terms: &[(VarIndex, F)]) -> VarIndex {
+    let zero_var = cs.zero_var();
+    let mut res = zero_var;
+    for chunk in terms.chunks(3) {
+        let mut wires = [res, zero_var, zero_var, zero_var];
+        let mut coefs = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        for (i, (var, coef)) in chunk.iter().enumerate() {
+            wires[i + 1] = *var;
+            coefs[i + 1] = *coef;
+        }
+        res = cs.linear_combine(&wires, coefs[0], coefs[1], coefs[2], coefs[3]);
+    }
+    res
+}
+
+/// Compute `(one - a)`.
+fn one_minus<F: PrimeField>(cs: &mut TurboCS<F>, var: VarIndex) -> VarIndex {
+    let one_var = cs.one_var();
+    cs.sub(one_var, var)
+}
+
+impl<F: PrimeField> TurboCS<F> {
+    /// Allocate the variables of a proof.
+    pub fn new_plonk_proof_vars<PCS: PolyComScheme<Field = F>>(
+        &mut self,
+        proof: &PlonkProof<PCS>,
+    ) -> PlonkProofVars {
+        let new_vec = |cs: &mut Self, values: &[F]| -> Vec<VarIndex> {
+            values.iter().map(|v| cs.new_variable(*v)).collect()
+        };
+        // the commitments have the same length, whose constant is shared.
+        let len_var = constant_var(self, pack_bytes::<F>(&proof.cm_z.to_transcript_bytes())[0]);
+        let new_cm = |cs: &mut Self, cm: &PCS::Commitment| -> CommitmentVars {
+            cs.new_commitment_vars_with_len(cm, len_var)
+        };
+        let new_cm_vec = |cs: &mut Self, cms: &[PCS::Commitment]| -> Vec<CommitmentVars> {
+            cms.iter().map(|cm| new_cm(cs, cm)).collect()
+        };
+        // the lookup commitments and openings are absent without lookups.
+        let lookup = proof.layout.has_lookup();
+        let new_lookup_var = |cs: &mut Self, value: F| -> VarIndex {
+            if lookup {
+                cs.new_variable(value)
+            } else {
+                cs.zero_var()
+            }
+        };
+        let new_lookup_cm = |cs: &mut Self, cm: &PCS::Commitment| -> CommitmentVars {
+            if lookup {
+                new_cm(cs, cm)
+            } else {
+                CommitmentVars::default()
+            }
+        };
+
+        PlonkProofVars {
+            layout: proof.layout,
+            cm_w_vec: new_cm_vec(self, &proof.cm_w_vec),
+            cm_w_sel_vec: new_cm_vec(self, &proof.cm_w_sel_vec),
+            cm_t_vec: new_cm_vec(self, &proof.cm_t_vec),
+            cm_z: new_cm(self, &proof.cm_z),
+            cm_lookup_f: new_lookup_cm(self, &proof.cm_lookup_f),
+            cm_lookup_h_vec: new_cm_vec(self, &proof.cm_lookup_h_vec),
+            cm_lookup_z: new_lookup_cm(self, &proof.cm_lookup_z),
+            prk_3_poly_eval_zeta: self.new_variable(proof.prk_3_poly_eval_zeta),
+            prk_4_poly_eval_zeta: self.new_variable(proof.prk_4_poly_eval_zeta),
+            w_polys_eval_zeta: new_vec(self, &proof.w_polys_eval_zeta),
+            w_polys_eval_zeta_omega: new_vec(self, &proof.w_polys_eval_zeta_omega),
+            z_eval_zeta_omega: self.new_variable(proof.z_eval_zeta_omega),
+            s_polys_eval_zeta: new_vec(self, &proof.s_polys_eval_zeta),
+            q_ecc_poly_eval_zeta: if proof.layout.has_shuffle() {
+                self.new_variable(proof.q_ecc_poly_eval_zeta)
+            } else {
+                self.zero_var()
+            },
+            w_sel_polys_eval_zeta: new_vec(self, &proof.w_sel_polys_eval_zeta),
+            lookup_f_eval_zeta: new_lookup_var(self, proof.lookup_f_eval_zeta),
+            lookup_table_eval_zeta: new_lookup_var(self, proof.lookup_table_eval_zeta),
+            lookup_table_eval_zeta_omega: new_lookup_var(self, proof.lookup_table_eval_zeta_omega),
+            lookup_h_polys_eval_zeta: new_vec(self, &proof.lookup_h_polys_eval_zeta),
+            lookup_h1_eval_zeta_omega: new_lookup_var(self, proof.lookup_h1_eval_zeta_omega),
+            lookup_z_eval_zeta_omega: new_lookup_var(self, proof.lookup_z_eval_zeta_omega),
+            q_lookup_polys_eval_zeta: new_vec(self, &proof.q_lookup_polys_eval_zeta),
+            opening_witness_zeta: new_cm(self, &proof.opening_witness_zeta),
+            opening_witness_zeta_omega: new_cm(self, &proof.opening_witness_zeta_omega),
+        }
+    }
+
+    /// Allocate the variables of a commitment, with the length as a constant.
+    pub fn new_commitment_vars<C: ToBytes>(&mut self, cm: &C) -> CommitmentVars {
+        let elems = pack_bytes::<F>(&cm.to_transcript_bytes());
+        let len_var = constant_var(self, elems[0]);
+        self.new_commitment_vars_with_len(cm, len_var)
+    }
+
+    /// Allocate the variables of a commitment, with the constant variable of its length.
+    fn new_commitment_vars_with_len<C: ToBytes>(
+        &mut self,
+        cm: &C,
+        len_var: VarIndex,
+    ) -> CommitmentVars {
+        let elems = pack_bytes::<F>(&cm.to_transcript_bytes());
+        assert_eq!(
+            self.witness[len_var], elems[0],
+            "the length of the commitment"
+        );
+        CommitmentVars {
+            len_var,
+            limbs: elems[1..].iter().map(|e| self.new_variable(*e)).collect(),
+        }
+    }
+
+    /// Create constraints for the verifier of the PLONK proofs up to the deferred pairing
+    /// check, whose scalars combine the pairing checks of all the proofs with a challenge
+    /// bound to every proof.
+    fn deferred_scalars_vars<
+        PCS: PolyComScheme<Field = F>,
+        P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>,
+    >(
+        &mut self,
+        params: &PlonkVerifierParams<PCS>,
+        instances: &mut [RecursiveInstance],
+    ) -> Result<DeferredScalarsVars, UzkgeError> {
+        if instances.is_empty() {
+            return Err(UzkgeError::FuncParamsError);
+        }
+        if params.cm_q_vec.len() != N_SELECTORS {
+            return Err(UzkgeError::VerifierParamsError);
+        }
+
+        let mut all_claims = Vec::with_capacity(instances.len());
+        for (transcript, pi_vars, proof_vars) in instances.iter_mut() {
+            all_claims
+                .push(self.opening_claims_vars::<PCS, P>(transcript, params, pi_vars, proof_vars)?);
+        }
+
+        // the claims of several proofs are combined by the powers of a challenge.
+        let challenge = if all_claims.len() > 1 {
+            let mut batch_transcript = AnemoiTranscriptVars::new(self, b"Plonk Aggregation");
+            batch_transcript.append_u64(self, all_claims.len() as u64);
+            for (claims, (_, _, proof_vars)) in all_claims.iter().zip(instances.iter()) {
+                batch_transcript.append_challenge(claims.u);
+                batch_transcript.append_commitment(&proof_vars.opening_witness_zeta);
+                batch_transcript.append_commitment(&proof_vars.opening_witness_zeta_omega);
+            }
+            Some(batch_transcript.get_challenge::<F, P>(self))
+        } else {
+            None
+        };
+
+        let n_params = verifier_params_commitments(params).len();
+        let mut params_msm = MsmVars::new(n_params);
+        let mut left = vec![];
+        let mut right = vec![];
+        let mut evals = vec![];
+        let mut multiplier = self.one_var();
+        for (i, claims) in all_claims.iter().enumerate() {
+            let params_part = MsmVars {
+                coefs: claims.msm.coefs[..n_params].to_vec(),
+            };
+            params_msm.add_msm(self, &params_part, multiplier);
+            right.push(
+                claims.msm.coefs[n_params..]
+                    .iter()
+                    .map(|coef| coef.map(|coef_var| self.mul(coef_var, multiplier)))
+                    .collect(),
+            );
+
+            left.push([
+                self.mul(claims.left[0], multiplier),
+                self.mul(claims.left[1], multiplier),
+            ]);
+            evals.push((self.mul(claims.eval, multiplier), F::ONE));
+
+            if let Some(challenge) = challenge.filter(|_| i + 1 < all_claims.len()) {
+                multiplier = self.mul(multiplier, challenge);
+            }
+        }
+
+        Ok(DeferredScalarsVars {
+            params: params_msm
+                .coefs
+                .iter()
+                .map(|c| c.unwrap_or(self.zero_var()))
+                .collect(),
+            left,
+            right,
+            eval: sum_with_coefs(self, &evals),
+        })
+    }
+
+    /// Create constraints for the verifier up to the pairing check, see `opening_claims`
+    /// of the verifier.
+    fn opening_claims_vars<PCS: PolyComScheme<Field = F>, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        transcript: &mut AnemoiTranscriptVars,
+        params: &PlonkVerifierParams<PCS>,
+        pi_vars: &[VarIndex],
+        proof: &PlonkProofVars,
+    ) -> Result<OpeningClaimsVars, UzkgeError> {
+        if pi_vars.len() != params.public_vars_constraint_indices.len() {
+            return Err(UzkgeError::FuncParamsError);
+        }
+        let shuffle = params.layout().has_shuffle();
+        let lookup = params.layout().has_lookup();
+        let n_wire_selectors = if shuffle { N_WIRE_SELECTORS } else { 0 };
+        if proof.layout != params.layout()
+            || proof.cm_w_sel_vec.len() != n_wire_selectors
+            || proof.w_sel_polys_eval_zeta.len() != n_wire_selectors
+        {
+            return Err(UzkgeError::FuncParamsError);
+        }
+
+        let domain = FpPolynomial::<F>::evaluation_domain(params.cs_size)
+            .ok_or(UzkgeError::GroupNotFound(params.cs_size))?;
+        let root = domain.group_gen;
+
+        let params_commitments = verifier_params_commitments(params);
+        let proof_commitments = proof.commitments();
+        let n_params = params_commitments.len();
+        let params_index = |cm: &PCS::Commitment| position(&params_commitments, cm);
+        let proof_index = |cm: &CommitmentVars| n_params + position(&proof_commitments, cm);
+        let n_points = n_params + proof_commitments.len();
+
+        let zero_var = self.zero_var();
+        let one_var = self.one_var();
+        let one = F::ONE;
+        let minus_one = one.neg();
+
+        // 1. init the transcript, see `transcript_init_plonk`.
+        transcript.append_message(self, b"PLONK");
+        transcript.append_u64(self, params.cs_size as u64);
+        transcript.append_message(self, &F::MODULUS.to_bytes_be());
+        #[allow(unused_mut)]
+        let mut init_commitments: Vec<&PCS::Commitment> = params
+            .cm_q_vec
+            .iter()
+            .chain(params.cm_s_vec.iter())
+            .collect();
+        init_commitments.extend(
+            params
+                .cm_q_lookup_vec
+                .iter()
+                .chain(params.cm_lookup_table_vec.iter()),
+        );
+        for cm in init_commitments.iter() {
+            transcript.append_message(self, &cm.to_transcript_bytes());
+        }
+        let root_var = constant_var(self, root);
+        transcript.append_challenge(root_var);
+        for k in params.k.iter() {
+            let k_var = constant_var(self, *k);
+            transcript.append_challenge(k_var);
+        }
+        for pi_var in pi_vars.iter() {
+            transcript.append_challenge(*pi_var);
+        }
+
+        // 2. compute the challenges, see `compute_challenges`.
+        for cm_w in proof.cm_w_vec.iter() {
+            transcript.append_commitment(cm_w);
+        }
+        for cm_w_sel in proof.cm_w_sel_vec.iter() {
+            transcript.append_commitment(cm_w_sel);
+        }
+        let eta = if lookup {
+            let eta = transcript.get_challenge::<F, P>(self);
+            transcript.append_commitment(&proof.cm_lookup_f);
+            for cm_lookup_h in proof.cm_lookup_h_vec.iter() {
+                transcript.append_commitment(cm_lookup_h);
+            }
+            eta
+        } else {
+            zero_var
+        };
+        let beta = transcript.get_challenge::<F, P>(self);
+        transcript.append_single_byte(self, 0x01);
+        let gamma = transcript.get_challenge::<F, P>(self);
+        let (delta, epsilon) = if lookup {
+            transcript.append_single_byte(self, 0x02);
+            let delta = transcript.get_challenge::<F, P>(self);
+            transcript.append_single_byte(self, 0x03);
+            let epsilon = transcript.get_challenge::<F, P>(self);
+            (delta, epsilon)
+        } else {
+            (zero_var, zero_var)
+        };
+        transcript.append_commitment(&proof.cm_z);
+        if lookup {
+            transcript.append_commitment(&proof.cm_lookup_z);
+        }
+        let alpha = transcript.get_challenge::<F, P>(self);
+        for cm_t in proof.cm_t_vec.iter() {
+            transcript.append_commitment(cm_t);
+        }
+        let zeta = transcript.get_challenge::<F, P>(self);
+        for eval_zeta in proof
+            .w_polys_eval_zeta
+            .iter()
+            .chain(proof.s_polys_eval_zeta.iter())
+        {
+            transcript.append_challenge(*eval_zeta);
+        }
+        for eval_zeta in proof.w_sel_polys_eval_zeta.iter() {
+            transcript.append_challenge(*eval_zeta);
+        }
+        transcript.append_challenge(proof.prk_3_poly_eval_zeta);
+        transcript.append_challenge(proof.prk_4_poly_eval_zeta);
+        transcript.append_challenge(proof.z_eval_zeta_omega);
+        if shuffle {
+            transcript.append_challenge(proof.q_ecc_poly_eval_zeta);
+        }
+        for eval_zeta_omega in proof.w_polys_eval_zeta_omega.iter() {
+            transcript.append_challenge(*eval_zeta_omega);
+        }
+        if lookup {
+            transcript.append_challenge(proof.lookup_f_eval_zeta);
+            transcript.append_challenge(proof.lookup_table_eval_zeta);
+            transcript.append_challenge(proof.lookup_table_eval_zeta_omega);
+            for eval_zeta in proof.lookup_h_polys_eval_zeta.iter() {
+                transcript.append_challenge(*eval_zeta);
+            }
+            transcript.append_challenge(proof.lookup_h1_eval_zeta_omega);
+            transcript.append_challenge(proof.lookup_z_eval_zeta_omega);
+            for eval_zeta in proof.q_lookup_polys_eval_zeta.iter() {
+                transcript.append_challenge(*eval_zeta);
+            }
+        }
+        let u = transcript.get_challenge::<F, P>(self);
+
+        // 3. compute Z_h(\zeta) and L_1(\zeta), see `first_lagrange_poly`.
+        let zeta_n = pow(self, zeta, params.cs_size as u64);
+        let z_h_eval_zeta = add_constant(self, zeta_n, minus_one);
+        let zeta_minus_one = add_constant(self, zeta, minus_one);
+        let first_lagrange_eval_zeta = div(self, z_h_eval_zeta, zeta_minus_one);
+
+        // 4. compute PI(\zeta), see `eval_pi_poly`.
+        let mut pi_terms = vec![];
+        for ((pi_var, constraint_index), lagrange_constant) in pi_vars
+            .iter()
+            .zip(params.public_vars_constraint_indices.iter())
+            .zip(params.lagrange_constants.iter())
+        {
+            let root_to_j = root.pow([*constraint_index as u64]);
+            let denominator = add_constant(self, zeta, root_to_j.neg());
+            let lagrange = div(self, *pi_var, denominator);
+            pi_terms.push((lagrange, *lagrange_constant));
+        }
+        let pi_eval = sum_with_coefs(self, &pi_terms);
+        let pi_eval_zeta = self.mul(pi_eval, z_h_eval_zeta);
+
+        let w = &proof.w_polys_eval_zeta;
+        let w_omega = &proof.w_polys_eval_zeta_omega;
+        let s = &proof.s_polys_eval_zeta;
+        let prk_3 = proof.prk_3_poly_eval_zeta;
+        let prk_4 = proof.prk_4_poly_eval_zeta;
+        let z_omega = proof.z_eval_zeta_omega;
+
+        // the powers of alpha up to the last term of the layout.
+        let n_alpha_pows = if lookup {
+            20
+        } else if shuffle {
+            17
+        } else {
+            10
+        };
+        let mut alpha_pows = vec![one_var, alpha];
+        for i in 2..n_alpha_pows {
+            let next = self.mul(alpha_pows[i - 1], alpha);
+            alpha_pows.push(next);
+        }
+
+        // prod_{i=1..n_wires_per_gate-1}(w_i + beta * s_i + gamma)
+        let mut perm_terms = vec![];
+        for i in 0..N_WIRES_PER_GATE - 1 {
+            let beta_s = self.mul(beta, s[i]);
+            perm_terms.push(sum_with_coefs(
+                self,
+                &[(w[i], one), (beta_s, one), (gamma, one)],
+            ));
+        }
+        let perm_prod = product(self, &perm_terms);
+
+        // 5. compute r(\zeta), see `r_eval_zeta`.
+        let r_eval_zeta = {
+            let g = params.anemoi_generator;
+            let g_inv = params.anemoi_generator_inv;
+            let g_square_plus_one = g.square() + one;
+
+            let w4_gamma = self.add(w[4], gamma);
+            let term1 = product(self, &[alpha, z_omega, perm_prod, w4_gamma]);
+            let term2 = self.mul(first_lagrange_eval_zeta, alpha_pows[2]);
+
+            let w3_w0 = self.add(w[3], w[0]);
+            let w2_w1 = self.add(w[2], w[1]);
+            let w3_2w0 = self.add(w3_w0, w[0]);
+            let w2_2w1 = self.add(w2_w1, w[1]);
+
+            let tmp = self.linear_combine(&[w3_w0, w2_w1, prk_3, zero_var], one, g, one, F::ZERO);
+            let tmp_minus_w = self.sub(tmp, w_omega[2]);
+            let tmp_pow_5 = pow(self, tmp_minus_w, 5);
+            let tmp_square = self.mul(tmp, tmp);
+            let term3_inner = self.linear_combine(
+                &[tmp_pow_5, tmp_square, w3_2w0, w2_2w1],
+                one,
+                g,
+                minus_one,
+                g.neg(),
+            );
+            let term3 = product(self, &[alpha_pows[6], prk_3, term3_inner]);
+
+            let w_omega_2_square = self.mul(w_omega[2], w_omega[2]);
+            let term5_inner = self.linear_combine(
+                &[tmp_pow_5, w_omega_2_square, one_var, w_omega[0]],
+                one,
+                g,
+                g_inv,
+                minus_one,
+            );
+            let term5 = product(self, &[alpha_pows[8], prk_3, term5_inner]);
+
+            let tmp = self.linear_combine(
+                &[w3_w0, w2_w1, prk_4, zero_var],
+                g,
+                g_square_plus_one,
+                one,
+                F::ZERO,
+            );
+            let tmp_minus_w = self.sub(tmp, w[4]);
+            let tmp_pow_5 = pow(self, tmp_minus_w, 5);
+            let tmp_square = self.mul(tmp, tmp);
+            let term4_inner = self.linear_combine(
+                &[tmp_pow_5, tmp_square, w3_2w0, w2_2w1],
+                one,
+                g,
+                g.neg(),
+                g_square_plus_one.neg(),
+            );
+            let term4 = product(self, &[alpha_pows[7], prk_3, term4_inner]);
+
+            let w_4_square = self.mul(w[4], w[4]);
+            let term6_inner = self.linear_combine(
+                &[tmp_pow_5, w_4_square, one_var, w_omega[1]],
+                one,
+                g,
+                g_inv,
+                minus_one,
+            );
+            let term6 = product(self, &[alpha_pows[9], prk_3, term6_inner]);
+
+            #[allow(unused_mut)]
+            let mut terms = vec![
+                (term1, one),
+                (term2, one),
+                (pi_eval_zeta, minus_one),
+                (term3, one),
+                (term4, one),
+                (term5, one),
+                (term6, one),
+            ];
+
+            if shuffle {
+                let w_sel = &proof.w_sel_polys_eval_zeta;
+                let q_ecc = proof.q_ecc_poly_eval_zeta;
+
+                let sels = self.shuffle_selectors(w_sel, q_ecc);
+                let sels_sum = sum_with_coefs(
+                    self,
+                    &sels.iter().map(|sel| (*sel, one)).collect::<Vec<_>>(),
+                );
+                let a10 = self.mul(alpha_pows[10], w_omega[0]);
+                let a11 = self.mul(alpha_pows[11], w_omega[1]);
+                let a12 = self.mul(alpha_pows[12], w_omega[2]);
+                let a13 = self.mul(alpha_pows[13], w[4]);
+                let inner = sum_with_coefs(self, &[(a10, one), (a11, one), (a12, one), (a13, one)]);
+                let term7 = product(self, &[w_sel[2], inner, sels_sum]);
+
+                let one_minus_q_ecc = one_minus(self, q_ecc);
+                let mut bool_terms = vec![];
+                for (i, alpha_pow) in [(0, alpha_pows[14]), (1, alpha_pows[15])] {
+                    let one_minus_sel = one_minus(self, w_sel[i]);
+                    let t0 = product(self, &[q_ecc, w_sel[i], one_minus_sel]);
+                    let t1 = self.mul(one_minus_q_ecc, w_sel[i]);
+                    let t = self.add(t0, t1);
+                    bool_terms.push(self.mul(alpha_pow, t));
+                }
+                let one_minus_sel = one_minus(self, w_sel[2]);
+                let one_plus_sel = add_constant(self, w_sel[2], one);
+                let term10 = product(self, &[alpha_pows[16], q_ecc, one_minus_sel, one_plus_sel]);
+
+                terms.push((term7, minus_one));
+                terms.push((bool_terms[0], minus_one));
+                terms.push((bool_terms[1], minus_one));
+                terms.push((term10, minus_one));
+            }
+
+            if lookup {
+                let one_plus_delta = add_constant(self, delta, one);
+                let epsilon_one_plus_delta = self.mul(epsilon, one_plus_delta);
+                let h1 = proof.lookup_h_polys_eval_zeta[0];
+                let h2 = proof.lookup_h_polys_eval_zeta[1];
+
+                let delta_h2 = self.mul(delta, h2);
+                let t0 = sum_with_coefs(
+                    self,
+                    &[(epsilon_one_plus_delta, one), (h1, one), (delta_h2, one)],
+                );
+                let delta_h1_omega = self.mul(delta, proof.lookup_h1_eval_zeta_omega);
+                let t1 = sum_with_coefs(
+                    self,
+                    &[
+                        (epsilon_one_plus_delta, one),
+                        (h2, one),
+                        (delta_h1_omega, one),
+                    ],
+                );
+                let term11 = product(
+                    self,
+                    &[alpha_pows[17], proof.lookup_z_eval_zeta_omega, t0, t1],
+                );
+                let term12 = self.mul(first_lagrange_eval_zeta, alpha_pows[18]);
+
+                let row = self.compress_lookup_row(
+                    eta,
+                    &[w[0], w[1], w[2], proof.q_lookup_polys_eval_zeta[1]],
+                );
+                let row_minus_f = self.sub(row, proof.lookup_f_eval_zeta);
+                let term13 = product(
+                    self,
+                    &[
+                        alpha_pows[19],
+                        proof.q_lookup_polys_eval_zeta[0],
+                        row_minus_f,
+                    ],
+                );
+
+                terms.push((term11, one));
+                terms.push((term12, one));
+                terms.push((term13, minus_one));
+            }
+
+            sum_with_coefs(self, &terms)
+        };
+
+        // 6. the linearization commitment, see `r_poly_or_comm`.
+        let mut r_msm = MsmVars::new(n_points);
+        {
+            let w0_w1 = self.mul(w[0], w[1]);
+            let w2_w3 = self.mul(w[2], w[3]);
+            let w0_w1_w2_w3_w4 = product(self, &[w0_w1, w2_w3, w[4]]);
+            let minus_w4 = mul_constant(self, w[4], minus_one);
+            let multipliers = [
+                w[0],
+                w[1],
+                w[2],
+                w[3],
+                w0_w1,
+                w2_w3,
+                one_var,
+                w0_w1_w2_w3_w4,
+                minus_w4,
+            ];
+            for (cm, multiplier) in params.cm_q_vec.iter().zip(multipliers.iter()) {
+                r_msm.add_term(self, params_index(cm), *multiplier);
+            }
+
+            let beta_zeta = self.mul(beta, zeta);
+            let mut z_terms = vec![alpha];
+            for (w_i, k_i) in w.iter().zip(params.k.iter()) {
+                z_terms.push(self.linear_combine(
+                    &[*w_i, beta_zeta, gamma, zero_var],
+                    one,
+                    *k_i,
+                    one,
+                    F::ZERO,
+                ));
+            }
+            let z_prod = product(self, &z_terms);
+            let z_l1 = self.mul(first_lagrange_eval_zeta, alpha_pows[2]);
+            let z_scalar = self.add(z_prod, z_l1);
+            r_msm.add_term(self, proof_index(&proof.cm_z), z_scalar);
+
+            let s_last_scalar = product(self, &[alpha, z_omega, beta, perm_prod]);
+            r_msm.sub_term(
+                self,
+                params_index(&params.cm_s_vec[N_WIRES_PER_GATE - 1]),
+                s_last_scalar,
+            );
+
+            let mut qb_terms = vec![];
+            for i in 1..4 {
+                let w_minus_one = add_constant(self, w[i], minus_one);
+                qb_terms.push(product(self, &[w[i], w_minus_one, alpha_pows[i + 2]]));
+            }
+            let qb_scalar = sum_with_coefs(
+                self,
+                &qb_terms.iter().map(|t| (*t, one)).collect::<Vec<_>>(),
+            );
+            r_msm.add_term(self, params_index(&params.cm_qb), qb_scalar);
+
+            let prk_0_scalar = self.mul(prk_3, alpha_pows[6]);
+            r_msm.add_term(self, params_index(&params.cm_prk_vec[0]), prk_0_scalar);
+            let prk_1_scalar = self.mul(prk_3, alpha_pows[7]);
+            r_msm.add_term(self, params_index(&params.cm_prk_vec[1]), prk_1_scalar);
+
+            if shuffle {
+                let w_sel = &proof.w_sel_polys_eval_zeta;
+                let sels = self.shuffle_selectors(w_sel, proof.q_ecc_poly_eval_zeta);
+                let a = params.edwards_a;
+
+                // the public key part with alpha^10 and alpha^11.
+                let t = self.mul(alpha_pows[10], w[1]);
+                let aw0 = mul_constant(self, w[0], a);
+                let t2 = self.mul(alpha_pows[11], aw0);
+                let x_pk = self.sub(t2, t);
+                let t = self.mul(alpha_pows[10], w[0]);
+                let t2 = self.mul(alpha_pows[11], w[1]);
+                let t = self.add(t, t2);
+                let y_pk = self.mul(w_sel[2], t);
+                let y_pk = mul_constant(self, y_pk, minus_one);
+                let t = self.mul(alpha_pows[10], w_omega[0]);
+                let t2 = self.mul(alpha_pows[11], w_omega[1]);
+                let t = self.sub(t, t2);
+                let dxy_pk = self.mul(w0_w1, t);
+
+                // the generator part with alpha^12 and alpha^13.
+                let t = self.mul(alpha_pows[12], w[3]);
+                let aw2 = mul_constant(self, w[2], a);
+                let t2 = self.mul(alpha_pows[13], aw2);
+                let x_g = self.sub(t2, t);
+                let t = self.mul(alpha_pows[12], w[2]);
+                let t2 = self.mul(alpha_pows[13], w[3]);
+                let t = self.add(t, t2);
+                let y_g = self.mul(w_sel[2], t);
+                let y_g = mul_constant(self, y_g, minus_one);
+                let t = self.mul(alpha_pows[12], w_omega[2]);
+                let t2 = self.mul(alpha_pows[13], w[4]);
+                let t = self.sub(t, t2);
+                let dxy_g = self.mul(w2_w3, t);
+
+                for (j, sel) in sels.iter().enumerate() {
+                    for (i, scalar) in [x_pk, y_pk, dxy_pk].iter().enumerate() {
+                        let coef = self.mul(*sel, *scalar);
+                        let cm = &params.cm_shuffle_public_key_vec[4 * i + j];
+                        r_msm.add_term(self, params_index(cm), coef);
+                    }
+                    for (i, scalar) in [x_g, y_g, dxy_g].iter().enumerate() {
+                        let coef = self.mul(*sel, *scalar);
+                        let cm = &params.cm_shuffle_generator_vec[4 * i + j];
+                        r_msm.add_term(self, params_index(cm), coef);
+                    }
+                }
+            }
+
+            if lookup {
+                let one_plus_delta = add_constant(self, delta, one);
+                let epsilon_f = self.add(epsilon, proof.lookup_f_eval_zeta);
+                let epsilon_one_plus_delta = self.mul(epsilon, one_plus_delta);
+                let delta_t_omega = self.mul(delta, proof.lookup_table_eval_zeta_omega);
+                let t = sum_with_coefs(
+                    self,
+                    &[
+                        (epsilon_one_plus_delta, one),
+                        (proof.lookup_table_eval_zeta, one),
+                        (delta_t_omega, one),
+                    ],
+                );
+                let z_lookup_prod = product(self, &[alpha_pows[17], one_plus_delta, epsilon_f, t]);
+                let z_lookup_l1 = self.mul(first_lagrange_eval_zeta, alpha_pows[18]);
+                let z_lookup_scalar = self.add(z_lookup_prod, z_lookup_l1);
+                r_msm.add_term(self, proof_index(&proof.cm_lookup_z), z_lookup_scalar);
+            }
+
+            let factor = pow(self, zeta, params.cs_size as u64 + 2);
+            let mut exponent = z_h_eval_zeta;
+            for (i, cm_t) in proof.cm_t_vec.iter().enumerate() {
+                r_msm.sub_term(self, proof_index(cm_t), exponent);
+                if i + 1 < proof.cm_t_vec.len() {
+                    exponent = self.mul(exponent, factor);
+                }
+            }
+        }
+
+        // 7. the batched openings at zeta and zeta * omega, see `PolyComScheme::batch`.
+        let mut lookup_table_msm = MsmVars::new(n_points);
+        if lookup {
+            let mut eta_pow = one_var;
+            for (i, cm) in params.cm_lookup_table_vec.iter().enumerate() {
+                lookup_table_msm.add_term(self, params_index(cm), eta_pow);
+                if i + 1 < params.cm_lookup_table_vec.len() {
+                    eta_pow = self.mul(eta_pow, eta);
+                }
+            }
+        }
+
+        let mut points_zeta: Vec<MsmVars> = vec![];
+        let single = |index: usize| {
+            let mut msm = MsmVars::new(n_points);
+            msm.coefs[index] = Some(one_var);
+            msm
+        };
+        for cm_w in proof.cm_w_vec.iter() {
+            points_zeta.push(single(proof_index(cm_w)));
+        }
+        for cm_s in params.cm_s_vec.iter().take(N_WIRES_PER_GATE - 1) {
+            points_zeta.push(single(params_index(cm_s)));
+        }
+        points_zeta.push(single(params_index(&params.cm_prk_vec[2])));
+        points_zeta.push(single(params_index(&params.cm_prk_vec[3])));
+        if shuffle {
+            points_zeta.push(single(params_index(&params.cm_q_ecc)));
+            for cm_w_sel in proof.cm_w_sel_vec.iter() {
+                points_zeta.push(single(proof_index(cm_w_sel)));
+            }
+        }
+        if lookup {
+            points_zeta.push(single(proof_index(&proof.cm_lookup_f)));
+            points_zeta.push(MsmVars {
+                coefs: lookup_table_msm.coefs.clone(),
+            });
+            points_zeta.push(single(proof_index(&proof.cm_lookup_h_vec[0])));
+            points_zeta.push(single(proof_index(&proof.cm_lookup_h_vec[1])));
+            points_zeta.push(single(params_index(&params.cm_q_lookup_vec[0])));
+            points_zeta.push(single(params_index(&params.cm_q_lookup_vec[1])));
+        }
+        points_zeta.push(r_msm);
+
+        let mut values_zeta: Vec<VarIndex> = w.iter().chain(s.iter()).cloned().collect();
+        values_zeta.push(prk_3);
+        values_zeta.push(prk_4);
+        if shuffle {
+            values_zeta.push(proof.q_ecc_poly_eval_zeta);
+            values_zeta.extend_from_slice(&proof.w_sel_polys_eval_zeta);
+        }
+        if lookup {
+            values_zeta.push(proof.lookup_f_eval_zeta);
+            values_zeta.push(proof.lookup_table_eval_zeta);
+            values_zeta.extend_from_slice(&proof.lookup_h_polys_eval_zeta);
+            values_zeta.extend_from_slice(&proof.q_lookup_polys_eval_zeta);
+        }
+        values_zeta.push(r_eval_zeta);
+
+        let mut points_zeta_omega = vec![
+            single(proof_index(&proof.cm_z)),
+            single(proof_index(&proof.cm_w_vec[0])),
+            single(proof_index(&proof.cm_w_vec[1])),
+            single(proof_index(&proof.cm_w_vec[2])),
+        ];
+        let mut values_zeta_omega = vec![z_omega, w_omega[0], w_omega[1], w_omega[2]];
+        if lookup {
+            points_zeta_omega.push(single(proof_index(&proof.cm_lookup_z)));
+            points_zeta_omega.push(lookup_table_msm);
+            points_zeta_omega.push(single(proof_index(&proof.cm_lookup_h_vec[0])));
+            values_zeta_omega.push(proof.lookup_z_eval_zeta_omega);
+            values_zeta_omega.push(proof.lookup_table_eval_zeta_omega);
+            values_zeta_omega.push(proof.lookup_h1_eval_zeta_omega);
+        }
+
+        let zeta_omega = mul_constant(self, zeta, root);
+        let (msm_zeta, eval_zeta) = self.batch_vars::<P>(
+            transcript,
+            params.cs_size + 2,
+            zeta,
+            &points_zeta,
+            &values_zeta,
+        );
+        let (msm_zeta_omega, eval_zeta_omega) = self.batch_vars::<P>(
+            transcript,
+            params.cs_size + 2,
+            zeta_omega,
+            &points_zeta_omega,
+            &values_zeta_omega,
+        );
+
+        // 8. combine the two claims with `u`, see `batch_verify_diff_points`:
+        // e(W + u * W', [x]_2) = e(zeta * W + u * zeta_omega * W' + C + u * C' - (v + u * v') * [1]_1, [1]_2)
+        let mut msm = MsmVars::new(n_points);
+        msm.add_msm(self, &msm_zeta, one_var);
+        msm.add_msm(self, &msm_zeta_omega, u);
+        msm.add_term(self, proof_index(&proof.opening_witness_zeta), zeta);
+        let u_zeta_omega = self.mul(u, zeta_omega);
+        msm.add_term(
+            self,
+            proof_index(&proof.opening_witness_zeta_omega),
+            u_zeta_omega,
+        );
+
+        let u_eval = self.mul(u, eval_zeta_omega);
+        let eval = self.add(eval_zeta, u_eval);
+
+        Ok(OpeningClaimsVars {
+            msm,
+            eval,
+            left: [one_var, u],
+            u,
+        })
+    }
+
+    /// Combine the commitments and the values with a challenge, see `PolyComScheme::batch`.
+    fn batch_vars<P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        transcript: &mut AnemoiTranscriptVars,
+        max_degree: usize,
+        point: VarIndex,
+        msms: &[MsmVars],
+        values: &[VarIndex],
+    ) -> (MsmVars, VarIndex) {
+        transcript.append_message(self, b"New PCS-Batch-Eval Protocol");
+        transcript.append_message(self, &F::MODULUS.to_bytes_be());
+        transcript.append_u64(self, max_degree as u64);
+        transcript.append_challenge(point);
+        let alpha = transcript.get_challenge::<F, P>(self);
+
+        let mut combined = MsmVars::new(msms[0].coefs.len());
+        let mut value_terms = vec![];
+        let mut multiplier = self.one_var();
+        for (i, (msm, value)) in msms.iter().zip(values.iter()).enumerate() {
+            combined.add_msm(self, msm, multiplier);
+            value_terms.push(self.mul(*value, multiplier));
+            if i + 1 < msms.len() {
+                multiplier = self.mul(multiplier, alpha);
+            }
+        }
+        let value = sum_with_coefs(
+            self,
+            &value_terms.iter().map(|t| (*t, F::ONE)).collect::<Vec<_>>(),
+        );
+        (combined, value)
+    }
+
+    /// The selectors `sel_00`, `sel_01`, `sel_10`, `sel_11` of the shuffle gates.
+    fn shuffle_selectors(&mut self, w_sel: &[VarIndex], q_ecc: VarIndex) -> [VarIndex; 4] {
+        let one_minus_s0 = one_minus(self, w_sel[0]);
+        let one_minus_s1 = one_minus(self, w_sel[1]);
+        let t = self.mul(one_minus_s0, one_minus_s1);
+        let sel_00 = self.linear_combine(
+            &[t, q_ecc, self.one_var(), self.zero_var()],
+            F::ONE,
+            F::ONE,
+            F::ONE.neg(),
+            F::ZERO,
+        );
+        let sel_01 = self.mul(w_sel[0], one_minus_s1);
+        let sel_10 = self.mul(one_minus_s0, w_sel[1]);
+        let sel_11 = self.mul(w_sel[0], w_sel[1]);
+        [sel_00, sel_01, sel_10, sel_11]
+    }
+
+    /// Compress a lookup row `(a, b, c, table_id)`, see `compress_lookup_row`.
+    fn compress_lookup_row(&mut self, eta: VarIndex, row: &[VarIndex; 4]) -> VarIndex {
+        let mut res = row[3];
+        for var in row.iter().take(3).rev() {
+            res = self.mul(res, eta);
+            res = self.add(res, *var);
+        }
+        res
+    }
+}
+
+impl TurboCS<Fr> {
+    /// Create constraints for the verifier of the PLONK proofs of the same verifier
+    /// params, with the Anemoi transcript over the field of the constraint system.
+    ///
+    /// The constraints compute the challenges and the scalars of the verifier, and the
+    /// pairing checks of all the proofs are combined into a [KZGAccumulatorVars] with
+    /// a challenge bound to every proof, which is checked by [KZGAccumulator::verify]
+    /// outside the constraint system. The commitments of the proofs are combined into the
+    /// two points of the accumulator with non-native arithmetic, so the accumulator has a
+    /// constant size. Each instance is `(transcript, pi_vars, proof_vars)`, the transcript
+    /// being in the same state as the one used by its prover. The public inputs of the
+    /// proofs and the accumulator should be the public inputs of the outer proof.
+    ///
+    /// The custom gates are not supported.
+    pub fn verify_plonk_proofs<P: AnemoiJive<Fr, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        params: &PlonkVerifierParams<KZGCommitmentSchemeBN254>,
+        instances: &mut [RecursiveInstance],
+    ) -> Result<KZGAccumulatorVars, UzkgeError> {
+        let msm = self.deferred_msm_vars::<P>(params, instances)?;
+        Ok(KZGAccumulatorVars {
+            left: self.non_native_msm(&msm.left_points, &msm.left_scalars),
+            right: self.non_native_msm(&msm.right_points, &msm.right_scalars),
+            params: msm.params,
+            eval: msm.eval,
+        })
+    }
+
+    /// Create constraints for the verifier of the PLONK proofs up to the two multi-scalar
+    /// multiplications of the points of [KZGAccumulatorVars], which take most of the
+    /// constraints of [TurboCS::verify_plonk_proofs].
+    pub(crate) fn deferred_msm_vars<P: AnemoiJive<Fr, 2, N_ANEMOI_ROUNDS>>(
+        &mut self,
+        params: &PlonkVerifierParams<KZGCommitmentSchemeBN254>,
+        instances: &mut [RecursiveInstance],
+    ) -> Result<DeferredMsmVars, UzkgeError> {
+        let scalars =
+            self.deferred_scalars_vars::<KZGCommitmentSchemeBN254, P>(params, instances)?;
+
+        let zero_var = self.zero_var();
+        let mut msm = DeferredMsmVars {
+            left_points: vec![],
+            left_scalars: vec![],
+            right_points: vec![],
+            right_scalars: vec![],
+            params: scalars.params,
+            eval: scalars.eval,
+        };
+        for ((_, _, proof_vars), (left, right)) in instances
+            .iter()
+            .zip(scalars.left.iter().zip(scalars.right.iter()))
+        {
+            let points: Vec<_> = proof_vars
+                .commitments()
+                .into_iter()
+                .map(|cm| self.commitment_point_vars(cm))
+                .collect();
+            for ((point, is_identity), coef) in points.iter().zip(right.iter()) {
+                if let Some(coef) = coef {
+                    msm.right_points.push(point.clone());
+                    msm.right_scalars
+                        .push(self.select(*coef, zero_var, *is_identity));
+                }
+            }
+            for ((point, is_identity), coef) in points[points.len() - 2..].iter().zip(left.iter()) {
+                msm.left_points.push(point.clone());
+                msm.left_scalars
+                    .push(self.select(*coef, zero_var, *is_identity));
+            }
+        }
+        Ok(msm)
+    }
+
+    /// Create constraints for the point of the commitment from the limbs of its bytes,
+    /// and return the point with a boolean variable of whether the commitment is the
+    /// identity, whose point is then the generator.
+    fn commitment_point_vars(
+        &mut self,
+        cm: &CommitmentVars,
+    ) -> (NonNativePointVar<G1Config>, VarIndex) {
+        // the limbs are the big-endian chunks of the big-endian coordinates `x || y`.
+        let chunk_size = (Fr::MODULUS_BIT_SIZE as usize - 1) / 8;
+        let n_bytes = 2 * (Fq::MODULUS_BIT_SIZE as usize).div_ceil(8);
+        assert_eq!(cm.limbs.len(), n_bytes.div_ceil(chunk_size));
+
+        let mut bits = vec![];
+        for (i, limb) in cm.limbs.iter().enumerate().rev() {
+            let len = chunk_size.min(n_bytes - i * chunk_size);
+            bits.extend(self.range_check(*limb, 8 * len));
+        }
+        let y = self.new_non_native_from_bits::<Fq>(&bits[..bits.len() / 2]);
+        let x = self.new_non_native_from_bits::<Fq>(&bits[bits.len() / 2..]);
+        self.non_native_enforce_canonical(&x);
+        self.non_native_enforce_canonical(&y);
+
+        // the identity is encoded as zeros.
+        let zero_var = self.zero_var();
+        let mut is_identity = self.one_var();
+        for limb in cm.limbs.iter() {
+            let is_zero = self.is_equal(*limb, zero_var);
+            is_identity = self.mul(is_identity, is_zero);
+        }
+        let generator = self.new_non_native_point_constant(&G1Affine::generator());
+        let point =
+            self.non_native_point_select(&NonNativePointVar::new(x, y), &generator, is_identity);
+        self.non_native_enforce_on_curve(&point);
+        (point, is_identity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::{g1::Config as G1Config, Fr, G1Projective};
+    use ark_std::{rand::SeedableRng, Zero};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        anemoi::AnemoiJive254,
+        plonk::{
+            constraint_system::{
+                ecc::non_native_ecc::NonNativePointVar, recursion::AnemoiTranscriptVars, TurboCS,
+                VarIndex,
+            },
+            indexer::{indexer, PlonkProof, PlonkVerifierParams},
+            prover::prover,
+            recursion::KZGAccumulator,
+            verifier::verifier,
+        },
+        poly_commit::{
+            kzg_poly_commitment::{KZGCommitment, KZGCommitmentScheme, KZGCommitmentSchemeBN254},
+            pcs::HomomorphicPolyComElem,
+        },
+        utils::transcript::{AnemoiTranscript, TranscriptScheme},
+    };
+
+    type AnemoiTranscriptBN254 = AnemoiTranscript<Fr, AnemoiJive254>;
+
+    fn build_cs(a: u64, b: u64) -> TurboCS<Fr> {
+        let mut cs = TurboCS::new();
+        // the Anemoi terms of the verifier have the generator as coefficients, which are
+        // zero without the Anemoi parameters.
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+        let var_a = cs.new_variable(Fr::from(a));
+        let var_b = cs.new_variable(Fr::from(b));
+        let var_c = cs.add(var_a, var_b);
+        let var_d = cs.mul(var_c, var_a);
+        cs.prepare_pi_variable(var_d);
+        cs.pad();
+        cs
+    }
+
+    fn prove(
+        pcs: &KZGCommitmentSchemeBN254,
+        mut cs: TurboCS<Fr>,
+    ) -> (
+        PlonkVerifierParams<KZGCommitmentSchemeBN254>,
+        PlonkProof<KZGCommitmentSchemeBN254>,
+    ) {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, pcs).unwrap();
+        let mut transcript = AnemoiTranscriptBN254::new(b"Test");
+        let proof = prover(
+            &mut prng,
+            &mut transcript,
+            pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        (prover_params.verifier_params, proof)
+    }
+
+    /// Verify the proofs in a new constraint system, and return the accumulator.
+    fn accumulate(
+        params: &PlonkVerifierParams<KZGCommitmentSchemeBN254>,
+        instances: &[(&[Fr], &PlonkProof<KZGCommitmentSchemeBN254>)],
+    ) -> KZGAccumulator<KZGCommitmentSchemeBN254> {
+        let mut cs = TurboCS::<Fr>::new();
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+
+        let mut pi = vec![];
+        let mut pi_vars = vec![];
+        let mut proof_vars = vec![];
+        for (instance_pi, proof) in instances.iter() {
+            pi.extend_from_slice(instance_pi);
+            pi_vars.push(
+                instance_pi
+                    .iter()
+                    .map(|v| cs.new_variable(*v))
+                    .collect::<Vec<_>>(),
+            );
+            proof_vars.push(cs.new_plonk_proof_vars(proof));
+        }
+        let mut recursive_instances: Vec<_> = pi_vars
+            .iter()
+            .zip(proof_vars.iter())
+            .map(|(vars, proof_vars)| {
+                (
+                    AnemoiTranscriptVars::new(&mut cs, b"Test"),
+                    &vars[..],
+                    proof_vars,
+                )
+            })
+            .collect();
+        let accumulator_vars = cs
+            .verify_plonk_proofs::<AnemoiJive254>(params, &mut recursive_instances)
+            .unwrap();
+        for var in pi_vars.iter().flatten() {
+            cs.prepare_pi_variable(*var);
+        }
+        for var in accumulator_vars.vars() {
+            cs.prepare_pi_variable(var);
+        }
+
+        let accumulator = accumulator_vars.get_value(&cs);
+        pi.extend(accumulator.public_inputs());
+        let witness = cs.get_and_clear_witness();
+        assert!(cs.verify_witness(&witness, &pi).is_ok());
+        accumulator
+    }
+
+    #[test]
+    fn test_verify_plonk_proofs() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+
+        let (params, proof_0) = prove(&pcs, build_cs(1, 2));
+        let (_, proof_1) = prove(&pcs, build_cs(3, 4));
+        let pi_0 = [Fr::from(3u64)];
+        let pi_1 = [Fr::from(21u64)];
+
+        let accumulator = accumulate(&params, &[(&pi_0, &proof_0), (&pi_1, &proof_1)]);
+        assert!(accumulator.verify(&pcs, &params).is_ok());
+
+        let mut wrong_accumulator = accumulator.clone();
+        wrong_accumulator.eval += Fr::from(1u64);
+        assert!(wrong_accumulator.verify(&pcs, &params).is_err());
+
+        let mut wrong_accumulator = accumulator.clone();
+        wrong_accumulator.left = wrong_accumulator.left.add(&KZGCommitment::get_base());
+        assert!(wrong_accumulator.verify(&pcs, &params).is_err());
+
+        // a wrong public input.
+        let wrong_pi = [Fr::from(22u64)];
+        let accumulator = accumulate(&params, &[(&wrong_pi, &proof_1)]);
+        assert!(accumulator.verify(&pcs, &params).is_err());
+
+        // a wrong evaluation.
+        let mut wrong_proof = proof_1.clone();
+        wrong_proof.z_eval_zeta_omega += Fr::from(1u64);
+        let accumulator = accumulate(&params, &[(&pi_1, &wrong_proof)]);
+        assert!(accumulator.verify(&pcs, &params).is_err());
+    }
+
+    /// The outer circuit has about 2^22 gates for the non-native accumulation, whose
+    /// proof needs much time and memory, so run it with `--ignored`. The outer proof of
+    /// the rest of the verifier is in `test_deferred_msm_in_outer_proof`.
+    #[test]
+    #[ignore]
+    fn test_verify_plonk_proofs_in_outer_proof() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+        let (params, proof) = prove(&pcs, build_cs(1, 2));
+        let pi = [Fr::from(3u64)];
+
+        let mut cs = TurboCS::<Fr>::new();
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+        let pi_vars: Vec<_> = pi.iter().map(|v| cs.new_variable(*v)).collect();
+        let proof_vars = cs.new_plonk_proof_vars(&proof);
+        let transcript = AnemoiTranscriptVars::new(&mut cs, b"Test");
+        let accumulator_vars = cs
+            .verify_plonk_proofs::<AnemoiJive254>(
+                &params,
+                &mut [(transcript, &pi_vars, &proof_vars)],
+            )
+            .unwrap();
+        for var in pi_vars.iter().chain(accumulator_vars.vars().iter()) {
+            cs.prepare_pi_variable(*var);
+        }
+        let accumulator = accumulator_vars.get_value(&cs);
+        cs.pad();
+
+        let outer_pcs = KZGCommitmentScheme::new(cs.size + 6, &mut prng);
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, &outer_pcs).unwrap();
+        let outer_proof = prover(
+            &mut prng,
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+
+        let mut outer_pi = pi.to_vec();
+        outer_pi.extend(accumulator.public_inputs());
+        assert!(verifier(
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params.verifier_params,
+            &outer_pi,
+            &outer_proof,
+        )
+        .is_ok());
+        assert!(accumulator.verify(&pcs, &params).is_ok());
+
+        // another accumulator for the outer proof.
+        let mut wrong_accumulator = accumulator.clone();
+        wrong_accumulator.eval += Fr::from(1u64);
+        let mut wrong_pi = pi.to_vec();
+        wrong_pi.extend(wrong_accumulator.public_inputs());
+        assert!(verifier(
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params.verifier_params,
+            &wrong_pi,
+            &outer_proof,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_deferred_msm_in_outer_proof() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+        let (params, proof) = prove(&pcs, build_cs(1, 2));
+        let pi = [Fr::from(3u64)];
+
+        let mut cs = TurboCS::<Fr>::new();
+        cs.load_anemoi_parameters::<AnemoiJive254>();
+        let pi_vars: Vec<_> = pi.iter().map(|v| cs.new_variable(*v)).collect();
+        let proof_vars = cs.new_plonk_proof_vars(&proof);
+        let transcript = AnemoiTranscriptVars::new(&mut cs, b"Test");
+        let msm = cs
+            .deferred_msm_vars::<AnemoiJive254>(&params, &mut [(transcript, &pi_vars, &proof_vars)])
+            .unwrap();
+
+        // the multi-scalar multiplications are left to the verifier of the outer proof.
+        let mut pi_vars = pi_vars.clone();
+        for (points, scalars) in [
+            (&msm.left_points, &msm.left_scalars),
+            (&msm.right_points, &msm.right_scalars),
+        ] {
+            for point in points.iter() {
+                pi_vars.extend_from_slice(point.get_x().get_limbs());
+                pi_vars.extend_from_slice(point.get_y().get_limbs());
+            }
+            pi_vars.extend_from_slice(scalars);
+        }
+        pi_vars.extend_from_slice(&msm.params);
+        pi_vars.push(msm.eval);
+        for var in pi_vars.iter() {
+            cs.prepare_pi_variable(*var);
+        }
+        let outer_pi: Vec<Fr> = pi_vars.iter().map(|var| cs.witness[*var]).collect();
+
+        let combine = |points: &[NonNativePointVar<G1Config>], scalars: &[VarIndex]| {
+            let mut acc = G1Projective::zero();
+            for (point, scalar) in points.iter().zip(scalars.iter()) {
+                acc += point.get_point() * cs.witness[*scalar];
+            }
+            KZGCommitment(acc)
+        };
+        let accumulator = KZGAccumulator {
+            left: combine(&msm.left_points, &msm.left_scalars),
+            right: combine(&msm.right_points, &msm.right_scalars),
+            params: msm.params.iter().map(|var| cs.witness[*var]).collect(),
+            eval: cs.witness[msm.eval],
+        };
+        assert!(accumulator.verify(&pcs, &params).is_ok());
+        cs.pad();
+
+        let outer_pcs = KZGCommitmentScheme::new(cs.size + 6, &mut prng);
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, &outer_pcs).unwrap();
+        let outer_proof = prover(
+            &mut prng,
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        assert!(verifier(
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params.verifier_params,
+            &outer_pi,
+            &outer_proof,
+        )
+        .is_ok());
+
+        // a wrong scalar of the generator.
+        let mut wrong_pi = outer_pi.clone();
+        *wrong_pi.last_mut().unwrap() += Fr::from(1u64);
+        assert!(verifier(
+            &mut AnemoiTranscriptBN254::new(b"Outer"),
+            &outer_pcs,
+            &cs,
+            &prover_params.verifier_params,
+            &wrong_pi,
+            &outer_proof,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_plonk_proofs_with_lookup() {
+        use crate::plonk::constraint_system::lookup::LookupTable;
+
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(300, &mut prng);
+
+        let mut cs = TurboCS::<Fr>::new();
+        let table_id = cs
+            .register_lookup_table(LookupTable::xor(3).unwrap())
+            .unwrap();
+        let a = cs.new_variable(Fr::from(5u64));
+        let b = cs.new_variable(Fr::from(3u64));
+        let c = cs.lookup(a, b, table_id).unwrap();
+        cs.prepare_pi_variable(c);
+        cs.pad();
+
+        let (params, proof) = prove(&pcs, cs);
+        let pi = [Fr::from(6u64)];
+        let accumulator = accumulate(&params, &[(&pi, &proof)]);
+        assert!(accumulator.verify(&pcs, &params).is_ok());
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.lookup_f_eval_zeta += Fr::from(1u64);
+        let accumulator = accumulate(&params, &[(&pi, &wrong_proof)]);
+        assert!(accumulator.verify(&pcs, &params).is_err());
+    }
+
+    #[test]
+    fn test_verify_plonk_proofs_with_shuffle() {
+        use ark_ec::PrimeGroup;
+        use ark_ed_on_bn254::EdwardsProjective;
+        use ark_std::UniformRand;
+
+        use crate::shuffle::{BabyJubjubShuffle, Ciphertext, Remark};
+
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(1030, &mut prng);
+
+        // the indexer sets the public key selectors as the generator ones,
+        // which are refreshed for other public keys.
+        let public = EdwardsProjective::generator();
+        let m = EdwardsProjective::rand(&mut prng);
+        let input = Ciphertext::encrypt(&mut prng, &m, &public);
+        let bits = BabyJubjubShuffle::sample_random_scalar_bits(&mut prng);
+        let trace = BabyJubjubShuffle::eval_remark_with_trace(&input, &bits, &public);
+
+        let mut cs = TurboCS::new();
+        cs.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(&public);
+        let input_vars = cs.new_card_variable(&input);
+        let output_vars = cs.eval_card_remark(&trace, &input_vars);
+        cs.prepare_pi_card_variable(&output_vars);
+        cs.pad();
+
+        let (params, proof) = prove(&pcs, cs);
+        let pi = trace.output.to_vec();
+        let accumulator = accumulate(&params, &[(&pi, &proof)]);
+        assert!(accumulator.verify(&pcs, &params).is_ok());
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.w_sel_polys_eval_zeta[2] += Fr::from(1u64);
+        let accumulator = accumulate(&params, &[(&pi, &wrong_proof)]);
+        assert!(accumulator.verify(&pcs, &params).is_err());
+    }
+}
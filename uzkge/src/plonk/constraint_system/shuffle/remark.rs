@@ -15,10 +15,15 @@ impl<F: PrimeField> TurboCS<F> {
         assert_eq!(trace.intermediate_values.len(), trace.n_round);
         assert_eq!(self.n_iteration_shuffle_scalar_mul, trace.n_round);
 
-        // Wire selector variables are not allocated in the constraint system,
-        // because they do not have copy constraints.
+        // The wire selector variables do not have copy constraints, so they are not wired.
         let bits = (0..N_WIRE_SELECTORS)
-            .map(|i| trace.bits.iter().map(|x| x[i]).collect_vec())
+            .map(|i| {
+                trace
+                    .bits
+                    .iter()
+                    .map(|x| self.new_variable(x[i]))
+                    .collect_vec()
+            })
             .collect_vec();
         self.attach_shuffle_remark_constraints_to_gate(bits.try_into().unwrap());
 
@@ -1,5 +1,6 @@
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::{BigInteger, PrimeField};
+use ark_std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "debug")]
@@ -8,7 +9,9 @@ use ark_std::collections::HashMap;
 use crate::{
     anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
     errors::UzkgeError,
-    plonk::constraint_system::{ConstraintSystem, CsIndex, VarIndex},
+    plonk::constraint_system::{
+        custom_gate::CustomGate, lookup::LookupTable, ConstraintSystem, CsIndex, VarIndex,
+    },
     shuffle::Remark,
     utils::serialization::{ark_deserialize, ark_serialize},
 };
@@ -82,18 +85,39 @@ pub struct TurboCS<F: PrimeField> {
     pub public_vars_witness_indices: Vec<VarIndex>,
     /// the gates with boolean constraint.
     pub boolean_constraint_indices: Vec<CsIndex>,
-    /// the gates with shuffle remark constraint.
-    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    pub shuffle_remark_constraint_indices: Vec<(CsIndex, [Vec<F>; N_WIRE_SELECTORS])>,
+    /// the gates with shuffle remark constraint, and the variables of their wire selectors.
+    pub shuffle_remark_constraint_indices: Vec<(CsIndex, [Vec<VarIndex>; N_WIRE_SELECTORS])>,
+    /// the registered lookup tables, the table id is the position plus one.
+    #[serde(bound = "")]
+    pub lookup_tables: Vec<LookupTable<F>>,
+    /// the gates with lookup constraint, and the id of the table they look up.
+    pub lookup_constraint_indices: Vec<(CsIndex, usize)>,
+    /// the registered custom gates, the gate id is the position.
+    #[serde(skip)]
+    pub custom_gates: Vec<Arc<dyn CustomGate<F>>>,
+    /// the gates with custom gate constraint, the id of the custom gate and its selectors.
+    #[serde(skip)]
+    pub custom_constraint_indices: Vec<(CsIndex, usize, Vec<F>)>,
+    /// the entered namespaces, for diagnostics.
+    #[cfg(feature = "debug")]
+    #[serde(skip)]
+    pub namespace_stack: Vec<String>,
+    /// the gates from which the namespace path changes, and the new path.
+    #[cfg(feature = "debug")]
+    #[serde(skip)]
+    pub namespace_spans: Vec<(CsIndex, String)>,
     /// only for verifier use.
     pub verifier_only: bool,
+    /// only the witness is assigned, the selectors and the shuffle parameters are not built.
+    #[serde(skip)]
+    pub witness_only: bool,
     /// A private witness for the circuit, cleared after computing a proof.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
     pub witness: Vec<F>,
     /// record witness backtracing info for checking dangling witness.
     #[cfg(feature = "debug")]
     #[serde(skip)]
-    pub witness_backtrace: HashMap<VarIndex, std::backtrace::Backtrace>,
+    pub witness_backtrace: HashMap<VarIndex, std::sync::Arc<std::backtrace::Backtrace>>,
 }
 
 impl<F: PrimeField> ConstraintSystem<F> for TurboCS<F> {
@@ -153,12 +177,10 @@ impl<F: PrimeField> ConstraintSystem<F> for TurboCS<F> {
     }
 
     fn shuffle_remark_constraint_indices(&self) -> Vec<CsIndex> {
-        let (indices, _): (Vec<_>, Vec<_>) = self
-            .shuffle_remark_constraint_indices
+        self.shuffle_remark_constraint_indices
             .iter()
-            .cloned()
-            .unzip();
-        indices
+            .map(|(i, _)| *i)
+            .collect()
     }
 
     fn selector(&self, index: usize) -> Result<&[F], UzkgeError> {
@@ -168,16 +190,16 @@ impl<F: PrimeField> ConstraintSystem<F> for TurboCS<F> {
         Ok(&self.selectors[index])
     }
 
-    fn compute_witness_selectors(&self) -> [Vec<F>; N_WIRE_SELECTORS] {
+    fn compute_witness_selectors(&self, witness: &[F]) -> [Vec<F>; N_WIRE_SELECTORS] {
         let empty_poly = vec![F::ZERO; self.size];
 
         let mut polys = [empty_poly.clone(), empty_poly.clone(), empty_poly];
 
         for (i, wire_selector) in self.shuffle_remark_constraint_indices.iter() {
             for j in 0..self.n_iteration_shuffle_scalar_mul() {
-                polys[0][*i + j] = wire_selector[0][j];
-                polys[1][*i + j] = wire_selector[1][j];
-                polys[2][*i + j] = wire_selector[2][j];
+                polys[0][*i + j] = witness[wire_selector[0][j]];
+                polys[1][*i + j] = witness[wire_selector[1][j]];
+                polys[2][*i + j] = witness[wire_selector[2][j]];
             }
         }
 
@@ -274,7 +296,16 @@ impl<F: PrimeField> ConstraintSystem<F> for TurboCS<F> {
             public_vars_witness_indices: vec![],
             boolean_constraint_indices: vec![],
             shuffle_remark_constraint_indices: vec![],
+            lookup_tables: vec![],
+            lookup_constraint_indices: vec![],
+            custom_gates: self.custom_gates.clone(),
+            custom_constraint_indices: vec![],
+            #[cfg(feature = "debug")]
+            namespace_stack: vec![],
+            #[cfg(feature = "debug")]
+            namespace_spans: vec![],
             verifier_only: true,
+            witness_only: false,
             witness: vec![],
 
             #[cfg(feature = "debug")]
@@ -363,6 +394,65 @@ impl<F: PrimeField> ConstraintSystem<F> for TurboCS<F> {
         polys
     }
 
+    fn has_lookup_constraints(&self) -> bool {
+        !self.lookup_constraint_indices.is_empty()
+    }
+
+    fn compute_lookup_selectors(&self) -> [Vec<F>; 2] {
+        let mut polys = [vec![F::ZERO; self.size], vec![F::ZERO; self.size]];
+
+        for (i, table_id) in self.lookup_constraint_indices.iter() {
+            polys[0][*i] = F::ONE;
+            polys[1][*i] = F::from(*table_id as u64);
+        }
+
+        polys
+    }
+
+    fn compute_lookup_table(&self) -> [Vec<F>; 4] {
+        debug_assert!(self.lookup_table_rows() <= self.size);
+        let mut polys = [vec![], vec![], vec![], vec![]];
+
+        for (i, table) in self.lookup_tables.iter().enumerate() {
+            let table_id = F::from((i + 1) as u64);
+            for row in table.rows.iter() {
+                polys[0].push(row[0]);
+                polys[1].push(row[1]);
+                polys[2].push(row[2]);
+                polys[3].push(table_id);
+            }
+        }
+
+        for poly in polys.iter_mut() {
+            let last = poly.last().copied().unwrap_or(F::ZERO);
+            poly.resize(self.size, last);
+        }
+
+        polys
+    }
+
+    fn custom_gates(&self) -> &[Arc<dyn CustomGate<F>>] {
+        &self.custom_gates
+    }
+
+    fn compute_custom_selectors(&self) -> Vec<Vec<F>> {
+        let mut offsets = Vec::with_capacity(self.custom_gates.len());
+        let mut offset = 0;
+        for gate in self.custom_gates.iter() {
+            offsets.push(offset);
+            offset += gate.num_selectors();
+        }
+
+        let mut polys = vec![vec![F::ZERO; self.size]; offset];
+        for (i, gate_id, selectors) in self.custom_constraint_indices.iter() {
+            for (j, q) in selectors.iter().enumerate() {
+                polys[offsets[*gate_id] + j][*i] = *q;
+            }
+        }
+
+        polys
+    }
+
     fn get_hiding_degree(&self, idx: usize) -> usize {
         // The first three wires, i.e., 0, 1, 2, would require a hiding degree of 3.
         if idx < 3 {
@@ -425,7 +515,16 @@ impl<F: PrimeField> TurboCS<F> {
             public_vars_witness_indices: vec![],
             boolean_constraint_indices: vec![],
             shuffle_remark_constraint_indices: vec![],
+            lookup_tables: vec![],
+            lookup_constraint_indices: vec![],
+            custom_gates: vec![],
+            custom_constraint_indices: vec![],
+            #[cfg(feature = "debug")]
+            namespace_stack: vec![],
+            #[cfg(feature = "debug")]
+            namespace_spans: vec![],
             verifier_only: false,
+            witness_only: false,
             witness: vec![F::ZERO, F::ONE],
 
             #[cfg(feature = "debug")]
@@ -438,6 +537,16 @@ impl<F: PrimeField> TurboCS<F> {
         cs
     }
 
+    /// Create a constraint system that only assigns the witness, for a circuit whose shape
+    /// is already synthesized. The selectors and the shuffle parameters are not built, so it
+    /// can be neither indexed nor proved, but its witness matches the one of the full system.
+    pub fn new_witness_only() -> TurboCS<F> {
+        let mut cs = Self::new();
+        cs.witness_only = true;
+        cs.selectors = vec![vec![]; N_SELECTORS];
+        cs
+    }
+
     /// 0-index is Zero
     pub fn zero_var(&self) -> VarIndex {
         0
@@ -528,8 +637,10 @@ impl<F: PrimeField> TurboCS<F> {
 
         #[cfg(feature = "debug")]
         {
-            self.witness_backtrace
-                .insert(self.num_vars - 1, std::backtrace::Backtrace::capture());
+            self.witness_backtrace.insert(
+                self.num_vars - 1,
+                std::sync::Arc::new(std::backtrace::Backtrace::capture()),
+            );
         }
 
         self.num_vars - 1
@@ -545,8 +656,10 @@ impl<F: PrimeField> TurboCS<F> {
         #[cfg(feature = "debug")]
         {
             for var in self.num_vars - values.len()..self.num_vars {
-                self.witness_backtrace
-                    .insert(var, std::backtrace::Backtrace::capture());
+                self.witness_backtrace.insert(
+                    var,
+                    std::sync::Arc::new(std::backtrace::Backtrace::capture()),
+                );
             }
         }
     }
@@ -555,6 +668,9 @@ impl<F: PrimeField> TurboCS<F> {
     #[cfg(feature = "debug")]
     pub fn finish_new_gate(&mut self) {
         self.size += 1;
+        if self.witness_only {
+            return;
+        }
         // does not work for the gate created for input.
 
         let wiring_0_var = self.wiring[0][self.size - 1];
@@ -888,6 +1004,10 @@ impl<F: PrimeField> TurboCS<F> {
         self.public_vars_witness_indices.push(var);
         self.public_vars_constraint_indices.push(self.size);
         self.insert_constant_gate_for_input(var, F::ZERO);
+
+        // the public inputs are constrained by the verifier.
+        #[cfg(feature = "debug")]
+        self.witness_backtrace.remove(&var);
     }
 
     /// Add constraint that certain values must be one or zero.
@@ -901,10 +1021,10 @@ impl<F: PrimeField> TurboCS<F> {
         self.anemoi_constraints_indices.push(self.size - 1);
     }
 
-    /// Add constraints about the shuffle remark.
+    /// Add constraints about the shuffle remark, with the variables of the wire selectors.
     pub fn attach_shuffle_remark_constraints_to_gate(
         &mut self,
-        wiring_selectors: [Vec<F>; N_WIRE_SELECTORS],
+        wiring_selectors: [Vec<VarIndex>; N_WIRE_SELECTORS],
     ) {
         for x in wiring_selectors.iter() {
             assert_eq!(x.len(), self.n_iteration_shuffle_scalar_mul);
@@ -927,10 +1047,23 @@ impl<F: PrimeField> TurboCS<F> {
         &mut self,
         shuffle_pk: &G,
     ) {
-        let generators_x = H::get_preprocessed_generators_x();
-        let generators_y = H::get_preprocessed_generators_y();
-        let generators_dxy = H::get_preprocessed_generators_dxy();
+        self.edwards_a = H::COFF_A;
+        self.n_iteration_shuffle_scalar_mul = H::NUM_ITERATIONS;
+        if self.witness_only {
+            return;
+        }
 
+        self.shuffle_generators_x = H::get_preprocessed_generators_x();
+        self.shuffle_generators_y = H::get_preprocessed_generators_y();
+        self.shuffle_generators_dxy = H::get_preprocessed_generators_dxy();
+        self.load_shuffle_public_key::<G, H>(shuffle_pk);
+    }
+
+    /// Set the public key of the shuffle remark, the other shuffle parameters are kept.
+    pub fn load_shuffle_public_key<G: CurveGroup<BaseField = F>, H: Remark<G>>(
+        &mut self,
+        shuffle_pk: &G,
+    ) {
         let public_keys = H::crate_public_keys(shuffle_pk);
 
         let mut public_keys_x = vec![];
@@ -954,22 +1087,20 @@ impl<F: PrimeField> TurboCS<F> {
             public_keys_dxy.push(pk_dxy_tmp);
         }
 
-        self.edwards_a = H::COFF_A;
-        self.n_iteration_shuffle_scalar_mul = H::NUM_ITERATIONS;
         self.shuffle_public_keys_x = public_keys_x;
         self.shuffle_public_keys_y = public_keys_y;
         self.shuffle_public_keys_dxy = public_keys_dxy;
-        self.shuffle_generators_x = generators_x;
-        self.shuffle_generators_y = generators_y;
-        self.shuffle_generators_dxy = generators_dxy;
     }
 
     /// Pad the number of constraints to a power of two.
     pub fn pad(&mut self) {
-        let n = self.size.next_power_of_two();
+        // the lookup tables should also fit in the evaluation domain.
+        let n = self.size.max(self.lookup_table_rows()).next_power_of_two();
         let diff = n - self.size();
-        for selector in self.selectors.iter_mut() {
-            selector.extend(vec![F::ZERO; diff]);
+        if !self.witness_only {
+            for selector in self.selectors.iter_mut() {
+                selector.extend(vec![F::ZERO; diff]);
+            }
         }
         for wire in self.wiring.iter_mut() {
             wire.extend(vec![0; diff]);
@@ -990,8 +1121,20 @@ impl<F: PrimeField> TurboCS<F> {
                     }
                 }
 
+                // the remark gates only have the remark constraints, and their wire
+                // selectors are not wired.
+                let mut remark_witness_var = std::collections::HashSet::new();
+                for (cs_index, wiring_selectors) in self.shuffle_remark_constraint_indices.iter() {
+                    for r in 0..=self.n_iteration_shuffle_scalar_mul {
+                        for i in 0..N_WIRES_PER_GATE {
+                            remark_witness_var.insert(self.get_witness_index(i, cs_index + r));
+                        }
+                    }
+                    remark_witness_var.extend(wiring_selectors.iter().flatten());
+                }
+
                 for (var, backtrace) in &self.witness_backtrace {
-                    if animoi_witness_var.contains(var) {
+                    if animoi_witness_var.contains(var) || remark_witness_var.contains(var) {
                         continue;
                     }
 
@@ -1003,6 +1146,9 @@ impl<F: PrimeField> TurboCS<F> {
 
     /// Add a Add selectors.
     pub fn push_add_selectors(&mut self, q1: F, q2: F, q3: F, q4: F) {
+        if self.witness_only {
+            return;
+        }
         self.selectors[0].push(q1);
         self.selectors[1].push(q2);
         self.selectors[2].push(q3);
@@ -1011,22 +1157,34 @@ impl<F: PrimeField> TurboCS<F> {
 
     /// Add a Mul selectors.
     pub fn push_mul_selectors(&mut self, q_mul12: F, q_mul34: F) {
+        if self.witness_only {
+            return;
+        }
         self.selectors[4].push(q_mul12);
         self.selectors[5].push(q_mul34);
     }
 
     /// Add a constant selectors.
     pub fn push_constant_selector(&mut self, q_c: F) {
+        if self.witness_only {
+            return;
+        }
         self.selectors[6].push(q_c);
     }
 
     /// Add an ECC selectors.
     pub fn push_ecc_selector(&mut self, q_ecc: F) {
+        if self.witness_only {
+            return;
+        }
         self.selectors[7].push(q_ecc);
     }
 
     /// Add an Out selectors.
     pub fn push_out_selector(&mut self, q_out: F) {
+        if self.witness_only {
+            return;
+        }
         self.selectors[8].push(q_out);
     }
 
@@ -1054,8 +1212,10 @@ impl<F: PrimeField> TurboCS<F> {
             ));
         }
 
-        if !self.anemoi_constraints_indices.is_empty() {
-            assert!(!self.anemoi_generator.is_zero());
+        if !self.anemoi_constraints_indices.is_empty() && self.anemoi_generator.is_zero() {
+            return Err(UzkgeError::Message(
+                "the Anemoi parameters are not loaded".to_owned(),
+            ));
         }
 
         for cs_index in self.anemoi_constraints_indices.iter() {
@@ -1072,13 +1232,11 @@ impl<F: PrimeField> TurboCS<F> {
                 let d_i_next = witness[self.get_witness_index(3, cs_index + 1 + r)].clone();
 
                 if o_i != d_i_next {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the output wire {:?} does not equal to the fourth wire {:?} in the next constraint",
-                        cs_index,
-                        r,
-                        o_i,
-                        d_i_next
-                    )));
+                    let reason = format!(
+                        "round {}: the output wire {:?} does not equal to the fourth wire {:?} in the next constraint",
+                        r, o_i, d_i_next
+                    );
+                    return Err(self.unsatisfied_gate(witness, cs_index + r, reason));
                 }
 
                 let prk_i_a = self.anemoi_preprocessed_round_keys_x[r][0].clone();
@@ -1100,10 +1258,14 @@ impl<F: PrimeField> TurboCS<F> {
                     + g * (da_i + g * cb_i + prk_i_c).square();
                 let right = d2a_i + g * c2b_i + prk_i_a;
                 if left != right {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the first of anemoi equation does not equal: {:?} != {:?}",
-                        cs_index, r, left, right
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the first of anemoi equation does not equal: {:?} != {:?}",
+                            r, left, right
+                        ),
+                    ));
                 }
 
                 // equation 2
@@ -1111,10 +1273,14 @@ impl<F: PrimeField> TurboCS<F> {
                     + g * (g * da_i + g2 * cb_i + prk_i_d).square();
                 let right = g * d2a_i + g2 * c2b_i + prk_i_b;
                 if left != right {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the second equation of anemoi does not equal: {:?} != {:?}",
-                        cs_index, r, left, right
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the second equation of anemoi does not equal: {:?} != {:?}",
+                            r, left, right
+                        ),
+                    ));
                 }
 
                 // equation 3
@@ -1123,10 +1289,14 @@ impl<F: PrimeField> TurboCS<F> {
                     + &self.anemoi_generator_inv;
                 let right = a_i_next;
                 if left != right {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the third equation of anemoi does not equal: {:?} != {:?}",
-                        cs_index, r, left, right
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the third equation of anemoi does not equal: {:?} != {:?}",
+                            r, left, right
+                        ),
+                    ));
                 }
 
                 // equation 4
@@ -1135,10 +1305,14 @@ impl<F: PrimeField> TurboCS<F> {
                     + &self.anemoi_generator_inv;
                 let right = b_i_next;
                 if left != right {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the fourth equation of anemoi does not equal: {:?} != {:?}",
-                        cs_index, r, left, right
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the fourth equation of anemoi does not equal: {:?} != {:?}",
+                            r, left, right
+                        ),
+                    ));
                 }
             }
         }
@@ -1147,8 +1321,10 @@ impl<F: PrimeField> TurboCS<F> {
         let zero = F::zero();
         let minus_one = -one;
 
-        if !self.shuffle_remark_constraint_indices.is_empty() {
-            assert!(!self.edwards_a.is_zero());
+        if !self.shuffle_remark_constraint_indices.is_empty() && self.edwards_a.is_zero() {
+            return Err(UzkgeError::Message(
+                "the shuffle remark parameters are not loaded".to_owned(),
+            ));
         }
 
         for (cs_index, wiring_selectors) in self.shuffle_remark_constraint_indices.iter() {
@@ -1163,32 +1339,50 @@ impl<F: PrimeField> TurboCS<F> {
                 let b_i_next = witness[self.get_witness_index(1, cs_index + 1 + r)].clone();
                 let c_i_next = witness[self.get_witness_index(2, cs_index + 1 + r)].clone();
                 let d_i_next = witness[self.get_witness_index(3, cs_index + 1 + r)].clone();
-                assert_eq!(o_i, d_i_next);
+                if o_i != d_i_next {
+                    let reason = format!(
+                        "round {}: the output wire {:?} does not equal to the fourth wire {:?} in the next constraint",
+                        r, o_i, d_i_next
+                    );
+                    return Err(self.unsatisfied_gate(witness, cs_index + r, reason));
+                }
 
-                let s1_i = wiring_selectors[0][r];
-                let s2_i = wiring_selectors[1][r];
-                let s3_i = wiring_selectors[2][r];
+                let s1_i = witness[wiring_selectors[0][r]];
+                let s2_i = witness[wiring_selectors[1][r]];
+                let s3_i = witness[wiring_selectors[2][r]];
 
                 // check special binary testing
                 if !s1_i.is_zero() && !s1_i.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the first wire selector {:?} is not one or zero",
-                        cs_index, s1_i
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the first wire selector {:?} is not one or zero",
+                            r, s1_i
+                        ),
+                    ));
                 }
 
                 if !s2_i.is_zero() && !s2_i.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the second wire selector {:?} is not one or zero",
-                        cs_index, s2_i
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the second wire selector {:?} is not one or zero",
+                            r, s2_i
+                        ),
+                    ));
                 }
 
                 if s3_i != minus_one && !s3_i.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the third wire selector {:?} is not one or minus one",
-                        cs_index, s3_i
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the third wire selector {:?} is not one or minus one",
+                            r, s3_i
+                        ),
+                    ));
                 }
 
                 let pk_x_0_0 = self.shuffle_public_keys_x[r][0].clone();
@@ -1239,10 +1433,14 @@ impl<F: PrimeField> TurboCS<F> {
                         * (s3_i * a_i_next - s3_i * a_i * pk_y_1_1 - b_i * pk_x_1_1
                             + a_i * b_i * a_i_next * pk_dxy_1_1);
                 if !result.is_zero() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {} round {}: the first equation of shuffle does not equal: {:?} != {:?}",
-                        cs_index, r, result, zero
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the first equation of shuffle does not equal: {:?} != {:?}",
+                            r, result, zero
+                        ),
+                    ));
                 }
 
                 // equation 2
@@ -1267,10 +1465,14 @@ impl<F: PrimeField> TurboCS<F> {
                             - s3_i * b_i * pk_y_1_1
                             - a_i * b_i * b_i_next * pk_dxy_1_1);
                 if !result.is_zero() {
-                    return Err(UzkgeError::Message(format!(
-                                 "cs index {} round {}: the second equation of shuffle does not equal: {:?} != {:?}",
-                                 cs_index, r, result, zero
-                             )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the second equation of shuffle does not equal: {:?} != {:?}",
+                            r, result, zero
+                        ),
+                    ));
                 }
 
                 // equation 3
@@ -1291,10 +1493,14 @@ impl<F: PrimeField> TurboCS<F> {
                         * (s3_i * c_i_next - s3_i * c_i * g_y_1_1 - d_i * g_x_1_1
                             + c_i * d_i * c_i_next * g_dxy_1_1);
                 if !result.is_zero() {
-                    return Err(UzkgeError::Message(format!(
-                                           "cs index {} round {}: the third equation of shuffle does not equal: {:?} != {:?}",
-                                           cs_index, r, result, zero
-                                       )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the third equation of shuffle does not equal: {:?} != {:?}",
+                            r, result, zero
+                        ),
+                    ));
                 }
 
                 // equation 4
@@ -1319,14 +1525,20 @@ impl<F: PrimeField> TurboCS<F> {
                             - s3_i * d_i * g_y_1_1
                             - c_i * d_i * o_i * g_dxy_1_1);
                 if !result.is_zero() {
-                    return Err(UzkgeError::Message(format!(
-                                                       "cs index {} round {}: the fourth equation of shuffle does not equal: {:?} != {:?}",
-                                                       cs_index, r, result, zero
-                                                   )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index + r,
+                        format!(
+                            "round {}: the fourth equation of shuffle does not equal: {:?} != {:?}",
+                            r, result, zero
+                        ),
+                    ));
                 }
             }
         }
 
+        let mut boolean_constraint_indices = self.boolean_constraint_indices.clone();
+        boolean_constraint_indices.sort_unstable();
         for cs_index in 0..self.size() {
             let mut public_online = F::ZERO;
             // check if the constraint constrains a public variable
@@ -1341,12 +1553,14 @@ impl<F: PrimeField> TurboCS<F> {
                     // found
                     public_online = (*online_var).clone();
                     if witness[*w_i] != public_online {
-                        return Err(UzkgeError::Message(format!(
-                            "cs index {}: online var {:?} does not match witness {:?}",
+                        return Err(self.unsatisfied_gate(
+                            witness,
                             cs_index,
-                            public_online,
-                            witness[*w_i].clone()
-                        )));
+                            format!(
+                                "online var {:?} does not match witness {:?}",
+                                public_online, witness[*w_i]
+                            ),
+                        ));
                     }
                 }
             }
@@ -1362,38 +1576,98 @@ impl<F: PrimeField> TurboCS<F> {
             let eval_gate = Self::eval_gate_func(&wire_vals, &sel_vals, &public_online)?;
 
             if eval_gate != F::ZERO {
-                return Err(UzkgeError::Message(format!(
-                    "cs index {}: wire_vals = ({:?}), sel_vals = ({:?})",
-                    cs_index, wire_vals, sel_vals
-                )));
+                return Err(self.unsatisfied_gate(
+                    witness,
+                    cs_index,
+                    "the gate does not hold".to_owned(),
+                ));
             }
 
-            if self.boolean_constraint_indices.contains(&cs_index) {
+            if boolean_constraint_indices.binary_search(&cs_index).is_ok() {
                 if !w2_value.is_zero() && !w2_value.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the second wire {:?} is not one or zero",
-                        cs_index, w2_value
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index,
+                        format!("the second wire {:?} is not one or zero", w2_value),
+                    ));
                 }
 
                 if !w3_value.is_zero() && !w3_value.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the third wire {:?} is not one or zero",
-                        cs_index, w3_value
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index,
+                        format!("the third wire {:?} is not one or zero", w3_value),
+                    ));
                 }
 
                 if !w4_value.is_zero() && !w4_value.is_one() {
-                    return Err(UzkgeError::Message(format!(
-                        "cs index {}: the fourth wire {:?} is not one or zero",
-                        cs_index, w4_value
-                    )));
+                    return Err(self.unsatisfied_gate(
+                        witness,
+                        cs_index,
+                        format!("the fourth wire {:?} is not one or zero", w4_value),
+                    ));
                 }
             }
         }
+
+        for (cs_index, gate_id, selectors) in self.custom_constraint_indices.iter() {
+            let gate = &self.custom_gates[*gate_id];
+            let wire_vals: Vec<&F> = (0..N_WIRES_PER_GATE)
+                .map(|i| &witness[self.get_witness_index(i, *cs_index)])
+                .collect();
+            let sel_vals: Vec<&F> = selectors.iter().collect();
+            let eval_gate = gate.eval_gate_func(&wire_vals, &sel_vals);
+            if !eval_gate.is_zero() {
+                return Err(self.unsatisfied_gate(
+                    witness,
+                    *cs_index,
+                    format!(
+                        "the custom gate {} does not hold with its selectors ({:?})",
+                        gate.name(),
+                        sel_vals
+                    ),
+                ));
+            }
+        }
+
+        for (cs_index, table_id) in self.lookup_constraint_indices.iter() {
+            let query = [
+                witness[self.get_witness_index(0, *cs_index)],
+                witness[self.get_witness_index(1, *cs_index)],
+                witness[self.get_witness_index(2, *cs_index)],
+            ];
+            if !self.lookup_tables[*table_id - 1].contains(&query) {
+                return Err(self.unsatisfied_gate(
+                    witness,
+                    *cs_index,
+                    format!(
+                        "the lookup query {:?} is not in the table {}",
+                        query, table_id
+                    ),
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// The error of the unsatisfied gate `cs_index`, with its namespace path, wire values
+    /// and selector values.
+    fn unsatisfied_gate(&self, witness: &[F], cs_index: CsIndex, reason: String) -> UzkgeError {
+        let wire_vals: Vec<&F> = (0..N_WIRES_PER_GATE)
+            .map(|i| &witness[self.get_witness_index(i, cs_index)])
+            .collect();
+        let sel_vals: Vec<&F> = self.selectors.iter().map(|q| &q[cs_index]).collect();
+        let label = match self.namespace_of(cs_index) {
+            "" => format!("cs index {}", cs_index),
+            path => format!("cs index {} in `{}`", cs_index, path),
+        };
+        UzkgeError::Message(format!(
+            "{}: {}, wire_vals = ({:?}), sel_vals = ({:?})",
+            label, reason, wire_vals, sel_vals
+        ))
+    }
+
     /// Extract and clear the entire witness of the circuit. The witness consists of
     /// secret inputs, public inputs, and the values of intermediate variables.
     pub fn get_and_clear_witness(&mut self) -> Vec<F> {
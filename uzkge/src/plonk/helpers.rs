@@ -1,7 +1,13 @@
 use ark_ff::{batch_inversion, Field, One, PrimeField, UniformRand, Zero};
-use ark_poly::EvaluationDomain;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
 use ark_std::cfg_into_iter;
-use ark_std::{cmp::min, ops::*};
+use ark_std::{
+    cmp::{max, min},
+    marker::PhantomData,
+    mem::size_of,
+    ops::*,
+    sync::Arc,
+};
 use itertools::Itertools;
 use rand_chacha::rand_core::{CryptoRng, RngCore};
 
@@ -9,7 +15,10 @@ use rand_chacha::rand_core::{CryptoRng, RngCore};
 use rayon::{iter::IntoParallelIterator, prelude::ParallelIterator};
 
 use super::{
-    constraint_system::ConstraintSystem,
+    constraint_system::{
+        custom_gate::{eval_custom_gates, eval_custom_selector_multipliers, CustomGate},
+        ConstraintSystem, PlonkLayout,
+    },
     indexer::{PlonkProof, PlonkProverParams, PlonkVerifierParams},
 };
 use crate::errors::UzkgeError;
@@ -20,6 +29,7 @@ use crate::poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme};
 #[derive(Default)]
 pub(super) struct PlonkChallenges<F> {
     challenges: Vec<F>,
+    lookup_challenges: Vec<F>,
 }
 
 impl<F: PrimeField> PlonkChallenges<F> {
@@ -27,6 +37,46 @@ impl<F: PrimeField> PlonkChallenges<F> {
     pub(super) fn new() -> PlonkChallenges<F> {
         PlonkChallenges {
             challenges: Vec::with_capacity(4),
+            lookup_challenges: Vec::with_capacity(3),
+        }
+    }
+
+    /// Insert eta.
+    pub(super) fn insert_eta(&mut self, eta: F) -> Result<(), UzkgeError> {
+        if self.challenges.is_empty() && self.lookup_challenges.is_empty() {
+            self.lookup_challenges.push(eta);
+            Ok(())
+        } else {
+            Err(UzkgeError::ChallengeError)
+        }
+    }
+
+    /// Insert delta and epsilon.
+    pub(super) fn insert_delta_epsilon(&mut self, delta: F, epsilon: F) -> Result<(), UzkgeError> {
+        if self.challenges.len() == 2 && self.lookup_challenges.len() == 1 {
+            self.lookup_challenges.push(delta);
+            self.lookup_challenges.push(epsilon);
+            Ok(())
+        } else {
+            Err(UzkgeError::ChallengeError)
+        }
+    }
+
+    /// Return eta.
+    pub(super) fn get_eta(&self) -> Result<&F, UzkgeError> {
+        if !self.lookup_challenges.is_empty() {
+            Ok(&self.lookup_challenges[0])
+        } else {
+            Err(UzkgeError::ChallengeError)
+        }
+    }
+
+    /// Return delta and epsilon.
+    pub(super) fn get_delta_epsilon(&self) -> Result<(&F, &F), UzkgeError> {
+        if self.lookup_challenges.len() > 2 {
+            Ok((&self.lookup_challenges[1], &self.lookup_challenges[2]))
+        } else {
+            Err(UzkgeError::ChallengeError)
         }
     }
 
@@ -219,12 +269,639 @@ pub(super) fn z_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
     FpPolynomial::from_coefs(z_evals)
 }
 
+/// Compress a lookup row (a, b, c, table_id) into a + eta * b + eta^2 * c + eta^3 * table_id.
+pub(super) fn compress_lookup_row<F: PrimeField>(eta: &F, a: &F, b: &F, c: &F, table_id: &F) -> F {
+    table_id.mul(eta).add(c).mul(eta).add(b).mul(eta).add(a)
+}
+
+/// Compress the lookup table polynomials or commitments with eta.
+pub(super) fn compress_lookup_table<F: PrimeField, PCSType: HomomorphicPolyComElem<Scalar = F>>(
+    eta: &F,
+    table_polys_or_comms: &[PCSType],
+) -> PCSType {
+    let mut res = table_polys_or_comms[3].clone();
+    for poly_or_comm in table_polys_or_comms.iter().take(3).rev() {
+        res.mul_assign(eta);
+        res.add_assign(poly_or_comm);
+    }
+    res
+}
+
+/// Build the evaluations of the lookup polynomials over the domain, i.e.,
+/// the compressed table t, the compressed queries f, and the two halves h1, h2
+/// of the vector s = (f, t) sorted by t, s.t. h1 = (s_0, s_2, ...) and h2 = (s_1, s_3, ...).
+/// The gates without lookup constraint query the last row of the table.
+pub(super) fn lookup_evals<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: &CS,
+    w: &[F],
+    challenges: &PlonkChallenges<F>,
+) -> Result<[Vec<F>; 4], UzkgeError> {
+    let n = cs.size();
+    let eta = challenges.get_eta()?;
+    let [q_lookup, q_table] = cs.compute_lookup_selectors();
+    let table = cs.compute_lookup_table();
+
+    let t: Vec<F> = (0..n)
+        .map(|i| compress_lookup_row(eta, &table[0][i], &table[1][i], &table[2][i], &table[3][i]))
+        .collect();
+    let f: Vec<F> = (0..n)
+        .map(|i| {
+            if q_lookup[i].is_zero() {
+                t[n - 1]
+            } else {
+                compress_lookup_row(eta, &w[i], &w[n + i], &w[2 * n + i], &q_table[i])
+            }
+        })
+        .collect();
+
+    let mut counts = ark_std::collections::BTreeMap::new();
+    for v in f.iter() {
+        *counts.entry(*v).or_insert(0usize) += 1;
+    }
+    let mut sorted = Vec::with_capacity(2 * n);
+    for v in t.iter() {
+        sorted.push(*v);
+        if let Some(count) = counts.remove(v) {
+            sorted.extend(ark_std::iter::repeat_n(*v, count));
+        }
+    }
+    if !counts.is_empty() {
+        return Err(UzkgeError::LookupError);
+    }
+
+    let h1 = sorted.iter().step_by(2).copied().collect();
+    let h2 = sorted.iter().skip(1).step_by(2).copied().collect();
+
+    Ok([t, f, h1, h2])
+}
+
+/// Build the lookup z polynomial, by interpolating
+/// z(\omega^{i+1}) = z(\omega^i)(1 + \delta)(\epsilon + f(\omega^i))
+///     (\epsilon(1 + \delta) + t(\omega^i) + \delta t(\omega^{i+1}))
+///     / (\epsilon(1 + \delta) + h1(\omega^i) + \delta h2(\omega^i))
+///     / (\epsilon(1 + \delta) + h2(\omega^i) + \delta h1(\omega^{i+1}))
+/// and setting z(1) = 1 for the base case
+pub(super) fn z_lookup_poly<F: PrimeField>(
+    lookup_evals: &[Vec<F>; 4],
+    challenges: &PlonkChallenges<F>,
+) -> FpPolynomial<F> {
+    let [t, f, h1, h2] = lookup_evals;
+    let n = t.len();
+    let (delta, epsilon) = challenges.get_delta_epsilon().unwrap();
+    let one_plus_delta = F::one().add(delta);
+    let epsilon_one_plus_delta = epsilon.mul(&one_plus_delta);
+
+    let res = cfg_into_iter!(0..n - 1)
+        .map(|i| {
+            let numerator = one_plus_delta
+                .mul(&epsilon.add(&f[i]))
+                .mul(&epsilon_one_plus_delta.add(&t[i]).add(&delta.mul(&t[i + 1])));
+            let denominator = epsilon_one_plus_delta
+                .add(&h1[i])
+                .add(&delta.mul(&h2[i]))
+                .mul(
+                    &epsilon_one_plus_delta
+                        .add(&h2[i])
+                        .add(&delta.mul(&h1[i + 1])),
+                );
+            (numerator, denominator)
+        })
+        .collect::<Vec<(F, F)>>();
+
+    let (numerators, mut denominators): (Vec<F>, Vec<F>) = res.into_iter().unzip();
+
+    batch_inversion(&mut denominators);
+
+    let mut prev = F::one();
+    let mut z_evals = vec![];
+    z_evals.push(prev);
+    for (x, y) in denominators.iter().zip(numerators.iter()) {
+        prev.mul_assign(y.mul(x));
+        z_evals.push(prev);
+    }
+
+    FpPolynomial::from_coefs(z_evals)
+}
+
+/// Tracks the field element buffers held by the prover, against an optional budget in bytes.
+/// It only sums the lengths of the buffers recorded by the prover, so it is an estimate that
+/// ignores the allocator overhead, the capacity beyond the lengths, and the temporary values,
+/// e.g., of the FFTs and the commitment scheme.
+pub(super) struct MemoryTracker<F> {
+    budget: Option<usize>,
+    current: usize,
+    peak: usize,
+    _field: PhantomData<F>,
+}
+
+impl<F> MemoryTracker<F> {
+    /// Create a tracker with the budget, unlimited if `None`.
+    pub(super) fn new(budget: Option<usize>) -> Self {
+        MemoryTracker {
+            budget,
+            current: 0,
+            peak: 0,
+            _field: PhantomData,
+        }
+    }
+
+    /// Record a buffer of `len` field elements, or fail if it exceeds the budget.
+    pub(super) fn alloc(&mut self, len: usize) -> Result<(), UzkgeError> {
+        let current = self.current + len * size_of::<F>();
+        if let Some(budget) = self.budget {
+            if current > budget {
+                return Err(UzkgeError::MemoryBudgetExceeded(budget));
+            }
+        }
+        self.current = current;
+        self.peak = max(self.peak, current);
+        Ok(())
+    }
+
+    /// Record that a buffer of `len` field elements is dropped.
+    pub(super) fn free(&mut self, len: usize) {
+        self.current -= len * size_of::<F>();
+    }
+
+    /// Return the peak number of bytes.
+    pub(super) fn peak(&self) -> usize {
+        self.peak
+    }
+}
+
+/// The evaluations of the polynomials in the quotient on a coset, where the point
+/// after `point`, i.e., `point` times the generator of the domain, is `(point + step) % len`.
+struct QuotientColumns<C> {
+    len: usize,
+    step: usize,
+    w: Vec<C>,
+    w_sel: Vec<C>,
+    pi: C,
+    z: C,
+    x: C,
+    l1: C,
+    q: Vec<C>,
+    s: Vec<C>,
+    qb: C,
+    q_prk: Vec<C>,
+    q_ecc: C,
+    q_shuffle_generator: Vec<C>,
+    q_shuffle_public_key: Vec<C>,
+    lookup: Vec<C>,
+    lookup_table: C,
+    q_lookup: Vec<C>,
+}
+
+fn as_slices<F>(evals: &[Vec<F>]) -> Vec<&[F]> {
+    evals.iter().map(|e| e.as_slice()).collect()
+}
+
+/// The values shared by all the points of the quotient.
+struct QuotientConstants<'a, F: PrimeField> {
+    challenges: &'a PlonkChallenges<F>,
+    /// alpha^0, alpha^1, ..., alpha^19.
+    alpha_pows: Vec<F>,
+    k: &'a [F],
+    anemoi_generator: F,
+    anemoi_generator_inv: F,
+    edwards_a: F,
+    layout: PlonkLayout,
+    custom_gates: &'a [Arc<dyn CustomGate<F>>],
+}
+
+impl<'a, F: PrimeField> QuotientConstants<'a, F> {
+    fn new<PCS: PolyComScheme<Field = F>, CS: ConstraintSystem<F>>(
+        cs: &'a CS,
+        prover_params: &'a PlonkProverParams<PCS>,
+        challenges: &'a PlonkChallenges<F>,
+    ) -> Self {
+        let alpha = challenges.get_alpha().unwrap();
+        let mut alpha_pows = vec![F::one()];
+        for i in 1..20 {
+            alpha_pows.push(alpha_pows[i - 1].mul(alpha));
+        }
+
+        QuotientConstants {
+            challenges,
+            alpha_pows,
+            k: &prover_params.verifier_params.k,
+            anemoi_generator: prover_params.verifier_params.anemoi_generator,
+            anemoi_generator_inv: prover_params.verifier_params.anemoi_generator_inv,
+            edwards_a: cs.get_edwards_a(),
+            layout: prover_params.verifier_params.layout(),
+            custom_gates: cs.custom_gates(),
+        }
+    }
+}
+
+/// Evaluate the numerator of the quotient polynomial at `point` of the coset.
+fn quotient_numerator<F: PrimeField, CS: ConstraintSystem<F>, C: Deref<Target = [F]>>(
+    c: &QuotientColumns<C>,
+    consts: &QuotientConstants<F>,
+    point: usize,
+) -> F {
+    let one = F::one();
+    let next = (point + c.step) % c.len;
+    let a = &consts.alpha_pows;
+    let (beta, gamma) = consts.challenges.get_beta_gamma().unwrap();
+    let k = consts.k;
+
+    let w_vals: Vec<&F> =
+        c.w.iter()
+            .map(|poly_coset_evals| &poly_coset_evals[point])
+            .collect();
+    let q_vals: Vec<&F> =
+        c.q.iter()
+            .map(|poly_coset_evals| &poly_coset_evals[point])
+            .collect();
+    // q * w, with the custom gates
+    let (q_vals, q_custom_vals) = q_vals.split_at(CS::num_selectors());
+    let mut term1 = CS::eval_gate_func(&w_vals, q_vals, &c.pi[point]).unwrap();
+    term1.add_assign(&eval_custom_gates(
+        consts.custom_gates,
+        &w_vals,
+        q_custom_vals,
+    ));
+
+    // alpha * [z(X)\prod_j (fj(X) + beta * kj * X + gamma)]
+    let mut term2 = a[1].mul(&c.z[point]);
+    for (w_coset_evals, k) in c.w.iter().zip(k.iter()).take(CS::n_wires_per_gate()) {
+        let tmp = w_coset_evals[point]
+            .add(gamma)
+            .add(&beta.mul(&k.mul(&c.x[point])));
+        term2.mul_assign(&tmp);
+    }
+
+    // alpha * [z(\omega * X)\prod_j (fj(X) + beta * perm_j(X) + gamma)]
+    let mut term3 = a[1].mul(&c.z[next]);
+    for (w_poly_coset_evals, s_coset_evals) in c.w.iter().zip(c.s.iter()) {
+        let tmp = &w_poly_coset_evals[point]
+            .add(gamma)
+            .add(&beta.mul(&s_coset_evals[point]));
+        term3.mul_assign(tmp);
+    }
+
+    // alpha^2 * (z(X) - 1) * L_1(X)
+    let term4 = a[2].mul(&c.l1[point]).mul(&c.z[point].sub(&one));
+
+    let qb_eval_point = c.qb[point];
+
+    // alpha^3 * qb(X) (w[1] (w[1] - 1))
+    let w1_eval_point = c.w[1][point];
+    let term5 = a[3]
+        .mul(&qb_eval_point)
+        .mul(&w1_eval_point)
+        .mul(&w1_eval_point.sub(&one));
+
+    // alpha^4 * qb(X) (w[2] (w[2] - 1))
+    let w2_eval_point = c.w[2][point];
+    let term6 = a[4]
+        .mul(&qb_eval_point)
+        .mul(&w2_eval_point)
+        .mul(&w2_eval_point.sub(&one));
+
+    // alpha^5 * qb(X) (w[3] (w[3] - 1))
+    let w3_eval_point = c.w[3][point];
+    let term7 = a[5]
+        .mul(&qb_eval_point)
+        .mul(&w3_eval_point)
+        .mul(&w3_eval_point.sub(&one));
+
+    let w0_eval_point = c.w[0][point];
+    let wo_eval_point = c.w[4][point];
+    let w0_eval_point_next = c.w[0][next];
+    let w1_eval_point_next = c.w[1][next];
+    let w2_eval_point_next = c.w[2][next];
+    let q_prk1_eval_point = c.q_prk[0][point];
+    let q_prk2_eval_point = c.q_prk[1][point];
+    let q_prk3_eval_point = c.q_prk[2][point];
+    let q_prk4_eval_point = c.q_prk[3][point];
+    let g = consts.anemoi_generator;
+    let g_square_plus_one = g.square().add(one);
+    let g_inv = consts.anemoi_generator_inv;
+    let five = &[5u64];
+
+    let w3_w0_eval_point = w0_eval_point + w3_eval_point;
+    let w2_w1_eval_point = w1_eval_point + w2_eval_point;
+
+    let w3_2w0_eval_point = w0_eval_point + w3_w0_eval_point;
+    let w2_2w1_eval_point = w1_eval_point + w2_w1_eval_point;
+
+    let tmp = w3_w0_eval_point + &(g * &w2_w1_eval_point) + &q_prk3_eval_point;
+
+    // - alpha^6 * q_{prk3} *
+    //  (
+    //    (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3} - w_next[2]) ^ 5
+    //    + g * (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3}) ^ 2
+    //    - (2w[0] + w[3] + g * (2w[1] + w[2]) + q_{prk1})
+    //  )
+    let term8 = a[6].mul(&q_prk3_eval_point).mul(
+        (tmp - &w2_eval_point_next).pow(five) + &(g * tmp.square())
+            - &(w3_2w0_eval_point + g * w2_2w1_eval_point + &q_prk1_eval_point),
+    );
+    // - alpha^8 * q_{prk3} *
+    //  (
+    //    (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3} - w_next[2]) ^ 5
+    //    + g * w_next[2] ^ 2 + g^-1
+    //    - w_next[0]
+    //  )
+    let term10 = a[8].mul(&q_prk3_eval_point).mul(
+        (tmp - &w2_eval_point_next).pow(five) + &(g * w2_eval_point_next.square()) + g_inv
+            - &w0_eval_point_next,
+    );
+
+    // - alpha^7 * q_{prk3} *
+    //  (
+    //    (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4} - w[4]) ^ 5
+    //    + g * (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4}) ^ 2
+    //    - (g * (2w[0] + w[3]) + (g^2 + 1) * (2w[1] + w[2]) + q_{prk2})
+    //  )
+    let tmp = g * &w3_w0_eval_point + &(g_square_plus_one * &w2_w1_eval_point) + &q_prk4_eval_point;
+    let term9 = a[7].mul(&q_prk3_eval_point).mul(
+        (tmp - &wo_eval_point).pow(five) + &(g * tmp.square())
+            - &(g * &w3_2w0_eval_point
+                + g_square_plus_one * &w2_2w1_eval_point
+                + &q_prk2_eval_point),
+    );
+
+    // - alpha^9 * q_{prk3} *
+    //  (
+    //    (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4} - w[4]) ^ 5
+    //    + g * w[4] ^ 2 + g^-1
+    //    - w_next[1]
+    //  )
+    let term11 = a[9].mul(&q_prk3_eval_point).mul(
+        (tmp - &wo_eval_point).pow(five) + &(g * wo_eval_point.square()) + g_inv
+            - &w1_eval_point_next,
+    );
+
+    let (term12, term13, term14, term15, term16, term17, term18) = if consts.layout.has_shuffle() {
+        let w_sel0_eval_point = c.w_sel[0][point];
+        let w_sel1_eval_point = c.w_sel[1][point];
+        let w_sel2_eval_point = c.w_sel[2][point];
+        let q_pk_x_00_eval_point = c.q_shuffle_public_key[0][point];
+        let q_pk_x_01_eval_point = c.q_shuffle_public_key[1][point];
+        let q_pk_x_10_eval_point = c.q_shuffle_public_key[2][point];
+        let q_pk_x_11_eval_point = c.q_shuffle_public_key[3][point];
+        let q_pk_y_00_eval_point = c.q_shuffle_public_key[4][point];
+        let q_pk_y_01_eval_point = c.q_shuffle_public_key[5][point];
+        let q_pk_y_10_eval_point = c.q_shuffle_public_key[6][point];
+        let q_pk_y_11_eval_point = c.q_shuffle_public_key[7][point];
+        let q_pk_dxy_00_eval_point = c.q_shuffle_public_key[8][point];
+        let q_pk_dxy_01_eval_point = c.q_shuffle_public_key[9][point];
+        let q_pk_dxy_10_eval_point = c.q_shuffle_public_key[10][point];
+        let q_pk_dxy_11_eval_point = c.q_shuffle_public_key[11][point];
+        let q_g_x_00_eval_point = c.q_shuffle_generator[0][point];
+        let q_g_x_01_eval_point = c.q_shuffle_generator[1][point];
+        let q_g_x_10_eval_point = c.q_shuffle_generator[2][point];
+        let q_g_x_11_eval_point = c.q_shuffle_generator[3][point];
+        let q_g_y_00_eval_point = c.q_shuffle_generator[4][point];
+        let q_g_y_01_eval_point = c.q_shuffle_generator[5][point];
+        let q_g_y_10_eval_point = c.q_shuffle_generator[6][point];
+        let q_g_y_11_eval_point = c.q_shuffle_generator[7][point];
+        let q_g_dxy_00_eval_point = c.q_shuffle_generator[8][point];
+        let q_g_dxy_01_eval_point = c.q_shuffle_generator[9][point];
+        let q_g_dxy_10_eval_point = c.q_shuffle_generator[10][point];
+        let q_g_dxy_11_eval_point = c.q_shuffle_generator[11][point];
+        let q_ecc = c.q_ecc[point];
+
+        let sel_00 = (one - w_sel0_eval_point) * (one - w_sel1_eval_point) + q_ecc - one;
+        let sel_01 = w_sel0_eval_point * (one - w_sel1_eval_point);
+        let sel_10 = (one - w_sel0_eval_point) * w_sel1_eval_point;
+        let sel_11 = w_sel0_eval_point * w_sel1_eval_point;
+
+        // alpha^10 *
+        // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_00} - w[1] * q_{pk_x_00} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_00}) *
+        // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_01} - w[1] * q_{pk_x_01} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_01})
+        // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_10} - w[1] * q_{pk_x_10} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_10})
+        // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_11} - w[1] * q_{pk_x_11} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_11})
+        let term12 = a[10].mul(
+            sel_00
+                * (w_sel2_eval_point * w0_eval_point_next
+                    - w_sel2_eval_point * w0_eval_point * q_pk_y_00_eval_point
+                    - w1_eval_point * q_pk_x_00_eval_point
+                    + w0_eval_point * w1_eval_point * w0_eval_point_next * q_pk_dxy_00_eval_point)
+                + sel_01
+                    * (w_sel2_eval_point * w0_eval_point_next
+                        - w_sel2_eval_point * w0_eval_point * q_pk_y_01_eval_point
+                        - w1_eval_point * q_pk_x_01_eval_point
+                        + w0_eval_point
+                            * w1_eval_point
+                            * w0_eval_point_next
+                            * q_pk_dxy_01_eval_point)
+                + sel_10
+                    * (w_sel2_eval_point * w0_eval_point_next
+                        - w_sel2_eval_point * w0_eval_point * q_pk_y_10_eval_point
+                        - w1_eval_point * q_pk_x_10_eval_point
+                        + w0_eval_point
+                            * w1_eval_point
+                            * w0_eval_point_next
+                            * q_pk_dxy_10_eval_point)
+                + sel_11
+                    * (w_sel2_eval_point * w0_eval_point_next
+                        - w_sel2_eval_point * w0_eval_point * q_pk_y_11_eval_point
+                        - w1_eval_point * q_pk_x_11_eval_point
+                        + w0_eval_point
+                            * w1_eval_point
+                            * w0_eval_point_next
+                            * q_pk_dxy_11_eval_point),
+        );
+
+        // alpha^11 *
+        // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_00} - w_sel[2] * w[1] * q_{pk_y_00} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_00}) +
+        // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_01} - w_sel[2] * w[1] * q_{pk_y_01} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_01}) +
+        // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_10} - w_sel[2] * w[1] * q_{pk_y_10} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_10}) +
+        // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_11} - w_sel[2] * w[1] * q_{pk_y_11} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_11}) +
+        let term13 = a[11].mul(
+            sel_00
+                * (w_sel2_eval_point * w1_eval_point_next
+                    + w0_eval_point * consts.edwards_a * q_pk_x_00_eval_point
+                    - w_sel2_eval_point * w1_eval_point * q_pk_y_00_eval_point
+                    - w0_eval_point * w1_eval_point * w1_eval_point_next * q_pk_dxy_00_eval_point)
+                + sel_01
+                    * (w_sel2_eval_point * w1_eval_point_next
+                        + w0_eval_point * consts.edwards_a * q_pk_x_01_eval_point
+                        - w_sel2_eval_point * w1_eval_point * q_pk_y_01_eval_point
+                        - w0_eval_point
+                            * w1_eval_point
+                            * w1_eval_point_next
+                            * q_pk_dxy_01_eval_point)
+                + sel_10
+                    * (w_sel2_eval_point * w1_eval_point_next
+                        + w0_eval_point * consts.edwards_a * q_pk_x_10_eval_point
+                        - w_sel2_eval_point * w1_eval_point * q_pk_y_10_eval_point
+                        - w0_eval_point
+                            * w1_eval_point
+                            * w1_eval_point_next
+                            * q_pk_dxy_10_eval_point)
+                + sel_11
+                    * (w_sel2_eval_point * w1_eval_point_next
+                        + w0_eval_point * consts.edwards_a * q_pk_x_11_eval_point
+                        - w_sel2_eval_point * w1_eval_point * q_pk_y_11_eval_point
+                        - w0_eval_point
+                            * w1_eval_point
+                            * w1_eval_point_next
+                            * q_pk_dxy_11_eval_point),
+        );
+
+        // alpha^12 *
+        // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_00} - w[3] * q_{g_x_00} + w[2] * w[3] * w_next[2] *  q_{g_dxy_00}) +
+        // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_01} - w[3] * q_{g_x_01} + w[2] * w[3] * w_next[2] *  q_{g_dxy_01}) +
+        // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_10} - w[3] * q_{g_x_10} + w[2] * w[3] * w_next[2] *  q_{g_dxy_10}) +
+        // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_11} - w[3] * q_{g_x_11} + w[2] * w[3] * w_next[2] *  q_{g_dxy_11}) +
+        let term14 = a[12].mul(
+            sel_00
+                * (w_sel2_eval_point * w2_eval_point_next
+                    - w_sel2_eval_point * w2_eval_point * q_g_y_00_eval_point
+                    - w3_eval_point * q_g_x_00_eval_point
+                    + w2_eval_point * w3_eval_point * w2_eval_point_next * q_g_dxy_00_eval_point)
+                + sel_01
+                    * (w_sel2_eval_point * w2_eval_point_next
+                        - w_sel2_eval_point * w2_eval_point * q_g_y_01_eval_point
+                        - w3_eval_point * q_g_x_01_eval_point
+                        + w2_eval_point
+                            * w3_eval_point
+                            * w2_eval_point_next
+                            * q_g_dxy_01_eval_point)
+                + sel_10
+                    * (w_sel2_eval_point * w2_eval_point_next
+                        - w_sel2_eval_point * w2_eval_point * q_g_y_10_eval_point
+                        - w3_eval_point * q_g_x_10_eval_point
+                        + w2_eval_point
+                            * w3_eval_point
+                            * w2_eval_point_next
+                            * q_g_dxy_10_eval_point)
+                + sel_11
+                    * (w_sel2_eval_point * w2_eval_point_next
+                        - w_sel2_eval_point * w2_eval_point * q_g_y_11_eval_point
+                        - w3_eval_point * q_g_x_11_eval_point
+                        + w2_eval_point
+                            * w3_eval_point
+                            * w2_eval_point_next
+                            * q_g_dxy_11_eval_point),
+        );
+
+        // alpha^13 *
+        // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w[4] + a * w[2] * q_{g_x_00} - w_sel[2] * w[3] * q_{g_y_00} + w[2] * w[3] * w[4] *  q_{pk_gxy_00}) +
+        // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w[4] + a * w[2] * q_{g_x_01} - w_sel[2] * w[3] * q_{g_y_01} + w[2] * w[3] * w[4] *  q_{pk_gxy_01}) +
+        // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w[4] + a * w[2] * q_{g_x_10} - w_sel[2] * w[3] * q_{g_y_10} + w[2] * w[3] * w[4] *  q_{pk_gxy_10}) +
+        // w_sel[0] *  w_sel[1] * (w_sel[2] * w[4] + a * w[2] * q_{g_x_11} - w_sel[2] * w[3] * q_{g_y_11} + w[2] * w[3] * w[4] *  q_{pk_gxy_11}) +
+        let term15 = a[13].mul(
+            sel_00
+                * (w_sel2_eval_point * wo_eval_point
+                    + w2_eval_point * consts.edwards_a * q_g_x_00_eval_point
+                    - w_sel2_eval_point * w3_eval_point * q_g_y_00_eval_point
+                    - w2_eval_point * w3_eval_point * wo_eval_point * q_g_dxy_00_eval_point)
+                + sel_01
+                    * (w_sel2_eval_point * wo_eval_point
+                        + w2_eval_point * consts.edwards_a * q_g_x_01_eval_point
+                        - w_sel2_eval_point * w3_eval_point * q_g_y_01_eval_point
+                        - w2_eval_point * w3_eval_point * wo_eval_point * q_g_dxy_01_eval_point)
+                + sel_10
+                    * (w_sel2_eval_point * wo_eval_point
+                        + w2_eval_point * consts.edwards_a * q_g_x_10_eval_point
+                        - w_sel2_eval_point * w3_eval_point * q_g_y_10_eval_point
+                        - w2_eval_point * w3_eval_point * wo_eval_point * q_g_dxy_10_eval_point)
+                + sel_11
+                    * (w_sel2_eval_point * wo_eval_point
+                        + w2_eval_point * consts.edwards_a * q_g_x_11_eval_point
+                        - w_sel2_eval_point * w3_eval_point * q_g_y_11_eval_point
+                        - w2_eval_point * w3_eval_point * wo_eval_point * q_g_dxy_11_eval_point),
+        );
+
+        // alpha^14 * (q_{ecc} * w_sel[0] * (1 - w_sel[0]) + (1 - q_{ecc}) *  w_sel[0])
+        let term16 = a[14].mul(
+            q_ecc * w_sel0_eval_point * (one - w_sel0_eval_point)
+                + (one - q_ecc) * w_sel0_eval_point,
+        );
+        // alpha^15 * (q_{ecc} * w_sel[1] * (1 - w_sel[1]) + (1 - q_{ecc}) *  w_sel[1])
+        let term17 = a[15].mul(
+            q_ecc * w_sel1_eval_point * (one - w_sel1_eval_point)
+                + (one - q_ecc) * w_sel1_eval_point,
+        );
+        // alpha^16 * q_{ecc} * (1 + w_sel[2])  * (1 - w_sel[2])
+        let term18 = a[16].mul(q_ecc * (one + w_sel2_eval_point) * (one - w_sel2_eval_point));
+
+        (term12, term13, term14, term15, term16, term17, term18)
+    } else {
+        Default::default()
+    };
+
+    let numerator = term1
+        .add(&term2)
+        .add(&term4.sub(&term3))
+        .add(&term5)
+        .add(&term6)
+        .add(&term7)
+        .sub(&term8)
+        .sub(&term9)
+        .sub(&term10)
+        .sub(&term11)
+        .add(&term12)
+        .add(&term13)
+        .add(&term14)
+        .add(&term15)
+        .add(&term16)
+        .add(&term17)
+        .add(&term18);
+
+    if !consts.layout.has_lookup() {
+        return numerator;
+    }
+
+    {
+        let eta = consts.challenges.get_eta().unwrap();
+        let (delta, epsilon) = consts.challenges.get_delta_epsilon().unwrap();
+        let one_plus_delta = one.add(delta);
+        let epsilon_one_plus_delta = epsilon.mul(&one_plus_delta);
+
+        let f_eval_point = c.lookup[0][point];
+        let h1_eval_point = c.lookup[1][point];
+        let h2_eval_point = c.lookup[2][point];
+        let z_lookup_eval_point = c.lookup[3][point];
+
+        // alpha^17 * [z_lookup(X) (1 + delta) (epsilon + f(X))
+        //     (epsilon (1 + delta) + t(X) + delta * t(\omega * X))
+        //   - z_lookup(\omega * X) (epsilon (1 + delta) + h1(X) + delta * h2(X))
+        //     (epsilon (1 + delta) + h2(X) + delta * h1(\omega * X))]
+        let term19 = a[17].mul(
+            z_lookup_eval_point
+                * one_plus_delta
+                * (epsilon.add(&f_eval_point))
+                * (epsilon_one_plus_delta
+                    + c.lookup_table[point]
+                    + delta.mul(&c.lookup_table[next]))
+                - c.lookup[3][next]
+                    * (epsilon_one_plus_delta + h1_eval_point + delta.mul(&h2_eval_point))
+                    * (epsilon_one_plus_delta + h2_eval_point + delta.mul(&c.lookup[1][next])),
+        );
+
+        // alpha^18 * (z_lookup(X) - 1) * L_1(X)
+        let term20 = a[18].mul(&c.l1[point]).mul(&z_lookup_eval_point.sub(&one));
+
+        // alpha^19 * q_lookup(X) * (w[0] + eta * w[1] + eta^2 * w[2] + eta^3 * q_table(X) - f(X))
+        let term21 = a[19].mul(&c.q_lookup[0][point]).mul(
+            compress_lookup_row(
+                eta,
+                &w0_eval_point,
+                &w1_eval_point,
+                &w2_eval_point,
+                &c.q_lookup[1][point],
+            )
+            .sub(&f_eval_point),
+        );
+
+        numerator.add(&term19).add(&term20).add(&term21)
+    }
+}
+
 /// Compute the t polynomial.
 pub(super) fn t_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
     cs: &CS,
     prover_params: &PlonkProverParams<PCS>,
     w_polys: &[FpPolynomial<PCS::Field>],
-    #[cfg(feature = "shuffle")] w_sel_polys: &[FpPolynomial<PCS::Field>],
+    w_sel_polys: &[FpPolynomial<PCS::Field>],
+    lookup_polys: &[&FpPolynomial<PCS::Field>],
     z: &FpPolynomial<PCS::Field>,
     challenges: &PlonkChallenges<PCS::Field>,
     pi: &FpPolynomial<PCS::Field>,
@@ -257,7 +934,6 @@ pub(super) fn t_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
         .iter()
         .map(|poly| poly.coset_fft_with_domain(&domain_m, &k[1]))
         .collect();
-    #[cfg(feature = "shuffle")]
     let w_sel_polys_coset_evals: Vec<Vec<PCS::Field>> = w_sel_polys
         .iter()
         .map(|poly| poly.coset_fft_with_domain(&domain_m, &k[1]))
@@ -265,435 +941,293 @@ pub(super) fn t_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
     let pi_coset_evals = pi.coset_fft_with_domain(&domain_m, &k[1]);
     let z_coset_evals = z.coset_fft_with_domain(&domain_m, &k[1]);
 
-    #[cfg(feature = "shuffle")]
-    let edwards_a: <PCS as PolyComScheme>::Field = cs.get_edwards_a();
-
-    // Compute the evaluations of the quotient polynomial on the coset.
-    let (beta, gamma) = challenges.get_beta_gamma().unwrap();
+    // The evaluations of the lookup f/h1/h2/z polynomials and the compressed table.
+    let lookup_coset_evals: Vec<Vec<PCS::Field>> = lookup_polys
+        .iter()
+        .map(|poly| poly.coset_fft_with_domain(&domain_m, &k[1]))
+        .collect();
+    let lookup_table_coset_evals: Vec<PCS::Field> =
+        if prover_params.verifier_params.layout().has_lookup() {
+            let eta = challenges.get_eta()?;
+            let table = &prover_params.lookup_table_coset_evals;
+            (0..m)
+                .map(|i| {
+                    compress_lookup_row(eta, &table[0][i], &table[1][i], &table[2][i], &table[3][i])
+                })
+                .collect()
+        } else {
+            vec![]
+        };
 
-    let alpha = challenges.get_alpha().unwrap();
-    let alpha_pow_2 = alpha.mul(alpha);
-    let alpha_pow_3 = alpha_pow_2.mul(alpha);
-    let alpha_pow_4 = alpha_pow_3.mul(alpha);
-    let alpha_pow_5 = alpha_pow_4.mul(alpha);
-    let alpha_pow_6 = alpha_pow_5.mul(alpha);
-    let alpha_pow_7 = alpha_pow_6.mul(alpha);
-    let alpha_pow_8 = alpha_pow_7.mul(alpha);
-    let alpha_pow_9 = alpha_pow_8.mul(alpha);
+    let columns = QuotientColumns {
+        len: m,
+        step: factor,
+        w: as_slices(&w_polys_coset_evals),
+        w_sel: as_slices(&w_sel_polys_coset_evals),
+        pi: pi_coset_evals.as_slice(),
+        z: z_coset_evals.as_slice(),
+        x: prover_params.coset_quotient.as_slice(),
+        l1: prover_params.l1_coset_evals.as_slice(),
+        q: as_slices(&prover_params.q_coset_evals),
+        s: as_slices(&prover_params.s_coset_evals),
+        qb: prover_params.qb_coset_eval.as_slice(),
+        q_prk: as_slices(&prover_params.q_prk_coset_evals),
+        q_ecc: prover_params.q_ecc_coset_eval.as_slice(),
+        q_shuffle_generator: as_slices(&prover_params.q_shuffle_generator_coset_evals),
+        q_shuffle_public_key: as_slices(&prover_params.q_shuffle_public_key_coset_evals),
+        lookup: as_slices(&lookup_coset_evals),
+        lookup_table: lookup_table_coset_evals.as_slice(),
+        q_lookup: as_slices(&prover_params.q_lookup_coset_evals),
+    };
+    let consts = QuotientConstants::new(cs, prover_params, challenges);
 
+    // Compute the evaluations of the quotient polynomial on the coset.
     let t_coset_evals = cfg_into_iter!(0..m)
         .map(|point| {
-            let w_vals: Vec<&PCS::Field> = w_polys_coset_evals
-                .iter()
-                .map(|poly_coset_evals| &poly_coset_evals[point])
-                .collect();
-            let q_vals: Vec<&PCS::Field> = prover_params
-                .q_coset_evals
-                .iter()
-                .map(|poly_coset_evals| &poly_coset_evals[point])
-                .collect();
-            // q * w
-            let term1 = CS::eval_gate_func(&w_vals, &q_vals, &pi_coset_evals[point]).unwrap();
-
-            // alpha * [z(X)\prod_j (fj(X) + beta * kj * X + gamma)]
-            let mut term2 = alpha.mul(&z_coset_evals[point]);
-            for j in 0..CS::n_wires_per_gate() {
-                let tmp = w_polys_coset_evals[j][point]
-                    .add(gamma)
-                    .add(&beta.mul(&k[j].mul(&prover_params.coset_quotient[point])));
-                term2.mul_assign(&tmp);
-            }
+            quotient_numerator::<_, CS, _>(&columns, &consts, point)
+                .mul(&z_h_inv_coset_evals[point % factor])
+        })
+        .collect::<Vec<PCS::Field>>();
 
-            // alpha * [z(\omega * X)\prod_j (fj(X) + beta * perm_j(X) + gamma)]
-            let mut term3 = alpha.mul(&z_coset_evals[(point + factor) % m]);
-            for (w_poly_coset_evals, s_coset_evals) in w_polys_coset_evals
-                .iter()
-                .zip(prover_params.s_coset_evals.iter())
-            {
-                let tmp = &w_poly_coset_evals[point]
-                    .add(gamma)
-                    .add(&beta.mul(&s_coset_evals[point]));
-                term3.mul_assign(tmp);
-            }
+    let k_inv = k[1].inverse().ok_or(UzkgeError::DivisionByZero)?;
 
-            // alpha^2 * (z(X) - 1) * L_1(X)
-            let term4 = alpha_pow_2
-                .mul(&prover_params.l1_coset_evals[point])
-                .mul(&z_coset_evals[point].sub(&one));
-
-            let qb_eval_point = prover_params.qb_coset_eval[point];
-
-            // alpha^3 * qb(X) (w[1] (w[1] - 1))
-            let w1_eval_point = w_polys_coset_evals[1][point];
-            let term5 = alpha_pow_3
-                .mul(&qb_eval_point)
-                .mul(&w1_eval_point)
-                .mul(&w1_eval_point.sub(&one));
-
-            // alpha^4 * qb(X) (w[2] (w[2] - 1))
-            let w2_eval_point = w_polys_coset_evals[2][point];
-            let term6 = alpha_pow_4
-                .mul(&qb_eval_point)
-                .mul(&w2_eval_point)
-                .mul(&w2_eval_point.sub(&one));
-
-            // alpha^5 * qb(X) (w[3] (w[3] - 1))
-            let w3_eval_point = w_polys_coset_evals[3][point];
-            let term7 = alpha_pow_5
-                .mul(&qb_eval_point)
-                .mul(&w3_eval_point)
-                .mul(&w3_eval_point.sub(&one));
-
-            let w0_eval_point = w_polys_coset_evals[0][point];
-            let wo_eval_point = w_polys_coset_evals[4][point];
-            let w0_eval_point_next = w_polys_coset_evals[0][(point + factor) % m];
-            let w1_eval_point_next = w_polys_coset_evals[1][(point + factor) % m];
-            let w2_eval_point_next = w_polys_coset_evals[2][(point + factor) % m];
-            let q_prk1_eval_point = prover_params.q_prk_coset_evals[0][point];
-            let q_prk2_eval_point = prover_params.q_prk_coset_evals[1][point];
-            let q_prk3_eval_point = prover_params.q_prk_coset_evals[2][point];
-            let q_prk4_eval_point = prover_params.q_prk_coset_evals[3][point];
-            let g = prover_params.verifier_params.anemoi_generator;
-            let g_square_plus_one = g.square().add(one);
-            let g_inv = prover_params.verifier_params.anemoi_generator_inv;
-            let five = &[5u64];
-
-            let w3_w0_eval_point = w0_eval_point + w3_eval_point;
-            let w2_w1_eval_point = w1_eval_point + w2_eval_point;
-
-            let w3_2w0_eval_point = w0_eval_point + w3_w0_eval_point;
-            let w2_2w1_eval_point = w1_eval_point + w2_w1_eval_point;
-
-            let tmp = w3_w0_eval_point + &(g * &w2_w1_eval_point) + &q_prk3_eval_point;
-
-            // - alpha^6 * q_{prk3} *
-            //  (
-            //    (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3} - w_next[2]) ^ 5
-            //    + g * (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3}) ^ 2
-            //    - (2w[0] + w[3] + g * (2w[1] + w[2]) + q_{prk1})
-            //  )
-            let term8 = alpha_pow_6.mul(&q_prk3_eval_point).mul(
-                (tmp - &w2_eval_point_next).pow(five) + &(g * tmp.square())
-                    - &(w3_2w0_eval_point + g * w2_2w1_eval_point + &q_prk1_eval_point),
-            );
-            // - alpha^8 * q_{prk3} *
-            //  (
-            //    (w[0] + w[3] + g * (w[1] + w[2]) + q_{prk3} - w_next[2]) ^ 5
-            //    + g * w_next[2] ^ 2 + g^-1
-            //    - w_next[0]
-            //  )
-            let term10 = alpha_pow_8.mul(&q_prk3_eval_point).mul(
-                (tmp - &w2_eval_point_next).pow(five) + &(g * w2_eval_point_next.square()) + g_inv
-                    - &w0_eval_point_next,
-            );
+    Ok(FpPolynomial::coset_ifft_with_domain(
+        &domain_m,
+        &t_coset_evals,
+        &k_inv,
+    ))
+}
 
-            // - alpha^7 * q_{prk3} *
-            //  (
-            //    (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4} - w[4]) ^ 5
-            //    + g * (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4}) ^ 2
-            //    - (g * (2w[0] + w[3]) + (g^2 + 1) * (2w[1] + w[2]) + q_{prk2})
-            //  )
-            let tmp = g * &w3_w0_eval_point
-                + &(g_square_plus_one * &w2_w1_eval_point)
-                + &q_prk4_eval_point;
-            let term9 = alpha_pow_7.mul(&q_prk3_eval_point).mul(
-                (tmp - &wo_eval_point).pow(five) + &(g * tmp.square())
-                    - &(g * &w3_2w0_eval_point
-                        + g_square_plus_one * &w2_2w1_eval_point
-                        + &q_prk2_eval_point),
-            );
+/// Evaluate `poly` on the coset `shift * H` of the domain `H`, where the coefficients
+/// of the degrees not less than `|H|` are folded into the lower ones.
+fn sub_coset_evals<F: PrimeField>(
+    poly: &FpPolynomial<F>,
+    domain: &Radix2EvaluationDomain<F>,
+    shift: &F,
+) -> Vec<F> {
+    let n = domain.size();
+    let mut evals = vec![F::zero(); n];
+    let mut shift_pow = F::one();
+    for (i, coef) in poly.coefs.iter().enumerate() {
+        evals[i % n].add_assign(shift_pow.mul(coef));
+        shift_pow.mul_assign(shift);
+    }
+    domain.fft_in_place(&mut evals);
+    evals
+}
 
-            // - alpha^9 * q_{prk3} *
-            //  (
-            //    (g * (w[0] + w[3]) + (g^2 + 1) * (w[1] + w[2]) + q_{prk4} - w[4]) ^ 5
-            //    + g * w[4] ^ 2 + g^-1
-            //    - w_next[1]
-            //  )
-            let term11 = alpha_pow_9.mul(&q_prk3_eval_point).mul(
-                (tmp - &wo_eval_point).pow(five) + &(g * wo_eval_point.square()) + g_inv
-                    - &w1_eval_point_next,
-            );
+/// Compute the t polynomial as `t_poly`, but evaluate the numerator on the `factor`
+/// sub-cosets `k[1] * root_m^j * <root_n>` of the coset `k[1] * <root_m>` one by one,
+/// so that only the evaluations of the polynomials on one sub-coset are held at a time.
+/// The coset evaluations in the prover parameters are used if they are not dropped.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn t_poly_by_cosets<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
+    cs: &CS,
+    prover_params: &PlonkProverParams<PCS>,
+    w_polys: &[FpPolynomial<PCS::Field>],
+    w_sel_polys: &[FpPolynomial<PCS::Field>],
+    lookup_polys: &[&FpPolynomial<PCS::Field>],
+    z: &FpPolynomial<PCS::Field>,
+    challenges: &PlonkChallenges<PCS::Field>,
+    pi: &FpPolynomial<PCS::Field>,
+    tracker: &mut MemoryTracker<PCS::Field>,
+) -> Result<FpPolynomial<PCS::Field>, UzkgeError> {
+    let n = cs.size();
+    let m = cs.quot_eval_dom_size();
+    let factor = m / n;
+    if n * factor != m {
+        return Err(UzkgeError::SetupError);
+    }
+    let one = PCS::Field::ONE;
 
-            #[cfg(feature = "shuffle")]
-            let (term12, term13, term14, term15, term16, term17, term18) = {
-                let alpha_pow_10 = alpha_pow_9.mul(alpha);
-                let alpha_pow_11 = alpha_pow_10.mul(alpha);
-                let alpha_pow_12 = alpha_pow_11.mul(alpha);
-                let alpha_pow_13 = alpha_pow_12.mul(alpha);
-                let alpha_pow_14 = alpha_pow_13.mul(alpha);
-                let alpha_pow_15 = alpha_pow_14.mul(alpha);
-                let alpha_pow_16 = alpha_pow_15.mul(alpha);
-
-                let w_sel0_eval_point = w_sel_polys_coset_evals[0][point];
-                let w_sel1_eval_point = w_sel_polys_coset_evals[1][point];
-                let w_sel2_eval_point = w_sel_polys_coset_evals[2][point];
-                let q_pk_x_00_eval_point = prover_params.q_shuffle_public_key_coset_evals[0][point];
-                let q_pk_x_01_eval_point = prover_params.q_shuffle_public_key_coset_evals[1][point];
-                let q_pk_x_10_eval_point = prover_params.q_shuffle_public_key_coset_evals[2][point];
-                let q_pk_x_11_eval_point = prover_params.q_shuffle_public_key_coset_evals[3][point];
-                let q_pk_y_00_eval_point = prover_params.q_shuffle_public_key_coset_evals[4][point];
-                let q_pk_y_01_eval_point = prover_params.q_shuffle_public_key_coset_evals[5][point];
-                let q_pk_y_10_eval_point = prover_params.q_shuffle_public_key_coset_evals[6][point];
-                let q_pk_y_11_eval_point = prover_params.q_shuffle_public_key_coset_evals[7][point];
-                let q_pk_dxy_00_eval_point =
-                    prover_params.q_shuffle_public_key_coset_evals[8][point];
-                let q_pk_dxy_01_eval_point =
-                    prover_params.q_shuffle_public_key_coset_evals[9][point];
-                let q_pk_dxy_10_eval_point =
-                    prover_params.q_shuffle_public_key_coset_evals[10][point];
-                let q_pk_dxy_11_eval_point =
-                    prover_params.q_shuffle_public_key_coset_evals[11][point];
-                let q_g_x_00_eval_point = prover_params.q_shuffle_generator_coset_evals[0][point];
-                let q_g_x_01_eval_point = prover_params.q_shuffle_generator_coset_evals[1][point];
-                let q_g_x_10_eval_point = prover_params.q_shuffle_generator_coset_evals[2][point];
-                let q_g_x_11_eval_point = prover_params.q_shuffle_generator_coset_evals[3][point];
-                let q_g_y_00_eval_point = prover_params.q_shuffle_generator_coset_evals[4][point];
-                let q_g_y_01_eval_point = prover_params.q_shuffle_generator_coset_evals[5][point];
-                let q_g_y_10_eval_point = prover_params.q_shuffle_generator_coset_evals[6][point];
-                let q_g_y_11_eval_point = prover_params.q_shuffle_generator_coset_evals[7][point];
-                let q_g_dxy_00_eval_point = prover_params.q_shuffle_generator_coset_evals[8][point];
-                let q_g_dxy_01_eval_point = prover_params.q_shuffle_generator_coset_evals[9][point];
-                let q_g_dxy_10_eval_point =
-                    prover_params.q_shuffle_generator_coset_evals[10][point];
-                let q_g_dxy_11_eval_point =
-                    prover_params.q_shuffle_generator_coset_evals[11][point];
-                let q_ecc = prover_params.q_ecc_coset_eval[point];
-
-                let sel_00 = (one - w_sel0_eval_point) * (one - w_sel1_eval_point) + q_ecc - one;
-                let sel_01 = w_sel0_eval_point * (one - w_sel1_eval_point);
-                let sel_10 = (one - w_sel0_eval_point) * w_sel1_eval_point;
-                let sel_11 = w_sel0_eval_point * w_sel1_eval_point;
-
-                // alpha^10 *
-                // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_00} - w[1] * q_{pk_x_00} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_00}) *
-                // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_01} - w[1] * q_{pk_x_01} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_01})
-                // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_10} - w[1] * q_{pk_x_10} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_10})
-                // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[0] - w_sel[2] * w[0] * q_{pk_y_11} - w[1] * q_{pk_x_11} + w[0] * w[1] * w_next[0] *  q_{pk_dxy_11})
-                let term12 = alpha_pow_10.mul(
-                    sel_00
-                        * (w_sel2_eval_point * w0_eval_point_next
-                            - w_sel2_eval_point * w0_eval_point * q_pk_y_00_eval_point
-                            - w1_eval_point * q_pk_x_00_eval_point
-                            + w0_eval_point
-                                * w1_eval_point
-                                * w0_eval_point_next
-                                * q_pk_dxy_00_eval_point)
-                        + sel_01
-                            * (w_sel2_eval_point * w0_eval_point_next
-                                - w_sel2_eval_point * w0_eval_point * q_pk_y_01_eval_point
-                                - w1_eval_point * q_pk_x_01_eval_point
-                                + w0_eval_point
-                                    * w1_eval_point
-                                    * w0_eval_point_next
-                                    * q_pk_dxy_01_eval_point)
-                        + sel_10
-                            * (w_sel2_eval_point * w0_eval_point_next
-                                - w_sel2_eval_point * w0_eval_point * q_pk_y_10_eval_point
-                                - w1_eval_point * q_pk_x_10_eval_point
-                                + w0_eval_point
-                                    * w1_eval_point
-                                    * w0_eval_point_next
-                                    * q_pk_dxy_10_eval_point)
-                        + sel_11
-                            * (w_sel2_eval_point * w0_eval_point_next
-                                - w_sel2_eval_point * w0_eval_point * q_pk_y_11_eval_point
-                                - w1_eval_point * q_pk_x_11_eval_point
-                                + w0_eval_point
-                                    * w1_eval_point
-                                    * w0_eval_point_next
-                                    * q_pk_dxy_11_eval_point),
-                );
+    let domain =
+        FpPolynomial::<PCS::Field>::evaluation_domain(n).ok_or(UzkgeError::GroupNotFound(n))?;
+    let domain_m = FpPolynomial::<PCS::Field>::quotient_evaluation_domain(m)
+        .ok_or(UzkgeError::GroupNotFound(n))?;
+    debug_assert_eq!(domain_m.group_gen.pow([factor as u64]), domain.group_gen);
+    let k = &prover_params.verifier_params.k;
+    let stored = prover_params.has_coset_evals();
+    let consts = QuotientConstants::new(cs, prover_params, challenges);
+
+    let num_columns = {
+        let num_columns = w_polys.len()
+            + w_sel_polys.len()
+            + prover_params.q_polys.len()
+            + prover_params.s_polys.len()
+            + prover_params.q_prk_polys.len()
+            + prover_params.q_shuffle_generator_polys.len()
+            + prover_params.q_shuffle_public_key_polys.len()
+            + 6;
+        if consts.layout.has_lookup() {
+            // the lookup polynomials and the compressed table.
+            num_columns
+                + lookup_polys.len()
+                + prover_params.q_lookup_polys.len()
+                + prover_params.lookup_table_polys.len()
+                + 1
+        } else {
+            num_columns
+        }
+    };
 
-                // alpha^11 *
-                // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_00} - w_sel[2] * w[1] * q_{pk_y_00} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_00}) +
-                // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_01} - w_sel[2] * w[1] * q_{pk_y_01} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_01}) +
-                // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_10} - w_sel[2] * w[1] * q_{pk_y_10} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_10}) +
-                // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[1] + a * w[0] * q_{pk_x_11} - w_sel[2] * w[1] * q_{pk_y_11} - w[0] * w[1] * w_next[1] *  q_{pk_dxy_11}) +
-                let term13 = alpha_pow_11.mul(
-                    sel_00
-                        * (w_sel2_eval_point * w1_eval_point_next
-                            + w0_eval_point * edwards_a * q_pk_x_00_eval_point
-                            - w_sel2_eval_point * w1_eval_point * q_pk_y_00_eval_point
-                            - w0_eval_point
-                                * w1_eval_point
-                                * w1_eval_point_next
-                                * q_pk_dxy_00_eval_point)
-                        + sel_01
-                            * (w_sel2_eval_point * w1_eval_point_next
-                                + w0_eval_point * edwards_a * q_pk_x_01_eval_point
-                                - w_sel2_eval_point * w1_eval_point * q_pk_y_01_eval_point
-                                - w0_eval_point
-                                    * w1_eval_point
-                                    * w1_eval_point_next
-                                    * q_pk_dxy_01_eval_point)
-                        + sel_10
-                            * (w_sel2_eval_point * w1_eval_point_next
-                                + w0_eval_point * edwards_a * q_pk_x_10_eval_point
-                                - w_sel2_eval_point * w1_eval_point * q_pk_y_10_eval_point
-                                - w0_eval_point
-                                    * w1_eval_point
-                                    * w1_eval_point_next
-                                    * q_pk_dxy_10_eval_point)
-                        + sel_11
-                            * (w_sel2_eval_point * w1_eval_point_next
-                                + w0_eval_point * edwards_a * q_pk_x_11_eval_point
-                                - w_sel2_eval_point * w1_eval_point * q_pk_y_11_eval_point
-                                - w0_eval_point
-                                    * w1_eval_point
-                                    * w1_eval_point_next
-                                    * q_pk_dxy_11_eval_point),
-                );
+    // t = sum_r X^{r * n} * t_r with deg(t_r) < n, and on the sub-coset j, where
+    // X^n = k[1]^n * zeta^j for the primitive `factor`-th root of unity zeta = root_m^n,
+    // t is reduced to u_j = sum_r zeta^{j * r} * k[1]^{r * n} * t_r, which is interpolated
+    // from the evaluations of the sub-coset, so t_r = k[1]^{-r * n} / factor * sum_j zeta^{-j * r} * u_j.
+    // The caller accounts for the coefficients of t.
+    let zeta_inv = domain_m.group_gen_inv.pow([n as u64]);
+    let mut t_coefs = vec![PCS::Field::zero(); m];
+    for j in 0..factor {
+        let shift = k[1].mul(domain_m.group_gen.pow([j as u64]));
+        let z_h_inv = shift
+            .pow([n as u64])
+            .sub(&one)
+            .inverse()
+            .ok_or(UzkgeError::DivisionByZero)?;
+
+        // The evaluations of a preprocessed polynomial are read from the coset evaluations if stored.
+        let evals = |poly: &FpPolynomial<PCS::Field>, coset_evals: &[PCS::Field]| {
+            if stored {
+                (0..n).map(|i| coset_evals[i * factor + j]).collect()
+            } else {
+                sub_coset_evals(poly, &domain, &shift)
+            }
+        };
+        let evals_vec = |polys: &[FpPolynomial<PCS::Field>],
+                         coset_evals: &[Vec<PCS::Field>]|
+         -> Vec<Vec<PCS::Field>> {
+            if stored {
+                polys
+                    .iter()
+                    .zip(coset_evals.iter())
+                    .map(|(poly, coset_evals)| evals(poly, coset_evals))
+                    .collect()
+            } else {
+                polys.iter().map(|poly| evals(poly, &[])).collect()
+            }
+        };
 
-                // alpha^12 *
-                // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_00} - w[3] * q_{g_x_00} + w[2] * w[3] * w_next[2] *  q_{g_dxy_00}) +
-                // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_01} - w[3] * q_{g_x_01} + w[2] * w[3] * w_next[2] *  q_{g_dxy_01}) +
-                // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_10} - w[3] * q_{g_x_10} + w[2] * w[3] * w_next[2] *  q_{g_dxy_10}) +
-                // w_sel[0] *  w_sel[1] * (w_sel[2] * w_next[2] - w_sel[2] * w[2] * q_{g_y_11} - w[3] * q_{g_x_11} + w[2] * w[3] * w_next[2] *  q_{g_dxy_11}) +
-                let term14 = alpha_pow_12.mul(
-                    sel_00
-                        * (w_sel2_eval_point * w2_eval_point_next
-                            - w_sel2_eval_point * w2_eval_point * q_g_y_00_eval_point
-                            - w3_eval_point * q_g_x_00_eval_point
-                            + w2_eval_point
-                                * w3_eval_point
-                                * w2_eval_point_next
-                                * q_g_dxy_00_eval_point)
-                        + sel_01
-                            * (w_sel2_eval_point * w2_eval_point_next
-                                - w_sel2_eval_point * w2_eval_point * q_g_y_01_eval_point
-                                - w3_eval_point * q_g_x_01_eval_point
-                                + w2_eval_point
-                                    * w3_eval_point
-                                    * w2_eval_point_next
-                                    * q_g_dxy_01_eval_point)
-                        + sel_10
-                            * (w_sel2_eval_point * w2_eval_point_next
-                                - w_sel2_eval_point * w2_eval_point * q_g_y_10_eval_point
-                                - w3_eval_point * q_g_x_10_eval_point
-                                + w2_eval_point
-                                    * w3_eval_point
-                                    * w2_eval_point_next
-                                    * q_g_dxy_10_eval_point)
-                        + sel_11
-                            * (w_sel2_eval_point * w2_eval_point_next
-                                - w_sel2_eval_point * w2_eval_point * q_g_y_11_eval_point
-                                - w3_eval_point * q_g_x_11_eval_point
-                                + w2_eval_point
-                                    * w3_eval_point
-                                    * w2_eval_point_next
-                                    * q_g_dxy_11_eval_point),
+        tracker.alloc((num_columns + 1) * n)?;
+        let columns = QuotientColumns {
+            len: n,
+            step: 1,
+            w: w_polys
+                .iter()
+                .map(|poly| sub_coset_evals(poly, &domain, &shift))
+                .collect(),
+            w_sel: w_sel_polys
+                .iter()
+                .map(|poly| sub_coset_evals(poly, &domain, &shift))
+                .collect(),
+            pi: sub_coset_evals(pi, &domain, &shift),
+            z: sub_coset_evals(z, &domain, &shift),
+            x: domain.elements().map(|x| shift.mul(x)).collect(),
+            l1: evals(&prover_params.l1_coefs, &prover_params.l1_coset_evals),
+            q: evals_vec(&prover_params.q_polys, &prover_params.q_coset_evals),
+            s: evals_vec(&prover_params.s_polys, &prover_params.s_coset_evals),
+            qb: evals(&prover_params.qb_poly, &prover_params.qb_coset_eval),
+            q_prk: evals_vec(&prover_params.q_prk_polys, &prover_params.q_prk_coset_evals),
+            q_ecc: evals(&prover_params.q_ecc_poly, &prover_params.q_ecc_coset_eval),
+            q_shuffle_generator: evals_vec(
+                &prover_params.q_shuffle_generator_polys,
+                &prover_params.q_shuffle_generator_coset_evals,
+            ),
+            q_shuffle_public_key: evals_vec(
+                &prover_params.q_shuffle_public_key_polys,
+                &prover_params.q_shuffle_public_key_coset_evals,
+            ),
+            lookup: lookup_polys
+                .iter()
+                .map(|poly| sub_coset_evals(poly, &domain, &shift))
+                .collect(),
+            lookup_table: if consts.layout.has_lookup() {
+                let eta = challenges.get_eta()?;
+                let table: Vec<Vec<PCS::Field>> = evals_vec(
+                    &prover_params.lookup_table_polys,
+                    &prover_params.lookup_table_coset_evals,
                 );
+                (0..n)
+                    .map(|i| {
+                        compress_lookup_row(
+                            eta,
+                            &table[0][i],
+                            &table[1][i],
+                            &table[2][i],
+                            &table[3][i],
+                        )
+                    })
+                    .collect()
+            } else {
+                vec![]
+            },
+            q_lookup: evals_vec(
+                &prover_params.q_lookup_polys,
+                &prover_params.q_lookup_coset_evals,
+            ),
+        };
 
-                // alpha^13 *
-                // ((1 - w_sel[0]) * (1 - w_sel[1]) + q_{ecc} - 1) * (w_sel[2] * w[4] + a * w[2] * q_{g_x_00} - w_sel[2] * w[3] * q_{g_y_00} + w[2] * w[3] * w[4] *  q_{pk_gxy_00}) +
-                // w_sel[0] *  (1 - w_sel[1]) * (w_sel[2] * w[4] + a * w[2] * q_{g_x_01} - w_sel[2] * w[3] * q_{g_y_01} + w[2] * w[3] * w[4] *  q_{pk_gxy_01}) +
-                // (1 - w_sel[0]) *  w_sel[1] * (w_sel[2] * w[4] + a * w[2] * q_{g_x_10} - w_sel[2] * w[3] * q_{g_y_10} + w[2] * w[3] * w[4] *  q_{pk_gxy_10}) +
-                // w_sel[0] *  w_sel[1] * (w_sel[2] * w[4] + a * w[2] * q_{g_x_11} - w_sel[2] * w[3] * q_{g_y_11} + w[2] * w[3] * w[4] *  q_{pk_gxy_11}) +
-                let term15 = alpha_pow_13.mul(
-                    sel_00
-                        * (w_sel2_eval_point * wo_eval_point
-                            + w2_eval_point * edwards_a * q_g_x_00_eval_point
-                            - w_sel2_eval_point * w3_eval_point * q_g_y_00_eval_point
-                            - w2_eval_point
-                                * w3_eval_point
-                                * wo_eval_point
-                                * q_g_dxy_00_eval_point)
-                        + sel_01
-                            * (w_sel2_eval_point * wo_eval_point
-                                + w2_eval_point * edwards_a * q_g_x_01_eval_point
-                                - w_sel2_eval_point * w3_eval_point * q_g_y_01_eval_point
-                                - w2_eval_point
-                                    * w3_eval_point
-                                    * wo_eval_point
-                                    * q_g_dxy_01_eval_point)
-                        + sel_10
-                            * (w_sel2_eval_point * wo_eval_point
-                                + w2_eval_point * edwards_a * q_g_x_10_eval_point
-                                - w_sel2_eval_point * w3_eval_point * q_g_y_10_eval_point
-                                - w2_eval_point
-                                    * w3_eval_point
-                                    * wo_eval_point
-                                    * q_g_dxy_10_eval_point)
-                        + sel_11
-                            * (w_sel2_eval_point * wo_eval_point
-                                + w2_eval_point * edwards_a * q_g_x_11_eval_point
-                                - w_sel2_eval_point * w3_eval_point * q_g_y_11_eval_point
-                                - w2_eval_point
-                                    * w3_eval_point
-                                    * wo_eval_point
-                                    * q_g_dxy_11_eval_point),
-                );
+        let mut u = cfg_into_iter!(0..n)
+            .map(|i| quotient_numerator::<_, CS, _>(&columns, &consts, i).mul(&z_h_inv))
+            .collect::<Vec<PCS::Field>>();
+        drop(columns);
+
+        domain.ifft_in_place(&mut u);
+        let shift_inv = shift.inverse().ok_or(UzkgeError::DivisionByZero)?;
+        let mut shift_inv_pow = one;
+        for coef in u.iter_mut() {
+            coef.mul_assign(&shift_inv_pow);
+            shift_inv_pow.mul_assign(&shift_inv);
+        }
 
-                // alpha^14 * (q_{ecc} * w_sel[0] * (1 - w_sel[0]) + (1 - q_{ecc}) *  w_sel[0])
-                let term16 = alpha_pow_14.mul(
-                    q_ecc * w_sel0_eval_point * (one - w_sel0_eval_point)
-                        + (one - q_ecc) * w_sel0_eval_point,
-                );
-                // alpha^15 * (q_{ecc} * w_sel[1] * (1 - w_sel[1]) + (1 - q_{ecc}) *  w_sel[1])
-                let term17 = alpha_pow_15.mul(
-                    q_ecc * w_sel1_eval_point * (one - w_sel1_eval_point)
-                        + (one - q_ecc) * w_sel1_eval_point,
-                );
-                // alpha^16 * q_{ecc} * (1 + w_sel[2])  * (1 - w_sel[2])
-                let term18 =
-                    alpha_pow_16.mul(q_ecc * (one + w_sel2_eval_point) * (one - w_sel2_eval_point));
-
-                (term12, term13, term14, term15, term16, term17, term18)
-            };
-
-            let numerator = term1
-                .add(&term2)
-                .add(&term4.sub(&term3))
-                .add(&term5)
-                .add(&term6)
-                .add(&term7)
-                .sub(&term8)
-                .sub(&term9)
-                .sub(&term10)
-                .sub(&term11);
-
-            #[cfg(feature = "shuffle")]
-            let numerator = {
-                numerator
-                    .add(&term12)
-                    .add(&term13)
-                    .add(&term14)
-                    .add(&term15)
-                    .add(&term16)
-                    .add(&term17)
-                    .add(&term18)
-            };
-
-            numerator.mul(&z_h_inv_coset_evals[point % factor])
-        })
-        .collect::<Vec<PCS::Field>>();
+        let zeta_inv_pow_j = zeta_inv.pow([j as u64]);
+        let mut zeta_inv_pow = one;
+        for t_r in t_coefs.chunks_mut(n) {
+            for (t_coef, coef) in t_r.iter_mut().zip(u.iter()) {
+                t_coef.add_assign(zeta_inv_pow.mul(coef));
+            }
+            zeta_inv_pow.mul_assign(&zeta_inv_pow_j);
+        }
+        drop(u);
+        tracker.free((num_columns + 1) * n);
+    }
 
-    let k_inv = k[1].inverse().ok_or(UzkgeError::DivisionByZero)?;
+    let k_n_inv = k[1]
+        .pow([n as u64])
+        .inverse()
+        .ok_or(UzkgeError::DivisionByZero)?;
+    let mut scale = PCS::Field::from(factor as u64)
+        .inverse()
+        .ok_or(UzkgeError::DivisionByZero)?;
+    for t_r in t_coefs.chunks_mut(n) {
+        for t_coef in t_r.iter_mut() {
+            t_coef.mul_assign(&scale);
+        }
+        scale.mul_assign(&k_n_inv);
+    }
 
-    Ok(FpPolynomial::coset_ifft_with_domain(
-        &domain_m,
-        &t_coset_evals,
-        &k_inv,
-    ))
+    Ok(FpPolynomial::from_coefs(t_coefs))
 }
-
 /// Compute r polynomial or commitment.
+#[allow(clippy::too_many_arguments)]
 fn r_poly_or_comm<F: PrimeField, PCSType: HomomorphicPolyComElem<Scalar = F>>(
+    layout: PlonkLayout,
     w: &[F],
     q_polys_or_comms: &[PCSType],
     qb_poly_or_comm: &PCSType,
     q_prk1_poly_or_comm: &PCSType,
     q_prk2_poly_or_comm: &PCSType,
-    #[cfg(feature = "shuffle")] q_shuffle_generator_polys_or_comms: &[PCSType],
-    #[cfg(feature = "shuffle")] q_shuffle_public_key_polys_or_comms: &[PCSType],
-    #[cfg(feature = "shuffle")] q_ecc_poly_eval_zeta: &F,
-    #[cfg(feature = "shuffle")] w_sel_polys_eval_zeta: &[&F],
+    q_shuffle_generator_polys_or_comms: &[PCSType],
+    q_shuffle_public_key_polys_or_comms: &[PCSType],
+    q_ecc_poly_eval_zeta: &F,
+    w_sel_polys_eval_zeta: &[&F],
     k: &[F],
-    #[cfg(feature = "shuffle")] edwards_a: &F,
+    edwards_a: &F,
+    z_lookup_poly_or_comm: &PCSType,
+    lookup_evals_zeta: &[&F],
     last_s_poly_or_comm: &PCSType,
     z_poly_or_comm: &PCSType,
     w_polys_eval_zeta: &[&F],
-    #[cfg(feature = "shuffle")] w_polys_eval_zeta_omega: &[&F],
+    w_polys_eval_zeta_omega: &[&F],
     s_polys_eval_zeta: &[&F],
     q_prk3_eval_zeta: &F,
     z_eval_zeta_omega: &F,
@@ -748,8 +1282,7 @@ fn r_poly_or_comm<F: PrimeField, PCSType: HomomorphicPolyComElem<Scalar = F>>(
     l.add_assign(&q_prk1_poly_or_comm.mul(&q_prk3_eval_zeta.mul(alpha_pow_6)));
     l.add_assign(&q_prk2_poly_or_comm.mul(&q_prk3_eval_zeta.mul(alpha_pow_7)));
 
-    #[cfg(feature = "shuffle")]
-    {
+    if layout.has_shuffle() {
         let alpha_pow_10 = alpha_pow_7.mul(alpha.mul(alpha).mul(alpha));
         let alpha_pow_11 = alpha_pow_10.mul(alpha);
         let alpha_pow_12 = alpha_pow_11.mul(alpha);
@@ -987,6 +1520,27 @@ fn r_poly_or_comm<F: PrimeField, PCSType: HomomorphicPolyComElem<Scalar = F>>(
         l.add_assign(&tmp.mul(&alpha_pow_13));
     }
 
+    // 6. + z_lookup(X) [alpha^17 (1 + delta) (epsilon + f(zeta))
+    //      (epsilon (1 + delta) + t(zeta) + delta * t(zeta * omega)) + alpha^18 * L1(zeta)]
+    if layout.has_lookup() {
+        let (delta, epsilon) = challenges.get_delta_epsilon().unwrap();
+        let alpha_pow_17 = alpha_pow_7.mul(alpha_pow_5).mul(alpha_pow_5);
+        let alpha_pow_18 = alpha_pow_17.mul(alpha);
+        let one_plus_delta = one.add(delta);
+
+        let z_lookup_scalar = alpha_pow_17
+            .mul(&one_plus_delta)
+            .mul(&epsilon.add(lookup_evals_zeta[0]))
+            .mul(
+                &epsilon
+                    .mul(&one_plus_delta)
+                    .add(lookup_evals_zeta[1])
+                    .add(&delta.mul(lookup_evals_zeta[2])),
+            )
+            .add(&first_lagrange_eval_zeta.mul(&alpha_pow_18));
+        l.add_assign(&z_lookup_poly_or_comm.mul(&z_lookup_scalar));
+    }
+
     let factor = zeta.pow(&[n_t_polys as u64]);
     let mut exponent = z_h_eval_zeta.mul(factor);
     let mut t_poly_combined = t_polys_or_comms[0].clone().mul(&z_h_eval_zeta);
@@ -1028,44 +1582,48 @@ fn compute_z_scalar_in_r<F: PrimeField>(
 
 /// Compute the r polynomial.
 pub(super) fn r_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
+    cs: &CS,
     prover_params: &PlonkProverParams<PCS>,
     z: &FpPolynomial<PCS::Field>,
     w_polys_eval_zeta: &[&PCS::Field],
-    #[cfg(feature = "shuffle")] w_polys_eval_zeta_omega: &[&PCS::Field],
+    w_polys_eval_zeta_omega: &[&PCS::Field],
     s_polys_eval_zeta: &[&PCS::Field],
     q_prk3_eval_zeta: &PCS::Field,
     z_eval_zeta_omega: &PCS::Field,
-    #[cfg(feature = "shuffle")] q_ecc_poly_eval_zeta: &PCS::Field,
-    #[cfg(feature = "shuffle")] w_sel_polys_eval_zeta: &[&PCS::Field],
+    q_ecc_poly_eval_zeta: &PCS::Field,
+    w_sel_polys_eval_zeta: &[&PCS::Field],
     challenges: &PlonkChallenges<PCS::Field>,
     t_polys: &[FpPolynomial<PCS::Field>],
     first_lagrange_eval_zeta: &PCS::Field,
     z_h_eval_zeta: &PCS::Field,
-    #[cfg(feature = "shuffle")] edwards_a: &PCS::Field,
+    edwards_a: &PCS::Field,
+    z_lookup: &FpPolynomial<PCS::Field>,
+    lookup_evals_zeta: &[&PCS::Field],
     n_t_polys: usize,
 ) -> FpPolynomial<PCS::Field> {
-    let w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
+    let mut w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
+    w.extend(eval_custom_selector_multipliers(
+        cs.custom_gates(),
+        w_polys_eval_zeta,
+    ));
     r_poly_or_comm::<PCS::Field, FpPolynomial<PCS::Field>>(
+        prover_params.verifier_params.layout(),
         &w,
         &prover_params.q_polys,
         &prover_params.qb_poly,
         &prover_params.q_prk_polys[0],
         &prover_params.q_prk_polys[1],
-        #[cfg(feature = "shuffle")]
         &prover_params.q_shuffle_generator_polys,
-        #[cfg(feature = "shuffle")]
         &prover_params.q_shuffle_public_key_polys,
-        #[cfg(feature = "shuffle")]
         q_ecc_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         w_sel_polys_eval_zeta,
         &prover_params.verifier_params.k,
-        #[cfg(feature = "shuffle")]
         edwards_a,
+        z_lookup,
+        lookup_evals_zeta,
         &prover_params.s_polys[CS::n_wires_per_gate() - 1],
         z,
         w_polys_eval_zeta,
-        #[cfg(feature = "shuffle")]
         w_polys_eval_zeta_omega,
         s_polys_eval_zeta,
         q_prk3_eval_zeta,
@@ -1080,14 +1638,17 @@ pub(super) fn r_poly<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
 
 /// Commit the r commitment.
 pub(super) fn r_commitment<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
+    cs: &CS,
     verifier_params: &PlonkVerifierParams<PCS>,
     cm_z: &PCS::Commitment,
+    cm_lookup_z: &PCS::Commitment,
+    lookup_evals_zeta: &[&PCS::Field],
     w_polys_eval_zeta: &[&PCS::Field],
-    #[cfg(feature = "shuffle")] w_sel_polys_eval_zeta: &[&PCS::Field],
+    w_sel_polys_eval_zeta: &[&PCS::Field],
     s_polys_eval_zeta: &[&PCS::Field],
     q_prk3_eval_zeta: &PCS::Field,
-    #[cfg(feature = "shuffle")] q_ecc_poly_eval_zeta: &PCS::Field,
-    #[cfg(feature = "shuffle")] w_polys_eval_zeta_omega: &[&PCS::Field],
+    q_ecc_poly_eval_zeta: &PCS::Field,
+    w_polys_eval_zeta_omega: &[&PCS::Field],
     z_eval_zeta_omega: &PCS::Field,
     challenges: &PlonkChallenges<PCS::Field>,
     t_polys: &[PCS::Commitment],
@@ -1095,28 +1656,29 @@ pub(super) fn r_commitment<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>
     z_h_eval_zeta: &PCS::Field,
     n_t_polys: usize,
 ) -> PCS::Commitment {
-    let w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
+    let mut w = CS::eval_selector_multipliers(w_polys_eval_zeta).unwrap(); // safe unwrap
+    w.extend(eval_custom_selector_multipliers(
+        cs.custom_gates(),
+        w_polys_eval_zeta,
+    ));
     r_poly_or_comm::<PCS::Field, PCS::Commitment>(
+        verifier_params.layout(),
         &w,
         &verifier_params.cm_q_vec,
         &verifier_params.cm_qb,
         &verifier_params.cm_prk_vec[0],
         &verifier_params.cm_prk_vec[1],
-        #[cfg(feature = "shuffle")]
         &verifier_params.cm_shuffle_generator_vec,
-        #[cfg(feature = "shuffle")]
         &verifier_params.cm_shuffle_public_key_vec,
-        #[cfg(feature = "shuffle")]
         q_ecc_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         w_sel_polys_eval_zeta,
         &verifier_params.k,
-        #[cfg(feature = "shuffle")]
         &verifier_params.edwards_a,
+        cm_lookup_z,
+        lookup_evals_zeta,
         &verifier_params.cm_s_vec[CS::n_wires_per_gate() - 1],
         cm_z,
         w_polys_eval_zeta,
-        #[cfg(feature = "shuffle")]
         w_polys_eval_zeta_omega,
         s_polys_eval_zeta,
         q_prk3_eval_zeta,
@@ -1246,8 +1808,7 @@ pub(super) fn r_eval_zeta<PCS: PolyComScheme>(
             - &proof.w_polys_eval_zeta_omega[1],
     );
 
-    #[cfg(feature = "shuffle")]
-    let (term7, term8, term9, term10) = {
+    let (term7, term8, term9, term10) = if proof.layout.has_shuffle() {
         let one = PCS::Field::ONE;
         let alpha_pow_10 = alpha_pow_9.mul(alpha);
         let alpha_pow_11 = alpha_pow_10.mul(alpha);
@@ -1303,6 +1864,8 @@ pub(super) fn r_eval_zeta<PCS: PolyComScheme>(
             .mul(one.add(&proof.w_sel_polys_eval_zeta[2]));
 
         (term7, term8, term9, term10)
+    } else {
+        Default::default()
     };
 
     let term1_plus_term2 = term1.add(&term2);
@@ -1313,10 +1876,43 @@ pub(super) fn r_eval_zeta<PCS: PolyComScheme>(
         .add(term5)
         .add(term6);
 
-    #[cfg(feature = "shuffle")]
-    let res = { res.sub(term7).sub(term8).sub(term9).sub(term10) };
+    let res = res.sub(term7).sub(term8).sub(term9).sub(term10);
 
-    res
+    if !proof.layout.has_lookup() {
+        return res;
+    }
+
+    {
+        let one = PCS::Field::ONE;
+        let eta = challenges.get_eta().unwrap();
+        let (delta, epsilon) = challenges.get_delta_epsilon().unwrap();
+        let alpha_pow_17 = alpha_pow_9.mul(alpha_pow_8);
+        let alpha_pow_18 = alpha_pow_17.mul(alpha);
+        let alpha_pow_19 = alpha_pow_18.mul(alpha);
+        let epsilon_one_plus_delta = epsilon.mul(one.add(delta));
+        let h1_eval_zeta = proof.lookup_h_polys_eval_zeta[0];
+        let h2_eval_zeta = proof.lookup_h_polys_eval_zeta[1];
+
+        let term11 = alpha_pow_17
+            .mul(&proof.lookup_z_eval_zeta_omega)
+            .mul(epsilon_one_plus_delta + h1_eval_zeta + delta.mul(&h2_eval_zeta))
+            .mul(
+                epsilon_one_plus_delta + h2_eval_zeta + delta.mul(&proof.lookup_h1_eval_zeta_omega),
+            );
+        let term12 = first_lagrange_eval_zeta.mul(alpha_pow_18);
+        let term13 = alpha_pow_19.mul(&proof.q_lookup_polys_eval_zeta[0]).mul(
+            compress_lookup_row(
+                eta,
+                &proof.w_polys_eval_zeta[0],
+                &proof.w_polys_eval_zeta[1],
+                &proof.w_polys_eval_zeta[2],
+                &proof.q_lookup_polys_eval_zeta[1],
+            )
+            .sub(&proof.lookup_f_eval_zeta),
+        );
+
+        res.add(term11).add(term12).sub(term13)
+    }
 }
 
 /// Split the t polynomial into `n_wires_per_gate` degree-`n` polynomials and commit.
@@ -1458,6 +2054,8 @@ mod test {
         cs.insert_add_gate(1 + 2, 4 + 2, 2 + 2);
         cs.insert_add_gate(2 + 2, 4 + 2, 6 + 2);
         cs.insert_add_gate(3 + 2, 5 + 2, 7 + 2);
+        cs.prepare_pi_variable(6 + 2);
+        cs.prepare_pi_variable(7 + 2);
         cs.pad();
 
         let mut prng = ChaChaRng::from_entropy();
@@ -9,6 +9,7 @@ use ark_std::{
 };
 use rand_chacha::ChaChaRng;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 
 use crate::{
     errors::UzkgeError,
@@ -26,20 +27,30 @@ use crate::{
     },
 };
 
-use super::{constraint_system::ConstraintSystem, helpers::compute_lagrange_constant};
+use super::{
+    constraint_system::{ConstraintSystem, PlonkLayout},
+    helpers::compute_lagrange_constant,
+};
 
 /// The data structure of a Plonk proof.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PlonkProof<PCS: PolyComScheme> {
+    /// The layout of the proof, i.e., the gate set of the circuit.
+    pub layout: PlonkLayout,
     /// The witness polynomial commitments.
     pub cm_w_vec: Vec<PCS::Commitment>,
-    /// The witness selector polynomial commitments.
-    #[cfg(feature = "shuffle")]
+    /// The witness selector polynomial commitments, empty in the standard layout.
     pub cm_w_sel_vec: Vec<PCS::Commitment>,
     /// The split quotient polynomial commitments
     pub cm_t_vec: Vec<PCS::Commitment>,
     /// The sigma polynomial commitment.
     pub cm_z: PCS::Commitment,
+    /// The commitment of the compressed lookup queries polynomial, the default without lookups.
+    pub cm_lookup_f: PCS::Commitment,
+    /// The commitments of the two halves of the sorted lookup polynomial, empty without lookups.
+    pub cm_lookup_h_vec: Vec<PCS::Commitment>,
+    /// The lookup grand product polynomial commitment, the default without lookups.
+    pub cm_lookup_z: PCS::Commitment,
     /// The opening of the third preprocessed round key polynomial at \zeta.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
     pub prk_3_poly_eval_zeta: PCS::Field,
@@ -58,14 +69,33 @@ pub struct PlonkProof<PCS: PolyComScheme> {
     /// The openings of permutation polynomials at \zeta.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
     pub s_polys_eval_zeta: Vec<PCS::Field>,
-    /// The opening of q_{ecc}(X) at point \zeta .
+    /// The opening of q_{ecc}(X) at point \zeta, zero in the standard layout.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub q_ecc_poly_eval_zeta: PCS::Field,
-    /// The opening of the witness selector polynomial at point \zeta .
+    /// The opening of the witness selector polynomial at point \zeta, empty in the standard layout.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub w_sel_polys_eval_zeta: Vec<PCS::Field>,
+    /// The opening of the compressed lookup queries polynomial at \zeta, zero without lookups.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_f_eval_zeta: PCS::Field,
+    /// The opening of the compressed lookup table polynomial at \zeta.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_table_eval_zeta: PCS::Field,
+    /// The opening of the compressed lookup table polynomial at \zeta * \omega.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_table_eval_zeta_omega: PCS::Field,
+    /// The openings of the sorted lookup polynomials at \zeta, empty without lookups.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_h_polys_eval_zeta: Vec<PCS::Field>,
+    /// The opening of the first sorted lookup polynomial at \zeta * \omega.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_h1_eval_zeta_omega: PCS::Field,
+    /// The opening of the lookup grand product polynomial at \zeta * \omega.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_z_eval_zeta_omega: PCS::Field,
+    /// The openings of the lookup selector polynomials at \zeta, empty without lookups.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub q_lookup_polys_eval_zeta: Vec<PCS::Field>,
     /// The commitment for the first witness polynomial, for \zeta.
     pub opening_witness_zeta: PCS::Commitment,
     /// The commitment for the second witness polynomial, for \zeta\omega.
@@ -83,15 +113,22 @@ pub struct PlonkProverParams<PCS: PolyComScheme> {
     pub qb_poly: FpPolynomial<PCS::Field>,
     /// The four polynomials for the Anemoi/Jive constraints.
     pub q_prk_polys: Vec<FpPolynomial<PCS::Field>>,
-    /// The polynomial for ecc constraints.
-    #[cfg(feature = "shuffle")]
+    /// The polynomial for ecc constraints, zero in the standard layout.
     pub q_ecc_poly: FpPolynomial<PCS::Field>,
-    /// The generator polynomials for the shuffle constraints.
-    #[cfg(feature = "shuffle")]
+    /// The generator polynomials for the shuffle constraints, empty in the standard layout.
     pub q_shuffle_generator_polys: Vec<FpPolynomial<PCS::Field>>,
-    /// The public key polynomials for the shuffle constraints.
-    #[cfg(feature = "shuffle")]
+    /// The public key polynomials for the shuffle constraints, empty in the standard layout.
     pub q_shuffle_public_key_polys: Vec<FpPolynomial<PCS::Field>>,
+    /// The commitments of the rows of each iteration of the shuffle remark, i.e., the sums of
+    /// their Lagrange bases, so the public key selectors are committed by the MSMs of their
+    /// values in the iterations. Empty in the standard layout.
+    pub shuffle_public_key_bases: Vec<PCS::Commitment>,
+    /// The two lookup selector polynomials, i.e., the lookup flag and the table id,
+    /// empty without lookups.
+    pub q_lookup_polys: Vec<FpPolynomial<PCS::Field>>,
+    /// The polynomials of the three columns and the table id column of the lookup table,
+    /// empty without lookups.
+    pub lookup_table_polys: Vec<FpPolynomial<PCS::Field>>,
     /// The permutation for copy constraints.
     pub permutation: Vec<usize>,
     /// The Plonk verifier parameters.
@@ -126,16 +163,19 @@ pub struct PlonkProverParams<PCS: PolyComScheme> {
     pub q_prk_coset_evals: Vec<Vec<PCS::Field>>,
     /// The ecc constraint polynomial's FFT of the polynomial of unity root set.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub q_ecc_coset_eval: Vec<PCS::Field>,
     /// The shuffle generator polynomials' FFT of the polynomial of unity root set.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub q_shuffle_generator_coset_evals: Vec<Vec<PCS::Field>>,
     /// The shuffle public key polynomials' FFT of the polynomial of unity root set.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub q_shuffle_public_key_coset_evals: Vec<Vec<PCS::Field>>,
+    /// The lookup selector polynomials' FFT of the polynomial of unity root set.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub q_lookup_coset_evals: Vec<Vec<PCS::Field>>,
+    /// The lookup table polynomials' FFT of the polynomial of unity root set.
+    #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
+    pub lookup_table_coset_evals: Vec<Vec<PCS::Field>>,
 }
 
 impl<PCS: PolyComScheme> PlonkProverParams<PCS> {
@@ -148,6 +188,64 @@ impl<PCS: PolyComScheme> PlonkProverParams<PCS> {
     pub fn get_verifier_params_ref(&self) -> &PlonkVerifierParams<PCS> {
         &self.verifier_params
     }
+
+    /// Drop the evaluations on the coset of the quotient domain, which take about
+    /// `quot_eval_dom_size / size` times the memory of the polynomials.
+    /// The provers then evaluate the polynomials coset by coset.
+    pub fn drop_coset_evals(&mut self) {
+        self.coset_quotient = vec![];
+        self.l1_coset_evals = vec![];
+        self.z_h_inv_coset_evals = vec![];
+        self.q_coset_evals = vec![];
+        self.s_coset_evals = vec![];
+        self.qb_coset_eval = vec![];
+        self.q_prk_coset_evals = vec![];
+        self.q_ecc_coset_eval = vec![];
+        self.q_shuffle_generator_coset_evals = vec![];
+        self.q_shuffle_public_key_coset_evals = vec![];
+        self.q_lookup_coset_evals = vec![];
+        self.lookup_table_coset_evals = vec![];
+    }
+
+    /// Return whether the evaluations on the coset of the quotient domain are kept.
+    pub fn has_coset_evals(&self) -> bool {
+        !self.q_coset_evals.is_empty()
+    }
+
+    /// Return the number of field elements held by the polynomials and the evaluations.
+    pub fn num_field_elems(&self) -> usize {
+        let polys_len = |polys: &[FpPolynomial<PCS::Field>]| -> usize {
+            polys.iter().map(|poly| poly.coefs.len()).sum()
+        };
+        let evals_len =
+            |evals: &[Vec<PCS::Field>]| -> usize { evals.iter().map(|e| e.len()).sum() };
+
+        let num = polys_len(&self.q_polys)
+            + polys_len(&self.s_polys)
+            + self.qb_poly.coefs.len()
+            + polys_len(&self.q_prk_polys)
+            + self.q_ecc_poly.coefs.len()
+            + polys_len(&self.q_shuffle_generator_polys)
+            + polys_len(&self.q_shuffle_public_key_polys)
+            + self.group.len()
+            + self.coset_quotient.len()
+            + self.l1_coefs.coefs.len()
+            + self.l1_coset_evals.len()
+            + self.z_h_coefs.coefs.len()
+            + self.z_h_inv_coset_evals.len()
+            + evals_len(&self.q_coset_evals)
+            + evals_len(&self.s_coset_evals)
+            + self.qb_coset_eval.len()
+            + evals_len(&self.q_prk_coset_evals)
+            + self.q_ecc_coset_eval.len()
+            + evals_len(&self.q_shuffle_generator_coset_evals)
+            + evals_len(&self.q_shuffle_public_key_coset_evals)
+            + polys_len(&self.q_lookup_polys)
+            + polys_len(&self.lookup_table_polys)
+            + evals_len(&self.q_lookup_coset_evals)
+            + evals_len(&self.lookup_table_coset_evals);
+        num
+    }
 }
 
 /// Plonk verifier parameters.
@@ -161,15 +259,16 @@ pub struct PlonkVerifierParams<PCS: PolyComScheme> {
     pub cm_qb: PCS::Commitment,
     /// The commitments of the preprocessed round key selectors.
     pub cm_prk_vec: Vec<PCS::Commitment>,
-    /// The commitment of the ecc selector.
-    #[cfg(feature = "shuffle")]
+    /// The commitment of the ecc selector, the default in the standard layout.
     pub cm_q_ecc: PCS::Commitment,
-    /// The commitments of the shuffle generator selectors.
-    #[cfg(feature = "shuffle")]
+    /// The commitments of the shuffle generator selectors, empty in the standard layout.
     pub cm_shuffle_generator_vec: Vec<PCS::Commitment>,
-    /// The commitments of the shuffle public key selectors.
-    #[cfg(feature = "shuffle")]
+    /// The commitments of the shuffle public key selectors, empty in the standard layout.
     pub cm_shuffle_public_key_vec: Vec<PCS::Commitment>,
+    /// The commitments of the lookup selectors, empty without lookups.
+    pub cm_q_lookup_vec: Vec<PCS::Commitment>,
+    /// The commitments of the lookup table columns, empty without lookups.
+    pub cm_lookup_table_vec: Vec<PCS::Commitment>,
     /// the Anemoi generator.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
     pub anemoi_generator: PCS::Field,
@@ -181,7 +280,6 @@ pub struct PlonkVerifierParams<PCS: PolyComScheme> {
     pub k: Vec<PCS::Field>,
     /// the paramater a of twisted edwards curve.
     #[serde(serialize_with = "ark_serialize", deserialize_with = "ark_deserialize")]
-    #[cfg(feature = "shuffle")]
     pub edwards_a: PCS::Field,
     /// The size of constraint system.
     pub cs_size: usize,
@@ -192,6 +290,17 @@ pub struct PlonkVerifierParams<PCS: PolyComScheme> {
     pub lagrange_constants: Vec<PCS::Field>,
 }
 
+impl<PCS: PolyComScheme> PlonkVerifierParams<PCS> {
+    /// Return the gate set of the circuit, which has shuffle selectors and lookup selectors
+    /// only when the shuffle gates and the lookup gates are enabled.
+    pub fn layout(&self) -> PlonkLayout {
+        PlonkLayout::new(
+            !self.cm_shuffle_generator_vec.is_empty(),
+            !self.cm_q_lookup_vec.is_empty(),
+        )
+    }
+}
+
 /// Encode the permutation value, from an index to a group element.
 pub fn encode_perm_to_group<F: PrimeField>(group: &[F], perm: &[usize], k: &[F]) -> Vec<F> {
     let n = group.len();
@@ -253,6 +362,7 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
     verifier_params: Option<PlonkVerifierParams<PCS>>,
 ) -> Result<PlonkProverParams<PCS>, UzkgeError> {
     let no_verifier = verifier_params.is_none();
+    let layout = cs.layout();
 
     // It's okay to choose a fixed seed to generate quadratic non-residue.
     let mut prng = ChaChaRng::from_seed([0u8; 32]);
@@ -328,16 +438,23 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
         s_polys.push(s_coefs);
     }
 
-    // Step 2: compute selector polynomials and commit them.
-    let mut q_coset_evals = vec![vec![]; CS::num_selectors()];
+    // Step 2: compute selector polynomials and commit them,
+    // the selectors of the custom gates follow the built-in ones.
+    let custom_selectors = cs.compute_custom_selectors();
+    let mut q_coset_evals = vec![vec![]; CS::num_selectors() + custom_selectors.len()];
     let mut q_polys = vec![];
     let mut cm_q_vec = vec![];
     for (i, q_coset_eval) in q_coset_evals.iter_mut().enumerate() {
-        let q_coefs = FpPolynomial::ifft_with_domain(&domain, cs.selector(i)?);
+        let selector = if i < CS::num_selectors() {
+            cs.selector(i)?
+        } else {
+            &custom_selectors[i - CS::num_selectors()]
+        };
+        let q_coefs = FpPolynomial::ifft_with_domain(&domain, selector);
         q_coset_eval.extend(q_coefs.coset_fft_with_domain(&domain_m, &k[1]));
 
         if no_verifier {
-            let cm_q = commit(cs.selector(i)?.to_vec(), &q_coefs)?;
+            let cm_q = commit(selector.to_vec(), &q_coefs)?;
             cm_q_vec.push(cm_q);
         }
         q_polys.push(q_coefs);
@@ -415,8 +532,7 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
     };
 
     // Step 7: commit `shuffle_remark_constraint_indices`
-    #[cfg(feature = "shuffle")]
-    let (q_ecc_coset_eval, q_ecc_poly, cm_q_ecc) = {
+    let (q_ecc_coset_eval, q_ecc_poly, cm_q_ecc) = if layout.has_shuffle() {
         let mut q_ecc = vec![PCS::Field::zero(); n];
         for i in cs.shuffle_remark_constraint_indices().iter() {
             for j in 0..cs.n_iteration_shuffle_scalar_mul() {
@@ -433,29 +549,120 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
         };
 
         (q_ecc_coset_eval, q_ecc_coef, cm_q_ecc)
+    } else {
+        Default::default()
     };
 
     // Step 8: compute polynomials related to shuffle and commit them.
-    #[cfg(feature = "shuffle")]
-    let (q_shuffle_generator_coset_evals, q_shuffle_generator_polys, cm_shuffle_generator_vec) = {
-        let q_shuffle_generator_evals = cs.compute_shuffle_generator_selectors();
+    let (q_shuffle_generator_coset_evals, q_shuffle_generator_polys, cm_shuffle_generator_vec) =
+        if layout.has_shuffle() {
+            let q_shuffle_generator_evals = cs.compute_shuffle_generator_selectors();
+
+            let q_shuffle_generator_polys: Vec<FpPolynomial<PCS::Field>> =
+                q_shuffle_generator_evals
+                    .iter()
+                    .map(|p| FpPolynomial::ifft_with_domain(&domain, &p))
+                    .collect::<Vec<FpPolynomial<PCS::Field>>>();
+
+            let q_shuffle_generator_coset_evals = q_shuffle_generator_polys
+                .iter()
+                .map(|p| p.coset_fft_with_domain(&domain_m, &k[1]))
+                .collect::<Vec<Vec<PCS::Field>>>();
+
+            let cm_shuffle_generator_vec: Vec<PCS::Commitment> = if no_verifier {
+                q_shuffle_generator_evals
+                    .into_iter()
+                    .zip(q_shuffle_generator_polys.iter())
+                    .map(|(q_shuffle_generator_eval, q_shuffle_generator_poly)| {
+                        commit(q_shuffle_generator_eval, q_shuffle_generator_poly)
+                    })
+                    .collect::<Result<_, UzkgeError>>()?
+            } else {
+                vec![]
+            };
+
+            (
+                q_shuffle_generator_coset_evals,
+                q_shuffle_generator_polys,
+                cm_shuffle_generator_vec,
+            )
+        } else {
+            Default::default()
+        };
 
-        let q_shuffle_generator_polys: Vec<FpPolynomial<PCS::Field>> = q_shuffle_generator_evals
+    //  Step 9: fake public key paramaters with generator paramaters.
+    let q_shuffle_public_key_polys = q_shuffle_generator_polys.clone();
+    let q_shuffle_public_key_coset_evals = q_shuffle_generator_coset_evals.clone();
+    let cm_shuffle_public_key_vec = cm_shuffle_generator_vec.clone();
+    let shuffle_public_key_bases = if layout.has_shuffle() {
+        let remark_indices = cs.shuffle_remark_constraint_indices();
+        (0..cs.n_iteration_shuffle_scalar_mul())
+            .map(|j| {
+                let mut evals = vec![PCS::Field::zero(); n];
+                for i in remark_indices.iter() {
+                    evals[i + j] = PCS::Field::one();
+                }
+                let poly = if lagrange_pcs.is_some() {
+                    FpPolynomial::default()
+                } else {
+                    FpPolynomial::ifft_with_domain(&domain, &evals)
+                };
+                commit(evals, &poly)
+            })
+            .collect::<Result<_, UzkgeError>>()?
+    } else {
+        vec![]
+    };
+
+    // Step 10: compute the lookup selector and table polynomials and commit them.
+    let (q_lookup_coset_evals, q_lookup_polys, cm_q_lookup_vec) = if layout.has_lookup() {
+        let q_lookup_evals = cs.compute_lookup_selectors().to_vec();
+
+        let q_lookup_polys: Vec<FpPolynomial<PCS::Field>> = q_lookup_evals
             .iter()
-            .map(|p| FpPolynomial::ifft_with_domain(&domain, &p))
+            .map(|p| FpPolynomial::ifft_with_domain(&domain, p))
+            .collect::<Vec<FpPolynomial<PCS::Field>>>();
+
+        let q_lookup_coset_evals = q_lookup_polys
+            .iter()
+            .map(|p| p.coset_fft_with_domain(&domain_m, &k[1]))
+            .collect::<Vec<Vec<PCS::Field>>>();
+
+        let cm_q_lookup_vec: Vec<PCS::Commitment> = if no_verifier {
+            q_lookup_evals
+                .into_iter()
+                .zip(q_lookup_polys.iter())
+                .map(|(q_lookup_eval, q_lookup_poly)| commit(q_lookup_eval, q_lookup_poly))
+                .collect::<Result<_, UzkgeError>>()?
+        } else {
+            vec![]
+        };
+
+        (q_lookup_coset_evals, q_lookup_polys, cm_q_lookup_vec)
+    } else {
+        Default::default()
+    };
+
+    let (lookup_table_coset_evals, lookup_table_polys, cm_lookup_table_vec) = if layout.has_lookup()
+    {
+        let lookup_table_evals = cs.compute_lookup_table().to_vec();
+
+        let lookup_table_polys: Vec<FpPolynomial<PCS::Field>> = lookup_table_evals
+            .iter()
+            .map(|p| FpPolynomial::ifft_with_domain(&domain, p))
             .collect::<Vec<FpPolynomial<PCS::Field>>>();
 
-        let q_shuffle_generator_coset_evals = q_shuffle_generator_polys
+        let lookup_table_coset_evals = lookup_table_polys
             .iter()
             .map(|p| p.coset_fft_with_domain(&domain_m, &k[1]))
             .collect::<Vec<Vec<PCS::Field>>>();
 
-        let cm_shuffle_generator_vec: Vec<PCS::Commitment> = if no_verifier {
-            q_shuffle_generator_evals
+        let cm_lookup_table_vec: Vec<PCS::Commitment> = if no_verifier {
+            lookup_table_evals
                 .into_iter()
-                .zip(q_shuffle_generator_polys.iter())
-                .map(|(q_shuffle_generator_eval, q_shuffle_generator_poly)| {
-                    commit(q_shuffle_generator_eval, q_shuffle_generator_poly)
+                .zip(lookup_table_polys.iter())
+                .map(|(lookup_table_eval, lookup_table_poly)| {
+                    commit(lookup_table_eval, lookup_table_poly)
                 })
                 .collect::<Result<_, UzkgeError>>()?
         } else {
@@ -463,21 +670,18 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
         };
 
         (
-            q_shuffle_generator_coset_evals,
-            q_shuffle_generator_polys,
-            cm_shuffle_generator_vec,
+            lookup_table_coset_evals,
+            lookup_table_polys,
+            cm_lookup_table_vec,
         )
+    } else {
+        Default::default()
     };
 
-    //  Step 9: fake public key paramaters with generator paramaters.
-    #[cfg(feature = "shuffle")]
-    let q_shuffle_public_key_polys = q_shuffle_generator_polys.clone();
-    #[cfg(feature = "shuffle")]
-    let q_shuffle_public_key_coset_evals = q_shuffle_generator_coset_evals.clone();
-    #[cfg(feature = "shuffle")]
-    let cm_shuffle_public_key_vec = cm_shuffle_generator_vec.clone();
-
     let verifier_params = if let Some(verifier) = verifier_params {
+        if verifier.layout() != layout {
+            return Err(UzkgeError::VerifierParamsError);
+        }
         verifier
     } else {
         let (anemoi_generator, anemoi_generator_inv) = cs.get_anemoi_parameters();
@@ -486,16 +690,14 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
             cm_s_vec,
             cm_qb,
             cm_prk_vec,
-            #[cfg(feature = "shuffle")]
             cm_q_ecc,
-            #[cfg(feature = "shuffle")]
             cm_shuffle_generator_vec,
-            #[cfg(feature = "shuffle")]
             cm_shuffle_public_key_vec,
+            cm_q_lookup_vec,
+            cm_lookup_table_vec,
             anemoi_generator,
             anemoi_generator_inv,
             k,
-            #[cfg(feature = "shuffle")]
             edwards_a: cs.get_edwards_a(),
             cs_size: n,
             public_vars_constraint_indices: cs.public_vars_constraint_indices().to_vec(),
@@ -508,12 +710,12 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
         s_polys,
         qb_poly,
         q_prk_polys,
-        #[cfg(feature = "shuffle")]
         q_ecc_poly,
-        #[cfg(feature = "shuffle")]
         q_shuffle_generator_polys,
-        #[cfg(feature = "shuffle")]
         q_shuffle_public_key_polys,
+        shuffle_public_key_bases,
+        q_lookup_polys,
+        lookup_table_polys,
         permutation: raw_perm,
         verifier_params,
         group,
@@ -526,24 +728,23 @@ pub fn indexer_with_lagrange<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field
         s_coset_evals,
         qb_coset_eval,
         q_prk_coset_evals,
-        #[cfg(feature = "shuffle")]
         q_ecc_coset_eval,
-        #[cfg(feature = "shuffle")]
         q_shuffle_generator_coset_evals,
-        #[cfg(feature = "shuffle")]
         q_shuffle_public_key_coset_evals,
+        q_lookup_coset_evals,
+        lookup_table_coset_evals,
     })
 }
 
 impl PlonkProof<KZGCommitmentSchemeBN254> {
+    /// The bytes of the proof, starting with the tag of its layout.
     pub fn to_bytes_be(&self) -> Vec<u8> {
-        let mut bytes = vec![];
+        let mut bytes = vec![self.layout.to_tag()];
 
         for p in &self.cm_w_vec {
             bytes.append(&mut point_to_uncompress_be(&p.0));
         }
 
-        #[cfg(feature = "shuffle")]
         for p in &self.cm_w_sel_vec {
             bytes.append(&mut point_to_uncompress_be(&p.0));
         }
@@ -554,6 +755,14 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
 
         bytes.append(&mut point_to_uncompress_be(&self.cm_z.0));
 
+        if self.layout.has_lookup() {
+            bytes.append(&mut point_to_uncompress_be(&self.cm_lookup_f.0));
+            for p in &self.cm_lookup_h_vec {
+                bytes.append(&mut point_to_uncompress_be(&p.0));
+            }
+            bytes.append(&mut point_to_uncompress_be(&self.cm_lookup_z.0));
+        }
+
         bytes.append(&mut scalar_to_bytes_be(&self.prk_3_poly_eval_zeta));
 
         bytes.append(&mut scalar_to_bytes_be(&self.prk_4_poly_eval_zeta));
@@ -572,14 +781,28 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
             bytes.append(&mut scalar_to_bytes_be(s));
         }
 
-        #[cfg(feature = "shuffle")]
-        bytes.append(&mut scalar_to_bytes_be(&self.q_ecc_poly_eval_zeta));
+        if self.layout.has_shuffle() {
+            bytes.append(&mut scalar_to_bytes_be(&self.q_ecc_poly_eval_zeta));
+        }
 
-        #[cfg(feature = "shuffle")]
         for s in &self.w_sel_polys_eval_zeta {
             bytes.append(&mut scalar_to_bytes_be(s));
         }
 
+        if self.layout.has_lookup() {
+            bytes.append(&mut scalar_to_bytes_be(&self.lookup_f_eval_zeta));
+            bytes.append(&mut scalar_to_bytes_be(&self.lookup_table_eval_zeta));
+            bytes.append(&mut scalar_to_bytes_be(&self.lookup_table_eval_zeta_omega));
+            for s in &self.lookup_h_polys_eval_zeta {
+                bytes.append(&mut scalar_to_bytes_be(s));
+            }
+            bytes.append(&mut scalar_to_bytes_be(&self.lookup_h1_eval_zeta_omega));
+            bytes.append(&mut scalar_to_bytes_be(&self.lookup_z_eval_zeta_omega));
+            for s in &self.q_lookup_polys_eval_zeta {
+                bytes.append(&mut scalar_to_bytes_be(s));
+            }
+        }
+
         bytes.append(&mut point_to_uncompress_be(&self.opening_witness_zeta.0));
 
         bytes.append(&mut point_to_uncompress_be(
@@ -589,36 +812,49 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
         bytes
     }
 
+    /// Decode the proof of either layout from the bytes of `to_bytes_be`.
     pub fn from_bytes_be<CS: ConstraintSystem<Fr>>(bytes: &[u8]) -> Result<Self, UzkgeError> {
         let n = G1Projective::generator().uncompressed_size();
         let m = Fr::one().uncompressed_size();
         let n_wire = CS::n_wires_per_gate();
 
-        #[cfg(feature = "shuffle")]
-        let n_selector = CS::num_wire_selectors();
+        let layout =
+            PlonkLayout::from_tag(*bytes.first().ok_or(UzkgeError::DeserializationError)?)?;
+        let n_selector = if layout.has_shuffle() {
+            CS::num_wire_selectors()
+        } else {
+            0
+        };
 
-        let mut bytes_len = 0;
+        let mut bytes_len = 1; // layout,
 
         bytes_len += n * n_wire; // cm_w_vec,
-        #[cfg(feature = "shuffle")]
-        {
-            bytes_len += n * n_selector; // cm_w_sel_vec,
-        }
+        bytes_len += n * n_selector; // cm_w_sel_vec,
         bytes_len += n * n_wire; // cm_t_vec,
         bytes_len += n; // cm_z,
+        if layout.has_lookup() {
+            bytes_len += n; // cm_lookup_f,
+            bytes_len += n * 2; // cm_lookup_h_vec,
+            bytes_len += n; // cm_lookup_z,
+        }
         bytes_len += m; // prk_3_poly_eval_zeta,
         bytes_len += m; // prk_4_poly_eval_zeta,
         bytes_len += m * n_wire; // w_polys_eval_zeta,
         bytes_len += m * 3; // w_polys_eval_zeta_omega,
         bytes_len += m; // z_eval_zeta_omega,
         bytes_len += m * (n_wire - 1); // s_polys_eval_zeta,
-        #[cfg(feature = "shuffle")]
-        {
+        if layout.has_shuffle() {
             bytes_len += m; // q_ecc_poly_eval_zeta,
         }
-        #[cfg(feature = "shuffle")]
-        {
-            bytes_len += m * n_selector; // w_sel_polys_eval_zeta,
+        bytes_len += m * n_selector; // w_sel_polys_eval_zeta,
+        if layout.has_lookup() {
+            bytes_len += m; // lookup_f_eval_zeta,
+            bytes_len += m; // lookup_table_eval_zeta,
+            bytes_len += m; // lookup_table_eval_zeta_omega,
+            bytes_len += m * 2; // lookup_h_polys_eval_zeta,
+            bytes_len += m; // lookup_h1_eval_zeta_omega,
+            bytes_len += m; // lookup_z_eval_zeta_omega,
+            bytes_len += m * 2; // q_lookup_polys_eval_zeta,
         }
         bytes_len += n; // opening_witness_zeta,
         bytes_len += n; // opening_witness_zeta_omega,
@@ -627,7 +863,7 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
             return Err(UzkgeError::DeserializationError);
         }
 
-        let mut p = 0;
+        let mut p = 1;
 
         let mut cm_w_vec = vec![];
         for _ in 0..n_wire {
@@ -638,9 +874,7 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
             p += n;
         }
 
-        #[cfg(feature = "shuffle")]
         let mut cm_w_sel_vec = vec![];
-        #[cfg(feature = "shuffle")]
         for _ in 0..n_selector {
             cm_w_sel_vec.push(KZGCommitment(point_from_uncompress_be(
                 &bytes[p..p + n],
@@ -661,6 +895,24 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
         let cm_z = KZGCommitment(point_from_uncompress_be(&bytes[p..p + n], false)?);
         p += n;
 
+        let (cm_lookup_f, cm_lookup_h_vec, cm_lookup_z) = if layout.has_lookup() {
+            let mut cm_lookup = vec![];
+            for _ in 0..4 {
+                cm_lookup.push(KZGCommitment(point_from_uncompress_be(
+                    &bytes[p..p + n],
+                    false,
+                )?));
+                p += n;
+            }
+            (
+                cm_lookup[0].clone(),
+                cm_lookup[1..3].to_vec(),
+                cm_lookup[3].clone(),
+            )
+        } else {
+            Default::default()
+        };
+
         let prk_3_poly_eval_zeta = scalar_from_bytes_be(&bytes[p..p + m], false)?;
         p += m;
 
@@ -688,21 +940,27 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
             p += m;
         }
 
-        #[cfg(feature = "shuffle")]
-        let q_ecc_poly_eval_zeta = scalar_from_bytes_be(&bytes[p..p + m], false)?;
-        #[cfg(feature = "shuffle")]
-        {
+        let q_ecc_poly_eval_zeta = if layout.has_shuffle() {
             p += m;
-        }
+            scalar_from_bytes_be(&bytes[p - m..p], false)?
+        } else {
+            Fr::zero()
+        };
 
-        #[cfg(feature = "shuffle")]
         let mut w_sel_polys_eval_zeta = vec![];
-        #[cfg(feature = "shuffle")]
         for _ in 0..n_selector {
             w_sel_polys_eval_zeta.push(scalar_from_bytes_be(&bytes[p..p + m], false)?);
             p += m;
         }
 
+        let mut lookup_scalars = vec![Fr::zero(); 9];
+        if layout.has_lookup() {
+            for s in lookup_scalars.iter_mut() {
+                *s = scalar_from_bytes_be(&bytes[p..p + m], false)?;
+                p += m;
+            }
+        }
+
         let opening_witness_zeta =
             KZGCommitment(point_from_uncompress_be(&bytes[p..p + n], false)?);
         p += n;
@@ -711,25 +969,62 @@ impl PlonkProof<KZGCommitmentSchemeBN254> {
             KZGCommitment(point_from_uncompress_be(&bytes[p..p + n], false)?);
 
         Ok(PlonkProof {
+            layout,
             cm_w_vec,
-            #[cfg(feature = "shuffle")]
             cm_w_sel_vec,
             cm_t_vec,
             cm_z,
+            cm_lookup_f,
+            cm_lookup_h_vec,
+            cm_lookup_z,
             prk_3_poly_eval_zeta,
             prk_4_poly_eval_zeta,
             w_polys_eval_zeta,
             w_polys_eval_zeta_omega,
             z_eval_zeta_omega,
             s_polys_eval_zeta,
-            #[cfg(feature = "shuffle")]
             q_ecc_poly_eval_zeta,
-            #[cfg(feature = "shuffle")]
             w_sel_polys_eval_zeta,
+            lookup_f_eval_zeta: lookup_scalars[0],
+            lookup_table_eval_zeta: lookup_scalars[1],
+            lookup_table_eval_zeta_omega: lookup_scalars[2],
+            lookup_h_polys_eval_zeta: lookup_scalars[3..5].to_vec(),
+            lookup_h1_eval_zeta_omega: lookup_scalars[5],
+            lookup_z_eval_zeta_omega: lookup_scalars[6],
+            q_lookup_polys_eval_zeta: lookup_scalars[7..9].to_vec(),
             opening_witness_zeta,
             opening_witness_zeta_omega,
         })
     }
+
+    /// ABI-encode the call `verify(bytes proof, uint256[] publicInputs)` of the Solidity
+    /// verifier generated by `gen_params::solidity::gen_solidity_verifier`.
+    pub fn to_solidity_calldata(&self, public_inputs: &[Fr]) -> Vec<u8> {
+        let proof = self.to_bytes_be();
+        let padded_len = proof.len().div_ceil(32) * 32;
+
+        let mut bytes = Keccak256::digest(b"verify(bytes,uint256[])")[..4].to_vec();
+        // the offsets of the two dynamic arguments, after the head of two words.
+        bytes.extend(abi_word(64));
+        bytes.extend(abi_word(64 + 32 + padded_len as u64));
+
+        bytes.extend(abi_word(proof.len() as u64));
+        bytes.extend_from_slice(&proof);
+        bytes.resize(bytes.len() + padded_len - proof.len(), 0);
+
+        bytes.extend(abi_word(public_inputs.len() as u64));
+        for pi in public_inputs.iter() {
+            bytes.extend(scalar_to_bytes_be(pi));
+        }
+        bytes
+    }
+}
+
+/// A u64 as an ABI word.
+fn abi_word(a: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&a.to_be_bytes());
+    word
 }
 
 #[cfg(test)]
@@ -758,4 +1053,54 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_solidity_calldata() {
+        use crate::{
+            plonk::{constraint_system::TurboCS, prover::prover},
+            poly_commit::kzg_poly_commitment::KZGCommitmentScheme,
+            utils::transcript::{Transcript, TranscriptScheme},
+        };
+
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+        let mut cs = TurboCS::new();
+        let a = cs.new_variable(F::from(1u64));
+        let b = cs.new_variable(F::from(2u64));
+        let c = cs.add(a, b);
+        cs.prepare_pi_variable(c);
+        cs.pad();
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let proof = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+
+        let pi = [F::from(3u64)];
+        let calldata = proof.to_solidity_calldata(&pi);
+        let proof_bytes = proof.to_bytes_be();
+        let n = proof_bytes.len();
+        assert_eq!(n % 32, 1);
+        assert_eq!(proof_bytes[0], PlonkLayout::Standard.to_tag());
+        let padded = n.div_ceil(32) * 32;
+        assert_eq!(calldata.len(), 4 + 32 * 2 + 32 + padded + 32 + 32);
+
+        assert_eq!(
+            &calldata[..4],
+            &Keccak256::digest(b"verify(bytes,uint256[])")[..4]
+        );
+        assert_eq!(calldata[4..36], abi_word(64));
+        assert_eq!(calldata[36..68], abi_word(96 + padded as u64));
+        assert_eq!(calldata[68..100], abi_word(n as u64));
+        assert_eq!(&calldata[100..100 + n], &proof_bytes[..]);
+        assert!(calldata[100 + n..100 + padded].iter().all(|b| *b == 0));
+        assert_eq!(calldata[100 + padded..132 + padded], abi_word(1));
+        assert_eq!(&calldata[132 + padded..], &scalar_to_bytes_be(&pi[0])[..]);
+    }
 }
@@ -13,5 +13,8 @@ pub mod transcript;
 /// Module for verifier.
 pub mod verifier;
 
+/// Module for the recursive verifier.
+pub mod recursion;
+
 /// Module for help functions.
 pub(crate) mod helpers;
@@ -1,5 +1,7 @@
+use ark_ff::Zero;
 use ark_poly::Radix2EvaluationDomain;
 use ark_std::{
+    cmp::max,
     ops::*,
     rand::{CryptoRng, RngCore},
 };
@@ -7,15 +9,18 @@ use ark_std::{
 use crate::{
     errors::UzkgeError,
     plonk::helpers::{
-        first_lagrange_poly, hide_polynomial, pi_poly, r_poly, split_t_and_commit, t_poly, z_poly,
+        compress_lookup_table, first_lagrange_poly, hide_polynomial, lookup_evals, pi_poly, r_poly,
+        split_t_and_commit, t_poly, t_poly_by_cosets, z_lookup_poly, z_poly, MemoryTracker,
         PlonkChallenges,
     },
     poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme},
-    utils::transcript::Transcript,
+    utils::transcript::TranscriptScheme,
 };
 
 use super::{
-    constraint_system::ConstraintSystem,
+    constraint_system::{
+        custom_gate::check_custom_gates, turbo::N_WIRE_SELECTORS, ConstraintSystem,
+    },
     indexer::{PlonkProof, PlonkProverParams},
     transcript::transcript_init_plonk,
 };
@@ -33,7 +38,7 @@ use super::{
 ///     indexer::indexer
 /// };
 /// use uzkge::poly_commit::kzg_poly_commitment::KZGCommitmentScheme;
-/// use uzkge::utils::transcript::Transcript;
+/// use uzkge::utils::transcript::{Transcript, TranscriptScheme};
 /// use rand_chacha::ChaChaRng;
 /// use ark_bn254::Fr;
 /// use ark_std::{One, rand::SeedableRng, ops::*};
@@ -73,9 +78,14 @@ use super::{
 ///     verifier(&mut transcript, &pcs, &cs, &prover_params.get_verifier_params(), &[], &proof).is_ok()
 /// )
 /// ```
-pub fn prover<R: CryptoRng + RngCore, PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
+pub fn prover<
+    R: CryptoRng + RngCore,
+    PCS: PolyComScheme,
+    CS: ConstraintSystem<PCS::Field>,
+    T: TranscriptScheme,
+>(
     prng: &mut R,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     pcs: &PCS,
     cs: &CS,
     params: &PlonkProverParams<PCS>,
@@ -89,18 +99,110 @@ pub fn prover_with_lagrange<
     R: CryptoRng + RngCore,
     PCS: PolyComScheme,
     CS: ConstraintSystem<PCS::Field>,
+    T: TranscriptScheme,
 >(
     prng: &mut R,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     pcs: &PCS,
     lagrange_pcs: Option<&PCS>,
     cs: &CS,
     prover_params: &PlonkProverParams<PCS>,
     w: &[PCS::Field],
+) -> Result<PlonkProof<PCS>, UzkgeError> {
+    let mut tracker = MemoryTracker::new(None);
+    prove(
+        prng,
+        transcript,
+        pcs,
+        lagrange_pcs,
+        cs,
+        prover_params,
+        w,
+        false,
+        &mut tracker,
+    )
+}
+
+/// The configuration of the low-memory prover.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProverMemoryConfig {
+    /// The maximum estimated number of bytes of the field elements held by the prover,
+    /// unlimited if `None`.
+    pub budget: Option<usize>,
+}
+
+/// The memory used by the low-memory prover.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProverMemoryReport {
+    /// The estimated peak number of bytes of the field elements held by the prover,
+    /// including the prover parameters but not the commitment scheme. It is the sum of the
+    /// lengths of the recorded buffers, not a measure of the allocations of the process.
+    pub peak_bytes: usize,
+}
+
+/// Prover that computes the quotient polynomial coset by coset and drops the intermediate
+/// values early, for the memory-constrained environments, e.g., a browser on mobile.
+/// The proof is the same as the one of `prover_with_lagrange` with the same randomness.
+/// It returns [UzkgeError::MemoryBudgetExceeded] if the prover needs more memory than the budget.
+/// Call `PlonkProverParams::drop_coset_evals` beforehand to also release the coset evaluations
+/// in the prover parameters.
+#[allow(clippy::too_many_arguments)]
+pub fn prover_low_memory<
+    R: CryptoRng + RngCore,
+    PCS: PolyComScheme,
+    CS: ConstraintSystem<PCS::Field>,
+    T: TranscriptScheme,
+>(
+    prng: &mut R,
+    transcript: &mut T,
+    pcs: &PCS,
+    lagrange_pcs: Option<&PCS>,
+    cs: &CS,
+    prover_params: &PlonkProverParams<PCS>,
+    w: &[PCS::Field],
+    config: &ProverMemoryConfig,
+) -> Result<(PlonkProof<PCS>, ProverMemoryReport), UzkgeError> {
+    let mut tracker = MemoryTracker::new(config.budget);
+    let proof = prove(
+        prng,
+        transcript,
+        pcs,
+        lagrange_pcs,
+        cs,
+        prover_params,
+        w,
+        true,
+        &mut tracker,
+    )?;
+    let report = ProverMemoryReport {
+        peak_bytes: tracker.peak(),
+    };
+    Ok((proof, report))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove<
+    R: CryptoRng + RngCore,
+    PCS: PolyComScheme,
+    CS: ConstraintSystem<PCS::Field>,
+    T: TranscriptScheme,
+>(
+    prng: &mut R,
+    transcript: &mut T,
+    pcs: &PCS,
+    lagrange_pcs: Option<&PCS>,
+    cs: &CS,
+    prover_params: &PlonkProverParams<PCS>,
+    w: &[PCS::Field],
+    low_memory: bool,
+    tracker: &mut MemoryTracker<PCS::Field>,
 ) -> Result<PlonkProof<PCS>, UzkgeError> {
     if cs.is_verifier_only() {
         return Err(UzkgeError::FuncParamsError);
     }
+    check_custom_gates(cs, prover_params.verifier_params.cm_q_vec.len())?;
+    // the prover parameters are held during the whole proof.
+    tracker.alloc(prover_params.num_field_elems())?;
 
     let domain = FpPolynomial::<PCS::Field>::evaluation_domain(cs.size())
         .ok_or(UzkgeError::GroupNotFound(cs.size()))?;
@@ -121,6 +223,7 @@ pub fn prover_with_lagrange<
     );
     let mut challenges = PlonkChallenges::new();
     let n_constraints = cs.size();
+    let layout = prover_params.verifier_params.layout();
 
     let lagrange_pcs =
         if lagrange_pcs.is_some() && lagrange_pcs.unwrap().max_degree() + 1 == n_constraints {
@@ -149,15 +252,18 @@ pub fn prover_with_lagrange<
     };
 
     // 1. Build the PI polynomial
+    tracker.alloc(n_constraints)?;
     let pi = pi_poly::<PCS, Radix2EvaluationDomain<_>>(&prover_params, &online_values, &domain);
 
     // 2. build witness polynomials, hide them and commit
-    let extended_witness = cs.extend_witness(w); /* Prepare extended witness */
     let n_wires_per_gate = CS::n_wires_per_gate();
+    tracker.alloc(n_wires_per_gate * n_constraints)?;
+    let extended_witness = cs.extend_witness(w); /* Prepare extended witness */
     let mut w_polys = vec![];
     let mut cm_w_vec = vec![];
 
     for i in 0..n_wires_per_gate {
+        tracker.alloc(n_constraints + cs.get_hiding_degree(i))?;
         let mut f_coefs = FpPolynomial::ifft_with_domain(
             &domain,
             &extended_witness[i * n_constraints..(i + 1) * n_constraints],
@@ -175,30 +281,72 @@ pub fn prover_with_lagrange<
     }
 
     // 3. build witness selector polynomials, hide them and commit
-    #[cfg(feature = "shuffle")]
     let mut w_sel_polys = vec![];
-    #[cfg(feature = "shuffle")]
     let mut cm_w_sel_vec = vec![];
 
-    #[cfg(feature = "shuffle")]
-    for witness_selector in cs.compute_witness_selectors().iter() {
-        let mut f_coefs = FpPolynomial::ifft_with_domain(&domain, witness_selector);
-        let blinds = hide_polynomial(prng, &mut f_coefs, 2, n_constraints);
-        let cm_w_sel = commit(witness_selector.to_vec(), &f_coefs, &blinds)?;
-        transcript.append_commitment::<PCS::Commitment>(&cm_w_sel);
-
-        w_sel_polys.push(f_coefs);
-        cm_w_sel_vec.push(cm_w_sel);
+    if layout.has_shuffle() {
+        tracker.alloc(N_WIRE_SELECTORS * n_constraints)?;
+        let witness_selectors = cs.compute_witness_selectors(w);
+        for witness_selector in witness_selectors.iter() {
+            tracker.alloc(n_constraints + 2)?;
+            let mut f_coefs = FpPolynomial::ifft_with_domain(&domain, witness_selector);
+            let blinds = hide_polynomial(prng, &mut f_coefs, 2, n_constraints);
+            let cm_w_sel = commit(witness_selector.to_vec(), &f_coefs, &blinds)?;
+            transcript.append_commitment::<PCS::Commitment>(&cm_w_sel);
+
+            w_sel_polys.push(f_coefs);
+            cm_w_sel_vec.push(cm_w_sel);
+        }
+        tracker.free(N_WIRE_SELECTORS * n_constraints);
     }
 
+    // 3. b) get challenge eta, build the lookup query polynomial and the sorted polynomials,
+    // hide them and commit
+    let (lookup_evals, lookup_f_poly, lookup_h_polys, cm_lookup_f, cm_lookup_h_vec) =
+        if layout.has_lookup() {
+            let eta = transcript.get_challenge_field_elem(b"eta");
+            challenges.insert_eta(eta).unwrap();
+
+            tracker.alloc(4 * n_constraints)?;
+            let lookup_evals = lookup_evals(cs, &extended_witness, &challenges)?;
+            let mut polys = vec![];
+            let mut cms = vec![];
+            for (evals, hiding_degree) in lookup_evals[1..].iter().zip([2, 3, 2]) {
+                tracker.alloc(n_constraints + hiding_degree)?;
+                let mut coefs = FpPolynomial::ifft_with_domain(&domain, evals);
+                let blinds = hide_polynomial(prng, &mut coefs, hiding_degree, n_constraints);
+                let cm = commit(evals.to_vec(), &coefs, &blinds)?;
+                transcript.append_commitment::<PCS::Commitment>(&cm);
+
+                polys.push(coefs);
+                cms.push(cm);
+            }
+            let lookup_f_poly = polys.remove(0);
+            let cm_lookup_f = cms.remove(0);
+
+            (lookup_evals, lookup_f_poly, polys, cm_lookup_f, cms)
+        } else {
+            Default::default()
+        };
+
     // 4. get challenges beta and gamma
     let beta = transcript.get_challenge_field_elem(b"beta");
     transcript.append_single_byte(b"gamma", 0x01);
     let gamma = transcript.get_challenge_field_elem(b"gamma");
     challenges.insert_beta_gamma(beta, gamma).unwrap(); // safe unwrap
 
+    // 4. b) get challenges delta and epsilon
+    if layout.has_lookup() {
+        transcript.append_single_byte(b"delta", 0x02);
+        let delta = transcript.get_challenge_field_elem(b"delta");
+        transcript.append_single_byte(b"epsilon", 0x03);
+        let epsilon = transcript.get_challenge_field_elem(b"epsilon");
+        challenges.insert_delta_epsilon(delta, epsilon).unwrap();
+    }
+
     // 5. build the z polynomial, hide it and commit
     let (cm_z, z_poly) = {
+        tracker.alloc(n_constraints + 3)?;
         let z_evals = z_poly::<PCS, CS>(prover_params, &extended_witness, &challenges);
         let mut z_coefs = FpPolynomial::ifft_with_domain(&domain, &z_evals.coefs);
         let blinds = hide_polynomial(prng, &mut z_coefs, 3, n_constraints);
@@ -208,22 +356,70 @@ pub fn prover_with_lagrange<
         (cm_z, z_coefs)
     };
 
+    // the extended witness is no longer used.
+    tracker.free(n_wires_per_gate * n_constraints);
+    drop(extended_witness);
+
+    // 5. b) build the lookup z polynomial, hide it and commit
+    let (cm_lookup_z, z_lookup_poly) = if layout.has_lookup() {
+        tracker.alloc(n_constraints + 3)?;
+        let z_evals = z_lookup_poly(&lookup_evals, &challenges);
+        let mut z_coefs = FpPolynomial::ifft_with_domain(&domain, &z_evals.coefs);
+        let blinds = hide_polynomial(prng, &mut z_coefs, 3, n_constraints);
+        let cm_lookup_z = commit(z_evals.coefs, &z_coefs, &blinds)?;
+        transcript.append_commitment::<PCS::Commitment>(&cm_lookup_z);
+
+        tracker.free(4 * n_constraints);
+        drop(lookup_evals);
+        (cm_lookup_z, z_coefs)
+    } else {
+        Default::default()
+    };
+
     // 6. get challenge alpha
     let alpha = transcript.get_challenge_field_elem(b"alpha");
     challenges.insert_alpha(alpha).unwrap();
 
     // 7. build t, split into `n_wires_per_gate` degree-(N+2) polynomials and commit
-    let t_poly = t_poly::<PCS, CS>(
-        cs,
-        prover_params,
-        &w_polys,
-        #[cfg(feature = "shuffle")]
-        &w_sel_polys,
-        &z_poly,
-        &challenges,
-        &pi,
-    )?;
+    let lookup_polys = if layout.has_lookup() {
+        vec![
+            &lookup_f_poly,
+            &lookup_h_polys[0],
+            &lookup_h_polys[1],
+            &z_lookup_poly,
+        ]
+    } else {
+        vec![]
+    };
+    let t_len = cs.quot_eval_dom_size();
+    tracker.alloc(t_len)?;
+    let t_poly = if low_memory || !prover_params.has_coset_evals() {
+        t_poly_by_cosets::<PCS, CS>(
+            cs,
+            prover_params,
+            &w_polys,
+            &w_sel_polys,
+            &lookup_polys,
+            &z_poly,
+            &challenges,
+            &pi,
+            tracker,
+        )?
+    } else {
+        t_poly::<PCS, CS>(
+            cs,
+            prover_params,
+            &w_polys,
+            &w_sel_polys,
+            &lookup_polys,
+            &z_poly,
+            &challenges,
+            &pi,
+        )?
+    };
 
+    // the first `n_wires_per_gate - 1` split polynomials have `n_constraints + 3` coefficients.
+    tracker.alloc(max(t_len, (n_wires_per_gate - 1) * (n_constraints + 2)) + n_wires_per_gate)?;
     let (cm_t_vec, t_polys) = split_t_and_commit(
         prng,
         pcs,
@@ -232,6 +428,8 @@ pub fn prover_with_lagrange<
         n_wires_per_gate,
         n_constraints + 2,
     )?;
+    tracker.free(t_len);
+    drop(t_poly);
 
     for cm_t in cm_t_vec.iter() {
         transcript.append_commitment::<PCS::Commitment>(cm_t);
@@ -264,20 +462,65 @@ pub fn prover_with_lagrange<
         .map(|poly| pcs.eval(poly, &zeta_omega))
         .collect();
 
-    #[cfg(feature = "shuffle")]
-    let q_ecc_poly_eval_zeta = pcs.eval(&prover_params.q_ecc_poly, &zeta);
-    #[cfg(feature = "shuffle")]
+    let q_ecc_poly_eval_zeta = if layout.has_shuffle() {
+        pcs.eval(&prover_params.q_ecc_poly, &zeta)
+    } else {
+        PCS::Field::zero()
+    };
     let w_sel_polys_eval_zeta: Vec<PCS::Field> = w_sel_polys
         .iter()
         .map(|poly| pcs.eval(poly, &zeta))
         .collect();
 
+    let (
+        lookup_table_poly,
+        lookup_f_eval_zeta,
+        lookup_table_eval_zeta,
+        lookup_table_eval_zeta_omega,
+        lookup_h_polys_eval_zeta,
+        lookup_h1_eval_zeta_omega,
+        lookup_z_eval_zeta_omega,
+        q_lookup_polys_eval_zeta,
+    ) = if layout.has_lookup() {
+        tracker.alloc(n_constraints)?;
+        let lookup_table_poly = compress_lookup_table(
+            challenges.get_eta().unwrap(),
+            &prover_params.lookup_table_polys,
+        );
+        let lookup_f_eval_zeta = pcs.eval(&lookup_f_poly, &zeta);
+        let lookup_table_eval_zeta = pcs.eval(&lookup_table_poly, &zeta);
+        let lookup_table_eval_zeta_omega = pcs.eval(&lookup_table_poly, &zeta_omega);
+        let lookup_h_polys_eval_zeta: Vec<PCS::Field> = lookup_h_polys
+            .iter()
+            .map(|poly| pcs.eval(poly, &zeta))
+            .collect();
+        let lookup_h1_eval_zeta_omega = pcs.eval(&lookup_h_polys[0], &zeta_omega);
+        let lookup_z_eval_zeta_omega = pcs.eval(&z_lookup_poly, &zeta_omega);
+        let q_lookup_polys_eval_zeta: Vec<PCS::Field> = prover_params
+            .q_lookup_polys
+            .iter()
+            .map(|poly| pcs.eval(poly, &zeta))
+            .collect();
+
+        (
+            lookup_table_poly,
+            lookup_f_eval_zeta,
+            lookup_table_eval_zeta,
+            lookup_table_eval_zeta_omega,
+            lookup_h_polys_eval_zeta,
+            lookup_h1_eval_zeta_omega,
+            lookup_z_eval_zeta_omega,
+            q_lookup_polys_eval_zeta,
+        )
+    } else {
+        Default::default()
+    };
+
     //  b). build the r polynomial, and eval at zeta
     for eval_zeta in w_polys_eval_zeta.iter().chain(s_polys_eval_zeta.iter()) {
         transcript.append_challenge(eval_zeta);
     }
 
-    #[cfg(feature = "shuffle")]
     for eval_zeta in w_sel_polys_eval_zeta.iter() {
         transcript.append_challenge(eval_zeta);
     }
@@ -285,44 +528,60 @@ pub fn prover_with_lagrange<
     transcript.append_challenge(&prk_3_poly_eval_zeta);
     transcript.append_challenge(&prk_4_poly_eval_zeta);
     transcript.append_challenge(&z_eval_zeta_omega);
-    #[cfg(feature = "shuffle")]
-    transcript.append_challenge(&q_ecc_poly_eval_zeta);
+    if layout.has_shuffle() {
+        transcript.append_challenge(&q_ecc_poly_eval_zeta);
+    }
     for eval_zeta_omega in w_polys_eval_zeta_omega.iter() {
         transcript.append_challenge(eval_zeta_omega);
     }
+    if layout.has_lookup() {
+        transcript.append_challenge(&lookup_f_eval_zeta);
+        transcript.append_challenge(&lookup_table_eval_zeta);
+        transcript.append_challenge(&lookup_table_eval_zeta_omega);
+        for eval_zeta in lookup_h_polys_eval_zeta.iter() {
+            transcript.append_challenge(eval_zeta);
+        }
+        transcript.append_challenge(&lookup_h1_eval_zeta_omega);
+        transcript.append_challenge(&lookup_z_eval_zeta_omega);
+        for eval_zeta in q_lookup_polys_eval_zeta.iter() {
+            transcript.append_challenge(eval_zeta);
+        }
+    }
 
     // 10. get challenge u
     let u = transcript.get_challenge_field_elem(b"u");
     challenges.insert_u(u).unwrap();
 
     let w_polys_eval_zeta_as_ref: Vec<&PCS::Field> = w_polys_eval_zeta.iter().collect();
-    #[cfg(feature = "shuffle")]
     let w_polys_eval_zeta_omega_as_ref: Vec<&PCS::Field> = w_polys_eval_zeta_omega.iter().collect();
     let s_poly_eval_zeta_as_ref: Vec<&PCS::Field> = s_polys_eval_zeta.iter().collect();
-    #[cfg(feature = "shuffle")]
     let w_sel_polys_eval_zeta_as_ref: Vec<&PCS::Field> = w_sel_polys_eval_zeta.iter().collect();
 
     let (z_h_eval_zeta, first_lagrange_eval_zeta) =
         first_lagrange_poly::<PCS>(&challenges, cs.size() as u64);
+    tracker.alloc(n_constraints + 3)?;
     let r_poly = r_poly::<PCS, CS>(
+        cs,
         prover_params,
         &z_poly,
         &w_polys_eval_zeta_as_ref,
-        #[cfg(feature = "shuffle")]
         &w_polys_eval_zeta_omega_as_ref,
         &s_poly_eval_zeta_as_ref,
         &prk_3_poly_eval_zeta,
         &z_eval_zeta_omega,
-        #[cfg(feature = "shuffle")]
         &q_ecc_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         &w_sel_polys_eval_zeta_as_ref,
         &challenges,
         &t_polys,
         &first_lagrange_eval_zeta,
         &z_h_eval_zeta,
-        #[cfg(feature = "shuffle")]
         &cs.get_edwards_a(),
+        &z_lookup_poly,
+        &[
+            &lookup_f_eval_zeta,
+            &lookup_table_eval_zeta,
+            &lookup_table_eval_zeta_omega,
+        ],
         n_constraints + 2,
     );
 
@@ -337,13 +596,20 @@ pub fn prover_with_lagrange<
         .collect();
     polys_to_open.push(&prover_params.q_prk_polys[2]);
     polys_to_open.push(&prover_params.q_prk_polys[3]);
-    #[cfg(feature = "shuffle")]
-    {
+    if layout.has_shuffle() {
         polys_to_open.push(&prover_params.q_ecc_poly);
         for w_sel_poly in w_sel_polys.iter() {
             polys_to_open.push(w_sel_poly);
         }
     }
+    if layout.has_lookup() {
+        polys_to_open.push(&lookup_f_poly);
+        polys_to_open.push(&lookup_table_poly);
+        polys_to_open.push(&lookup_h_polys[0]);
+        polys_to_open.push(&lookup_h_polys[1]);
+        polys_to_open.push(&prover_params.q_lookup_polys[0]);
+        polys_to_open.push(&prover_params.q_lookup_polys[1]);
+    }
     polys_to_open.push(&r_poly);
 
     let zeta = challenges.get_zeta().unwrap();
@@ -358,8 +624,13 @@ pub fn prover_with_lagrange<
         )
         .map_err(|_| UzkgeError::ProofError)?;
 
-    let polys_to_open: Vec<&FpPolynomial<PCS::Field>> =
+    let mut polys_to_open: Vec<&FpPolynomial<PCS::Field>> =
         vec![&z_poly, &w_polys[0], &w_polys[1], &w_polys[2]];
+    if layout.has_lookup() {
+        polys_to_open.push(&z_lookup_poly);
+        polys_to_open.push(&lookup_table_poly);
+        polys_to_open.push(&lookup_h_polys[0]);
+    }
 
     let opening_witness_zeta_omega = pcs
         .batch_prove(
@@ -373,22 +644,138 @@ pub fn prover_with_lagrange<
 
     // return proof
     Ok(PlonkProof {
+        layout,
         cm_w_vec,
-        #[cfg(feature = "shuffle")]
         cm_w_sel_vec,
         cm_t_vec,
         cm_z,
+        cm_lookup_f,
+        cm_lookup_h_vec,
+        cm_lookup_z,
         prk_3_poly_eval_zeta,
         prk_4_poly_eval_zeta,
         w_polys_eval_zeta,
         w_polys_eval_zeta_omega,
         z_eval_zeta_omega,
         s_polys_eval_zeta,
-        #[cfg(feature = "shuffle")]
         q_ecc_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         w_sel_polys_eval_zeta,
+        lookup_f_eval_zeta,
+        lookup_table_eval_zeta,
+        lookup_table_eval_zeta_omega,
+        lookup_h_polys_eval_zeta,
+        lookup_h1_eval_zeta_omega,
+        lookup_z_eval_zeta_omega,
+        q_lookup_polys_eval_zeta,
         opening_witness_zeta,
         opening_witness_zeta_omega,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_ec::PrimeGroup;
+    use ark_ed_on_bn254::EdwardsProjective;
+    use ark_std::{rand::SeedableRng, UniformRand};
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        errors::UzkgeError,
+        plonk::{
+            constraint_system::TurboCS,
+            indexer::indexer,
+            prover::{prover, prover_low_memory, ProverMemoryConfig},
+            verifier::verifier,
+        },
+        poly_commit::kzg_poly_commitment::KZGCommitmentScheme,
+        shuffle::{BabyJubjubShuffle, Ciphertext, Remark},
+        utils::transcript::{Transcript, TranscriptScheme},
+    };
+
+    #[test]
+    fn test_prover_low_memory() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(1030, &mut prng);
+
+        let public = EdwardsProjective::generator();
+        let m = EdwardsProjective::rand(&mut prng);
+        let input = Ciphertext::encrypt(&mut prng, &m, &public);
+        let bits = BabyJubjubShuffle::sample_random_scalar_bits(&mut prng);
+        let trace = BabyJubjubShuffle::eval_remark_with_trace(&input, &bits, &public);
+
+        let mut cs = TurboCS::new();
+        cs.load_shuffle_remark_parameters::<_, BabyJubjubShuffle>(&public);
+        let input_vars = cs.new_card_variable(&input);
+        let output_vars = cs.eval_card_remark(&trace, &input_vars);
+        cs.prepare_pi_card_variable(&output_vars);
+        cs.pad();
+
+        let witness = cs.get_and_clear_witness();
+        let mut prover_params = indexer(&cs, &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+        let pi: Vec<Fr> = trace.output.to_vec();
+
+        let expected = prover(
+            &mut ChaChaRng::from_seed([1u8; 32]),
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap()
+        .to_bytes_be();
+
+        let (proof, report) = prover_low_memory(
+            &mut ChaChaRng::from_seed([1u8; 32]),
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            None,
+            &cs,
+            &prover_params,
+            &witness,
+            &ProverMemoryConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes_be(), expected);
+        let mut transcript = Transcript::new(b"Test");
+        assert!(verifier(&mut transcript, &pcs, &cs, &verifier_params, &pi, &proof).is_ok());
+
+        // the provers evaluate the polynomials coset by coset without the coset evaluations.
+        prover_params.drop_coset_evals();
+        let proof = prover(
+            &mut ChaChaRng::from_seed([1u8; 32]),
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes_be(), expected);
+
+        let prove = |budget: Option<usize>| {
+            prover_low_memory(
+                &mut ChaChaRng::from_seed([1u8; 32]),
+                &mut Transcript::new(b"Test"),
+                &pcs,
+                None,
+                &cs,
+                &prover_params,
+                &witness,
+                &ProverMemoryConfig { budget },
+            )
+        };
+        let (proof, dropped_report) = prove(None).unwrap();
+        assert_eq!(proof.to_bytes_be(), expected);
+        assert!(dropped_report.peak_bytes < report.peak_bytes);
+
+        let (proof, _) = prove(Some(dropped_report.peak_bytes)).unwrap();
+        assert_eq!(proof.to_bytes_be(), expected);
+        assert_eq!(
+            prove(Some(dropped_report.peak_bytes - 1)).unwrap_err(),
+            UzkgeError::MemoryBudgetExceeded(dropped_report.peak_bytes - 1)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use ark_bn254::{Fq, Fr};
+use ark_ec::CurveGroup;
+use ark_ff::{AdditiveGroup, Field};
+
+use crate::{
+    errors::UzkgeError,
+    plonk::{
+        constraint_system::non_native::non_native_limbs,
+        indexer::{PlonkProof, PlonkVerifierParams},
+    },
+    poly_commit::{
+        kzg_poly_commitment::KZGCommitmentSchemeBN254,
+        pcs::{HomomorphicPolyComElem, PolyComScheme},
+    },
+};
+
+/// The commitments of the verifier params, in the order of the coefficients of the
+/// deferred check of the recursive verifier.
+pub fn verifier_params_commitments<PCS: PolyComScheme>(
+    params: &PlonkVerifierParams<PCS>,
+) -> Vec<&PCS::Commitment> {
+    let mut commitments: Vec<&PCS::Commitment> = params
+        .cm_q_vec
+        .iter()
+        .chain(params.cm_s_vec.iter())
+        .collect();
+    commitments.push(&params.cm_qb);
+    commitments.extend(params.cm_prk_vec.iter());
+    if params.layout().has_shuffle() {
+        commitments.push(&params.cm_q_ecc);
+        commitments.extend(params.cm_shuffle_generator_vec.iter());
+        commitments.extend(params.cm_shuffle_public_key_vec.iter());
+    }
+    commitments.extend(params.cm_q_lookup_vec.iter());
+    commitments.extend(params.cm_lookup_table_vec.iter());
+    commitments
+}
+
+/// The commitments of a proof that the recursive verifier accumulates as points, see
+/// [KZGAccumulator]. The opening witnesses are the last two.
+pub fn proof_commitments<PCS: PolyComScheme>(proof: &PlonkProof<PCS>) -> Vec<&PCS::Commitment> {
+    let mut commitments: Vec<&PCS::Commitment> = proof.cm_w_vec.iter().collect();
+    commitments.extend(proof.cm_w_sel_vec.iter());
+    commitments.extend(proof.cm_t_vec.iter());
+    commitments.push(&proof.cm_z);
+    if proof.layout.has_lookup() {
+        commitments.push(&proof.cm_lookup_f);
+        commitments.extend(proof.cm_lookup_h_vec.iter());
+        commitments.push(&proof.cm_lookup_z);
+    }
+    commitments.push(&proof.opening_witness_zeta);
+    commitments.push(&proof.opening_witness_zeta_omega);
+    commitments
+}
+
+/// The pairing check deferred by the recursive verifier,
+/// ```text
+///     e(left, [x]_2) = e(right + sum_i params_i * C_i - eval * [1]_1, [1]_2)
+/// ```
+/// where `C_i` are the commitments of the verifier params.
+///
+/// The recursive verifier computes `left` and `right` from the commitments of the proofs
+/// with non-native arithmetic, so that the accumulator has a constant size for any
+/// number of proofs, and the verifier of the outer proof does a multi-scalar
+/// multiplication of the commitments of the verifier params and a single pairing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KZGAccumulator<PCS: PolyComScheme> {
+    /// The combination of the opening witnesses.
+    pub left: PCS::Commitment,
+    /// The combination of the commitments of the proofs.
+    pub right: PCS::Commitment,
+    /// The scalars of the commitments of the verifier params.
+    pub params: Vec<PCS::Field>,
+    /// The scalar of the generator.
+    pub eval: PCS::Field,
+}
+
+impl<PCS: PolyComScheme> KZGAccumulator<PCS> {
+    /// Check the deferred pairing equation for the verifier params.
+    pub fn verify(&self, pcs: &PCS, params: &PlonkVerifierParams<PCS>) -> Result<(), UzkgeError> {
+        let commitments = verifier_params_commitments(params);
+        if commitments.len() != self.params.len() {
+            return Err(UzkgeError::VerificationError);
+        }
+
+        let mut right = self.right.clone();
+        for (cm, scalar) in commitments.iter().zip(self.params.iter()) {
+            right.add_assign(&cm.mul(scalar));
+        }
+
+        // with the point zero, the check is `e(left, [x]_2) = e(right - eval * [1]_1, [1]_2)`.
+        pcs.batch_verify_diff_points(
+            &[right],
+            &[PCS::Field::ZERO],
+            &[self.eval],
+            ark_std::slice::from_ref(&self.left),
+            &PCS::Field::ONE,
+        )
+        .map_err(|_| UzkgeError::VerificationError)
+    }
+}
+
+impl KZGAccumulator<KZGCommitmentSchemeBN254> {
+    /// The public inputs of the outer proof for the accumulator, in the order of
+    /// [KZGAccumulatorVars::vars](crate::plonk::constraint_system::recursion::KZGAccumulatorVars::vars).
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        let mut pi = vec![];
+        for point in [&self.left.0, &self.right.0] {
+            let point = point.into_affine();
+            pi.extend(non_native_limbs::<Fq, Fr>(&point.x));
+            pi.extend(non_native_limbs::<Fq, Fr>(&point.y));
+        }
+        pi.extend_from_slice(&self.params);
+        pi.push(self.eval);
+        pi
+    }
+}
@@ -1,12 +1,12 @@
 use ark_ff::{BigInteger, PrimeField};
 
-use crate::{poly_commit::pcs::PolyComScheme, utils::transcript::Transcript};
+use crate::{poly_commit::pcs::PolyComScheme, utils::transcript::TranscriptScheme};
 
 use super::indexer::PlonkVerifierParams;
 
 /// Initialize the transcript when compute PLONK proof.
-pub(crate) fn transcript_init_plonk<PCS: PolyComScheme>(
-    transcript: &mut Transcript,
+pub(crate) fn transcript_init_plonk<PCS: PolyComScheme, T: TranscriptScheme>(
+    transcript: &mut T,
     params: &PlonkVerifierParams<PCS>,
     pi_values: &[PCS::Field],
     root: &PCS::Field,
@@ -21,6 +21,13 @@ pub(crate) fn transcript_init_plonk<PCS: PolyComScheme>(
     for p in params.cm_s_vec.iter() {
         transcript.append_commitment(p);
     }
+    for t in params
+        .cm_q_lookup_vec
+        .iter()
+        .chain(params.cm_lookup_table_vec.iter())
+    {
+        transcript.append_commitment(t);
+    }
     transcript.append_challenge(root);
     for k in params.k.iter() {
         transcript.append_challenge(k);
@@ -3,25 +3,149 @@ use ark_std::ops::Mul;
 use crate::{
     errors::UzkgeError,
     poly_commit::{field_polynomial::FpPolynomial, pcs::PolyComScheme},
-    utils::transcript::Transcript,
+    utils::transcript::{Transcript, TranscriptScheme},
 };
 
 use super::{
-    constraint_system::ConstraintSystem,
-    helpers::{eval_pi_poly, first_lagrange_poly, r_commitment, r_eval_zeta, PlonkChallenges},
+    constraint_system::{custom_gate::check_custom_gates, ConstraintSystem},
+    helpers::{
+        compress_lookup_table, eval_pi_poly, first_lagrange_poly, r_commitment, r_eval_zeta,
+        PlonkChallenges,
+    },
     indexer::{PlonkProof, PlonkVerifierParams},
     transcript::transcript_init_plonk,
 };
 
+/// The opening claims of one proof, at `zeta` and at `zeta * omega`.
+struct OpeningClaims<PCS: PolyComScheme> {
+    cm_vec: [PCS::Commitment; 2],
+    point_vec: [PCS::Field; 2],
+    eval_vec: [PCS::Field; 2],
+    proofs: [PCS::Commitment; 2],
+    u: PCS::Field,
+}
+
+impl<PCS: PolyComScheme> OpeningClaims<PCS> {
+    fn verify(&self, pcs: &PCS, challenge: &PCS::Field) -> Result<(), UzkgeError> {
+        pcs.batch_verify_diff_points(
+            &self.cm_vec,
+            &self.point_vec,
+            &self.eval_vec,
+            &self.proofs,
+            challenge,
+        )
+        .map_err(|_| UzkgeError::VerificationError)
+    }
+}
+
+/// A proof to be verified in a batch, with its transcript and public inputs.
+pub type BatchInstance<'a, PCS, T = Transcript> =
+    (T, &'a [<PCS as PolyComScheme>::Field], &'a PlonkProof<PCS>);
+
 /// Verify a proof.
-pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
-    transcript: &mut Transcript,
+pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>, T: TranscriptScheme>(
+    transcript: &mut T,
     pcs: &PCS,
     cs: &CS,
     verifier_params: &PlonkVerifierParams<PCS>,
     pi: &[PCS::Field],
     proof: &PlonkProof<PCS>,
 ) -> Result<(), UzkgeError> {
+    let claims = opening_claims(transcript, pcs, cs, verifier_params, pi, proof)?;
+    claims.verify(pcs, &claims.u)
+}
+
+/// Verify many proofs of the same constraint system with a single final check.
+/// Each instance is `(transcript, pi, proof)`, the transcript being in the same state as the one
+/// used by its prover. The opening claims of all proofs are combined with powers of a challenge
+/// bound to every proof, so that only one pairing check is needed when all proofs are valid.
+/// If the combined check fails, it returns [UzkgeError::BatchVerificationError] with the index
+/// of the first invalid proof.
+pub fn batch_verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>, T: TranscriptScheme>(
+    pcs: &PCS,
+    cs: &CS,
+    verifier_params: &PlonkVerifierParams<PCS>,
+    instances: &mut [BatchInstance<PCS, T>],
+) -> Result<(), UzkgeError> {
+    if instances.is_empty() {
+        return Ok(());
+    }
+
+    let mut all_claims = Vec::with_capacity(instances.len());
+    for (transcript, pi, proof) in instances.iter_mut() {
+        all_claims.push(opening_claims(
+            transcript,
+            pcs,
+            cs,
+            verifier_params,
+            pi,
+            proof,
+        )?);
+    }
+
+    let mut batch_transcript = T::new(b"Plonk Batch Verification");
+    batch_transcript.append_u64(b"Number of proofs", all_claims.len() as u64);
+    for claims in all_claims.iter() {
+        batch_transcript.append_challenge(&claims.u);
+        for (cm, proof) in claims.cm_vec.iter().zip(claims.proofs.iter()) {
+            batch_transcript.append_commitment::<PCS::Commitment>(cm);
+            batch_transcript.append_commitment::<PCS::Commitment>(proof);
+        }
+    }
+    let challenge = batch_transcript.get_challenge_field_elem::<PCS::Field>(b"batch");
+
+    let mut cm_vec = Vec::with_capacity(2 * all_claims.len());
+    let mut point_vec = Vec::with_capacity(2 * all_claims.len());
+    let mut eval_vec = Vec::with_capacity(2 * all_claims.len());
+    let mut proofs = Vec::with_capacity(2 * all_claims.len());
+    for claims in all_claims.iter() {
+        cm_vec.extend_from_slice(&claims.cm_vec);
+        point_vec.extend_from_slice(&claims.point_vec);
+        eval_vec.extend_from_slice(&claims.eval_vec);
+        proofs.extend_from_slice(&claims.proofs);
+    }
+
+    if pcs
+        .batch_verify_diff_points(&cm_vec, &point_vec, &eval_vec, &proofs, &challenge)
+        .is_ok()
+    {
+        return Ok(());
+    }
+
+    // The combined check failed, find out which proof is invalid.
+    for (i, claims) in all_claims.iter().enumerate() {
+        if claims.verify(pcs, &claims.u).is_err() {
+            return Err(UzkgeError::BatchVerificationError(i));
+        }
+    }
+    Err(UzkgeError::VerificationError)
+}
+
+/// Run the verifier up to the final pairing check and return the resulting opening claims.
+fn opening_claims<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>, T: TranscriptScheme>(
+    transcript: &mut T,
+    pcs: &PCS,
+    cs: &CS,
+    verifier_params: &PlonkVerifierParams<PCS>,
+    pi: &[PCS::Field],
+    proof: &PlonkProof<PCS>,
+) -> Result<OpeningClaims<PCS>, UzkgeError> {
+    // the selectors of the custom gates should be committed in the verifier params.
+    check_custom_gates(cs, verifier_params.cm_q_vec.len())?;
+
+    // the proof should be of the layout of the circuit.
+    let n_wire_selectors = if verifier_params.layout().has_shuffle() {
+        CS::num_wire_selectors()
+    } else {
+        0
+    };
+    if proof.layout != verifier_params.layout()
+        || proof.cm_w_sel_vec.len() != n_wire_selectors
+        || proof.w_sel_polys_eval_zeta.len() != n_wire_selectors
+    {
+        return Err(UzkgeError::VerificationError);
+    }
+
     let domain = FpPolynomial::<PCS::Field>::evaluation_domain(cs.size())
         .ok_or(UzkgeError::GroupNotFound(cs.size()))?;
     let root = domain.group_gen;
@@ -29,7 +153,7 @@ pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
     transcript_init_plonk(transcript, verifier_params, pi, &root);
     let mut challenges = PlonkChallenges::new();
     // 1. compute all challenges such as gamma, beta, alpha, zeta and u.
-    compute_challenges::<PCS>(&mut challenges, transcript, &proof);
+    compute_challenges::<PCS, T>(&mut challenges, transcript, proof);
 
     // 2. compute Z_h(\zeta) and L_1(\zeta).
     let (z_h_eval_zeta, first_lagrange_eval_zeta) =
@@ -55,24 +179,26 @@ pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
     );
 
     let w_polys_eval_zeta_as_ref: Vec<&PCS::Field> = proof.w_polys_eval_zeta.iter().collect();
-    #[cfg(feature = "shuffle")]
     let w_polys_eval_zeta_omega_as_ref: Vec<&PCS::Field> =
         proof.w_polys_eval_zeta_omega.iter().collect();
-    #[cfg(feature = "shuffle")]
     let w_sel_polys_eval_zeta_as_ref: Vec<&PCS::Field> =
         proof.w_sel_polys_eval_zeta.iter().collect();
     let s_eval_zeta_as_ref: Vec<&PCS::Field> = proof.s_polys_eval_zeta.iter().collect();
     let cm_r = r_commitment::<PCS, CS>(
+        cs,
         verifier_params,
         &proof.cm_z,
+        &proof.cm_lookup_z,
+        &[
+            &proof.lookup_f_eval_zeta,
+            &proof.lookup_table_eval_zeta,
+            &proof.lookup_table_eval_zeta_omega,
+        ],
         &w_polys_eval_zeta_as_ref[..],
-        #[cfg(feature = "shuffle")]
         &w_sel_polys_eval_zeta_as_ref[..],
         &s_eval_zeta_as_ref[..],
         &proof.prk_3_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         &proof.q_ecc_poly_eval_zeta,
-        #[cfg(feature = "shuffle")]
         &w_polys_eval_zeta_omega_as_ref[..],
         &proof.z_eval_zeta_omega,
         &challenges,
@@ -95,13 +221,28 @@ pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
         .collect();
     commitments.push(&verifier_params.cm_prk_vec[2]);
     commitments.push(&verifier_params.cm_prk_vec[3]);
-    #[cfg(feature = "shuffle")]
-    {
+    if verifier_params.layout().has_shuffle() {
         commitments.push(&verifier_params.cm_q_ecc);
         for cm_w_sel in proof.cm_w_sel_vec.iter() {
             commitments.push(cm_w_sel);
         }
     }
+    let cm_lookup_table = if proof.layout.has_lookup() {
+        compress_lookup_table(
+            challenges.get_eta().unwrap(),
+            &verifier_params.cm_lookup_table_vec,
+        )
+    } else {
+        Default::default()
+    };
+    if proof.layout.has_lookup() {
+        commitments.push(&proof.cm_lookup_f);
+        commitments.push(&cm_lookup_table);
+        commitments.push(&proof.cm_lookup_h_vec[0]);
+        commitments.push(&proof.cm_lookup_h_vec[1]);
+        commitments.push(&verifier_params.cm_q_lookup_vec[0]);
+        commitments.push(&verifier_params.cm_q_lookup_vec[1]);
+    }
     commitments.push(&cm_r);
 
     let mut values: Vec<PCS::Field> = proof
@@ -112,13 +253,20 @@ pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
         .collect();
     values.push(proof.prk_3_poly_eval_zeta);
     values.push(proof.prk_4_poly_eval_zeta);
-    #[cfg(feature = "shuffle")]
-    {
+    if verifier_params.layout().has_shuffle() {
         values.push(proof.q_ecc_poly_eval_zeta);
         for w_sel_eval_zeta in proof.w_sel_polys_eval_zeta.iter() {
             values.push(*w_sel_eval_zeta);
         }
     }
+    if proof.layout.has_lookup() {
+        values.push(proof.lookup_f_eval_zeta);
+        values.push(proof.lookup_table_eval_zeta);
+        values.push(proof.lookup_h_polys_eval_zeta[0]);
+        values.push(proof.lookup_h_polys_eval_zeta[1]);
+        values.push(proof.q_lookup_polys_eval_zeta[0]);
+        values.push(proof.q_lookup_polys_eval_zeta[1]);
+    }
     values.push(r_eval_zeta);
 
     let zeta = challenges.get_zeta().unwrap();
@@ -132,40 +280,50 @@ pub fn verifier<PCS: PolyComScheme, CS: ConstraintSystem<PCS::Field>>(
         &values[..],
     );
 
+    let mut commitments_omega = vec![
+        &proof.cm_z,
+        &proof.cm_w_vec[0],
+        &proof.cm_w_vec[1],
+        &proof.cm_w_vec[2],
+    ];
+    let mut values_omega = vec![
+        proof.z_eval_zeta_omega,
+        proof.w_polys_eval_zeta_omega[0],
+        proof.w_polys_eval_zeta_omega[1],
+        proof.w_polys_eval_zeta_omega[2],
+    ];
+    if proof.layout.has_lookup() {
+        commitments_omega.push(&proof.cm_lookup_z);
+        commitments_omega.push(&cm_lookup_table);
+        commitments_omega.push(&proof.cm_lookup_h_vec[0]);
+        values_omega.push(proof.lookup_z_eval_zeta_omega);
+        values_omega.push(proof.lookup_table_eval_zeta_omega);
+        values_omega.push(proof.lookup_h1_eval_zeta_omega);
+    }
+
     let (comm_omega, val_omega) = pcs.batch(
         transcript,
-        &[
-            &proof.cm_z,
-            &proof.cm_w_vec[0],
-            &proof.cm_w_vec[1],
-            &proof.cm_w_vec[2],
-        ],
+        &commitments_omega[..],
         verifier_params.cs_size + 2,
         &zeta_omega,
-        &[
-            proof.z_eval_zeta_omega,
-            proof.w_polys_eval_zeta_omega[0],
-            proof.w_polys_eval_zeta_omega[1],
-            proof.w_polys_eval_zeta_omega[2],
-        ],
+        &values_omega[..],
     );
 
-    pcs.batch_verify_diff_points(
-        &[comm, comm_omega],
-        &[zeta.clone(), zeta_omega.clone()],
-        &[val, val_omega],
-        &[
+    Ok(OpeningClaims {
+        cm_vec: [comm, comm_omega],
+        point_vec: [*zeta, zeta_omega],
+        eval_vec: [val, val_omega],
+        proofs: [
             proof.opening_witness_zeta.clone(),
             proof.opening_witness_zeta_omega.clone(),
         ],
-        challenges.get_u().unwrap(),
-    )
-    .map_err(|_| UzkgeError::VerificationError)
+        u: *challenges.get_u().unwrap(),
+    })
 }
 
-fn compute_challenges<PCS: PolyComScheme>(
+fn compute_challenges<PCS: PolyComScheme, T: TranscriptScheme>(
     challenges: &mut PlonkChallenges<PCS::Field>,
-    transcript: &mut Transcript,
+    transcript: &mut T,
     proof: &PlonkProof<PCS>,
 ) {
     // 1. compute gamma and beta challenges.
@@ -173,18 +331,37 @@ fn compute_challenges<PCS: PolyComScheme>(
         transcript.append_commitment::<PCS::Commitment>(cm_w);
     }
 
-    #[cfg(feature = "shuffle")]
     for cm_w_sel in proof.cm_w_sel_vec.iter() {
         transcript.append_commitment::<PCS::Commitment>(cm_w_sel);
     }
 
+    if proof.layout.has_lookup() {
+        let eta = transcript.get_challenge_field_elem(b"eta");
+        challenges.insert_eta(eta).unwrap();
+        transcript.append_commitment::<PCS::Commitment>(&proof.cm_lookup_f);
+        for cm_lookup_h in proof.cm_lookup_h_vec.iter() {
+            transcript.append_commitment::<PCS::Commitment>(cm_lookup_h);
+        }
+    }
+
     let beta = transcript.get_challenge_field_elem(b"beta");
     transcript.append_single_byte(b"gamma", 0x01);
     let gamma = transcript.get_challenge_field_elem(b"gamma");
     challenges.insert_beta_gamma(beta, gamma).unwrap();
 
+    if proof.layout.has_lookup() {
+        transcript.append_single_byte(b"delta", 0x02);
+        let delta = transcript.get_challenge_field_elem(b"delta");
+        transcript.append_single_byte(b"epsilon", 0x03);
+        let epsilon = transcript.get_challenge_field_elem(b"epsilon");
+        challenges.insert_delta_epsilon(delta, epsilon).unwrap();
+    }
+
     // 2. compute alpha challenge.
     transcript.append_commitment::<PCS::Commitment>(&proof.cm_z);
+    if proof.layout.has_lookup() {
+        transcript.append_commitment::<PCS::Commitment>(&proof.cm_lookup_z);
+    }
     let alpha = transcript.get_challenge_field_elem(b"alpha");
     challenges.insert_alpha(alpha).unwrap();
     for cm_t in &proof.cm_t_vec {
@@ -201,22 +378,223 @@ fn compute_challenges<PCS: PolyComScheme>(
     {
         transcript.append_challenge(eval_zeta);
     }
-    #[cfg(feature = "shuffle")]
-    {
-        for eval_zeta in proof.w_sel_polys_eval_zeta.iter() {
-            transcript.append_challenge(eval_zeta);
-        }
+    for eval_zeta in proof.w_sel_polys_eval_zeta.iter() {
+        transcript.append_challenge(eval_zeta);
     }
     transcript.append_challenge(&proof.prk_3_poly_eval_zeta);
     transcript.append_challenge(&proof.prk_4_poly_eval_zeta);
     transcript.append_challenge(&proof.z_eval_zeta_omega);
-    #[cfg(feature = "shuffle")]
-    transcript.append_challenge(&proof.q_ecc_poly_eval_zeta);
+    if proof.layout.has_shuffle() {
+        transcript.append_challenge(&proof.q_ecc_poly_eval_zeta);
+    }
     for eval_zeta_omega in proof.w_polys_eval_zeta_omega.iter() {
         transcript.append_challenge(eval_zeta_omega);
     }
+    if proof.layout.has_lookup() {
+        transcript.append_challenge(&proof.lookup_f_eval_zeta);
+        transcript.append_challenge(&proof.lookup_table_eval_zeta);
+        transcript.append_challenge(&proof.lookup_table_eval_zeta_omega);
+        for eval_zeta in proof.lookup_h_polys_eval_zeta.iter() {
+            transcript.append_challenge(eval_zeta);
+        }
+        transcript.append_challenge(&proof.lookup_h1_eval_zeta_omega);
+        transcript.append_challenge(&proof.lookup_z_eval_zeta_omega);
+        for eval_zeta in proof.q_lookup_polys_eval_zeta.iter() {
+            transcript.append_challenge(eval_zeta);
+        }
+    }
 
     // 4. compute u challenge.
     let u = transcript.get_challenge_field_elem(b"u");
     challenges.insert_u(u).unwrap();
 }
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::{
+        anemoi::AnemoiJive254,
+        errors::UzkgeError,
+        plonk::{
+            constraint_system::{PlonkLayout, TurboCS},
+            indexer::{indexer, PlonkProof},
+            prover::prover,
+            verifier::{batch_verifier, verifier},
+        },
+        poly_commit::kzg_poly_commitment::{KZGCommitmentScheme, KZGCommitmentSchemeBN254},
+        utils::transcript::{AnemoiTranscript, Transcript, TranscriptScheme},
+    };
+
+    fn build_cs(a: u64, b: u64) -> TurboCS<Fr> {
+        let mut cs = TurboCS::new();
+        let var_a = cs.new_variable(Fr::from(a));
+        let var_b = cs.new_variable(Fr::from(b));
+        let var_c = cs.add(var_a, var_b);
+        cs.prepare_pi_variable(var_c);
+        cs.pad();
+        cs
+    }
+
+    #[test]
+    fn test_batch_verifier() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+
+        let inputs = [(1u64, 2u64), (3, 4), (5, 6)];
+        let prover_params = indexer(&build_cs(0, 0), &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+
+        let mut proofs: Vec<PlonkProof<KZGCommitmentSchemeBN254>> = vec![];
+        let mut pis = vec![];
+        for (a, b) in inputs.iter() {
+            let mut cs = build_cs(*a, *b);
+            let witness = cs.get_and_clear_witness();
+            let mut transcript = Transcript::new(b"Test");
+            proofs.push(
+                prover(
+                    &mut prng,
+                    &mut transcript,
+                    &pcs,
+                    &cs,
+                    &prover_params,
+                    &witness,
+                )
+                .unwrap(),
+            );
+            pis.push(vec![Fr::from(a + b)]);
+        }
+        let cs = build_cs(0, 0);
+
+        for (pi, proof) in pis.iter().zip(proofs.iter()) {
+            let mut transcript = Transcript::new(b"Test");
+            assert!(verifier(&mut transcript, &pcs, &cs, &verifier_params, pi, proof).is_ok());
+        }
+
+        let mut instances: Vec<_> = pis
+            .iter()
+            .zip(proofs.iter())
+            .map(|(pi, proof)| (Transcript::new(b"Test"), pi.as_slice(), proof))
+            .collect();
+        assert!(batch_verifier(&pcs, &cs, &verifier_params, &mut instances).is_ok());
+
+        let wrong_pi = vec![Fr::from(100u64)];
+        let mut instances: Vec<_> = pis
+            .iter()
+            .zip(proofs.iter())
+            .enumerate()
+            .map(|(i, (pi, proof))| {
+                let pi = if i == 1 { &wrong_pi } else { pi };
+                (Transcript::new(b"Test"), pi.as_slice(), proof)
+            })
+            .collect();
+        assert_eq!(
+            batch_verifier(&pcs, &cs, &verifier_params, &mut instances),
+            Err(UzkgeError::BatchVerificationError(1))
+        );
+    }
+
+    #[test]
+    fn test_anemoi_transcript() {
+        type AnemoiTranscriptBN254 = AnemoiTranscript<Fr, AnemoiJive254>;
+
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+
+        let mut cs = build_cs(1, 2);
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+
+        let mut transcript = AnemoiTranscriptBN254::new(b"Test");
+        let proof = prover(
+            &mut prng,
+            &mut transcript,
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+
+        let pi = [Fr::from(3u64)];
+        let mut transcript = AnemoiTranscriptBN254::new(b"Test");
+        assert!(verifier(&mut transcript, &pcs, &cs, &verifier_params, &pi, &proof).is_ok());
+
+        let wrong_pi = [Fr::from(4u64)];
+        let mut transcript = AnemoiTranscriptBN254::new(b"Test");
+        assert!(verifier(
+            &mut transcript,
+            &pcs,
+            &cs,
+            &verifier_params,
+            &wrong_pi,
+            &proof
+        )
+        .is_err());
+
+        // the challenges of the Keccak transcript are different.
+        let mut transcript = Transcript::new(b"Test");
+        assert!(verifier(&mut transcript, &pcs, &cs, &verifier_params, &pi, &proof).is_err());
+
+        let mut instances = vec![(AnemoiTranscriptBN254::new(b"Test"), &pi[..], &proof)];
+        assert!(batch_verifier(&pcs, &cs, &verifier_params, &mut instances).is_ok());
+    }
+
+    #[test]
+    fn test_standard_layout() {
+        let mut prng = ChaChaRng::from_seed([0u8; 32]);
+        let pcs = KZGCommitmentScheme::new(20, &mut prng);
+
+        let mut cs = build_cs(1, 2);
+        let witness = cs.get_and_clear_witness();
+        let prover_params = indexer(&cs, &pcs).unwrap();
+        let verifier_params = prover_params.verifier_params.clone();
+        assert_eq!(verifier_params.layout(), PlonkLayout::Standard);
+        assert!(verifier_params.cm_shuffle_public_key_vec.is_empty());
+
+        let proof = prover(
+            &mut prng,
+            &mut Transcript::new(b"Test"),
+            &pcs,
+            &cs,
+            &prover_params,
+            &witness,
+        )
+        .unwrap();
+        assert_eq!(proof.layout, PlonkLayout::Standard);
+        assert!(proof.cm_w_sel_vec.is_empty());
+
+        let bytes = proof.to_bytes_be();
+        assert_eq!(bytes[0], PlonkLayout::Standard.to_tag());
+        let proof = PlonkProof::from_bytes_be::<TurboCS<Fr>>(&bytes).unwrap();
+
+        let pi = [Fr::from(3u64)];
+        let mut transcript = Transcript::new(b"Test");
+        assert!(verifier(&mut transcript, &pcs, &cs, &verifier_params, &pi, &proof).is_ok());
+
+        // the bytes of the standard proof are too short for the shuffle layout.
+        let mut wrong_bytes = bytes.clone();
+        wrong_bytes[0] = PlonkLayout::Shuffle.to_tag();
+        assert!(PlonkProof::from_bytes_be::<TurboCS<Fr>>(&wrong_bytes).is_err());
+        wrong_bytes[0] = 2;
+        assert!(PlonkProof::from_bytes_be::<TurboCS<Fr>>(&wrong_bytes).is_err());
+
+        let mut wrong_proof = proof.clone();
+        wrong_proof.layout = PlonkLayout::Shuffle;
+        let mut transcript = Transcript::new(b"Test");
+        assert_eq!(
+            verifier(
+                &mut transcript,
+                &pcs,
+                &cs,
+                &verifier_params,
+                &pi,
+                &wrong_proof
+            ),
+            Err(UzkgeError::VerificationError)
+        );
+    }
+}
@@ -590,6 +590,35 @@ impl<F: PrimeField> FpPolynomial<F> {
         self.mul_var(k).fft_with_domain(domain)
     }
 
+    /// Compute the FFT of the polynomial on the set k * <root_m> of the quotient domain `domain_m`
+    /// by the radix-2 FFTs on its sub-cosets k * root_m^j * <root_n>, which is faster than the
+    /// mixed-radix FFT. The degree should be less than the size of `domain`, which divides `domain_m`.
+    pub fn quotient_coset_fft_with_domain<E: EvaluationDomain<F>>(
+        &self,
+        domain: &Radix2EvaluationDomain<F>,
+        domain_m: &E,
+        k: &F,
+    ) -> Vec<F> {
+        let n = domain.size();
+        let m = domain_m.size();
+        let factor = m / n;
+        assert!(n * factor == m && self.coefs.len() <= n);
+        debug_assert_eq!(domain_m.group_gen().pow([factor as u64]), domain.group_gen);
+
+        let mut evals = vec![F::ZERO; m];
+        let mut shift = *k;
+        for j in 0..factor {
+            let mut sub_evals = self.mul_var(&shift).coefs;
+            sub_evals.resize(n, F::ZERO);
+            domain.fft_in_place(&mut sub_evals);
+            for (i, eval) in sub_evals.into_iter().enumerate() {
+                evals[j + factor * i] = eval;
+            }
+            shift.mul_assign(domain_m.group_gen());
+        }
+        evals
+    }
+
     /// Compute the polynomial given its evaluation values and domain.
     pub fn ifft_with_domain<E: EvaluationDomain<F>>(domain: &E, values: &[F]) -> Self {
         let coefs = domain.ifft(&values);
@@ -717,6 +746,25 @@ macro_rules! _test_polynomial {
             let ffti_polynomial = FpPolynomial::ifft_with_domain(&domain, &fft);
             assert_eq!(ffti_polynomial, polynomial);
         }
+
+        #[test]
+        fn test_quotient_coset_fft() {
+            let mut prng = test_rng();
+            let k = <$scalar>::rand(&mut prng);
+            let mut coefs = vec![];
+            for _ in 0..15 {
+                coefs.push(<$scalar>::rand(&mut prng));
+            }
+            let polynomial = FpPolynomial::from_coefs(coefs);
+            let domain = FpPolynomial::<$scalar>::evaluation_domain(16).unwrap();
+            for m in [16, 48, 96] {
+                let domain_m = FpPolynomial::<$scalar>::quotient_evaluation_domain(m).unwrap();
+                assert_eq!(
+                    polynomial.quotient_coset_fft_with_domain(&domain, &domain_m, &k),
+                    polynomial.coset_fft_with_domain(&domain_m, &k)
+                );
+            }
+        }
     };
 }
 
@@ -3,7 +3,8 @@ use ark_std::fmt::Debug;
 use std::ops::{AddAssign, Mul, MulAssign, Neg};
 
 use crate::{
-    errors::UzkgeError, poly_commit::field_polynomial::FpPolynomial, utils::transcript::Transcript,
+    errors::UzkgeError, poly_commit::field_polynomial::FpPolynomial,
+    utils::transcript::TranscriptScheme,
 };
 
 /// The trait for serialization to bytes
@@ -104,9 +105,9 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     /// `param` stores the instance parameters to be appended to the transcript.
     /// When `param` is `None`, our function assumes `params` are implicit
     /// in the transcript already.
-    fn batch_prove(
+    fn batch_prove<T: TranscriptScheme>(
         &self,
-        transcript: &mut Transcript,
+        transcript: &mut T,
         lagrange_pcs: Option<&Self>,
         polys: &[&FpPolynomial<Self::Field>],
         point: &Self::Field,
@@ -168,9 +169,9 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     }
 
     /// Combine multiple commitments into one commitment.
-    fn batch(
+    fn batch<T: TranscriptScheme>(
         &self,
-        transcript: &mut Transcript,
+        transcript: &mut T,
         cm_vec: &[&Self::Commitment],
         max_degree: usize,
         point: &Self::Field,
@@ -191,9 +192,9 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     }
 
     /// Verify a batched proof.
-    fn batch_verify(
+    fn batch_verify<T: TranscriptScheme>(
         &self,
-        transcript: &mut Transcript,
+        transcript: &mut T,
         commitments: &[&Self::Commitment],
         max_degree: usize,
         point: &Self::Field,
@@ -217,8 +218,8 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     ) -> Result<(), UzkgeError>;
 
     /// Initialize the transcript for batch evaluation.
-    fn init_pcs_batch_eval_transcript(
-        transcript: &mut Transcript,
+    fn init_pcs_batch_eval_transcript<T: TranscriptScheme>(
+        transcript: &mut T,
         max_degree: usize,
         point: &Self::Field,
     ) {
@@ -227,8 +228,8 @@ pub trait PolyComScheme: Sized + Eq + PartialEq + Clone {
     }
 
     /// Append params to the transcript.
-    fn transcript_append_params(
-        transcript: &mut Transcript,
+    fn transcript_append_params<T: TranscriptScheme>(
+        transcript: &mut T,
         max_degree: usize,
         point: &Self::Field,
     ) {
@@ -254,7 +255,7 @@ mod test {
             field_polynomial::FpPolynomial, kzg_poly_commitment::KZGCommitmentScheme,
             pcs::PolyComScheme,
         },
-        utils::transcript::Transcript,
+        utils::transcript::{Transcript, TranscriptScheme},
     };
 
     #[test]
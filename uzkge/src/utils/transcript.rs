@@ -1,23 +1,62 @@
 use ark_ff::{BigInteger, PrimeField};
+use ark_std::marker::PhantomData;
 use sha3::{Digest, Keccak256};
 
-use crate::poly_commit::pcs::ToBytes;
+use crate::{
+    anemoi::{AnemoiJive, N_ANEMOI_ROUNDS},
+    poly_commit::pcs::ToBytes,
+};
 
 pub const SLOT_SIZE: usize = 32;
 
+/// The Fiat-Shamir transcript used by the provers and the verifiers.
+pub trait TranscriptScheme {
+    /// Create a new transcript with the domain separator `msg`.
+    fn new(msg: &'static [u8]) -> Self;
+
+    /// Append the message to the transcript.
+    fn append_message(&mut self, label: &'static [u8], msg: &[u8]);
+
+    /// Append the u64 to the transcript.
+    fn append_u64(&mut self, label: &'static [u8], a: u64);
+
+    /// Append a single byte to the transcript.
+    fn append_single_byte(&mut self, label: &'static [u8], b: u8);
+
+    /// Append a single commitment to the transcript.
+    fn append_commitment<C: ToBytes>(&mut self, comm: &C) {
+        self.append_message(b"", &comm.to_transcript_bytes());
+    }
+
+    /// Append a challenge to the transcript.
+    fn append_challenge<F: PrimeField>(&mut self, challenge: &F);
+
+    /// Generate the challenge for the current transcript,
+    /// and then append it to the transcript.
+    fn get_challenge_field_elem<F: PrimeField>(&mut self, label: &'static [u8]) -> F;
+}
+
+/// The Keccak256 transcript, which is reproduced by the Solidity verifier.
 pub struct Transcript {
     state: Vec<u8>,
 }
 
 impl Transcript {
-    pub fn new(msg: &'static [u8]) -> Self {
+    /// The bytes appended since the last challenge.
+    pub fn state(&self) -> &[u8] {
+        &self.state
+    }
+}
+
+impl TranscriptScheme for Transcript {
+    fn new(msg: &'static [u8]) -> Self {
         let mut t = Transcript { state: Vec::new() };
         t.append_message(b"", msg);
         t
     }
 
     /// Append the message to the transcript. `_label` is omitted for efficiency.
-    pub fn append_message(&mut self, _label: &'static [u8], msg: &[u8]) {
+    fn append_message(&mut self, _label: &'static [u8], msg: &[u8]) {
         if msg.len() < SLOT_SIZE {
             let mut tmp = vec![0; SLOT_SIZE];
             let index = SLOT_SIZE - msg.len();
@@ -30,7 +69,7 @@ impl Transcript {
     }
 
     /// Append the u64 to the transcript. `_label` is omitted for efficiency.
-    pub fn append_u64(&mut self, _label: &'static [u8], a: u64) {
+    fn append_u64(&mut self, _label: &'static [u8], a: u64) {
         let a = a.to_be_bytes();
         let mut tmp = vec![0; SLOT_SIZE];
         let index = SLOT_SIZE - a.len();
@@ -39,24 +78,18 @@ impl Transcript {
     }
 
     /// Append a single byte to the transcript. `_label` is omitted for efficiency.
-    pub fn append_single_byte(&mut self, _label: &'static [u8], b: u8) {
+    fn append_single_byte(&mut self, _label: &'static [u8], b: u8) {
         self.state.push(b);
     }
 
-    /// Append a single commitment to the transcript.
-    pub fn append_commitment<C: ToBytes>(&mut self, comm: &C) {
-        Self::append_message(self, b"", &comm.to_transcript_bytes());
-    }
-
-    /// Append a challenge to the transcript.
-    pub fn append_challenge<F: PrimeField>(&mut self, challenge: &F) {
-        Self::append_message(self, b"", &challenge.into_bigint().to_bytes_be());
+    fn append_challenge<F: PrimeField>(&mut self, challenge: &F) {
+        self.append_message(b"", &challenge.into_bigint().to_bytes_be());
     }
 
     /// Generate the challenge for the current transcript,
     /// and then append it to the transcript. `_label` is omitted for
     /// efficiency.
-    pub fn get_challenge_field_elem<F: PrimeField>(&mut self, _label: &'static [u8]) -> F {
+    fn get_challenge_field_elem<F: PrimeField>(&mut self, _label: &'static [u8]) -> F {
         let mut hasher = Keccak256::new();
         hasher.update(&self.state);
         let mut buf = hasher.finalize();
@@ -68,3 +101,82 @@ impl Transcript {
         challenge
     }
 }
+
+/// Pack the bytes into the elements of `F` as the Anemoi transcript absorbs them,
+/// i.e., the length followed by the chunks of the bytes below the modulus.
+pub fn pack_bytes<F: PrimeField>(msg: &[u8]) -> Vec<F> {
+    let chunk_size = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+    let mut elems = vec![F::from(msg.len() as u64)];
+    elems.extend(msg.chunks(chunk_size).map(F::from_be_bytes_mod_order));
+    elems
+}
+
+/// The transcript with the Anemoi sponge over the field `F`, which is cheap to
+/// reproduce in a `TurboCS` over the same field, so that the proofs can be verified
+/// recursively.
+///
+/// The elements of `F` are absorbed as they are, and the bytes are packed into elements
+/// of `F` after their length. The challenge is the Anemoi hash of the elements absorbed
+/// since the last challenge, prefixed with the last challenge.
+pub struct AnemoiTranscript<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>> {
+    state: Vec<F>,
+    _hash: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>> AnemoiTranscript<F, P> {
+    /// The elements absorbed since the last challenge.
+    pub fn state(&self) -> &[F] {
+        &self.state
+    }
+}
+
+impl<F: PrimeField, P: AnemoiJive<F, 2, N_ANEMOI_ROUNDS>> TranscriptScheme
+    for AnemoiTranscript<F, P>
+{
+    fn new(msg: &'static [u8]) -> Self {
+        let mut t = AnemoiTranscript {
+            state: Vec::new(),
+            _hash: PhantomData,
+        };
+        t.append_message(b"", msg);
+        t
+    }
+
+    /// Append the message to the transcript. `_label` is omitted for efficiency.
+    fn append_message(&mut self, _label: &'static [u8], msg: &[u8]) {
+        self.state.extend(pack_bytes::<F>(msg));
+    }
+
+    /// Append the u64 to the transcript. `_label` is omitted for efficiency.
+    fn append_u64(&mut self, _label: &'static [u8], a: u64) {
+        self.state.push(F::from(a));
+    }
+
+    /// Append a single byte to the transcript. `_label` is omitted for efficiency.
+    fn append_single_byte(&mut self, _label: &'static [u8], b: u8) {
+        self.state.push(F::from(b));
+    }
+
+    /// Append a challenge to the transcript, as one element if it fits in `F`.
+    fn append_challenge<G: PrimeField>(&mut self, challenge: &G) {
+        let bytes = challenge.into_bigint().to_bytes_le();
+        if G::MODULUS_BIT_SIZE < F::MODULUS_BIT_SIZE
+            || G::MODULUS.to_bytes_le() == F::MODULUS.to_bytes_le()
+        {
+            self.state.push(F::from_le_bytes_mod_order(&bytes));
+        } else {
+            self.state
+                .extend(pack_bytes::<F>(&challenge.into_bigint().to_bytes_be()));
+        }
+    }
+
+    /// Generate the challenge for the current transcript,
+    /// and then append it to the transcript. `_label` is omitted for
+    /// efficiency.
+    fn get_challenge_field_elem<G: PrimeField>(&mut self, _label: &'static [u8]) -> G {
+        let challenge = P::eval_variable_length_hash(&self.state);
+        self.state = vec![challenge];
+
+        G::from_le_bytes_mod_order(&challenge.into_bigint().to_bytes_le())
+    }
+}